@@ -6,9 +6,20 @@ pub const AFTER_FAILED_AUTOMATION_REPLY_ID: u64 = 3;
 pub const AFTER_DELEGATION_REPLY_ID: u64 = 4;
 pub const AFTER_ORDER_MIGRATION_REPLY_ID: u64 = 5;
 pub const FAIL_SILENTLY_REPLY_ID: u64 = 6;
+pub const AFTER_BATCHED_TRIGGER_EXECUTION_REPLY_ID: u64 = 7;
+pub const AFTER_AGGREGATED_SWAP_REPLY_ID: u64 = 8;
+pub const AFTER_POST_EXECUTION_ACTION_RETRY_REPLY_ID: u64 = 9;
+pub const AFTER_RECURRING_DEPOSIT_REPLY_ID: u64 = 10;
+pub const AFTER_DEPOSIT_CONVERSION_REPLY_ID: u64 = 11;
+pub const AFTER_BATCHED_SETTLEMENT_REPLY_ID: u64 = 12;
+pub const AFTER_BATCHED_AGGREGATED_SWAP_REPLY_ID: u64 = 13;
 
 pub const SWAP_FEE_RATE: &str = "0.0015";
 
+pub const PARTIAL_SWAP_AMOUNT_SEARCH_STEPS: u8 = 10;
+
+pub const RECURRING_DEPOSIT_MAX_FAILED_ATTEMPTS: u32 = 3;
+
 pub const ONE_MICRON: Uint128 = Uint128::new(1);
 pub const TWO_MICRONS: Uint128 = Uint128::new(2);
 pub const TEN_MICRONS: Uint128 = Uint128::new(10);
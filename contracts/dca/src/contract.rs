@@ -1,19 +1,34 @@
 use crate::constants::{
-    AFTER_DELEGATION_REPLY_ID, AFTER_FAILED_AUTOMATION_REPLY_ID, AFTER_LIMIT_ORDER_PLACED_REPLY_ID,
-    AFTER_SWAP_REPLY_ID, FAIL_SILENTLY_REPLY_ID,
+    AFTER_AGGREGATED_SWAP_REPLY_ID, AFTER_BATCHED_AGGREGATED_SWAP_REPLY_ID,
+    AFTER_BATCHED_SETTLEMENT_REPLY_ID, AFTER_BATCHED_TRIGGER_EXECUTION_REPLY_ID,
+    AFTER_DELEGATION_REPLY_ID, AFTER_DEPOSIT_CONVERSION_REPLY_ID, AFTER_FAILED_AUTOMATION_REPLY_ID,
+    AFTER_LIMIT_ORDER_PLACED_REPLY_ID, AFTER_POST_EXECUTION_ACTION_RETRY_REPLY_ID,
+    AFTER_RECURRING_DEPOSIT_REPLY_ID, AFTER_SWAP_REPLY_ID, FAIL_SILENTLY_REPLY_ID,
 };
 use crate::error::ContractError;
+use crate::handlers::accept_vault_ownership::accept_vault_ownership_handler;
 use crate::handlers::cancel_vault::cancel_vault_handler;
+use crate::handlers::create_custom_swap_fee::create_custom_swap_fee_handler;
 use crate::handlers::create_vault::{create_vault_handler, save_price_trigger};
-use crate::handlers::deposit::deposit_handler;
+use crate::handlers::deposit::{deposit_handler, handle_deposit_conversion_result};
 use crate::handlers::disburse_escrow::disburse_escrow_handler;
 use crate::handlers::disburse_funds::disburse_funds_handler;
+use crate::handlers::execute_aggregated_swap::{
+    execute_aggregated_swap_handler, handle_aggregated_swap_result,
+};
 use crate::handlers::execute_trigger::execute_trigger_handler;
+use crate::handlers::execute_triggers::{
+    execute_triggers_handler, handle_batched_aggregated_swap_result,
+    handle_batched_settlement_result, handle_batched_trigger_execution_result,
+};
 use crate::handlers::get_config::get_config_handler;
+use crate::handlers::get_custom_swap_fees::get_custom_swap_fees_handler;
 use crate::handlers::get_disburse_escrow_tasks::get_disburse_escrow_tasks_handler;
 use crate::handlers::get_events::get_events_handler;
 use crate::handlers::get_events_by_resource_id::get_events_by_resource_id_handler;
 use crate::handlers::get_pairs::get_pairs_handler;
+use crate::handlers::get_pending_post_execution_actions::get_pending_post_execution_actions_handler;
+use crate::handlers::get_referral_fees::get_referral_fees_handler;
 use crate::handlers::get_time_trigger_ids::get_time_trigger_ids_handler;
 use crate::handlers::get_trigger_id_by_fin_limit_order_idx::get_trigger_id_by_fin_limit_order_idx_handler;
 use crate::handlers::get_vault::get_vault_handler;
@@ -21,13 +36,29 @@ use crate::handlers::get_vault_performance::get_vault_performance_handler;
 use crate::handlers::get_vaults::get_vaults_handler;
 use crate::handlers::get_vaults_by_address::get_vaults_by_address_handler;
 use crate::handlers::handle_failed_automation::handle_failed_automation_handler;
+use crate::handlers::ibc_lifecycle_complete::ibc_lifecycle_complete_handler;
 use crate::handlers::instantiate::instantiate_handler;
 use crate::handlers::migrate::migrate_handler;
+use crate::handlers::pause_vault::pause_vault_handler;
+use crate::handlers::recurring_deposit::handle_recurring_deposit_result;
+use crate::handlers::remove_custom_swap_fee::remove_custom_swap_fee_handler;
+use crate::handlers::resume_vault::resume_vault_handler;
+use crate::handlers::retry_post_execution_action::{
+    handle_post_execution_action_retry_result, retry_post_execution_action_handler,
+};
+use crate::handlers::settle_execution::{
+    settle_execution_handler, settle_netted_executions_handler,
+};
+use crate::handlers::simulate_vault::simulate_vault_handler;
+use crate::handlers::transfer_vault_ownership::transfer_vault_ownership_handler;
 use crate::handlers::update_config::update_config_handler;
+use crate::handlers::update_referral_fee_caps::update_referral_fee_caps_handler;
 use crate::handlers::update_swap_adjustment_handler::update_swap_adjustment_handler;
 use crate::handlers::update_vault::update_vault_handler;
+use crate::handlers::withdraw_from_vault::withdraw_from_vault_handler;
 use crate::handlers::z_delegate::{log_delegation_result, z_delegate_handler};
-use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+use crate::helpers::message::split_vault_reply_id;
+use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, SudoMsg};
 use cosmwasm_std::from_json;
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::{
@@ -76,6 +107,17 @@ pub fn execute(
             target_receive_amount,
             performance_assessment_strategy,
             swap_adjustment_strategy,
+            basket,
+            end_conditions,
+            missed_execution_policy,
+            allow_partial_executions,
+            retry_policy,
+            retry_failed_post_execution_actions,
+            recurring_deposits,
+            deposit_conversion,
+            referrer,
+            escrow_vesting,
+            escrow_level,
         } => create_vault_handler(
             deps,
             env,
@@ -93,6 +135,17 @@ pub fn execute(
             target_receive_amount,
             performance_assessment_strategy,
             swap_adjustment_strategy,
+            basket,
+            end_conditions,
+            missed_execution_policy,
+            allow_partial_executions,
+            retry_policy,
+            retry_failed_post_execution_actions,
+            recurring_deposits,
+            deposit_conversion,
+            referrer,
+            escrow_vesting,
+            escrow_level,
         ),
         ExecuteMsg::UpdateVault {
             vault_id,
@@ -103,6 +156,10 @@ pub fn execute(
             time_interval,
             swap_adjustment_strategy,
             swap_amount,
+            allow_partial_executions,
+            retry_failed_post_execution_actions,
+            recurring_deposits,
+            escrow_level,
         } => update_vault_handler(
             deps,
             env,
@@ -115,13 +172,42 @@ pub fn execute(
             time_interval,
             swap_adjustment_strategy,
             swap_amount,
+            allow_partial_executions,
+            retry_failed_post_execution_actions,
+            recurring_deposits,
+            escrow_level,
         ),
         ExecuteMsg::CancelVault { vault_id } => cancel_vault_handler(deps, env, info, vault_id),
+        ExecuteMsg::PauseVault { vault_id } => pause_vault_handler(deps, env, info, vault_id),
+        ExecuteMsg::ResumeVault { vault_id } => resume_vault_handler(deps, env, info, vault_id),
+        ExecuteMsg::TransferVaultOwnership {
+            vault_id,
+            new_owner,
+            update_destinations,
+        } => transfer_vault_ownership_handler(
+            deps,
+            info,
+            vault_id,
+            new_owner,
+            update_destinations.unwrap_or(false),
+        ),
+        ExecuteMsg::AcceptVaultOwnership { vault_id } => {
+            accept_vault_ownership_handler(deps, env, info, vault_id)
+        }
         ExecuteMsg::ExecuteTrigger { trigger_id, route } => {
-            execute_trigger_handler(deps, env, trigger_id, route)
+            execute_trigger_handler(deps, env, info, trigger_id, route)
         }
-        ExecuteMsg::Deposit { address, vault_id } => {
-            deposit_handler(deps, env, info, address, vault_id)
+        ExecuteMsg::ExecuteTriggers {
+            trigger_ids,
+            routes,
+        } => execute_triggers_handler(deps, env, info, trigger_ids, routes),
+        ExecuteMsg::Deposit {
+            address,
+            vault_id,
+            conversion,
+        } => deposit_handler(deps, env, info, address, vault_id, conversion),
+        ExecuteMsg::WithdrawFromVault { vault_id, amount } => {
+            withdraw_from_vault_handler(deps, env, info, vault_id, amount)
         }
         ExecuteMsg::UpdateConfig {
             executors,
@@ -135,6 +221,15 @@ pub fn execute(
             twap_period,
             default_slippage_tolerance,
             exchange_contract_address,
+            default_retry_policy,
+            permissionless_execution,
+            keeper_bounty_percent,
+            referral_fee_percent,
+            high_water_mark_performance_fee_percent,
+            minimum_escrow_level,
+            maximum_escrow_level,
+            post_execution_action_max_retry_attempts,
+            post_execution_action_max_retry_age_seconds,
         } => update_config_handler(
             deps,
             info,
@@ -149,13 +244,76 @@ pub fn execute(
             twap_period,
             default_slippage_tolerance,
             exchange_contract_address,
+            default_retry_policy,
+            permissionless_execution,
+            keeper_bounty_percent,
+            referral_fee_percent,
+            high_water_mark_performance_fee_percent,
+            minimum_escrow_level,
+            maximum_escrow_level,
+            post_execution_action_max_retry_attempts,
+            post_execution_action_max_retry_age_seconds,
         ),
         ExecuteMsg::UpdateSwapAdjustment { strategy, value } => {
             update_swap_adjustment_handler(deps, env, info, strategy, value)
         }
+        ExecuteMsg::CreateCustomSwapFee {
+            denom,
+            paired_denom,
+            swap_fee_percent,
+        } => create_custom_swap_fee_handler(deps, info, denom, paired_denom, swap_fee_percent),
+        ExecuteMsg::RemoveCustomSwapFee {
+            denom,
+            paired_denom,
+        } => remove_custom_swap_fee_handler(deps, info, denom, paired_denom),
+        ExecuteMsg::UpdateReferralFeeCaps { referrer, caps } => {
+            update_referral_fee_caps_handler(deps, info, referrer, caps)
+        }
         ExecuteMsg::DisburseEscrow { vault_id } => {
             disburse_escrow_handler(deps, env, info, vault_id)
         }
+        ExecuteMsg::ExecuteAggregatedSwap {
+            vault_ids,
+            swap_amounts,
+            minimum_receive_amount,
+            route,
+            asset_price,
+            keeper,
+        } => execute_aggregated_swap_handler(
+            deps,
+            env,
+            info,
+            vault_ids,
+            swap_amounts,
+            minimum_receive_amount,
+            route,
+            asset_price,
+            keeper,
+        ),
+        ExecuteMsg::SettleExecution {
+            vault_id,
+            sent,
+            received,
+            asset_price,
+            residual,
+            keeper,
+        } => settle_execution_handler(
+            deps,
+            env,
+            info,
+            vault_id,
+            sent,
+            received,
+            asset_price,
+            residual,
+            keeper,
+        ),
+        ExecuteMsg::SettleNettedExecutions { executions, keeper } => {
+            settle_netted_executions_handler(env, info, executions, keeper)
+        }
+        ExecuteMsg::RetryPostExecutionAction { vault_id } => {
+            retry_post_execution_action_handler(deps, env, info, vault_id)
+        }
         ExecuteMsg::ZDelegate {
             delegator_address,
             validator_address,
@@ -183,18 +341,38 @@ pub fn execute(
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn reply(deps: DepsMut, env: Env, reply: Reply) -> Result<Response, ContractError> {
-    match reply.id {
+    match split_vault_reply_id(reply.id).0 {
         AFTER_LIMIT_ORDER_PLACED_REPLY_ID => save_price_trigger(deps, reply),
         AFTER_SWAP_REPLY_ID => disburse_funds_handler(deps, &env, reply),
         AFTER_FAILED_AUTOMATION_REPLY_ID => handle_failed_automation_handler(deps, env, reply),
         AFTER_DELEGATION_REPLY_ID => log_delegation_result(reply),
         FAIL_SILENTLY_REPLY_ID => Ok(Response::new()),
+        AFTER_BATCHED_TRIGGER_EXECUTION_REPLY_ID => {
+            handle_batched_trigger_execution_result(deps, env, reply)
+        }
+        AFTER_AGGREGATED_SWAP_REPLY_ID => handle_aggregated_swap_result(deps, env, reply),
+        AFTER_POST_EXECUTION_ACTION_RETRY_REPLY_ID => {
+            handle_post_execution_action_retry_result(deps, env, reply)
+        }
+        AFTER_RECURRING_DEPOSIT_REPLY_ID => handle_recurring_deposit_result(deps, env, reply),
+        AFTER_DEPOSIT_CONVERSION_REPLY_ID => handle_deposit_conversion_result(deps, env, reply),
+        AFTER_BATCHED_SETTLEMENT_REPLY_ID => handle_batched_settlement_result(deps, env, reply),
+        AFTER_BATCHED_AGGREGATED_SWAP_REPLY_ID => {
+            handle_batched_aggregated_swap_result(deps, env, reply)
+        }
         id => Err(ContractError::CustomError {
             val: format!("unhandled DCA contract reply id: {}", id),
         }),
     }
 }
 
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn sudo(deps: DepsMut, env: Env, msg: SudoMsg) -> Result<Response, ContractError> {
+    match msg {
+        SudoMsg::IbcLifecycleComplete(msg) => ibc_lifecycle_complete_handler(deps, env, msg),
+    }
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
@@ -243,11 +421,73 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
             reverse,
         } => to_json_binary(&get_events_handler(deps, start_after, limit, reverse)?),
         QueryMsg::GetConfig {} => to_json_binary(&get_config_handler(deps)?),
+        QueryMsg::GetCustomSwapFees {} => to_json_binary(&get_custom_swap_fees_handler(deps)?),
+        QueryMsg::GetReferralFees { referrer } => {
+            to_json_binary(&get_referral_fees_handler(deps, referrer)?)
+        }
         QueryMsg::GetVaultPerformance { vault_id } => {
             to_json_binary(&get_vault_performance_handler(deps, vault_id)?)
         }
         QueryMsg::GetDisburseEscrowTasks { limit } => {
             to_json_binary(&get_disburse_escrow_tasks_handler(deps, env, limit)?)
         }
+        QueryMsg::GetPendingPostExecutionActions { vault_id } => {
+            to_json_binary(&get_pending_post_execution_actions_handler(deps, vault_id)?)
+        }
+        QueryMsg::SimulateVault {
+            deposit,
+            owner,
+            label,
+            destinations,
+            target_denom,
+            route,
+            slippage_tolerance,
+            minimum_receive_amount,
+            swap_amount,
+            time_interval,
+            target_start_time_utc_seconds,
+            target_receive_amount,
+            performance_assessment_strategy,
+            swap_adjustment_strategy,
+            basket,
+            end_conditions,
+            missed_execution_policy,
+            allow_partial_executions,
+            retry_policy,
+            retry_failed_post_execution_actions,
+            recurring_deposits,
+            deposit_conversion,
+            referrer,
+            escrow_vesting,
+            escrow_level,
+        } => to_json_binary(&simulate_vault_handler(
+            deps,
+            env,
+            deposit,
+            owner,
+            label,
+            destinations.unwrap_or_default(),
+            target_denom,
+            route,
+            slippage_tolerance,
+            minimum_receive_amount,
+            swap_amount,
+            time_interval,
+            target_start_time_utc_seconds,
+            target_receive_amount,
+            performance_assessment_strategy,
+            swap_adjustment_strategy,
+            basket,
+            end_conditions,
+            missed_execution_policy,
+            allow_partial_executions,
+            retry_policy,
+            retry_failed_post_execution_actions,
+            recurring_deposits,
+            deposit_conversion,
+            referrer,
+            escrow_vesting,
+            escrow_level,
+        )?),
     }
 }
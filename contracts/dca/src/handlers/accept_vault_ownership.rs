@@ -0,0 +1,435 @@
+use crate::error::ContractError;
+use crate::helpers::validation::assert_vault_is_not_cancelled;
+use crate::state::events::create_event;
+use crate::state::vault_ownership_transfers::{
+    delete_vault_ownership_transfer, get_vault_ownership_transfer,
+};
+use crate::state::vaults::{get_vault, update_vault};
+use crate::types::destination::{Destination, DestinationAction};
+use crate::types::event::{EventBuilder, EventData};
+use crate::types::vault::Vault;
+use cosmwasm_std::{DepsMut, Env, MessageInfo, Response, Uint128};
+
+pub fn accept_vault_ownership_handler(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    vault_id: Uint128,
+) -> Result<Response, ContractError> {
+    let vault = get_vault(deps.storage, vault_id)?;
+
+    assert_vault_is_not_cancelled(&vault)?;
+
+    let transfer = get_vault_ownership_transfer(deps.storage, vault.id)?.ok_or_else(|| {
+        ContractError::CustomError {
+            val: format!("vault {} has no pending ownership transfer", vault.id),
+        }
+    })?;
+
+    if info.sender != transfer.new_owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let previous_owner = vault.owner.clone();
+
+    let destinations = if transfer.update_destinations {
+        vault
+            .destinations
+            .iter()
+            .map(|destination| {
+                let pays_out_to_address = matches!(
+                    destination.action,
+                    DestinationAction::Send
+                        | DestinationAction::Delegate { .. }
+                        | DestinationAction::IbcTransfer { .. }
+                );
+
+                if pays_out_to_address && destination.address == previous_owner {
+                    Destination {
+                        address: transfer.new_owner.clone(),
+                        ..destination.clone()
+                    }
+                } else {
+                    destination.clone()
+                }
+            })
+            .collect::<Vec<Destination>>()
+    } else {
+        vault.destinations.clone()
+    };
+
+    let vault = update_vault(
+        deps.storage,
+        Vault {
+            owner: transfer.new_owner.clone(),
+            destinations,
+            ..vault
+        },
+    )?;
+
+    delete_vault_ownership_transfer(deps.storage, vault.id);
+
+    create_event(
+        deps.storage,
+        EventBuilder::new(
+            vault.id,
+            env.block,
+            EventData::DcaVaultOwnershipTransferred {
+                previous_owner: previous_owner.clone(),
+                new_owner: vault.owner.clone(),
+            },
+        ),
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("accept_vault_ownership", "true")
+        .add_attribute("vault_id", vault.id)
+        .add_attribute("previous_owner", previous_owner)
+        .add_attribute("owner", vault.owner))
+}
+
+#[cfg(test)]
+mod accept_vault_ownership_tests {
+    use super::*;
+    use crate::handlers::get_events_by_resource_id::get_events_by_resource_id_handler;
+    use crate::handlers::get_vault::get_vault_handler;
+    use crate::handlers::get_vaults_by_address::get_vaults_by_address_handler;
+    use crate::handlers::transfer_vault_ownership::transfer_vault_ownership_handler;
+    use crate::tests::helpers::{instantiate_contract, setup_vault};
+    use crate::tests::mocks::{ADMIN, USER};
+    use crate::types::vault::VaultStatus;
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+    use cosmwasm_std::{Addr, Decimal};
+
+    const NEW_OWNER: &str = "new-owner";
+
+    #[test]
+    fn updates_vault_owner() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        transfer_vault_ownership_handler(
+            deps.as_mut(),
+            mock_info(USER, &[]),
+            vault.id,
+            Addr::unchecked(NEW_OWNER),
+            false,
+        )
+        .unwrap();
+
+        accept_vault_ownership_handler(deps.as_mut(), env, mock_info(NEW_OWNER, &[]), vault.id)
+            .unwrap();
+
+        let updated_vault = get_vault_handler(deps.as_ref(), vault.id).unwrap().vault;
+
+        assert_eq!(updated_vault.owner, Addr::unchecked(NEW_OWNER));
+    }
+
+    #[test]
+    fn rewrites_owner_indexes() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        transfer_vault_ownership_handler(
+            deps.as_mut(),
+            mock_info(USER, &[]),
+            vault.id,
+            Addr::unchecked(NEW_OWNER),
+            false,
+        )
+        .unwrap();
+
+        accept_vault_ownership_handler(deps.as_mut(), env, mock_info(NEW_OWNER, &[]), vault.id)
+            .unwrap();
+
+        let previous_owner_vaults =
+            get_vaults_by_address_handler(deps.as_ref(), vault.owner.clone(), None, None, None)
+                .unwrap()
+                .vaults;
+
+        let previous_owner_active_vaults = get_vaults_by_address_handler(
+            deps.as_ref(),
+            vault.owner.clone(),
+            Some(VaultStatus::Active),
+            None,
+            None,
+        )
+        .unwrap()
+        .vaults;
+
+        let new_owner_vaults = get_vaults_by_address_handler(
+            deps.as_ref(),
+            Addr::unchecked(NEW_OWNER),
+            None,
+            None,
+            None,
+        )
+        .unwrap()
+        .vaults;
+
+        let new_owner_active_vaults = get_vaults_by_address_handler(
+            deps.as_ref(),
+            Addr::unchecked(NEW_OWNER),
+            Some(VaultStatus::Active),
+            None,
+            None,
+        )
+        .unwrap()
+        .vaults;
+
+        assert!(previous_owner_vaults.is_empty());
+        assert!(previous_owner_active_vaults.is_empty());
+        assert_eq!(new_owner_vaults.len(), 1);
+        assert_eq!(new_owner_vaults[0].id, vault.id);
+        assert_eq!(new_owner_active_vaults.len(), 1);
+    }
+
+    #[test]
+    fn with_update_destinations_rewrites_destinations_pointing_at_previous_owner() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let other_destination = Destination {
+            allocation: Decimal::percent(50),
+            address: Addr::unchecked("other"),
+            action: DestinationAction::Send,
+        };
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                destinations: vec![
+                    Destination {
+                        allocation: Decimal::percent(50),
+                        address: Addr::unchecked(USER),
+                        action: DestinationAction::Send,
+                    },
+                    other_destination.clone(),
+                ],
+                ..Vault::default()
+            },
+        );
+
+        transfer_vault_ownership_handler(
+            deps.as_mut(),
+            mock_info(USER, &[]),
+            vault.id,
+            Addr::unchecked(NEW_OWNER),
+            true,
+        )
+        .unwrap();
+
+        accept_vault_ownership_handler(deps.as_mut(), env, mock_info(NEW_OWNER, &[]), vault.id)
+            .unwrap();
+
+        let updated_vault = get_vault_handler(deps.as_ref(), vault.id).unwrap().vault;
+
+        assert_eq!(
+            updated_vault.destinations,
+            vec![
+                Destination {
+                    allocation: Decimal::percent(50),
+                    address: Addr::unchecked(NEW_OWNER),
+                    action: DestinationAction::Send,
+                },
+                other_destination,
+            ]
+        );
+    }
+
+    #[test]
+    fn with_update_destinations_keeps_vault_deposit_destinations() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let deposit_destination = Destination {
+            allocation: Decimal::percent(100),
+            address: Addr::unchecked(USER),
+            action: DestinationAction::DepositToVault {
+                vault_id: Uint128::new(2),
+            },
+        };
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                destinations: vec![deposit_destination.clone()],
+                ..Vault::default()
+            },
+        );
+
+        transfer_vault_ownership_handler(
+            deps.as_mut(),
+            mock_info(USER, &[]),
+            vault.id,
+            Addr::unchecked(NEW_OWNER),
+            true,
+        )
+        .unwrap();
+
+        accept_vault_ownership_handler(deps.as_mut(), env, mock_info(NEW_OWNER, &[]), vault.id)
+            .unwrap();
+
+        let updated_vault = get_vault_handler(deps.as_ref(), vault.id).unwrap().vault;
+
+        assert_eq!(updated_vault.destinations, vec![deposit_destination]);
+    }
+
+    #[test]
+    fn without_update_destinations_keeps_destinations() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                destinations: vec![Destination {
+                    allocation: Decimal::percent(100),
+                    address: Addr::unchecked(USER),
+                    action: DestinationAction::Send,
+                }],
+                ..Vault::default()
+            },
+        );
+
+        transfer_vault_ownership_handler(
+            deps.as_mut(),
+            mock_info(USER, &[]),
+            vault.id,
+            Addr::unchecked(NEW_OWNER),
+            false,
+        )
+        .unwrap();
+
+        accept_vault_ownership_handler(deps.as_mut(), env, mock_info(NEW_OWNER, &[]), vault.id)
+            .unwrap();
+
+        let updated_vault = get_vault_handler(deps.as_ref(), vault.id).unwrap().vault;
+
+        assert_eq!(updated_vault.destinations, vault.destinations);
+    }
+
+    #[test]
+    fn publishes_ownership_transferred_event() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        transfer_vault_ownership_handler(
+            deps.as_mut(),
+            mock_info(USER, &[]),
+            vault.id,
+            Addr::unchecked(NEW_OWNER),
+            false,
+        )
+        .unwrap();
+
+        accept_vault_ownership_handler(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(NEW_OWNER, &[]),
+            vault.id,
+        )
+        .unwrap();
+
+        let events = get_events_by_resource_id_handler(deps.as_ref(), vault.id, None, None, None)
+            .unwrap()
+            .events;
+
+        assert!(events.contains(
+            &EventBuilder::new(
+                vault.id,
+                env.block,
+                EventData::DcaVaultOwnershipTransferred {
+                    previous_owner: vault.owner,
+                    new_owner: Addr::unchecked(NEW_OWNER),
+                },
+            )
+            .build(1)
+        ));
+    }
+
+    #[test]
+    fn clears_pending_transfer() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        transfer_vault_ownership_handler(
+            deps.as_mut(),
+            mock_info(USER, &[]),
+            vault.id,
+            Addr::unchecked(NEW_OWNER),
+            false,
+        )
+        .unwrap();
+
+        accept_vault_ownership_handler(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(NEW_OWNER, &[]),
+            vault.id,
+        )
+        .unwrap();
+
+        let err =
+            accept_vault_ownership_handler(deps.as_mut(), env, mock_info(NEW_OWNER, &[]), vault.id)
+                .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "Error: vault {} has no pending ownership transfer",
+                vault.id
+            )
+        );
+    }
+
+    #[test]
+    fn from_address_other_than_pending_owner_fails() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        transfer_vault_ownership_handler(
+            deps.as_mut(),
+            mock_info(USER, &[]),
+            vault.id,
+            Addr::unchecked(NEW_OWNER),
+            false,
+        )
+        .unwrap();
+
+        let err =
+            accept_vault_ownership_handler(deps.as_mut(), env, mock_info(USER, &[]), vault.id)
+                .unwrap_err();
+
+        assert_eq!(err.to_string(), "Unauthorized");
+    }
+}
@@ -1,19 +1,17 @@
-use crate::constants::FAIL_SILENTLY_REPLY_ID;
 use crate::error::ContractError;
 use crate::helpers::validation::{
     assert_sender_is_admin_or_vault_owner, assert_vault_is_not_cancelled,
 };
-use crate::state::config::get_config;
+use crate::helpers::vault::get_retract_order_messages;
 use crate::state::disburse_escrow_tasks::save_disburse_escrow_task;
 use crate::state::events::create_event;
 use crate::state::triggers::delete_trigger;
+use crate::state::vault_ownership_transfers::delete_vault_ownership_transfer;
 use crate::state::vaults::{get_vault, update_vault};
 use crate::types::event::{EventBuilder, EventData};
-use crate::types::trigger::TriggerConfiguration;
 use crate::types::vault::{Vault, VaultStatus};
-use cosmwasm_std::{to_json_binary, BankMsg, DepsMut, Response, Uint128, WasmMsg};
+use cosmwasm_std::{BankMsg, DepsMut, Response, Uint128};
 use cosmwasm_std::{Env, MessageInfo, SubMsg};
-use exchange::msg::ExecuteMsg;
 use shared::coin::empty_of;
 
 pub fn cancel_vault_handler(
@@ -58,37 +56,10 @@ pub fn cancel_vault_handler(
         },
     )?;
 
-    if let Some(TriggerConfiguration::Price { order_idx, .. }) = vault.trigger {
-        let config = get_config(deps.storage)?;
-
-        submessages.push(SubMsg::reply_on_error(
-            WasmMsg::Execute {
-                contract_addr: config.exchange_contract_address.to_string(),
-                msg: to_json_binary(&ExecuteMsg::RetractOrder {
-                    order_idx,
-                    denoms: vault.denoms(),
-                })
-                .unwrap(),
-                funds: vec![],
-            },
-            FAIL_SILENTLY_REPLY_ID,
-        ));
-
-        submessages.push(SubMsg::reply_on_error(
-            WasmMsg::Execute {
-                contract_addr: config.exchange_contract_address.to_string(),
-                msg: to_json_binary(&ExecuteMsg::WithdrawOrder {
-                    order_idx,
-                    denoms: vault.denoms(),
-                })
-                .unwrap(),
-                funds: vec![],
-            },
-            FAIL_SILENTLY_REPLY_ID,
-        ));
-    };
+    submessages.append(&mut get_retract_order_messages(deps.storage, &vault)?);
 
     delete_trigger(deps.storage, vault.id)?;
+    delete_vault_ownership_transfer(deps.storage, vault.id);
 
     Ok(Response::new()
         .add_attribute("cancel_vault", "true")
@@ -101,16 +72,22 @@ pub fn cancel_vault_handler(
 #[cfg(test)]
 mod cancel_vault_tests {
     use super::*;
-    use crate::constants::ONE;
+    use crate::constants::{FAIL_SILENTLY_REPLY_ID, ONE};
     use crate::handlers::get_events_by_resource_id::get_events_by_resource_id_handler;
     use crate::handlers::get_vault::get_vault_handler;
+    use crate::state::config::get_config;
     use crate::state::disburse_escrow_tasks::get_disburse_escrow_tasks;
+    use crate::state::vault_ownership_transfers::{
+        get_vault_ownership_transfer, save_vault_ownership_transfer, VaultOwnershipTransfer,
+    };
     use crate::tests::helpers::{instantiate_contract, setup_vault};
     use crate::tests::mocks::{calc_mock_dependencies, ADMIN, DENOM_UKUJI};
     use crate::types::event::{EventBuilder, EventData};
+    use crate::types::trigger::TriggerConfiguration;
     use crate::types::vault::{Vault, VaultStatus};
     use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
-    use cosmwasm_std::{BankMsg, Coin, Decimal, SubMsg, Uint128};
+    use cosmwasm_std::{to_json_binary, Addr, BankMsg, Coin, Decimal, SubMsg, Uint128, WasmMsg};
+    use exchange::msg::ExecuteMsg;
 
     #[test]
     fn should_return_balance_to_owner() {
@@ -403,4 +380,33 @@ mod cancel_vault_tests {
             )
         );
     }
+
+    #[test]
+    fn should_clear_pending_ownership_transfer() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info(ADMIN, &[]);
+
+        instantiate_contract(deps.as_mut(), env.clone(), info.clone());
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        save_vault_ownership_transfer(
+            deps.as_mut().storage,
+            vault.id,
+            &VaultOwnershipTransfer {
+                new_owner: Addr::unchecked("new-owner"),
+                update_destinations: false,
+            },
+        )
+        .unwrap();
+
+        cancel_vault_handler(deps.as_mut(), env, info, vault.id).unwrap();
+
+        assert!(
+            get_vault_ownership_transfer(deps.as_ref().storage, vault.id)
+                .unwrap()
+                .is_none()
+        );
+    }
 }
@@ -0,0 +1,136 @@
+use crate::{
+    error::ContractError,
+    helpers::validation::{assert_fee_level_is_valid, assert_sender_is_admin},
+    state::custom_swap_fees::{get_custom_swap_fee_key, save_custom_swap_fee},
+};
+use cosmwasm_std::{Decimal, DepsMut, MessageInfo, Response};
+
+pub fn create_custom_swap_fee_handler(
+    deps: DepsMut,
+    info: MessageInfo,
+    denom: String,
+    paired_denom: Option<String>,
+    swap_fee_percent: Decimal,
+) -> Result<Response, ContractError> {
+    assert_sender_is_admin(deps.storage, info.sender)?;
+    assert_fee_level_is_valid(&swap_fee_percent)?;
+
+    if paired_denom.as_ref() == Some(&denom) {
+        return Err(ContractError::CustomError {
+            val: String::from("cannot create a custom swap fee for a pair of identical denoms"),
+        });
+    }
+
+    save_custom_swap_fee(
+        deps.storage,
+        &denom,
+        paired_denom.as_deref(),
+        swap_fee_percent,
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("create_custom_swap_fee", "true")
+        .add_attribute(
+            "key",
+            get_custom_swap_fee_key(&denom, paired_denom.as_deref()),
+        )
+        .add_attribute("swap_fee_percent", swap_fee_percent.to_string()))
+}
+
+#[cfg(test)]
+mod create_custom_swap_fee_tests {
+    use super::create_custom_swap_fee_handler;
+    use crate::{
+        state::custom_swap_fees::get_custom_swap_fee,
+        tests::{
+            helpers::instantiate_contract,
+            mocks::{ADMIN, DENOM_UKUJI, DENOM_UUSK, USER},
+        },
+    };
+    use cosmwasm_std::{
+        testing::{mock_dependencies, mock_env, mock_info},
+        Decimal,
+    };
+
+    #[test]
+    fn with_non_admin_sender_fails() {
+        let mut deps = mock_dependencies();
+
+        instantiate_contract(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]));
+
+        let err = create_custom_swap_fee_handler(
+            deps.as_mut(),
+            mock_info(USER, &[]),
+            DENOM_UKUJI.to_string(),
+            None,
+            Decimal::percent(1),
+        )
+        .unwrap_err();
+
+        assert_eq!(err.to_string(), "Unauthorized");
+    }
+
+    #[test]
+    fn with_fee_larger_than_5_percent_fails() {
+        let mut deps = mock_dependencies();
+
+        instantiate_contract(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]));
+
+        let err = create_custom_swap_fee_handler(
+            deps.as_mut(),
+            mock_info(ADMIN, &[]),
+            DENOM_UKUJI.to_string(),
+            None,
+            Decimal::percent(6),
+        )
+        .unwrap_err();
+
+        assert_eq!(err.to_string(), "Error: fee level cannot be larger than 5%");
+    }
+
+    #[test]
+    fn with_identical_paired_denom_fails() {
+        let mut deps = mock_dependencies();
+
+        instantiate_contract(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]));
+
+        let err = create_custom_swap_fee_handler(
+            deps.as_mut(),
+            mock_info(ADMIN, &[]),
+            DENOM_UKUJI.to_string(),
+            Some(DENOM_UKUJI.to_string()),
+            Decimal::percent(1),
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Error: cannot create a custom swap fee for a pair of identical denoms"
+        );
+    }
+
+    #[test]
+    fn saves_pair_fee_regardless_of_denom_order() {
+        let mut deps = mock_dependencies();
+
+        instantiate_contract(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]));
+
+        create_custom_swap_fee_handler(
+            deps.as_mut(),
+            mock_info(ADMIN, &[]),
+            DENOM_UUSK.to_string(),
+            Some(DENOM_UKUJI.to_string()),
+            Decimal::percent(1),
+        )
+        .unwrap();
+
+        assert_eq!(
+            get_custom_swap_fee(deps.as_ref().storage, DENOM_UKUJI, Some(DENOM_UUSK)).unwrap(),
+            Some(Decimal::percent(1))
+        );
+        assert_eq!(
+            get_custom_swap_fee(deps.as_ref().storage, DENOM_UKUJI, None).unwrap(),
+            None
+        );
+    }
+}
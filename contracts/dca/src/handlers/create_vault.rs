@@ -1,29 +1,41 @@
 use crate::constants::{AFTER_LIMIT_ORDER_PLACED_REPLY_ID, TWO_MICRONS};
 use crate::error::ContractError;
-use crate::helpers::message::get_attribute_in_event;
+use crate::handlers::deposit::get_deposit_conversion_message;
+use crate::helpers::message::{get_attribute_in_event, get_vault_reply_id, split_vault_reply_id};
+use crate::helpers::time::get_next_target_time;
 use crate::helpers::validation::{
-    assert_address_is_valid, assert_contract_destination_callbacks_are_valid,
-    assert_contract_is_not_paused, assert_destination_allocations_add_up_to_one,
-    assert_destination_callback_addresses_are_valid, assert_destinations_limit_is_not_breached,
+    assert_address_is_valid, assert_basket_denoms_are_unique, assert_basket_size_is_valid,
+    assert_basket_vault_options_are_supported, assert_basket_weights_add_up_to_one,
+    assert_contract_destination_callbacks_are_valid, assert_contract_is_not_paused,
+    assert_deposit_conversion_is_valid, assert_destination_actions_are_valid,
+    assert_destination_allocations_add_up_to_one, assert_destination_callback_addresses_are_valid,
+    assert_destinations_limit_is_not_breached, assert_end_conditions_are_valid,
+    assert_escrow_level_is_within_bounds, assert_escrow_vesting_schedule_is_valid,
     assert_exactly_one_asset, assert_label_is_no_longer_than_100_characters,
-    assert_no_destination_allocations_are_zero, assert_route_exists_for_denoms,
-    assert_slippage_tolerance_is_less_than_or_equal_to_one,
+    assert_missed_execution_policy_is_valid, assert_no_basket_weights_are_zero,
+    assert_no_destination_allocations_are_zero, assert_retry_policy_is_valid,
+    assert_route_exists_for_denoms, assert_slippage_tolerance_is_less_than_or_equal_to_one,
     assert_swap_adjustment_and_performance_assessment_strategies_are_compatible,
     assert_swap_adjustment_strategy_params_are_valid, assert_target_start_time_is_not_in_the_past,
     assert_time_interval_is_valid, assert_weighted_scale_multiplier_is_no_more_than_10,
 };
 use crate::helpers::vault::get_risk_weighted_average_model_id;
 use crate::msg::ExecuteMsg;
-use crate::state::cache::VAULT_ID_CACHE;
 use crate::state::config::get_config;
 use crate::state::events::create_event;
 use crate::state::triggers::save_trigger;
 use crate::state::vaults::{save_vault, update_vault};
-use crate::types::destination::Destination;
+use crate::types::basket::{BasketAsset, BasketAssetParams};
+use crate::types::deposit_conversion::DepositConversion;
+use crate::types::destination::{Destination, DestinationAction};
+use crate::types::end_conditions::{EndConditions, EndConditionsParams};
+use crate::types::escrow_vesting::{EscrowVesting, EscrowVestingSchedule};
 use crate::types::event::{EventBuilder, EventData};
+use crate::types::missed_execution_policy::MissedExecutionPolicy;
 use crate::types::performance_assessment_strategy::{
     PerformanceAssessmentStrategy, PerformanceAssessmentStrategyParams,
 };
+use crate::types::retry_policy::RetryPolicy;
 use crate::types::swap_adjustment_strategy::{
     SwapAdjustmentStrategy, SwapAdjustmentStrategyParams,
 };
@@ -31,13 +43,14 @@ use crate::types::time_interval::TimeInterval;
 use crate::types::trigger::{Trigger, TriggerConfiguration};
 use crate::types::vault::{Vault, VaultBuilder, VaultStatus};
 use cosmwasm_std::{to_json_binary, Addr, Binary, Coin, Decimal, Reply, SubMsg, WasmMsg};
-use cosmwasm_std::{DepsMut, Env, MessageInfo, Response, Timestamp, Uint128, Uint64};
+use cosmwasm_std::{Deps, DepsMut, Env, MessageInfo, Response, Timestamp, Uint128, Uint64};
 use exchange::msg::ExecuteMsg as ExchangeExecuteMsg;
 
-pub fn create_vault_handler(
-    deps: DepsMut,
-    env: Env,
-    info: &MessageInfo,
+#[allow(clippy::too_many_arguments)]
+pub fn get_vault_builder(
+    deps: Deps,
+    env: &Env,
+    funds: &[Coin],
     owner: Addr,
     label: Option<String>,
     mut destinations: Vec<Destination>,
@@ -51,19 +64,87 @@ pub fn create_vault_handler(
     target_receive_amount: Option<Uint128>,
     performance_assessment_strategy_params: Option<PerformanceAssessmentStrategyParams>,
     swap_adjustment_strategy_params: Option<SwapAdjustmentStrategyParams>,
-) -> Result<Response, ContractError> {
+    basket: Option<Vec<BasketAssetParams>>,
+    end_conditions: Option<EndConditionsParams>,
+    missed_execution_policy: Option<MissedExecutionPolicy>,
+    allow_partial_executions: Option<bool>,
+    retry_policy: Option<RetryPolicy>,
+    retry_failed_post_execution_actions: Option<bool>,
+    recurring_deposits: Option<bool>,
+    deposit_conversion: &Option<DepositConversion>,
+    referrer: Option<Addr>,
+    escrow_vesting: Option<EscrowVestingSchedule>,
+    escrow_level: Option<Decimal>,
+) -> Result<VaultBuilder, ContractError> {
     assert_contract_is_not_paused(deps.storage)?;
-    assert_address_is_valid(deps.as_ref(), &owner, "owner")?;
-    assert_exactly_one_asset(info.funds.clone())?;
+
+    if target_start_time_utc_seconds.is_some() && target_receive_amount.is_some() {
+        return Err(ContractError::CustomError {
+            val: String::from(
+                "cannot provide both a target_start_time_utc_seconds and a target_price",
+            ),
+        });
+    }
+    assert_address_is_valid(deps, &owner, "owner")?;
+    assert_exactly_one_asset(funds.to_vec())?;
     assert_destinations_limit_is_not_breached(&destinations)?;
     assert_time_interval_is_valid(&time_interval)?;
 
-    assert_route_exists_for_denoms(
-        deps.as_ref(),
-        info.funds[0].denom.clone(),
-        target_denom.clone(),
-        route.clone(),
-    )?;
+    let swap_denom = deposit_conversion.as_ref().map_or_else(
+        || funds[0].denom.clone(),
+        |conversion| conversion.minimum_receive_amount.denom.clone(),
+    );
+
+    if let Some(deposit_conversion) = deposit_conversion {
+        if target_receive_amount.is_some() {
+            return Err(ContractError::CustomError {
+                val: String::from("deposit conversion is not supported for price triggered vaults"),
+            });
+        }
+
+        assert_deposit_conversion_is_valid(deps, &funds[0], &swap_denom, deposit_conversion)?;
+    }
+
+    match &basket {
+        Some(basket) => {
+            assert_basket_size_is_valid(basket)?;
+            assert_basket_vault_options_are_supported(
+                &target_denom,
+                basket,
+                minimum_receive_amount,
+                target_receive_amount,
+                &performance_assessment_strategy_params,
+                &swap_adjustment_strategy_params,
+                &end_conditions,
+            )?;
+            assert_no_basket_weights_are_zero(basket)?;
+            assert_basket_weights_add_up_to_one(basket)?;
+            assert_basket_denoms_are_unique(&swap_denom, basket)?;
+
+            if allow_partial_executions.unwrap_or(false) {
+                return Err(ContractError::CustomError {
+                    val: String::from("basket vaults do not support partial executions"),
+                });
+            }
+
+            for asset in basket {
+                assert_route_exists_for_denoms(
+                    deps,
+                    swap_denom.clone(),
+                    asset.target_denom.clone(),
+                    asset.route.clone(),
+                )?;
+            }
+        }
+        None => {
+            assert_route_exists_for_denoms(
+                deps,
+                swap_denom.clone(),
+                target_denom.clone(),
+                route.clone(),
+            )?;
+        }
+    }
 
     assert_swap_adjustment_and_performance_assessment_strategies_are_compatible(
         &swap_adjustment_strategy_params,
@@ -89,22 +170,82 @@ pub fn create_vault_handler(
         )?;
     }
 
+    if let Some(end_conditions) = &end_conditions {
+        assert_end_conditions_are_valid(
+            env.block.time,
+            target_start_time_utc_seconds.map(|seconds| Timestamp::from_seconds(seconds.u64())),
+            end_conditions,
+        )?;
+    }
+
+    if let Some(missed_execution_policy) = &missed_execution_policy {
+        assert_missed_execution_policy_is_valid(missed_execution_policy)?;
+    }
+
+    if let Some(retry_policy) = &retry_policy {
+        assert_retry_policy_is_valid(retry_policy)?;
+    }
+
+    if recurring_deposits.unwrap_or(false) && target_receive_amount.is_some() {
+        return Err(ContractError::CustomError {
+            val: String::from("recurring deposits are only supported for time triggered vaults"),
+        });
+    }
+
+    if let Some(referrer) = &referrer {
+        assert_address_is_valid(deps, referrer, "referrer")?;
+
+        if *referrer == owner {
+            return Err(ContractError::CustomError {
+                val: String::from("vault owner cannot be its own referrer"),
+            });
+        }
+    }
+
+    if let Some(escrow_vesting) = &escrow_vesting {
+        if performance_assessment_strategy_params.is_none() {
+            return Err(ContractError::CustomError {
+                val: String::from(
+                    "escrow vesting is only supported for vaults with a performance assessment strategy",
+                ),
+            });
+        }
+
+        assert_escrow_vesting_schedule_is_valid(escrow_vesting)?;
+    }
+
     if destinations.is_empty() {
         destinations.push(Destination {
             allocation: Decimal::percent(100),
             address: owner.clone(),
-            msg: None,
+            action: DestinationAction::Send,
         });
     }
 
-    assert_destination_callback_addresses_are_valid(deps.as_ref(), &destinations)?;
+    assert_destination_callback_addresses_are_valid(deps, &destinations)?;
     assert_contract_destination_callbacks_are_valid(&destinations, &env.contract.address)?;
     assert_no_destination_allocations_are_zero(&destinations)?;
     assert_destination_allocations_add_up_to_one(&destinations)?;
+    assert_destination_actions_are_valid(
+        deps,
+        &destinations,
+        &basket.as_ref().map_or_else(
+            || vec![target_denom.clone()],
+            |basket| {
+                basket
+                    .iter()
+                    .map(|asset| asset.target_denom.clone())
+                    .collect()
+            },
+        ),
+    )?;
 
     let config = get_config(deps.storage)?;
 
-    let swap_denom = info.funds[0].denom.clone();
+    let balance = match deposit_conversion {
+        Some(_) => Coin::new(0, swap_denom.clone()),
+        None => funds[0].clone(),
+    };
 
     let swap_adjustment_strategy = match swap_adjustment_strategy_params {
         Some(params) => Some(match params {
@@ -114,7 +255,7 @@ pub fn create_vault_handler(
             } => SwapAdjustmentStrategy::RiskWeightedAverage {
                 model_id: get_risk_weighted_average_model_id(
                     &env.block.time,
-                    &info.funds[0],
+                    &balance,
                     &swap_amount,
                     &time_interval,
                 ),
@@ -144,16 +285,32 @@ pub fn create_vault_handler(
                 received_amount: Coin::new(0, target_denom.clone()),
             })
         }
+        Some(PerformanceAssessmentStrategyParams::HighWaterMark) => {
+            Some(PerformanceAssessmentStrategy::HighWaterMark {
+                mark: Decimal::one(),
+            })
+        }
+        Some(PerformanceAssessmentStrategyParams::CompareToLumpSum) => {
+            Some(PerformanceAssessmentStrategy::CompareToLumpSum { start_price: None })
+        }
         _ => None,
     };
 
-    let escrow_level = performance_assessment_strategy
-        .clone()
-        .map_or(Decimal::zero(), |_| {
-            config.risk_weighted_average_escrow_level
-        });
+    let escrow_level = match (&performance_assessment_strategy, escrow_level) {
+        (None, Some(_)) => return Err(ContractError::CustomError {
+            val: String::from(
+                "escrow level can only be set for vaults with a performance assessment strategy",
+            ),
+        }),
+        (None, None) => Decimal::zero(),
+        (Some(_), Some(escrow_level)) => {
+            assert_escrow_level_is_within_bounds(&config, escrow_level)?;
+            escrow_level
+        }
+        (Some(_), None) => config.risk_weighted_average_escrow_level,
+    };
 
-    let vault_builder = VaultBuilder {
+    Ok(VaultBuilder {
         owner,
         label,
         destinations,
@@ -164,50 +321,140 @@ pub fn create_vault_handler(
         route,
         slippage_tolerance: slippage_tolerance.unwrap_or(config.default_slippage_tolerance),
         minimum_receive_amount,
-        balance: info.funds[0].clone(),
+        balance: balance.clone(),
         time_interval,
         started_at: None,
         escrow_level,
-        deposited_amount: info.funds[0].clone(),
+        base_escrow_level: config.risk_weighted_average_escrow_level,
+        deposited_amount: balance,
         swapped_amount: Coin::new(0, swap_denom),
         received_amount: Coin::new(0, target_denom.clone()),
         escrowed_amount: Coin::new(0, target_denom),
         swap_adjustment_strategy,
         performance_assessment_strategy,
-    };
+        basket: basket.map(|basket| basket.into_iter().map(BasketAsset::from).collect()),
+        end_conditions: end_conditions.map(EndConditions::from),
+        missed_execution_policy: missed_execution_policy.unwrap_or(MissedExecutionPolicy::Skip),
+        allow_partial_executions: allow_partial_executions.unwrap_or(false),
+        retry_policy,
+        retry_failed_post_execution_actions: retry_failed_post_execution_actions.unwrap_or(false),
+        recurring_deposits: recurring_deposits.unwrap_or(false),
+        referrer,
+        escrow_vesting: escrow_vesting.map(|schedule| EscrowVesting {
+            schedule,
+            released_tranches: 0,
+        }),
+    })
+}
 
-    let vault = save_vault(deps.storage, vault_builder)?;
+#[allow(clippy::too_many_arguments)]
+pub fn create_vault_handler(
+    mut deps: DepsMut,
+    env: Env,
+    info: &MessageInfo,
+    owner: Addr,
+    label: Option<String>,
+    destinations: Vec<Destination>,
+    target_denom: String,
+    route: Option<Binary>,
+    slippage_tolerance: Option<Decimal>,
+    minimum_receive_amount: Option<Uint128>,
+    swap_amount: Uint128,
+    time_interval: TimeInterval,
+    target_start_time_utc_seconds: Option<Uint64>,
+    target_receive_amount: Option<Uint128>,
+    performance_assessment_strategy_params: Option<PerformanceAssessmentStrategyParams>,
+    swap_adjustment_strategy_params: Option<SwapAdjustmentStrategyParams>,
+    basket: Option<Vec<BasketAssetParams>>,
+    end_conditions: Option<EndConditionsParams>,
+    missed_execution_policy: Option<MissedExecutionPolicy>,
+    allow_partial_executions: Option<bool>,
+    retry_policy: Option<RetryPolicy>,
+    retry_failed_post_execution_actions: Option<bool>,
+    recurring_deposits: Option<bool>,
+    deposit_conversion: Option<DepositConversion>,
+    referrer: Option<Addr>,
+    escrow_vesting: Option<EscrowVestingSchedule>,
+    escrow_level: Option<Decimal>,
+) -> Result<Response, ContractError> {
+    let vault_builder = get_vault_builder(
+        deps.as_ref(),
+        &env,
+        &info.funds,
+        owner,
+        label,
+        destinations,
+        target_denom,
+        route,
+        slippage_tolerance,
+        minimum_receive_amount,
+        swap_amount,
+        time_interval,
+        target_start_time_utc_seconds,
+        target_receive_amount,
+        performance_assessment_strategy_params,
+        swap_adjustment_strategy_params,
+        basket,
+        end_conditions,
+        missed_execution_policy,
+        allow_partial_executions,
+        retry_policy,
+        retry_failed_post_execution_actions,
+        recurring_deposits,
+        &deposit_conversion,
+        referrer,
+        escrow_vesting,
+        escrow_level,
+    )?;
 
-    VAULT_ID_CACHE.save(deps.storage, &vault.id)?;
+    let config = get_config(deps.storage)?;
 
-    create_event(
-        deps.storage,
-        EventBuilder::new(
-            vault.id,
-            env.block.clone(),
-            EventData::DcaVaultFundsDeposited {
-                amount: Coin::new(
-                    (info.funds[0].amount
-                        - if target_receive_amount.is_some() {
-                            TWO_MICRONS
-                        } else {
-                            Uint128::zero()
-                        })
-                    .into(),
-                    info.funds[0].denom.clone(),
-                ),
-            },
-        ),
-    )?;
+    let vault = save_vault(deps.storage, vault_builder)?;
 
     let mut response = Response::new()
         .add_attribute("create_vault", "true")
         .add_attribute("vault_id", vault.id)
-        .add_attribute("owner", vault.owner.clone())
-        .add_attribute("deposited_amount", vault.balance.to_string());
+        .add_attribute("owner", vault.owner.clone());
+
+    match deposit_conversion {
+        Some(deposit_conversion) => {
+            response = response
+                .add_attribute("converted_amount", info.funds[0].to_string())
+                .add_submessage(get_deposit_conversion_message(
+                    deps.branch(),
+                    &env,
+                    vault.id,
+                    info.funds[0].clone(),
+                    deposit_conversion,
+                )?);
+        }
+        None => {
+            create_event(
+                deps.storage,
+                EventBuilder::new(
+                    vault.id,
+                    env.block.clone(),
+                    EventData::DcaVaultFundsDeposited {
+                        amount: Coin::new(
+                            (info.funds[0].amount
+                                - if target_receive_amount.is_some() {
+                                    TWO_MICRONS
+                                } else {
+                                    Uint128::zero()
+                                })
+                            .into(),
+                            info.funds[0].denom.clone(),
+                        ),
+                    },
+                ),
+            )?;
+
+            response = response.add_attribute("deposited_amount", vault.balance.to_string());
+        }
+    }
 
-    match (target_start_time_utc_seconds, target_receive_amount) {
-        (None, None) | (Some(_), None) => {
+    match target_receive_amount {
+        None => {
             save_trigger(
                 deps.storage,
                 Trigger {
@@ -215,13 +462,22 @@ pub fn create_vault_handler(
                     configuration: TriggerConfiguration::Time {
                         target_time: match target_start_time_utc_seconds {
                             Some(time) => Timestamp::from_seconds(time.u64()),
+                            None if vault.time_interval.is_calendar_schedule() => {
+                                get_next_target_time(
+                                    env.block.time,
+                                    env.block.time,
+                                    vault.time_interval.clone(),
+                                )
+                            }
                             None => env.block.time,
                         },
                     },
                 },
             )?;
 
-            if target_start_time_utc_seconds.is_none() {
+            if target_start_time_utc_seconds.is_none()
+                && !vault.time_interval.is_calendar_schedule()
+            {
                 response = response.add_submessage(SubMsg::new(WasmMsg::Execute {
                     contract_addr: env.contract.address.to_string(),
                     msg: to_json_binary(&ExecuteMsg::ExecuteTrigger {
@@ -235,7 +491,7 @@ pub fn create_vault_handler(
 
             Ok(response)
         }
-        (None, Some(target_receive_amount)) => {
+        Some(target_receive_amount) => {
             let vault = update_vault(
                 deps.storage,
                 Vault {
@@ -263,14 +519,9 @@ pub fn create_vault_handler(
                     .unwrap(),
                     funds: vec![Coin::new(TWO_MICRONS.into(), vault.get_swap_denom())],
                 },
-                AFTER_LIMIT_ORDER_PLACED_REPLY_ID,
+                get_vault_reply_id(AFTER_LIMIT_ORDER_PLACED_REPLY_ID, vault.id),
             )))
         }
-        (Some(_), Some(_)) => Err(ContractError::CustomError {
-            val: String::from(
-                "cannot provide both a target_start_time_utc_seconds and a target_price",
-            ),
-        }),
     }
 }
 
@@ -286,7 +537,7 @@ pub fn save_price_trigger(deps: DepsMut, reply: Reply) -> Result<Response, Contr
             .parse::<Decimal>()
             .expect("the target price of the submitted order");
 
-    let vault_id = VAULT_ID_CACHE.load(deps.storage)?;
+    let (_, vault_id) = split_vault_reply_id(reply.id);
 
     save_trigger(
         deps.storage,
@@ -307,17 +558,19 @@ pub fn save_price_trigger(deps: DepsMut, reply: Reply) -> Result<Response, Contr
 #[cfg(test)]
 mod create_vault_tests {
     use super::*;
-    use crate::constants::{ONE, TEN};
+    use crate::constants::{AFTER_DEPOSIT_CONVERSION_REPLY_ID, ONE, TEN};
     use crate::handlers::get_events_by_resource_id::get_events_by_resource_id_handler;
     use crate::handlers::get_vault::get_vault_handler;
+    use crate::helpers::message::get_vault_reply_id;
     use crate::msg::ExecuteMsg;
     use crate::state::config::{get_config, update_config};
-    use crate::tests::helpers::instantiate_contract;
+    use crate::state::triggers::get_trigger;
+    use crate::tests::helpers::{instantiate_contract, setup_vault};
     use crate::tests::mocks::{
         calc_mock_dependencies, ADMIN, DENOM_UKUJI, DENOM_UUSK, USER, VALIDATOR,
     };
     use crate::types::config::Config;
-    use crate::types::destination::Destination;
+    use crate::types::destination::{Destination, DestinationAction};
     use crate::types::event::{EventBuilder, EventData};
     use crate::types::swap_adjustment_strategy::SwapAdjustmentStrategy;
     use crate::types::time_interval::TimeInterval;
@@ -325,8 +578,8 @@ mod create_vault_tests {
     use crate::types::vault::{Vault, VaultStatus};
     use cosmwasm_std::testing::{mock_env, mock_info};
     use cosmwasm_std::{
-        to_json_binary, Addr, Coin, ContractResult, Decimal, Decimal256, SubMsg, SystemResult,
-        Timestamp, Uint128, WasmMsg,
+        to_json_binary, Addr, Coin, ContractResult, CosmosMsg, Decimal, Decimal256, SubMsg,
+        SystemResult, Timestamp, Uint128, WasmMsg,
     };
     use exchange::msg::Pair;
 
@@ -355,6 +608,17 @@ mod create_vault_tests {
             None,
             None,
             None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap_err();
 
@@ -392,6 +656,17 @@ mod create_vault_tests {
             None,
             None,
             None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap_err();
 
@@ -432,6 +707,17 @@ mod create_vault_tests {
             None,
             None,
             None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap_err();
 
@@ -463,7 +749,7 @@ mod create_vault_tests {
             vec![Destination {
                 allocation: Decimal::percent(50),
                 address: Addr::unchecked(USER),
-                msg: None,
+                action: DestinationAction::Send,
             }],
             DENOM_UKUJI.to_string(),
             None,
@@ -475,6 +761,17 @@ mod create_vault_tests {
             None,
             None,
             None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap_err();
 
@@ -504,12 +801,12 @@ mod create_vault_tests {
                 Destination {
                     allocation: Decimal::percent(100),
                     address: Addr::unchecked(USER),
-                    msg: None,
+                    action: DestinationAction::Send,
                 },
                 Destination {
                     allocation: Decimal::percent(0),
                     address: Addr::unchecked("other"),
-                    msg: None,
+                    action: DestinationAction::Send,
                 },
             ],
             DENOM_UKUJI.to_string(),
@@ -522,6 +819,17 @@ mod create_vault_tests {
             None,
             None,
             None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap_err();
 
@@ -549,7 +857,7 @@ mod create_vault_tests {
                 .map(|i| Destination {
                     allocation: Decimal::percent(5),
                     address: Addr::unchecked(format!("destination-{}", i)),
-                    msg: None,
+                    action: DestinationAction::Send,
                 })
                 .collect(),
             DENOM_UKUJI.to_string(),
@@ -562,6 +870,17 @@ mod create_vault_tests {
             None,
             None,
             None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap_err();
 
@@ -602,6 +921,17 @@ mod create_vault_tests {
                 multiplier: Decimal::percent(1100),
                 increase_only: false,
             }),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap_err();
 
@@ -647,6 +977,17 @@ mod create_vault_tests {
             None,
             None,
             None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap_err();
 
@@ -680,6 +1021,17 @@ mod create_vault_tests {
             None,
             None,
             None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap_err();
 
@@ -714,6 +1066,17 @@ mod create_vault_tests {
             None,
             None,
             None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap_err();
 
@@ -724,55 +1087,61 @@ mod create_vault_tests {
     }
 
     #[test]
-    fn with_both_target_time_and_target_price_fails() {
+    fn with_invalid_time_of_day_fails() {
         let mut deps = calc_mock_dependencies();
         let env = mock_env();
-        let info = mock_info(ADMIN, &[]);
+        let info = mock_info(USER, &[Coin::new(10000, DENOM_UUSK)]);
 
         instantiate_contract(deps.as_mut(), env.clone(), info.clone());
 
-        let pair = Pair::default();
-
         let err = create_vault_handler(
             deps.as_mut(),
-            env.clone(),
-            &mock_info(ADMIN, &[Coin::new(1233123, pair.denoms[0].clone())]),
-            info.sender,
+            env,
+            &info,
+            info.sender.clone(),
             None,
             vec![],
-            pair.denoms[1].clone(),
+            DENOM_UKUJI.to_string(),
             None,
             None,
             None,
             Uint128::new(100000),
-            TimeInterval::Daily,
-            Some(env.block.time.seconds().into()),
-            Some(Uint128::new(872316)),
+            TimeInterval::DailyAt {
+                hour: 24,
+                minute: 0,
+            },
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
             None,
             None,
         )
         .unwrap_err();
 
-        assert_eq!(
-            err.to_string(),
-            "Error: cannot provide both a target_start_time_utc_seconds and a target_price"
-        );
+        assert_eq!(err.to_string(), "Error: 24:00 is not a valid time of day");
     }
 
     #[test]
-    fn with_no_swap_adjustment_strategy_and_performance_assessment_strategy_fails() {
+    fn with_no_weekdays_fails() {
         let mut deps = calc_mock_dependencies();
         let env = mock_env();
-        let mut info = mock_info(ADMIN, &[]);
+        let info = mock_info(USER, &[Coin::new(10000, DENOM_UUSK)]);
 
         instantiate_contract(deps.as_mut(), env.clone(), info.clone());
 
-        let swap_amount = Uint128::new(100000);
-        info = mock_info(USER, &[Coin::new(100000, DENOM_UUSK)]);
-
         let err = create_vault_handler(
             deps.as_mut(),
-            env.clone(),
+            env,
             &info,
             info.sender.clone(),
             None,
@@ -781,35 +1150,47 @@ mod create_vault_tests {
             None,
             None,
             None,
-            swap_amount,
-            TimeInterval::Daily,
-            Some(env.block.time.plus_seconds(10).seconds().into()),
+            Uint128::new(100000),
+            TimeInterval::WeekdaysAt {
+                weekdays: vec![],
+                hour: 9,
+                minute: 0,
+            },
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
             None,
-            Some(PerformanceAssessmentStrategyParams::CompareToStandardDca),
             None,
         )
         .unwrap_err();
 
         assert_eq!(
             err.to_string(),
-            "Error: incompatible swap adjustment and performance assessment strategies"
+            "Error: at least one weekday must be provided"
         );
     }
 
     #[test]
-    fn with_swap_adjustment_strategy_and_no_performance_assessment_strategy_fails() {
+    fn with_zero_retry_delay_fails() {
         let mut deps = calc_mock_dependencies();
         let env = mock_env();
-        let mut info = mock_info(ADMIN, &[]);
+        let info = mock_info(USER, &[Coin::new(10000, DENOM_UUSK)]);
 
         instantiate_contract(deps.as_mut(), env.clone(), info.clone());
 
-        let swap_amount = Uint128::new(100000);
-        info = mock_info(USER, &[Coin::new(100000, DENOM_UUSK)]);
-
         let err = create_vault_handler(
             deps.as_mut(),
-            env.clone(),
+            env,
             &info,
             info.sender.clone(),
             None,
@@ -818,35 +1199,46 @@ mod create_vault_tests {
             None,
             None,
             None,
-            swap_amount,
+            Uint128::new(100000),
             TimeInterval::Daily,
-            Some(env.block.time.plus_seconds(10).seconds().into()),
             None,
             None,
-            Some(SwapAdjustmentStrategyParams::default()),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(RetryPolicy {
+                retry_delay_minutes: 0,
+                max_attempts: 3,
+            }),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap_err();
 
         assert_eq!(
             err.to_string(),
-            "Error: incompatible swap adjustment and performance assessment strategies"
+            "Error: retry delay must be at least 1 minute"
         );
     }
 
     #[test]
-    fn with_weighted_scale_multiplier_larger_than_10_fails() {
+    fn with_too_many_retry_attempts_fails() {
         let mut deps = calc_mock_dependencies();
         let env = mock_env();
-        let mut info = mock_info(ADMIN, &[]);
+        let info = mock_info(USER, &[Coin::new(10000, DENOM_UUSK)]);
 
         instantiate_contract(deps.as_mut(), env.clone(), info.clone());
 
-        let swap_amount = Uint128::new(100000);
-        info = mock_info(USER, &[Coin::new(100000, DENOM_UUSK)]);
-
         let err = create_vault_handler(
             deps.as_mut(),
-            env.clone(),
+            env,
             &info,
             info.sender.clone(),
             None,
@@ -855,36 +1247,43 @@ mod create_vault_tests {
             None,
             None,
             None,
-            swap_amount,
+            Uint128::new(100000),
             TimeInterval::Daily,
-            Some(env.block.time.plus_seconds(10).seconds().into()),
             None,
             None,
-            Some(SwapAdjustmentStrategyParams::WeightedScale {
-                base_receive_amount: Uint128::new(232231),
-                multiplier: Decimal::percent(1001),
-                increase_only: false,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(RetryPolicy {
+                retry_delay_minutes: 15,
+                max_attempts: 11,
             }),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap_err();
 
         assert_eq!(
             err.to_string(),
-            "Error: Cannot set weighted scale multiplier to more than 10"
+            "Error: max retry attempts must be between 1 and 10"
         );
     }
 
     #[test]
-    fn with_slippage_tolerance_larger_than_one_fails() {
+    fn with_invalid_day_of_month_fails() {
         let mut deps = calc_mock_dependencies();
         let env = mock_env();
-        let mut info = mock_info(ADMIN, &[]);
+        let info = mock_info(USER, &[Coin::new(10000, DENOM_UUSK)]);
 
         instantiate_contract(deps.as_mut(), env.clone(), info.clone());
 
-        let swap_amount = Uint128::new(100000);
-        info = mock_info(USER, &[Coin::new(100000, DENOM_UUSK)]);
-
         let err = create_vault_handler(
             deps.as_mut(),
             env,
@@ -894,10 +1293,25 @@ mod create_vault_tests {
             vec![],
             DENOM_UKUJI.to_string(),
             None,
-            Some(Decimal::percent(150)),
             None,
-            swap_amount,
-            TimeInterval::Daily,
+            None,
+            Uint128::new(100000),
+            TimeInterval::DaysOfMonthAt {
+                days: vec![1, 32],
+                hour: 9,
+                minute: 0,
+            },
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
             None,
             None,
             None,
@@ -907,24 +1321,21 @@ mod create_vault_tests {
 
         assert_eq!(
             err.to_string(),
-            "Error: slippage tolerance must be less than or equal to 1"
+            "Error: days of the month must be between 1 and 31"
         );
     }
 
     #[test]
-    fn should_create_vault_with_time_trigger() {
+    fn with_missed_executions_spread_over_zero_executions_fails() {
         let mut deps = calc_mock_dependencies();
         let env = mock_env();
-        let mut info = mock_info(ADMIN, &[]);
+        let info = mock_info(USER, &[Coin::new(10000, DENOM_UUSK)]);
 
         instantiate_contract(deps.as_mut(), env.clone(), info.clone());
 
-        let swap_amount = Uint128::new(100000);
-        info = mock_info(USER, &[Coin::new(100000, DENOM_UUSK)]);
-
-        create_vault_handler(
+        let err = create_vault_handler(
             deps.as_mut(),
-            env.clone(),
+            env,
             &info,
             info.sender.clone(),
             None,
@@ -933,135 +1344,91 @@ mod create_vault_tests {
             None,
             None,
             None,
-            swap_amount,
+            Uint128::new(100000),
             TimeInterval::Daily,
-            Some(env.block.time.plus_seconds(10).seconds().into()),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(MissedExecutionPolicy::Spread { executions: 0 }),
+            None,
+            None,
+            None,
+            None,
+            None,
             None,
             None,
             None,
         )
-        .unwrap();
-
-        let vault = get_vault_handler(deps.as_ref(), Uint128::one())
-            .unwrap()
-            .vault;
-
-        let config = get_config(deps.as_ref().storage).unwrap();
+        .unwrap_err();
 
         assert_eq!(
-            vault,
-            Vault {
-                minimum_receive_amount: None,
-                label: None,
-                id: Uint128::new(1),
-                owner: info.sender,
-                destinations: vec![Destination::default()],
-                created_at: env.block.time,
-                status: VaultStatus::Scheduled,
-                time_interval: TimeInterval::Daily,
-                balance: info.funds[0].clone(),
-                slippage_tolerance: config.default_slippage_tolerance,
-                route: None,
-                swap_amount,
-                target_denom: DENOM_UKUJI.to_string(),
-                started_at: None,
-                deposited_amount: info.funds[0].clone(),
-                escrow_level: Decimal::zero(),
-                swapped_amount: Coin::new(0, DENOM_UUSK.to_string()),
-                received_amount: Coin::new(0, DENOM_UKUJI.to_string()),
-                escrowed_amount: Coin::new(0, DENOM_UKUJI.to_string()),
-                swap_adjustment_strategy: None,
-                performance_assessment_strategy: None,
-                trigger: Some(TriggerConfiguration::Time {
-                    target_time: Timestamp::from_seconds(env.block.time.plus_seconds(10).seconds()),
-                }),
-            }
+            err.to_string(),
+            "Error: missed executions must be spread over at least 1 execution"
         );
     }
 
     #[test]
-    fn should_create_vault_with_pending_price_trigger() {
+    fn with_both_target_time_and_target_price_fails() {
         let mut deps = calc_mock_dependencies();
         let env = mock_env();
-        let mut info = mock_info(ADMIN, &[]);
+        let info = mock_info(ADMIN, &[]);
 
         instantiate_contract(deps.as_mut(), env.clone(), info.clone());
 
-        let swap_amount = ONE;
-        info = mock_info(USER, &[Coin::new(TEN.into(), DENOM_UUSK)]);
+        let pair = Pair::default();
 
-        create_vault_handler(
+        let err = create_vault_handler(
             deps.as_mut(),
             env.clone(),
-            &info,
-            info.sender.clone(),
+            &mock_info(ADMIN, &[Coin::new(1233123, pair.denoms[0].clone())]),
+            info.sender,
             None,
             vec![],
-            DENOM_UKUJI.to_string(),
+            pair.denoms[1].clone(),
             None,
             None,
             None,
-            swap_amount,
+            Uint128::new(100000),
             TimeInterval::Daily,
+            Some(env.block.time.seconds().into()),
+            Some(Uint128::new(872316)),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
             None,
-            Some(ONE / TWO_MICRONS),
             None,
             None,
         )
-        .unwrap();
-
-        let vault = get_vault_handler(deps.as_ref(), Uint128::one())
-            .unwrap()
-            .vault;
-
-        let config = get_config(deps.as_ref().storage).unwrap();
+        .unwrap_err();
 
         assert_eq!(
-            vault,
-            Vault {
-                minimum_receive_amount: None,
-                label: None,
-                id: Uint128::new(1),
-                owner: info.sender,
-                destinations: vec![Destination::default()],
-                created_at: env.block.time,
-                status: VaultStatus::Scheduled,
-                time_interval: TimeInterval::Daily,
-                balance: Coin::new(
-                    (info.funds[0].amount - TWO_MICRONS).into(),
-                    info.funds[0].denom.clone()
-                ),
-                slippage_tolerance: config.default_slippage_tolerance,
-                swap_amount,
-                target_denom: DENOM_UKUJI.to_string(),
-                route: None,
-                started_at: None,
-                deposited_amount: Coin::new(
-                    (info.funds[0].amount - TWO_MICRONS).into(),
-                    info.funds[0].denom.clone()
-                ),
-                escrow_level: Decimal::zero(),
-                swapped_amount: Coin::new(0, DENOM_UUSK.to_string()),
-                received_amount: Coin::new(0, DENOM_UKUJI.to_string()),
-                escrowed_amount: Coin::new(0, DENOM_UKUJI.to_string()),
-                swap_adjustment_strategy: None,
-                performance_assessment_strategy: None,
-                trigger: None,
-            }
+            err.to_string(),
+            "Error: cannot provide both a target_start_time_utc_seconds and a target_price"
         );
     }
 
     #[test]
-    fn should_publish_deposit_event() {
+    fn with_no_swap_adjustment_strategy_and_performance_assessment_strategy_fails() {
         let mut deps = calc_mock_dependencies();
         let env = mock_env();
         let mut info = mock_info(ADMIN, &[]);
 
         instantiate_contract(deps.as_mut(), env.clone(), info.clone());
 
+        let swap_amount = Uint128::new(100000);
         info = mock_info(USER, &[Coin::new(100000, DENOM_UUSK)]);
 
-        create_vault_handler(
+        let err = create_vault_handler(
             deps.as_mut(),
             env.clone(),
             &info,
@@ -1072,143 +1439,92 @@ mod create_vault_tests {
             None,
             None,
             None,
-            Uint128::new(100000),
+            swap_amount,
             TimeInterval::Daily,
             Some(env.block.time.plus_seconds(10).seconds().into()),
             None,
+            Some(PerformanceAssessmentStrategyParams::CompareToStandardDca),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
             None,
             None,
         )
-        .unwrap();
+        .unwrap_err();
 
-        let events =
-            get_events_by_resource_id_handler(deps.as_ref(), Uint128::one(), None, None, None)
-                .unwrap()
-                .events;
-
-        assert!(events.contains(
-            &EventBuilder::new(
-                Uint128::one(),
-                env.block,
-                EventData::DcaVaultFundsDeposited {
-                    amount: info.funds[0].clone()
-                },
-            )
-            .build(1),
-        ))
+        assert_eq!(
+            err.to_string(),
+            "Error: incompatible swap adjustment and performance assessment strategies"
+        );
     }
 
     #[test]
-    fn for_different_owner_should_succeed() {
+    fn with_swap_adjustment_strategy_and_no_performance_assessment_strategy_fails() {
         let mut deps = calc_mock_dependencies();
         let env = mock_env();
         let mut info = mock_info(ADMIN, &[]);
 
         instantiate_contract(deps.as_mut(), env.clone(), info.clone());
 
-        let owner = Addr::unchecked(USER);
-        info = mock_info(ADMIN, &[Coin::new(100000, DENOM_UUSK)]);
+        let swap_amount = Uint128::new(100000);
+        info = mock_info(USER, &[Coin::new(100000, DENOM_UUSK)]);
 
-        create_vault_handler(
+        let err = create_vault_handler(
             deps.as_mut(),
             env.clone(),
             &info,
-            owner,
+            info.sender.clone(),
             None,
             vec![],
             DENOM_UKUJI.to_string(),
             None,
             None,
             None,
-            Uint128::new(100000),
+            swap_amount,
             TimeInterval::Daily,
             Some(env.block.time.plus_seconds(10).seconds().into()),
             None,
             None,
+            Some(SwapAdjustmentStrategyParams::default()),
+            None,
+            None,
+            None,
             None,
-        )
-        .unwrap();
-
-        let vault = get_vault_handler(deps.as_ref(), Uint128::one())
-            .unwrap()
-            .vault;
-
-        assert_eq!(vault.owner, Addr::unchecked(USER));
-    }
-
-    #[test]
-    fn with_multiple_destinations_should_succeed() {
-        let mut deps = calc_mock_dependencies();
-        let env = mock_env();
-        let mut info = mock_info(ADMIN, &[]);
-
-        instantiate_contract(deps.as_mut(), env.clone(), info.clone());
-
-        info = mock_info(USER, &[Coin::new(100000, DENOM_UUSK)]);
-
-        let destinations = vec![
-            Destination {
-                allocation: Decimal::percent(50),
-                address: env.contract.address.clone(),
-                msg: Some(
-                    to_json_binary(&ExecuteMsg::ZDelegate {
-                        delegator_address: Addr::unchecked("dest-1"),
-                        validator_address: Addr::unchecked(VALIDATOR),
-                    })
-                    .unwrap(),
-                ),
-            },
-            Destination {
-                allocation: Decimal::percent(50),
-                address: env.contract.address.clone(),
-                msg: Some(
-                    to_json_binary(&ExecuteMsg::ZDelegate {
-                        delegator_address: Addr::unchecked("dest-2"),
-                        validator_address: Addr::unchecked(VALIDATOR),
-                    })
-                    .unwrap(),
-                ),
-            },
-        ];
-
-        create_vault_handler(
-            deps.as_mut(),
-            env.clone(),
-            &info,
-            info.sender.clone(),
             None,
-            destinations.clone(),
-            DENOM_UKUJI.to_string(),
             None,
             None,
             None,
-            Uint128::new(100000),
-            TimeInterval::Daily,
-            Some(env.block.time.plus_seconds(10).seconds().into()),
             None,
             None,
             None,
         )
-        .unwrap();
-
-        let vault = get_vault_handler(deps.as_ref(), Uint128::one())
-            .unwrap()
-            .vault;
+        .unwrap_err();
 
-        assert_eq!(vault.destinations, destinations);
+        assert_eq!(
+            err.to_string(),
+            "Error: incompatible swap adjustment and performance assessment strategies"
+        );
     }
 
     #[test]
-    fn should_create_swap_adjustment_strategy() {
+    fn with_weighted_scale_multiplier_larger_than_10_fails() {
         let mut deps = calc_mock_dependencies();
         let env = mock_env();
         let mut info = mock_info(ADMIN, &[]);
 
         instantiate_contract(deps.as_mut(), env.clone(), info.clone());
 
+        let swap_amount = Uint128::new(100000);
         info = mock_info(USER, &[Coin::new(100000, DENOM_UUSK)]);
 
-        create_vault_handler(
+        let err = create_vault_handler(
             deps.as_mut(),
             env.clone(),
             &info,
@@ -1219,74 +1535,93 @@ mod create_vault_tests {
             None,
             None,
             None,
-            Uint128::new(100000),
+            swap_amount,
             TimeInterval::Daily,
             Some(env.block.time.plus_seconds(10).seconds().into()),
             None,
-            Some(PerformanceAssessmentStrategyParams::CompareToStandardDca),
-            Some(SwapAdjustmentStrategyParams::default()),
+            None,
+            Some(SwapAdjustmentStrategyParams::WeightedScale {
+                base_receive_amount: Uint128::new(232231),
+                multiplier: Decimal::percent(1001),
+                increase_only: false,
+            }),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
         )
-        .unwrap();
-
-        let vault = get_vault_handler(deps.as_ref(), Uint128::one())
-            .unwrap()
-            .vault;
+        .unwrap_err();
 
         assert_eq!(
-            vault.swap_adjustment_strategy,
-            Some(SwapAdjustmentStrategy::default())
+            err.to_string(),
+            "Error: Cannot set weighted scale multiplier to more than 10"
         );
     }
 
     #[test]
-    fn should_save_no_performance_assessment_strategy_when_none_provided() {
+    fn with_slippage_tolerance_larger_than_one_fails() {
         let mut deps = calc_mock_dependencies();
         let env = mock_env();
         let mut info = mock_info(ADMIN, &[]);
 
         instantiate_contract(deps.as_mut(), env.clone(), info.clone());
 
+        let swap_amount = Uint128::new(100000);
         info = mock_info(USER, &[Coin::new(100000, DENOM_UUSK)]);
 
-        create_vault_handler(
+        let err = create_vault_handler(
             deps.as_mut(),
-            env.clone(),
+            env,
             &info,
             info.sender.clone(),
             None,
             vec![],
             DENOM_UKUJI.to_string(),
             None,
+            Some(Decimal::percent(150)),
             None,
-            None,
-            Uint128::new(100000),
+            swap_amount,
             TimeInterval::Daily,
-            Some(env.block.time.plus_seconds(10).seconds().into()),
             None,
             None,
-            Some(SwapAdjustmentStrategyParams::WeightedScale {
-                base_receive_amount: Uint128::new(100000),
-                multiplier: Decimal::percent(200),
-                increase_only: false,
-            }),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
         )
-        .unwrap();
-
-        let vault = get_vault_handler(deps.as_ref(), Uint128::one())
-            .unwrap()
-            .vault;
+        .unwrap_err();
 
-        assert_eq!(vault.performance_assessment_strategy, None);
+        assert_eq!(
+            err.to_string(),
+            "Error: slippage tolerance must be less than or equal to 1"
+        );
     }
 
     #[test]
-    fn should_create_performance_assessment_strategy() {
+    fn should_create_vault_with_time_trigger() {
         let mut deps = calc_mock_dependencies();
         let env = mock_env();
         let mut info = mock_info(ADMIN, &[]);
 
         instantiate_contract(deps.as_mut(), env.clone(), info.clone());
 
+        let swap_amount = Uint128::new(100000);
         info = mock_info(USER, &[Coin::new(100000, DENOM_UUSK)]);
 
         create_vault_handler(
@@ -1300,12 +1635,23 @@ mod create_vault_tests {
             None,
             None,
             None,
-            Uint128::new(100000),
+            swap_amount,
             TimeInterval::Daily,
             Some(env.block.time.plus_seconds(10).seconds().into()),
             None,
-            Some(PerformanceAssessmentStrategyParams::CompareToStandardDca),
-            Some(SwapAdjustmentStrategyParams::default()),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap();
 
@@ -1313,24 +1659,60 @@ mod create_vault_tests {
             .unwrap()
             .vault;
 
+        let config = get_config(deps.as_ref().storage).unwrap();
+
         assert_eq!(
-            vault.performance_assessment_strategy,
-            Some(PerformanceAssessmentStrategy::CompareToStandardDca {
-                swapped_amount: Coin::new(0, vault.balance.denom),
-                received_amount: Coin::new(0, DENOM_UKUJI),
-            })
+            vault,
+            Vault {
+                minimum_receive_amount: None,
+                label: None,
+                id: Uint128::new(1),
+                owner: info.sender,
+                destinations: vec![Destination::default()],
+                created_at: env.block.time,
+                status: VaultStatus::Scheduled,
+                time_interval: TimeInterval::Daily,
+                balance: info.funds[0].clone(),
+                slippage_tolerance: config.default_slippage_tolerance,
+                route: None,
+                swap_amount,
+                target_denom: DENOM_UKUJI.to_string(),
+                started_at: None,
+                deposited_amount: info.funds[0].clone(),
+                escrow_level: Decimal::zero(),
+                base_escrow_level: config.risk_weighted_average_escrow_level,
+                swapped_amount: Coin::new(0, DENOM_UUSK.to_string()),
+                received_amount: Coin::new(0, DENOM_UKUJI.to_string()),
+                escrowed_amount: Coin::new(0, DENOM_UKUJI.to_string()),
+                swap_adjustment_strategy: None,
+                performance_assessment_strategy: None,
+                basket: None,
+                end_conditions: None,
+                missed_execution_policy: MissedExecutionPolicy::Skip,
+                catch_up_schedule: None,
+                allow_partial_executions: false,
+                retry_policy: None,
+                retry_failed_post_execution_actions: false,
+                recurring_deposits: false,
+                referrer: None,
+                escrow_vesting: None,
+                trigger: Some(TriggerConfiguration::Time {
+                    target_time: Timestamp::from_seconds(env.block.time.plus_seconds(10).seconds()),
+                }),
+            }
         );
     }
 
     #[test]
-    fn with_large_deposit_should_select_longer_duration_model() {
+    fn should_create_vault_with_pending_price_trigger() {
         let mut deps = calc_mock_dependencies();
         let env = mock_env();
         let mut info = mock_info(ADMIN, &[]);
 
         instantiate_contract(deps.as_mut(), env.clone(), info.clone());
 
-        info = mock_info(USER, &[Coin::new(1000000000, DENOM_UUSK)]);
+        let swap_amount = ONE;
+        info = mock_info(USER, &[Coin::new(TEN.into(), DENOM_UUSK)]);
 
         create_vault_handler(
             deps.as_mut(),
@@ -1343,12 +1725,23 @@ mod create_vault_tests {
             None,
             None,
             None,
-            Uint128::new(100000),
+            swap_amount,
             TimeInterval::Daily,
-            Some(env.block.time.plus_seconds(10).seconds().into()),
             None,
-            Some(PerformanceAssessmentStrategyParams::CompareToStandardDca),
-            Some(SwapAdjustmentStrategyParams::default()),
+            Some(ONE / TWO_MICRONS),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap();
 
@@ -1356,11 +1749,441 @@ mod create_vault_tests {
             .unwrap()
             .vault;
 
+        let config = get_config(deps.as_ref().storage).unwrap();
+
         assert_eq!(
-            vault
-                .swap_adjustment_strategy
-                .map(|strategy| match strategy {
-                    SwapAdjustmentStrategy::RiskWeightedAverage { model_id, .. } => model_id,
+            vault,
+            Vault {
+                minimum_receive_amount: None,
+                label: None,
+                id: Uint128::new(1),
+                owner: info.sender,
+                destinations: vec![Destination::default()],
+                created_at: env.block.time,
+                status: VaultStatus::Scheduled,
+                time_interval: TimeInterval::Daily,
+                balance: Coin::new(
+                    (info.funds[0].amount - TWO_MICRONS).into(),
+                    info.funds[0].denom.clone()
+                ),
+                slippage_tolerance: config.default_slippage_tolerance,
+                swap_amount,
+                target_denom: DENOM_UKUJI.to_string(),
+                route: None,
+                started_at: None,
+                deposited_amount: Coin::new(
+                    (info.funds[0].amount - TWO_MICRONS).into(),
+                    info.funds[0].denom.clone()
+                ),
+                escrow_level: Decimal::zero(),
+                base_escrow_level: config.risk_weighted_average_escrow_level,
+                swapped_amount: Coin::new(0, DENOM_UUSK.to_string()),
+                received_amount: Coin::new(0, DENOM_UKUJI.to_string()),
+                escrowed_amount: Coin::new(0, DENOM_UKUJI.to_string()),
+                swap_adjustment_strategy: None,
+                performance_assessment_strategy: None,
+                basket: None,
+                end_conditions: None,
+                missed_execution_policy: MissedExecutionPolicy::Skip,
+                catch_up_schedule: None,
+                allow_partial_executions: false,
+                retry_policy: None,
+                retry_failed_post_execution_actions: false,
+                recurring_deposits: false,
+                referrer: None,
+                escrow_vesting: None,
+                trigger: None,
+            }
+        );
+    }
+
+    #[test]
+    fn should_publish_deposit_event() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+        let mut info = mock_info(ADMIN, &[]);
+
+        instantiate_contract(deps.as_mut(), env.clone(), info.clone());
+
+        info = mock_info(USER, &[Coin::new(100000, DENOM_UUSK)]);
+
+        create_vault_handler(
+            deps.as_mut(),
+            env.clone(),
+            &info,
+            info.sender.clone(),
+            None,
+            vec![],
+            DENOM_UKUJI.to_string(),
+            None,
+            None,
+            None,
+            Uint128::new(100000),
+            TimeInterval::Daily,
+            Some(env.block.time.plus_seconds(10).seconds().into()),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+
+        let events =
+            get_events_by_resource_id_handler(deps.as_ref(), Uint128::one(), None, None, None)
+                .unwrap()
+                .events;
+
+        assert!(events.contains(
+            &EventBuilder::new(
+                Uint128::one(),
+                env.block,
+                EventData::DcaVaultFundsDeposited {
+                    amount: info.funds[0].clone()
+                },
+            )
+            .build(1),
+        ))
+    }
+
+    #[test]
+    fn for_different_owner_should_succeed() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+        let mut info = mock_info(ADMIN, &[]);
+
+        instantiate_contract(deps.as_mut(), env.clone(), info.clone());
+
+        let owner = Addr::unchecked(USER);
+        info = mock_info(ADMIN, &[Coin::new(100000, DENOM_UUSK)]);
+
+        create_vault_handler(
+            deps.as_mut(),
+            env.clone(),
+            &info,
+            owner,
+            None,
+            vec![],
+            DENOM_UKUJI.to_string(),
+            None,
+            None,
+            None,
+            Uint128::new(100000),
+            TimeInterval::Daily,
+            Some(env.block.time.plus_seconds(10).seconds().into()),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+
+        let vault = get_vault_handler(deps.as_ref(), Uint128::one())
+            .unwrap()
+            .vault;
+
+        assert_eq!(vault.owner, Addr::unchecked(USER));
+    }
+
+    #[test]
+    fn with_multiple_destinations_should_succeed() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+        let mut info = mock_info(ADMIN, &[]);
+
+        instantiate_contract(deps.as_mut(), env.clone(), info.clone());
+
+        info = mock_info(USER, &[Coin::new(100000, DENOM_UUSK)]);
+
+        let destinations = vec![
+            Destination {
+                allocation: Decimal::percent(50),
+                address: env.contract.address.clone(),
+                action: DestinationAction::ContractCall {
+                    msg: to_json_binary(&ExecuteMsg::ZDelegate {
+                        delegator_address: Addr::unchecked("dest-1"),
+                        validator_address: Addr::unchecked(VALIDATOR),
+                    })
+                    .unwrap(),
+                },
+            },
+            Destination {
+                allocation: Decimal::percent(50),
+                address: env.contract.address.clone(),
+                action: DestinationAction::ContractCall {
+                    msg: to_json_binary(&ExecuteMsg::ZDelegate {
+                        delegator_address: Addr::unchecked("dest-2"),
+                        validator_address: Addr::unchecked(VALIDATOR),
+                    })
+                    .unwrap(),
+                },
+            },
+        ];
+
+        create_vault_handler(
+            deps.as_mut(),
+            env.clone(),
+            &info,
+            info.sender.clone(),
+            None,
+            destinations.clone(),
+            DENOM_UKUJI.to_string(),
+            None,
+            None,
+            None,
+            Uint128::new(100000),
+            TimeInterval::Daily,
+            Some(env.block.time.plus_seconds(10).seconds().into()),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+
+        let vault = get_vault_handler(deps.as_ref(), Uint128::one())
+            .unwrap()
+            .vault;
+
+        assert_eq!(vault.destinations, destinations);
+    }
+
+    #[test]
+    fn should_create_swap_adjustment_strategy() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+        let mut info = mock_info(ADMIN, &[]);
+
+        instantiate_contract(deps.as_mut(), env.clone(), info.clone());
+
+        info = mock_info(USER, &[Coin::new(100000, DENOM_UUSK)]);
+
+        create_vault_handler(
+            deps.as_mut(),
+            env.clone(),
+            &info,
+            info.sender.clone(),
+            None,
+            vec![],
+            DENOM_UKUJI.to_string(),
+            None,
+            None,
+            None,
+            Uint128::new(100000),
+            TimeInterval::Daily,
+            Some(env.block.time.plus_seconds(10).seconds().into()),
+            None,
+            Some(PerformanceAssessmentStrategyParams::CompareToStandardDca),
+            Some(SwapAdjustmentStrategyParams::default()),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+
+        let vault = get_vault_handler(deps.as_ref(), Uint128::one())
+            .unwrap()
+            .vault;
+
+        assert_eq!(
+            vault.swap_adjustment_strategy,
+            Some(SwapAdjustmentStrategy::default())
+        );
+    }
+
+    #[test]
+    fn should_save_no_performance_assessment_strategy_when_none_provided() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+        let mut info = mock_info(ADMIN, &[]);
+
+        instantiate_contract(deps.as_mut(), env.clone(), info.clone());
+
+        info = mock_info(USER, &[Coin::new(100000, DENOM_UUSK)]);
+
+        create_vault_handler(
+            deps.as_mut(),
+            env.clone(),
+            &info,
+            info.sender.clone(),
+            None,
+            vec![],
+            DENOM_UKUJI.to_string(),
+            None,
+            None,
+            None,
+            Uint128::new(100000),
+            TimeInterval::Daily,
+            Some(env.block.time.plus_seconds(10).seconds().into()),
+            None,
+            None,
+            Some(SwapAdjustmentStrategyParams::WeightedScale {
+                base_receive_amount: Uint128::new(100000),
+                multiplier: Decimal::percent(200),
+                increase_only: false,
+            }),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+
+        let vault = get_vault_handler(deps.as_ref(), Uint128::one())
+            .unwrap()
+            .vault;
+
+        assert_eq!(vault.performance_assessment_strategy, None);
+    }
+
+    #[test]
+    fn should_create_performance_assessment_strategy() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+        let mut info = mock_info(ADMIN, &[]);
+
+        instantiate_contract(deps.as_mut(), env.clone(), info.clone());
+
+        info = mock_info(USER, &[Coin::new(100000, DENOM_UUSK)]);
+
+        create_vault_handler(
+            deps.as_mut(),
+            env.clone(),
+            &info,
+            info.sender.clone(),
+            None,
+            vec![],
+            DENOM_UKUJI.to_string(),
+            None,
+            None,
+            None,
+            Uint128::new(100000),
+            TimeInterval::Daily,
+            Some(env.block.time.plus_seconds(10).seconds().into()),
+            None,
+            Some(PerformanceAssessmentStrategyParams::CompareToStandardDca),
+            Some(SwapAdjustmentStrategyParams::default()),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+
+        let vault = get_vault_handler(deps.as_ref(), Uint128::one())
+            .unwrap()
+            .vault;
+
+        assert_eq!(
+            vault.performance_assessment_strategy,
+            Some(PerformanceAssessmentStrategy::CompareToStandardDca {
+                swapped_amount: Coin::new(0, vault.balance.denom),
+                received_amount: Coin::new(0, DENOM_UKUJI),
+            })
+        );
+    }
+
+    #[test]
+    fn with_large_deposit_should_select_longer_duration_model() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+        let mut info = mock_info(ADMIN, &[]);
+
+        instantiate_contract(deps.as_mut(), env.clone(), info.clone());
+
+        info = mock_info(USER, &[Coin::new(1000000000, DENOM_UUSK)]);
+
+        create_vault_handler(
+            deps.as_mut(),
+            env.clone(),
+            &info,
+            info.sender.clone(),
+            None,
+            vec![],
+            DENOM_UKUJI.to_string(),
+            None,
+            None,
+            None,
+            Uint128::new(100000),
+            TimeInterval::Daily,
+            Some(env.block.time.plus_seconds(10).seconds().into()),
+            None,
+            Some(PerformanceAssessmentStrategyParams::CompareToStandardDca),
+            Some(SwapAdjustmentStrategyParams::default()),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+
+        let vault = get_vault_handler(deps.as_ref(), Uint128::one())
+            .unwrap()
+            .vault;
+
+        assert_eq!(
+            vault
+                .swap_adjustment_strategy
+                .map(|strategy| match strategy {
+                    SwapAdjustmentStrategy::RiskWeightedAverage { model_id, .. } => model_id,
                     _ => panic!("unexpected swap adjustment strategy"),
                 }),
             Some(90)
@@ -1368,14 +2191,993 @@ mod create_vault_tests {
     }
 
     #[test]
-    fn with_no_target_time_should_execute_vault() {
+    fn with_no_target_time_should_execute_vault() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+        let mut info = mock_info(ADMIN, &[]);
+
+        instantiate_contract(deps.as_mut(), env.clone(), info.clone());
+
+        info = mock_info(USER, &[Coin::new(100000, DENOM_UUSK)]);
+
+        let response = create_vault_handler(
+            deps.as_mut(),
+            env.clone(),
+            &info,
+            info.sender.clone(),
+            None,
+            vec![],
+            DENOM_UKUJI.to_string(),
+            None,
+            None,
+            None,
+            Uint128::new(100000),
+            TimeInterval::Daily,
+            None,
+            None,
+            Some(PerformanceAssessmentStrategyParams::CompareToStandardDca),
+            Some(SwapAdjustmentStrategyParams::default()),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(
+            response.messages.first().unwrap(),
+            &SubMsg::new(WasmMsg::Execute {
+                contract_addr: env.contract.address.to_string(),
+                funds: vec![],
+                msg: to_json_binary(&ExecuteMsg::ExecuteTrigger {
+                    trigger_id: Uint128::one(),
+                    route: None,
+                })
+                .unwrap()
+            })
+        );
+    }
+
+    #[test]
+    fn with_calendar_schedule_and_no_start_time_should_wait_for_next_scheduled_time() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+        let mut info = mock_info(ADMIN, &[]);
+
+        instantiate_contract(deps.as_mut(), env.clone(), info.clone());
+
+        info = mock_info(USER, &[Coin::new(100000, DENOM_UUSK)]);
+
+        let response = create_vault_handler(
+            deps.as_mut(),
+            env.clone(),
+            &info,
+            info.sender.clone(),
+            None,
+            vec![],
+            DENOM_UKUJI.to_string(),
+            None,
+            None,
+            None,
+            Uint128::new(100000),
+            TimeInterval::DailyAt { hour: 9, minute: 0 },
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+
+        assert!(!response.messages.iter().any(|message| matches!(
+            &message.msg,
+            CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, .. })
+                if contract_addr == env.contract.address.as_str()
+        )));
+        assert_eq!(
+            get_trigger(deps.as_ref().storage, Uint128::one())
+                .unwrap()
+                .unwrap()
+                .configuration,
+            TriggerConfiguration::Time {
+                target_time: Timestamp::from_seconds(1571821200),
+            }
+        );
+    }
+
+    #[test]
+    fn with_target_price_should_create_limit_order() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+        let mut info = mock_info(ADMIN, &[]);
+
+        instantiate_contract(deps.as_mut(), env.clone(), info.clone());
+
+        let pair = Pair::default();
+
+        let swap_amount = ONE;
+        info = mock_info(USER, &[Coin::new(TEN.into(), pair.denoms[1].clone())]);
+
+        let response = create_vault_handler(
+            deps.as_mut(),
+            env,
+            &info,
+            info.sender.clone(),
+            None,
+            vec![],
+            pair.denoms[0].to_string(),
+            None,
+            None,
+            None,
+            swap_amount,
+            TimeInterval::Daily,
+            None,
+            Some(ONE / TWO_MICRONS),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+
+        let config = get_config(deps.as_ref().storage).unwrap();
+
+        assert_eq!(
+            response.messages.first().unwrap(),
+            &SubMsg::reply_on_success(
+                WasmMsg::Execute {
+                    contract_addr: config.exchange_contract_address.to_string(),
+                    funds: vec![Coin::new(TWO_MICRONS.into(), info.funds[0].denom.clone())],
+                    msg: to_json_binary(&ExchangeExecuteMsg::SubmitOrder {
+                        target_price: Decimal256::percent(200),
+                        target_denom: pair.denoms[0].clone(),
+                    })
+                    .unwrap()
+                },
+                get_vault_reply_id(AFTER_LIMIT_ORDER_PLACED_REPLY_ID, Uint128::one())
+            )
+        );
+    }
+
+    #[test]
+    fn should_set_appropriate_escrow_level_for_compare_dca_performance_assessment_strategy() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+        let mut info = mock_info(ADMIN, &[]);
+
+        instantiate_contract(deps.as_mut(), env.clone(), info.clone());
+
+        info = mock_info(USER, &[Coin::new(100000, DENOM_UUSK)]);
+
+        create_vault_handler(
+            deps.as_mut(),
+            env.clone(),
+            &info,
+            info.sender.clone(),
+            None,
+            vec![],
+            DENOM_UKUJI.to_string(),
+            None,
+            None,
+            None,
+            Uint128::new(100000),
+            TimeInterval::Daily,
+            Some(env.block.time.plus_seconds(10).seconds().into()),
+            None,
+            Some(PerformanceAssessmentStrategyParams::CompareToStandardDca),
+            Some(SwapAdjustmentStrategyParams::default()),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+
+        let vault = get_vault_handler(deps.as_ref(), Uint128::one())
+            .unwrap()
+            .vault;
+
+        let config = get_config(deps.as_ref().storage).unwrap();
+
+        assert_eq!(
+            vault.escrow_level,
+            config.risk_weighted_average_escrow_level
+        );
+    }
+
+    #[test]
+    fn should_set_chosen_escrow_level_for_compare_dca_performance_assessment_strategy() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+        let mut info = mock_info(ADMIN, &[]);
+
+        instantiate_contract(deps.as_mut(), env.clone(), info.clone());
+
+        info = mock_info(USER, &[Coin::new(100000, DENOM_UUSK)]);
+
+        create_vault_handler(
+            deps.as_mut(),
+            env.clone(),
+            &info,
+            info.sender.clone(),
+            None,
+            vec![],
+            DENOM_UKUJI.to_string(),
+            None,
+            None,
+            None,
+            Uint128::new(100000),
+            TimeInterval::Daily,
+            Some(env.block.time.plus_seconds(10).seconds().into()),
+            None,
+            Some(PerformanceAssessmentStrategyParams::CompareToStandardDca),
+            Some(SwapAdjustmentStrategyParams::default()),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(Decimal::percent(10)),
+        )
+        .unwrap();
+
+        let vault = get_vault_handler(deps.as_ref(), Uint128::one())
+            .unwrap()
+            .vault;
+
+        assert_eq!(vault.escrow_level, Decimal::percent(10));
+    }
+
+    #[test]
+    fn should_set_appropriate_escrow_level_for_no_performance_assessment_strategy() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+        let mut info = mock_info(ADMIN, &[]);
+
+        instantiate_contract(deps.as_mut(), env.clone(), info.clone());
+
+        info = mock_info(USER, &[Coin::new(100000, DENOM_UUSK)]);
+
+        create_vault_handler(
+            deps.as_mut(),
+            env.clone(),
+            &info,
+            info.sender.clone(),
+            None,
+            vec![],
+            DENOM_UKUJI.to_string(),
+            None,
+            None,
+            None,
+            Uint128::new(100000),
+            TimeInterval::Daily,
+            Some(env.block.time.plus_seconds(10).seconds().into()),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+
+        let vault = get_vault_handler(deps.as_ref(), Uint128::one())
+            .unwrap()
+            .vault;
+
+        assert_eq!(vault.escrow_level, Decimal::zero());
+    }
+
+    #[test]
+    fn with_invalid_ibc_channel_fails() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+        let mut info = mock_info(ADMIN, &[]);
+
+        instantiate_contract(deps.as_mut(), env.clone(), info.clone());
+
+        info = mock_info(USER, &[Coin::new(100000, DENOM_UUSK)]);
+
+        let err = create_vault_handler(
+            deps.as_mut(),
+            env.clone(),
+            &info,
+            info.sender.clone(),
+            None,
+            vec![Destination {
+                address: Addr::unchecked(USER),
+                allocation: Decimal::percent(100),
+                action: DestinationAction::IbcTransfer {
+                    channel: "transfer/0".to_string(),
+                    receiver: "osmo1receiver".to_string(),
+                    timeout: 600,
+                },
+            }],
+            DENOM_UKUJI.to_string(),
+            None,
+            None,
+            None,
+            Uint128::new(100000),
+            TimeInterval::Daily,
+            Some(env.block.time.plus_seconds(10).seconds().into()),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap_err();
+
+        assert_eq!(err.to_string(), "Error: ibc channel transfer/0 is invalid");
+    }
+
+    #[test]
+    fn with_ibc_transfer_timeout_too_short_fails() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+        let mut info = mock_info(ADMIN, &[]);
+
+        instantiate_contract(deps.as_mut(), env.clone(), info.clone());
+
+        info = mock_info(USER, &[Coin::new(100000, DENOM_UUSK)]);
+
+        let err = create_vault_handler(
+            deps.as_mut(),
+            env.clone(),
+            &info,
+            info.sender.clone(),
+            None,
+            vec![Destination {
+                address: Addr::unchecked(USER),
+                allocation: Decimal::percent(100),
+                action: DestinationAction::IbcTransfer {
+                    channel: "channel-0".to_string(),
+                    receiver: "osmo1receiver".to_string(),
+                    timeout: 10,
+                },
+            }],
+            DENOM_UKUJI.to_string(),
+            None,
+            None,
+            None,
+            Uint128::new(100000),
+            TimeInterval::Daily,
+            Some(env.block.time.plus_seconds(10).seconds().into()),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Error: ibc transfer timeout must be between 60 and 604800 seconds"
+        );
+    }
+
+    #[test]
+    fn with_deposit_to_vault_not_owned_by_destination_fails() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+        let mut info = mock_info(ADMIN, &[]);
+
+        instantiate_contract(deps.as_mut(), env.clone(), info.clone());
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                balance: Coin::new(TEN.into(), DENOM_UUSK),
+                ..Vault::default()
+            },
+        );
+
+        info = mock_info(USER, &[Coin::new(100000, DENOM_UUSK)]);
+
+        let err = create_vault_handler(
+            deps.as_mut(),
+            env.clone(),
+            &info,
+            info.sender.clone(),
+            None,
+            vec![Destination {
+                address: Addr::unchecked("other"),
+                allocation: Decimal::percent(100),
+                action: DestinationAction::DepositToVault { vault_id: vault.id },
+            }],
+            DENOM_UKUJI.to_string(),
+            None,
+            None,
+            None,
+            Uint128::new(100000),
+            TimeInterval::Daily,
+            Some(env.block.time.plus_seconds(10).seconds().into()),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "Error: destination address must be the owner of vault with id {}",
+                vault.id
+            )
+        );
+    }
+
+    #[test]
+    fn invoking_contract_callback_with_unauthorised_msg_fails() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+        let mut info = mock_info(ADMIN, &[]);
+
+        instantiate_contract(deps.as_mut(), env.clone(), info.clone());
+
+        info = mock_info(USER, &[Coin::new(100000, DENOM_UUSK)]);
+
+        let err = create_vault_handler(
+            deps.as_mut(),
+            env.clone(),
+            &info,
+            info.sender.clone(),
+            None,
+            vec![Destination {
+                address: env.contract.address,
+                allocation: Decimal::percent(100),
+                action: DestinationAction::ContractCall {
+                    msg: to_json_binary(&ExecuteMsg::DisburseEscrow {
+                        vault_id: Uint128::one(),
+                    })
+                    .unwrap(),
+                },
+            }],
+            DENOM_UKUJI.to_string(),
+            None,
+            None,
+            None,
+            Uint128::new(100000),
+            TimeInterval::Daily,
+            Some(env.block.time.plus_seconds(10).seconds().into()),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Error: Cannot invoke provided destination callback against the DCA contract"
+        );
+    }
+
+    #[test]
+    fn invoking_contract_callback_with_authorised_msg_succeeds() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+        let mut info = mock_info(ADMIN, &[]);
+
+        instantiate_contract(deps.as_mut(), env.clone(), info.clone());
+
+        info = mock_info(USER, &[Coin::new(100000, DENOM_UUSK)]);
+
+        create_vault_handler(
+            deps.as_mut(),
+            env.clone(),
+            &info,
+            info.sender.clone(),
+            None,
+            vec![Destination {
+                address: env.contract.address.clone(),
+                allocation: Decimal::percent(100),
+                action: DestinationAction::ContractCall {
+                    msg: to_json_binary(&ExecuteMsg::Deposit {
+                        address: Addr::unchecked(USER),
+                        vault_id: Uint128::one(),
+                        conversion: None,
+                    })
+                    .unwrap(),
+                },
+            }],
+            DENOM_UKUJI.to_string(),
+            None,
+            None,
+            None,
+            Uint128::new(100000),
+            TimeInterval::Daily,
+            Some(env.block.time.plus_seconds(10).seconds().into()),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+
+        let vault = get_vault_handler(deps.as_ref(), Uint128::one())
+            .unwrap()
+            .vault;
+
+        assert_eq!(
+            vault.destinations,
+            vec![Destination {
+                address: env.contract.address,
+                allocation: Decimal::percent(100),
+                action: DestinationAction::ContractCall {
+                    msg: to_json_binary(&ExecuteMsg::Deposit {
+                        address: Addr::unchecked(USER),
+                        vault_id: Uint128::one(),
+                        conversion: None,
+                    })
+                    .unwrap(),
+                },
+            }]
+        );
+    }
+
+    fn basket() -> Vec<BasketAssetParams> {
+        vec![
+            BasketAssetParams {
+                target_denom: DENOM_UKUJI.to_string(),
+                weight: Decimal::percent(60),
+                route: None,
+            },
+            BasketAssetParams {
+                target_denom: "uatom".to_string(),
+                weight: Decimal::percent(40),
+                route: None,
+            },
+        ]
+    }
+
+    fn create_basket_vault(
+        deps: DepsMut,
+        env: Env,
+        target_denom: &str,
+        basket: Vec<BasketAssetParams>,
+    ) -> Result<Response, ContractError> {
+        let info = mock_info(USER, &[Coin::new(100000, DENOM_UUSK)]);
+
+        create_vault_handler(
+            deps,
+            env,
+            &info,
+            info.sender.clone(),
+            None,
+            vec![],
+            target_denom.to_string(),
+            None,
+            None,
+            None,
+            Uint128::new(10000),
+            TimeInterval::Daily,
+            None,
+            None,
+            None,
+            None,
+            Some(basket),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+    }
+
+    #[test]
+    fn with_basket_saves_basket_assets() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        create_basket_vault(deps.as_mut(), env, DENOM_UKUJI, basket()).unwrap();
+
+        let vault = get_vault_handler(deps.as_ref(), Uint128::one())
+            .unwrap()
+            .vault;
+
+        assert_eq!(vault.target_denom, DENOM_UKUJI.to_string());
+        assert_eq!(
+            vault.basket,
+            Some(
+                basket()
+                    .into_iter()
+                    .map(BasketAsset::from)
+                    .collect::<Vec<BasketAsset>>()
+            )
+        );
+        assert_eq!(
+            vault.basket.unwrap()[1].received_amount,
+            Coin::new(0, "uatom")
+        );
+    }
+
+    #[test]
+    fn with_basket_weights_not_adding_up_to_one_fails() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let mut basket = basket();
+        basket[1].weight = Decimal::percent(30);
+
+        let err = create_basket_vault(deps.as_mut(), env, DENOM_UKUJI, basket).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Error: basket asset weights must add up to 1"
+        );
+    }
+
+    #[test]
+    fn with_single_asset_basket_fails() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let err = create_basket_vault(
+            deps.as_mut(),
+            env,
+            DENOM_UKUJI,
+            vec![BasketAssetParams {
+                target_denom: DENOM_UKUJI.to_string(),
+                weight: Decimal::percent(100),
+                route: None,
+            }],
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Error: a basket must contain between 2 and 10 assets"
+        );
+    }
+
+    #[test]
+    fn with_basket_target_denom_mismatch_fails() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let err = create_basket_vault(deps.as_mut(), env, "uatom", basket()).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Error: target denom must match the first basket asset"
+        );
+    }
+
+    #[test]
+    fn with_duplicate_basket_asset_fails() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let mut basket = basket();
+        basket[1].target_denom = DENOM_UKUJI.to_string();
+
+        let err = create_basket_vault(deps.as_mut(), env, DENOM_UKUJI, basket).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "Error: basket asset {} is provided more than once",
+                DENOM_UKUJI
+            )
+        );
+    }
+
+    #[test]
+    fn with_basket_asset_matching_swap_denom_fails() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let mut basket = basket();
+        basket[1].target_denom = DENOM_UUSK.to_string();
+
+        let err = create_basket_vault(deps.as_mut(), env, DENOM_UKUJI, basket).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "Error: basket asset {} cannot be the same as the swap denom",
+                DENOM_UUSK
+            )
+        );
+    }
+
+    fn create_vault_with_end_conditions(
+        deps: DepsMut,
+        env: Env,
+        end_conditions: EndConditionsParams,
+    ) -> Result<Response, ContractError> {
+        let info = mock_info(USER, &[Coin::new(100000, DENOM_UUSK)]);
+
+        create_vault_handler(
+            deps,
+            env,
+            &info,
+            info.sender.clone(),
+            None,
+            vec![],
+            DENOM_UKUJI.to_string(),
+            None,
+            None,
+            None,
+            Uint128::new(10000),
+            TimeInterval::Daily,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(end_conditions),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+    }
+
+    #[test]
+    fn with_end_conditions_saves_end_conditions() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        create_vault_with_end_conditions(
+            deps.as_mut(),
+            env.clone(),
+            EndConditionsParams {
+                end_time_utc_seconds: Some(env.block.time.plus_seconds(100).seconds().into()),
+                max_executions: Some(Uint64::new(5)),
+                received_amount_goal: Some(ONE),
+            },
+        )
+        .unwrap();
+
+        let vault = get_vault_handler(deps.as_ref(), Uint128::one())
+            .unwrap()
+            .vault;
+
+        assert_eq!(
+            vault.end_conditions,
+            Some(EndConditions {
+                end_time: Some(Timestamp::from_seconds(
+                    env.block.time.plus_seconds(100).seconds()
+                )),
+                max_executions: Some(5),
+                received_amount_goal: Some(ONE),
+                executions: 0,
+            })
+        );
+    }
+
+    #[test]
+    fn with_end_time_in_the_past_fails() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let err = create_vault_with_end_conditions(
+            deps.as_mut(),
+            env.clone(),
+            EndConditionsParams {
+                end_time_utc_seconds: Some(env.block.time.minus_seconds(10).seconds().into()),
+                max_executions: None,
+                received_amount_goal: None,
+            },
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Error: end_time_utc_seconds must be some time in the future"
+        );
+    }
+
+    #[test]
+    fn with_zero_max_executions_fails() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let err = create_vault_with_end_conditions(
+            deps.as_mut(),
+            env,
+            EndConditionsParams {
+                end_time_utc_seconds: None,
+                max_executions: Some(Uint64::zero()),
+                received_amount_goal: None,
+            },
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Error: max executions must be greater than 0"
+        );
+    }
+
+    #[test]
+    fn with_basket_and_received_amount_goal_fails() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+        let info = mock_info(USER, &[Coin::new(100000, DENOM_UUSK)]);
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let err = create_vault_handler(
+            deps.as_mut(),
+            env,
+            &info,
+            info.sender.clone(),
+            None,
+            vec![],
+            DENOM_UKUJI.to_string(),
+            None,
+            None,
+            None,
+            Uint128::new(10000),
+            TimeInterval::Daily,
+            None,
+            None,
+            None,
+            None,
+            Some(basket()),
+            Some(EndConditionsParams {
+                end_time_utc_seconds: None,
+                max_executions: None,
+                received_amount_goal: Some(ONE),
+            }),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Error: basket vaults do not support a received amount goal"
+        );
+    }
+
+    #[test]
+    fn with_deposit_conversion_swaps_deposit_before_executing_vault() {
         let mut deps = calc_mock_dependencies();
         let env = mock_env();
-        let mut info = mock_info(ADMIN, &[]);
+        let info = mock_info(USER, &[Coin::new(10000, DENOM_UUSK)]);
 
-        instantiate_contract(deps.as_mut(), env.clone(), info.clone());
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
 
-        info = mock_info(USER, &[Coin::new(100000, DENOM_UUSK)]);
+        let deposit_conversion = DepositConversion {
+            minimum_receive_amount: Coin::new(9000, DENOM_UKUJI),
+            route: None,
+        };
 
         let response = create_vault_handler(
             deps.as_mut(),
@@ -1384,98 +3186,124 @@ mod create_vault_tests {
             info.sender.clone(),
             None,
             vec![],
-            DENOM_UKUJI.to_string(),
+            DENOM_UUSK.to_string(),
             None,
             None,
             None,
-            Uint128::new(100000),
+            Uint128::new(1000),
             TimeInterval::Daily,
             None,
             None,
-            Some(PerformanceAssessmentStrategyParams::CompareToStandardDca),
-            Some(SwapAdjustmentStrategyParams::default()),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(deposit_conversion.clone()),
+            None,
+            None,
+            None,
         )
         .unwrap();
 
+        let config = get_config(deps.as_ref().storage).unwrap();
+        let vault = get_vault_handler(deps.as_ref(), Uint128::one())
+            .unwrap()
+            .vault;
+
+        assert_eq!(vault.balance, Coin::new(0, DENOM_UKUJI));
+        assert_eq!(vault.deposited_amount, Coin::new(0, DENOM_UKUJI));
         assert_eq!(
-            response.messages.first().unwrap(),
-            &SubMsg::new(WasmMsg::Execute {
-                contract_addr: env.contract.address.to_string(),
-                funds: vec![],
-                msg: to_json_binary(&ExecuteMsg::ExecuteTrigger {
-                    trigger_id: Uint128::one(),
-                    route: None,
-                })
-                .unwrap()
-            })
+            response.messages,
+            vec![
+                SubMsg::reply_on_success(
+                    WasmMsg::Execute {
+                        contract_addr: config.exchange_contract_address.to_string(),
+                        msg: to_json_binary(&ExchangeExecuteMsg::Swap {
+                            minimum_receive_amount: deposit_conversion.minimum_receive_amount,
+                            route: None,
+                        })
+                        .unwrap(),
+                        funds: info.funds,
+                    },
+                    AFTER_DEPOSIT_CONVERSION_REPLY_ID,
+                ),
+                SubMsg::new(WasmMsg::Execute {
+                    contract_addr: env.contract.address.to_string(),
+                    msg: to_json_binary(&ExecuteMsg::ExecuteTrigger {
+                        trigger_id: vault.id,
+                        route: None,
+                    })
+                    .unwrap(),
+                    funds: vec![],
+                }),
+            ]
         );
     }
 
     #[test]
-    fn with_target_price_should_create_limit_order() {
+    fn with_deposit_conversion_and_target_receive_amount_fails() {
         let mut deps = calc_mock_dependencies();
         let env = mock_env();
-        let mut info = mock_info(ADMIN, &[]);
-
-        instantiate_contract(deps.as_mut(), env.clone(), info.clone());
-
-        let pair = Pair::default();
+        let info = mock_info(USER, &[Coin::new(10000, DENOM_UUSK)]);
 
-        let swap_amount = ONE;
-        info = mock_info(USER, &[Coin::new(TEN.into(), pair.denoms[1].clone())]);
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
 
-        let response = create_vault_handler(
+        let err = create_vault_handler(
             deps.as_mut(),
             env,
             &info,
             info.sender.clone(),
             None,
             vec![],
-            pair.denoms[0].to_string(),
+            DENOM_UUSK.to_string(),
             None,
             None,
             None,
-            swap_amount,
+            Uint128::new(1000),
             TimeInterval::Daily,
             None,
-            Some(ONE / TWO_MICRONS),
+            Some(Uint128::new(1000)),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(DepositConversion {
+                minimum_receive_amount: Coin::new(9000, DENOM_UKUJI),
+                route: None,
+            }),
+            None,
             None,
             None,
         )
-        .unwrap();
-
-        let config = get_config(deps.as_ref().storage).unwrap();
+        .unwrap_err();
 
         assert_eq!(
-            response.messages.first().unwrap(),
-            &SubMsg::reply_on_success(
-                WasmMsg::Execute {
-                    contract_addr: config.exchange_contract_address.to_string(),
-                    funds: vec![Coin::new(TWO_MICRONS.into(), info.funds[0].denom.clone())],
-                    msg: to_json_binary(&ExchangeExecuteMsg::SubmitOrder {
-                        target_price: Decimal256::percent(200),
-                        target_denom: pair.denoms[0].clone(),
-                    })
-                    .unwrap()
-                },
-                AFTER_LIMIT_ORDER_PLACED_REPLY_ID
-            )
+            err.to_string(),
+            "Error: deposit conversion is not supported for price triggered vaults"
         );
     }
 
     #[test]
-    fn should_set_appropriate_escrow_level_for_compare_dca_performance_assessment_strategy() {
+    fn with_owner_as_referrer_fails() {
         let mut deps = calc_mock_dependencies();
         let env = mock_env();
-        let mut info = mock_info(ADMIN, &[]);
-
-        instantiate_contract(deps.as_mut(), env.clone(), info.clone());
+        let info = mock_info(USER, &[Coin::new(10000, DENOM_UUSK)]);
 
-        info = mock_info(USER, &[Coin::new(100000, DENOM_UUSK)]);
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
 
-        create_vault_handler(
+        let err = create_vault_handler(
             deps.as_mut(),
-            env.clone(),
+            env,
             &info,
             info.sender.clone(),
             None,
@@ -1484,40 +3312,43 @@ mod create_vault_tests {
             None,
             None,
             None,
-            Uint128::new(100000),
+            Uint128::new(1000),
             TimeInterval::Daily,
-            Some(env.block.time.plus_seconds(10).seconds().into()),
             None,
-            Some(PerformanceAssessmentStrategyParams::CompareToStandardDca),
-            Some(SwapAdjustmentStrategyParams::default()),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(info.sender.clone()),
+            None,
+            None,
         )
-        .unwrap();
-
-        let vault = get_vault_handler(deps.as_ref(), Uint128::one())
-            .unwrap()
-            .vault;
-
-        let config = get_config(deps.as_ref().storage).unwrap();
+        .unwrap_err();
 
         assert_eq!(
-            vault.escrow_level,
-            config.risk_weighted_average_escrow_level
+            err.to_string(),
+            "Error: vault owner cannot be its own referrer"
         );
     }
 
     #[test]
-    fn should_set_appropriate_escrow_level_for_no_performance_assessment_strategy() {
+    fn with_escrow_vesting_and_no_performance_assessment_strategy_fails() {
         let mut deps = calc_mock_dependencies();
         let env = mock_env();
-        let mut info = mock_info(ADMIN, &[]);
-
-        instantiate_contract(deps.as_mut(), env.clone(), info.clone());
+        let info = mock_info(USER, &[Coin::new(10000, DENOM_UUSK)]);
 
-        info = mock_info(USER, &[Coin::new(100000, DENOM_UUSK)]);
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
 
-        create_vault_handler(
+        let err = create_vault_handler(
             deps.as_mut(),
-            env.clone(),
+            env,
             &info,
             info.sender.clone(),
             None,
@@ -1526,124 +3357,136 @@ mod create_vault_tests {
             None,
             None,
             None,
-            Uint128::new(100000),
+            Uint128::new(1000),
             TimeInterval::Daily,
-            Some(env.block.time.plus_seconds(10).seconds().into()),
             None,
             None,
             None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(EscrowVestingSchedule::Linear {
+                tranches: 2,
+                interval_seconds: 100,
+            }),
+            None,
         )
-        .unwrap();
-
-        let vault = get_vault_handler(deps.as_ref(), Uint128::one())
-            .unwrap()
-            .vault;
+        .unwrap_err();
 
-        assert_eq!(vault.escrow_level, Decimal::zero());
+        assert_eq!(
+            err.to_string(),
+            "Error: escrow vesting is only supported for vaults with a performance assessment strategy"
+        );
     }
 
     #[test]
-    fn invoking_contract_callback_with_unauthorised_msg_fails() {
+    fn with_escrow_vesting_shares_not_adding_up_to_one_fails() {
         let mut deps = calc_mock_dependencies();
         let env = mock_env();
-        let mut info = mock_info(ADMIN, &[]);
-
-        instantiate_contract(deps.as_mut(), env.clone(), info.clone());
+        let info = mock_info(USER, &[Coin::new(10000, DENOM_UUSK)]);
 
-        info = mock_info(USER, &[Coin::new(100000, DENOM_UUSK)]);
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
 
         let err = create_vault_handler(
             deps.as_mut(),
-            env.clone(),
+            env,
             &info,
             info.sender.clone(),
             None,
-            vec![Destination {
-                address: env.contract.address,
-                allocation: Decimal::percent(100),
-                msg: Some(
-                    to_json_binary(&ExecuteMsg::DisburseEscrow {
-                        vault_id: Uint128::one(),
-                    })
-                    .unwrap(),
-                ),
-            }],
+            vec![],
             DENOM_UKUJI.to_string(),
             None,
             None,
             None,
-            Uint128::new(100000),
+            Uint128::new(1000),
             TimeInterval::Daily,
-            Some(env.block.time.plus_seconds(10).seconds().into()),
             None,
             None,
+            Some(PerformanceAssessmentStrategyParams::HighWaterMark),
+            Some(SwapAdjustmentStrategyParams::WeightedScale {
+                base_receive_amount: Uint128::new(1000),
+                multiplier: Decimal::percent(200),
+                increase_only: false,
+            }),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(EscrowVestingSchedule::Stepped {
+                shares: vec![Decimal::percent(50), Decimal::percent(40)],
+                interval_seconds: 100,
+            }),
             None,
         )
         .unwrap_err();
 
         assert_eq!(
             err.to_string(),
-            "Error: Cannot invoke provided destination callback against the DCA contract"
+            "Error: escrow vesting tranche shares must add up to 1, got 0.9"
         );
     }
 
     #[test]
-    fn invoking_contract_callback_with_authorised_msg_succeeds() {
+    fn with_zero_linear_escrow_vesting_tranches_fails() {
         let mut deps = calc_mock_dependencies();
         let env = mock_env();
-        let mut info = mock_info(ADMIN, &[]);
-
-        instantiate_contract(deps.as_mut(), env.clone(), info.clone());
+        let info = mock_info(USER, &[Coin::new(10000, DENOM_UUSK)]);
 
-        info = mock_info(USER, &[Coin::new(100000, DENOM_UUSK)]);
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
 
-        create_vault_handler(
+        let err = create_vault_handler(
             deps.as_mut(),
-            env.clone(),
+            env,
             &info,
             info.sender.clone(),
             None,
-            vec![Destination {
-                address: env.contract.address.clone(),
-                allocation: Decimal::percent(100),
-                msg: Some(
-                    to_json_binary(&ExecuteMsg::Deposit {
-                        address: Addr::unchecked(USER),
-                        vault_id: Uint128::one(),
-                    })
-                    .unwrap(),
-                ),
-            }],
+            vec![],
             DENOM_UKUJI.to_string(),
             None,
             None,
             None,
-            Uint128::new(100000),
+            Uint128::new(1000),
             TimeInterval::Daily,
-            Some(env.block.time.plus_seconds(10).seconds().into()),
+            None,
+            None,
+            Some(PerformanceAssessmentStrategyParams::HighWaterMark),
+            Some(SwapAdjustmentStrategyParams::WeightedScale {
+                base_receive_amount: Uint128::new(1000),
+                multiplier: Decimal::percent(200),
+                increase_only: false,
+            }),
+            None,
+            None,
             None,
             None,
             None,
+            None,
+            None,
+            None,
+            None,
+            Some(EscrowVestingSchedule::Linear {
+                tranches: 0,
+                interval_seconds: 100,
+            }),
+            None,
         )
-        .unwrap();
-
-        let vault = get_vault_handler(deps.as_ref(), Uint128::one())
-            .unwrap()
-            .vault;
+        .unwrap_err();
 
         assert_eq!(
-            vault.destinations,
-            vec![Destination {
-                address: env.contract.address,
-                allocation: Decimal::percent(100),
-                msg: Some(
-                    to_json_binary(&ExecuteMsg::Deposit {
-                        address: Addr::unchecked(USER),
-                        vault_id: Uint128::one(),
-                    })
-                    .unwrap(),
-                ),
-            }]
+            err.to_string(),
+            "Error: escrow vesting must have between 1 and 10 tranches"
         );
     }
 }
@@ -1652,7 +3495,9 @@ mod create_vault_tests {
 mod save_limit_order_id_tests {
     use super::save_price_trigger;
     use crate::{
-        state::{cache::VAULT_ID_CACHE, triggers::get_trigger},
+        constants::AFTER_LIMIT_ORDER_PLACED_REPLY_ID,
+        helpers::message::get_vault_reply_id,
+        state::triggers::get_trigger,
         types::trigger::{Trigger, TriggerConfiguration},
     };
     use cosmwasm_std::{
@@ -1666,12 +3511,8 @@ mod save_limit_order_id_tests {
         let vault_id = Uint128::one();
         let order_idx = Uint128::new(67);
 
-        VAULT_ID_CACHE
-            .save(deps.as_mut().storage, &vault_id)
-            .unwrap();
-
         let reply = Reply {
-            id: 1,
+            id: get_vault_reply_id(AFTER_LIMIT_ORDER_PLACED_REPLY_ID, vault_id),
             result: SubMsgResult::Ok(SubMsgResponse {
                 events: vec![Event::new("wasm")
                     .add_attribute("order_idx", order_idx.to_string())
@@ -1,21 +1,29 @@
+use crate::constants::AFTER_DEPOSIT_CONVERSION_REPLY_ID;
 use crate::error::ContractError;
 use crate::helpers::time::get_next_target_time;
 use crate::helpers::validation::{
-    assert_contract_is_not_paused, assert_deposited_denom_matches_send_denom,
-    assert_exactly_one_asset, assert_vault_is_not_cancelled,
+    assert_contract_is_not_paused, assert_deposit_conversion_is_valid,
+    assert_deposited_denom_matches_send_denom, assert_exactly_one_asset,
+    assert_vault_is_not_cancelled,
 };
 use crate::helpers::vault::get_risk_weighted_average_model_id;
+use crate::state::cache::{DepositConversionCache, DEPOSIT_CONVERSION_CACHE};
+use crate::state::config::get_config;
 use crate::state::events::create_event;
 use crate::state::triggers::save_trigger;
 use crate::state::vaults::{get_vault, update_vault};
+use crate::types::deposit_conversion::DepositConversion;
 use crate::types::event::{EventBuilder, EventData};
 use crate::types::swap_adjustment_strategy::SwapAdjustmentStrategy;
 use crate::types::trigger::{Trigger, TriggerConfiguration};
 use crate::types::vault::{Vault, VaultStatus};
-use cosmwasm_std::{Addr, Env};
+use cosmwasm_std::{to_json_binary, Addr, Coin, Env, Reply, Storage, SubMsg};
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::{DepsMut, MessageInfo, Response, Uint128};
-use shared::coin::add;
+use exchange::msg::ExecuteMsg as ExchangeExecuteMsg;
+use shared::balance::query_balance;
+use shared::coin::{add, subtract};
+use shared::cw20::into_execute_msg;
 
 pub fn deposit_handler(
     deps: DepsMut,
@@ -23,6 +31,7 @@ pub fn deposit_handler(
     info: MessageInfo,
     address: Addr,
     vault_id: Uint128,
+    conversion: Option<DepositConversion>,
 ) -> Result<Response, ContractError> {
     assert_contract_is_not_paused(deps.storage)?;
     deps.api.addr_validate(address.as_str())?;
@@ -40,19 +49,120 @@ pub fn deposit_handler(
     }
 
     assert_vault_is_not_cancelled(&vault)?;
+
+    let response = Response::new()
+        .add_attribute("deposit", "true")
+        .add_attribute("vault_id", vault.id)
+        .add_attribute("owner", vault.owner.clone());
+
+    if let Some(conversion) = conversion {
+        assert_deposit_conversion_is_valid(
+            deps.as_ref(),
+            &info.funds[0],
+            &vault.balance.denom,
+            &conversion,
+        )?;
+
+        return Ok(response
+            .add_attribute("converted_amount", info.funds[0].to_string())
+            .add_submessage(get_deposit_conversion_message(
+                deps,
+                &env,
+                vault.id,
+                info.funds[0].clone(),
+                conversion,
+            )?));
+    }
+
     assert_deposited_denom_matches_send_denom(
         info.funds[0].denom.clone(),
         vault.balance.denom.clone(),
     )?;
 
+    credit_deposit(deps.storage, &env, vault, info.funds[0].clone())?;
+
+    Ok(response.add_attribute("deposited_amount", info.funds[0].amount))
+}
+
+pub fn get_deposit_conversion_message(
+    deps: DepsMut,
+    env: &Env,
+    vault_id: Uint128,
+    deposit: Coin,
+    conversion: DepositConversion,
+) -> Result<SubMsg, ContractError> {
+    let config = get_config(deps.storage)?;
+
+    DEPOSIT_CONVERSION_CACHE.save(
+        deps.storage,
+        &DepositConversionCache {
+            vault_id,
+            deposit: deposit.clone(),
+            swap_denom_balance: query_balance(
+                deps.api,
+                &deps.querier,
+                &conversion.minimum_receive_amount.denom,
+                &env.contract.address,
+            )?,
+        },
+    )?;
+
+    Ok(SubMsg::reply_on_success(
+        into_execute_msg(
+            deps.api,
+            config.exchange_contract_address,
+            to_json_binary(&ExchangeExecuteMsg::Swap {
+                minimum_receive_amount: conversion.minimum_receive_amount,
+                route: conversion.route,
+            })?,
+            deposit,
+        )?,
+        AFTER_DEPOSIT_CONVERSION_REPLY_ID,
+    ))
+}
+
+pub fn handle_deposit_conversion_result(
+    deps: DepsMut,
+    env: Env,
+    _reply: Reply,
+) -> Result<Response, ContractError> {
+    let cache = DEPOSIT_CONVERSION_CACHE.load(deps.storage)?;
+    DEPOSIT_CONVERSION_CACHE.remove(deps.storage);
+
+    let received_amount = subtract(
+        &query_balance(
+            deps.api,
+            &deps.querier,
+            &cache.swap_denom_balance.denom,
+            &env.contract.address,
+        )?,
+        &cache.swap_denom_balance,
+    )?;
+
+    let vault = get_vault(deps.storage, cache.vault_id)?;
+    let vault = credit_deposit(deps.storage, &env, vault, received_amount.clone())?;
+
+    Ok(Response::new()
+        .add_attribute("deposit_conversion_result", "true")
+        .add_attribute("vault_id", vault.id)
+        .add_attribute("converted_amount", cache.deposit.to_string())
+        .add_attribute("deposited_amount", received_amount.amount))
+}
+
+fn credit_deposit(
+    store: &mut dyn Storage,
+    env: &Env,
+    vault: Vault,
+    amount: Coin,
+) -> Result<Vault, ContractError> {
     let vault_was_inactive = vault.is_inactive();
-    let new_balance = add(vault.balance.clone(), info.funds[0].clone())?;
+    let new_balance = add(vault.balance.clone(), amount.clone())?;
 
     let vault = update_vault(
-        deps.storage,
+        store,
         Vault {
             balance: new_balance.clone(),
-            deposited_amount: add(vault.deposited_amount.clone(), info.funds[0].clone())?,
+            deposited_amount: add(vault.deposited_amount.clone(), amount.clone())?,
             status: if vault.is_inactive() {
                 VaultStatus::Active
             } else {
@@ -82,37 +192,31 @@ pub fn deposit_handler(
     )?;
 
     create_event(
-        deps.storage,
+        store,
         EventBuilder::new(
             vault.id,
             env.block.clone(),
-            EventData::DcaVaultFundsDeposited {
-                amount: info.funds[0].clone(),
-            },
+            EventData::DcaVaultFundsDeposited { amount },
         ),
     )?;
 
     if vault.is_active() && vault_was_inactive && vault.trigger.is_none() {
         save_trigger(
-            deps.storage,
+            store,
             Trigger {
-                vault_id,
+                vault_id: vault.id,
                 configuration: TriggerConfiguration::Time {
                     target_time: get_next_target_time(
                         env.block.time,
                         vault.started_at.unwrap_or(env.block.time),
-                        vault.time_interval,
+                        vault.time_interval.clone(),
                     ),
                 },
             },
         )?;
     };
 
-    Ok(Response::new()
-        .add_attribute("deposit", "true")
-        .add_attribute("vault_id", vault.id)
-        .add_attribute("owner", vault.owner)
-        .add_attribute("deposited_amount", info.funds[0].amount))
+    Ok(vault)
 }
 
 #[cfg(test)]
@@ -123,22 +227,21 @@ mod deposit_tests {
     use crate::handlers::get_vault::get_vault_handler;
     use crate::state::config::{get_config, update_config};
     use crate::tests::helpers::{instantiate_contract, setup_vault};
-    use crate::tests::mocks::{ADMIN, DENOM_UKUJI, DENOM_UUSK, USER};
+    use crate::tests::mocks::{calc_mock_dependencies, ADMIN, DENOM_UKUJI, DENOM_UUSK, USER};
     use crate::types::config::Config;
     use crate::types::event::{EventBuilder, EventData};
     use crate::types::position_type::PositionType;
     use crate::types::swap_adjustment_strategy::{BaseDenom, SwapAdjustmentStrategy};
     use crate::types::vault::{Vault, VaultStatus};
     use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
-    use cosmwasm_std::{Addr, Coin};
-    use shared::coin::subtract;
+    use cosmwasm_std::{Addr, Coin, WasmMsg};
 
     #[test]
     fn updates_the_vault_balance() {
         let mut deps = mock_dependencies();
         let env = mock_env();
         let deposit_amount = Coin::new(TEN.into(), DENOM_UKUJI);
-        let info = mock_info(ADMIN, &[deposit_amount.clone()]);
+        let info = mock_info(ADMIN, std::slice::from_ref(&deposit_amount));
 
         instantiate_contract(deps.as_mut(), env.clone(), info.clone());
 
@@ -151,7 +254,7 @@ mod deposit_tests {
             },
         );
 
-        deposit_handler(deps.as_mut(), env, info, vault.owner, vault.id).unwrap();
+        deposit_handler(deps.as_mut(), env, info, vault.owner, vault.id, None).unwrap();
 
         let updated_vault = get_vault_handler(deps.as_ref(), vault.id).unwrap().vault;
 
@@ -167,13 +270,21 @@ mod deposit_tests {
         let mut deps = mock_dependencies();
         let env = mock_env();
         let deposit_amount = Coin::new(TEN.into(), DENOM_UKUJI);
-        let info = mock_info(ADMIN, &[deposit_amount.clone()]);
+        let info = mock_info(ADMIN, std::slice::from_ref(&deposit_amount));
 
         instantiate_contract(deps.as_mut(), env.clone(), info.clone());
 
         let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
 
-        deposit_handler(deps.as_mut(), env.clone(), info, vault.owner, vault.id).unwrap();
+        deposit_handler(
+            deps.as_mut(),
+            env.clone(),
+            info,
+            vault.owner,
+            vault.id,
+            None,
+        )
+        .unwrap();
 
         let events = get_events_by_resource_id_handler(deps.as_ref(), vault.id, None, None, None)
             .unwrap()
@@ -209,7 +320,7 @@ mod deposit_tests {
             },
         );
 
-        deposit_handler(deps.as_mut(), env, info, vault.owner, vault.id).unwrap();
+        deposit_handler(deps.as_mut(), env, info, vault.owner, vault.id, None).unwrap();
 
         let updated_vault = get_vault_handler(deps.as_ref(), vault.id).unwrap().vault;
 
@@ -235,7 +346,7 @@ mod deposit_tests {
             },
         );
 
-        deposit_handler(deps.as_mut(), env, info, vault.owner, vault.id).unwrap();
+        deposit_handler(deps.as_mut(), env, info, vault.owner, vault.id, None).unwrap();
 
         let updated_vault = get_vault_handler(deps.as_ref(), vault.id).unwrap().vault;
 
@@ -254,7 +365,7 @@ mod deposit_tests {
 
         let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
 
-        deposit_handler(deps.as_mut(), env, info, vault.owner, vault.id).unwrap();
+        deposit_handler(deps.as_mut(), env, info, vault.owner, vault.id, None).unwrap();
 
         let updated_vault = get_vault_handler(deps.as_ref(), vault.id).unwrap().vault;
 
@@ -273,8 +384,15 @@ mod deposit_tests {
 
         let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
 
-        let response =
-            deposit_handler(deps.as_mut(), env, info, Addr::unchecked(USER), vault.id).unwrap();
+        let response = deposit_handler(
+            deps.as_mut(),
+            env,
+            info,
+            Addr::unchecked(USER),
+            vault.id,
+            None,
+        )
+        .unwrap();
 
         assert!(response.messages.is_empty())
     }
@@ -297,8 +415,15 @@ mod deposit_tests {
             },
         );
 
-        let response =
-            deposit_handler(deps.as_mut(), env, info, Addr::unchecked(USER), vault.id).unwrap();
+        let response = deposit_handler(
+            deps.as_mut(),
+            env,
+            info,
+            Addr::unchecked(USER),
+            vault.id,
+            None,
+        )
+        .unwrap();
 
         assert!(response.messages.is_empty())
     }
@@ -321,7 +446,8 @@ mod deposit_tests {
             },
         );
 
-        let err = deposit_handler(deps.as_mut(), env, info, vault.owner, vault.id).unwrap_err();
+        let err =
+            deposit_handler(deps.as_mut(), env, info, vault.owner, vault.id, None).unwrap_err();
 
         assert_eq!(err.to_string(), "Error: vault is already cancelled");
     }
@@ -350,6 +476,7 @@ mod deposit_tests {
             info,
             Addr::unchecked("not-the-owner"),
             vault.id,
+            None,
         )
         .unwrap_err();
 
@@ -379,6 +506,7 @@ mod deposit_tests {
             ),
             vault.owner.clone(),
             vault.id,
+            None,
         )
         .unwrap_err();
 
@@ -403,7 +531,8 @@ mod deposit_tests {
 
         let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
 
-        let err = deposit_handler(deps.as_mut(), env, info, vault.owner, vault.id).unwrap_err();
+        let err =
+            deposit_handler(deps.as_mut(), env, info, vault.owner, vault.id, None).unwrap_err();
 
         assert_eq!(
             err.to_string(),
@@ -433,7 +562,8 @@ mod deposit_tests {
 
         let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
 
-        let err = deposit_handler(deps.as_mut(), env, info, vault.owner, vault.id).unwrap_err();
+        let err =
+            deposit_handler(deps.as_mut(), env, info, vault.owner, vault.id, None).unwrap_err();
 
         assert_eq!(err.to_string(), "Error: contract is paused");
     }
@@ -460,7 +590,7 @@ mod deposit_tests {
             },
         );
 
-        deposit_handler(deps.as_mut(), env, info, vault.owner, vault.id).unwrap();
+        deposit_handler(deps.as_mut(), env, info, vault.owner, vault.id, None).unwrap();
 
         let updated_vault = get_vault_handler(deps.as_ref(), vault.id).unwrap().vault;
 
@@ -489,13 +619,13 @@ mod deposit_tests {
         let mut deps = mock_dependencies();
         let env = mock_env();
         let deposit_amount = Coin::new(ONE_HUNDRED.into(), DENOM_UKUJI);
-        let info = mock_info(ADMIN, &[deposit_amount.clone()]);
+        let info = mock_info(ADMIN, std::slice::from_ref(&deposit_amount));
 
         instantiate_contract(deps.as_mut(), env.clone(), info.clone());
 
         let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
 
-        deposit_handler(deps.as_mut(), env, info, vault.owner, vault.id).unwrap();
+        deposit_handler(deps.as_mut(), env, info, vault.owner, vault.id, None).unwrap();
 
         let updated_vault = get_vault_handler(deps.as_ref(), vault.id).unwrap().vault;
 
@@ -505,4 +635,228 @@ mod deposit_tests {
             add(vault.balance, deposit_amount).unwrap()
         );
     }
+
+    #[test]
+    fn with_conversion_swaps_deposit_into_swap_denom() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+        let deposit_amount = Coin::new(ONE_HUNDRED.into(), DENOM_UUSK);
+        let info = mock_info(ADMIN, std::slice::from_ref(&deposit_amount));
+
+        instantiate_contract(deps.as_mut(), env.clone(), info.clone());
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        let conversion = DepositConversion {
+            minimum_receive_amount: Coin::new(TEN.into(), DENOM_UKUJI),
+            route: None,
+        };
+
+        let response = deposit_handler(
+            deps.as_mut(),
+            env,
+            info,
+            vault.owner.clone(),
+            vault.id,
+            Some(conversion.clone()),
+        )
+        .unwrap();
+
+        let config = get_config(deps.as_ref().storage).unwrap();
+
+        assert_eq!(
+            response.messages,
+            vec![SubMsg::reply_on_success(
+                WasmMsg::Execute {
+                    contract_addr: config.exchange_contract_address.to_string(),
+                    msg: to_json_binary(&ExchangeExecuteMsg::Swap {
+                        minimum_receive_amount: conversion.minimum_receive_amount,
+                        route: None,
+                    })
+                    .unwrap(),
+                    funds: vec![deposit_amount.clone()],
+                },
+                AFTER_DEPOSIT_CONVERSION_REPLY_ID,
+            )]
+        );
+        assert_eq!(
+            DEPOSIT_CONVERSION_CACHE
+                .load(deps.as_ref().storage)
+                .unwrap(),
+            DepositConversionCache {
+                vault_id: vault.id,
+                deposit: deposit_amount,
+                swap_denom_balance: Coin::new(0, DENOM_UKUJI),
+            }
+        );
+        assert_eq!(
+            get_vault_handler(deps.as_ref(), vault.id)
+                .unwrap()
+                .vault
+                .balance,
+            vault.balance
+        );
+    }
+
+    #[test]
+    fn with_conversion_of_swap_denom_fails() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+        let info = mock_info(ADMIN, &[Coin::new(ONE_HUNDRED.into(), DENOM_UKUJI)]);
+
+        instantiate_contract(deps.as_mut(), env.clone(), info.clone());
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        let err = deposit_handler(
+            deps.as_mut(),
+            env,
+            info,
+            vault.owner,
+            vault.id,
+            Some(DepositConversion {
+                minimum_receive_amount: Coin::new(TEN.into(), DENOM_UKUJI),
+                route: None,
+            }),
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Error: deposits of ukuji do not need to be converted"
+        );
+    }
+
+    #[test]
+    fn with_conversion_into_other_denom_fails() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+        let info = mock_info(ADMIN, &[Coin::new(ONE_HUNDRED.into(), DENOM_UUSK)]);
+
+        instantiate_contract(deps.as_mut(), env.clone(), info.clone());
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        let err = deposit_handler(
+            deps.as_mut(),
+            env,
+            info,
+            vault.owner,
+            vault.id,
+            Some(DepositConversion {
+                minimum_receive_amount: Coin::new(TEN.into(), "uatom"),
+                route: None,
+            }),
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Error: deposit conversion must receive ukuji, but received uatom"
+        );
+    }
+}
+
+#[cfg(test)]
+mod handle_deposit_conversion_result_tests {
+    use super::handle_deposit_conversion_result;
+    use crate::{
+        constants::{AFTER_DEPOSIT_CONVERSION_REPLY_ID, ONE, TEN},
+        handlers::{
+            get_events_by_resource_id::get_events_by_resource_id_handler,
+            get_vault::get_vault_handler,
+        },
+        state::cache::{DepositConversionCache, DEPOSIT_CONVERSION_CACHE},
+        tests::{
+            helpers::{instantiate_contract, setup_vault},
+            mocks::{ADMIN, DENOM_UKUJI, DENOM_UUSK},
+        },
+        types::{
+            event::{EventBuilder, EventData},
+            vault::{Vault, VaultStatus},
+        },
+    };
+    use cosmwasm_std::{
+        testing::{mock_dependencies, mock_env, mock_info},
+        Coin, Reply, SubMsgResponse, SubMsgResult,
+    };
+    use shared::coin::add;
+
+    #[test]
+    fn credits_received_amount_to_vault() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                status: VaultStatus::Inactive,
+                ..Vault::default()
+            },
+        );
+
+        DEPOSIT_CONVERSION_CACHE
+            .save(
+                deps.as_mut().storage,
+                &DepositConversionCache {
+                    vault_id: vault.id,
+                    deposit: Coin::new(TEN.into(), DENOM_UUSK),
+                    swap_denom_balance: Coin::new(ONE.into(), DENOM_UKUJI),
+                },
+            )
+            .unwrap();
+
+        deps.querier.update_balance(
+            env.contract.address.clone(),
+            vec![Coin::new((ONE + TEN).into(), DENOM_UKUJI)],
+        );
+
+        handle_deposit_conversion_result(
+            deps.as_mut(),
+            env.clone(),
+            Reply {
+                id: AFTER_DEPOSIT_CONVERSION_REPLY_ID,
+                result: SubMsgResult::Ok(SubMsgResponse {
+                    events: vec![],
+                    data: None,
+                }),
+            },
+        )
+        .unwrap();
+
+        let received_amount = Coin::new(TEN.into(), DENOM_UKUJI);
+        let updated_vault = get_vault_handler(deps.as_ref(), vault.id).unwrap().vault;
+
+        assert_eq!(
+            updated_vault.balance,
+            add(vault.balance, received_amount.clone()).unwrap()
+        );
+        assert_eq!(
+            updated_vault.deposited_amount,
+            add(vault.deposited_amount, received_amount.clone()).unwrap()
+        );
+        assert_eq!(updated_vault.status, VaultStatus::Active);
+        assert!(DEPOSIT_CONVERSION_CACHE
+            .may_load(deps.as_ref().storage)
+            .unwrap()
+            .is_none());
+
+        let events = get_events_by_resource_id_handler(deps.as_ref(), vault.id, None, None, None)
+            .unwrap()
+            .events;
+
+        assert!(events.contains(
+            &EventBuilder::new(
+                vault.id,
+                env.block,
+                EventData::DcaVaultFundsDeposited {
+                    amount: received_amount,
+                },
+            )
+            .build(events.len() as u64)
+        ));
+    }
 }
@@ -4,22 +4,28 @@ use crate::{
         disbursement::get_disbursement_messages,
         fees::{get_fee_messages, get_performance_fee},
         price::get_twap_to_now,
-        validation::assert_sender_is_executor,
+        validation::{assert_keeper_can_disburse_escrow, assert_sender_is_executor},
+        vault::get_vault_value_ratio,
     },
     state::{
-        cache::VAULT_ID_CACHE,
         config::get_config,
-        disburse_escrow_tasks::{delete_disburse_escrow_task, get_disburse_escrow_task_due_date},
+        disburse_escrow_tasks::{
+            delete_disburse_escrow_task, get_disburse_escrow_task_due_date,
+            save_disburse_escrow_task,
+        },
         events::create_event,
         vaults::{get_vault, update_vault},
     },
     types::{
+        escrow_vesting::EscrowVesting,
         event::{EventBuilder, EventData},
+        performance_assessment_strategy::PerformanceAssessmentStrategy,
         vault::Vault,
     },
 };
-use cosmwasm_std::{Coin, DepsMut, Env, MessageInfo, Response, Uint128};
-use shared::coin::{empty_of, subtract};
+use cosmwasm_std::{Coin, Decimal, DepsMut, Env, MessageInfo, Response, Uint128};
+use shared::coin::subtract;
+use std::cmp::{max, min};
 
 pub fn disburse_escrow_handler(
     deps: DepsMut,
@@ -27,7 +33,11 @@ pub fn disburse_escrow_handler(
     info: MessageInfo,
     vault_id: Uint128,
 ) -> Result<Response, ContractError> {
-    assert_sender_is_executor(deps.storage, &env, &info.sender)?;
+    let is_keeper = assert_sender_is_executor(deps.storage, &env, &info.sender).is_err();
+
+    if is_keeper {
+        assert_keeper_can_disburse_escrow(deps.storage, &env, vault_id)?;
+    }
 
     let vault = get_vault(deps.storage, vault_id)?;
 
@@ -73,13 +83,94 @@ pub fn disburse_escrow_handler(
         vault.route.clone(),
     )?;
 
-    let performance_fee = get_performance_fee(&vault, current_price)?;
-    let amount_to_disburse = subtract(&vault.escrowed_amount, &performance_fee)?;
+    let (share_of_remaining_escrow, share_of_total_escrow) =
+        vault
+            .escrow_vesting
+            .as_ref()
+            .map_or((Decimal::one(), Decimal::one()), |escrow_vesting| {
+                (
+                    escrow_vesting.next_tranche_share_of_remaining_escrow(),
+                    escrow_vesting.next_tranche_share_of_total_escrow(),
+                )
+            });
+
+    let escrow_released = Coin::new(
+        (vault.escrowed_amount.amount * share_of_remaining_escrow).into(),
+        vault.target_denom.clone(),
+    );
+
+    let is_last_tranche = vault
+        .escrow_vesting
+        .as_ref()
+        .is_none_or(|escrow_vesting| escrow_vesting.is_last_tranche());
+
+    let remaining_share_of_total_escrow = vault
+        .escrow_vesting
+        .as_ref()
+        .map_or(Decimal::one(), |escrow_vesting| {
+            escrow_vesting.remaining_share_of_total_escrow()
+        });
+
+    let performance_fee = match vault.performance_assessment_strategy {
+        // each release is its own assessment point, so it is charged for all value above the
+        // mark left by the previous release
+        Some(PerformanceAssessmentStrategy::HighWaterMark { .. }) => get_performance_fee(
+            &config,
+            &Vault {
+                escrowed_amount: escrow_released.clone(),
+                ..vault.clone()
+            },
+            current_price,
+        )?,
+        _ => {
+            let performance_fee = get_performance_fee(
+                &config,
+                &Vault {
+                    escrowed_amount: Coin::new(
+                        (vault.escrowed_amount.amount
+                            * (Decimal::one() / remaining_share_of_total_escrow))
+                            .into(),
+                        vault.target_denom.clone(),
+                    ),
+                    ..vault.clone()
+                },
+                current_price,
+            )?;
+
+            Coin::new(
+                min(
+                    performance_fee.amount * share_of_total_escrow,
+                    escrow_released.amount,
+                )
+                .into(),
+                performance_fee.denom,
+            )
+        }
+    };
+
+    let amount_to_disburse = subtract(&escrow_released, &performance_fee)?;
+
+    let performance_assessment_strategy = match vault.performance_assessment_strategy.clone() {
+        Some(PerformanceAssessmentStrategy::HighWaterMark { mark }) => {
+            Some(PerformanceAssessmentStrategy::HighWaterMark {
+                mark: max(mark, get_vault_value_ratio(&vault, current_price)),
+            })
+        }
+        strategy => strategy,
+    };
 
     let vault = update_vault(
         deps.storage,
         Vault {
-            escrowed_amount: empty_of(vault.escrowed_amount),
+            escrowed_amount: subtract(&vault.escrowed_amount, &escrow_released)?,
+            performance_assessment_strategy,
+            escrow_vesting: vault
+                .escrow_vesting
+                .clone()
+                .map(|escrow_vesting| EscrowVesting {
+                    released_tranches: escrow_vesting.released_tranches + 1,
+                    ..escrow_vesting
+                }),
             ..vault
         },
     )?;
@@ -96,16 +187,24 @@ pub fn disburse_escrow_handler(
         ),
     )?;
 
-    delete_disburse_escrow_task(deps.storage, vault.id)?;
-
-    VAULT_ID_CACHE.save(deps.storage, &vault.id)?;
+    match (is_last_tranche, vault.escrow_vesting.clone()) {
+        (false, Some(escrow_vesting)) => save_disburse_escrow_task(
+            deps.storage,
+            vault.id,
+            due_date
+                .unwrap_or(env.block.time)
+                .plus_seconds(escrow_vesting.schedule.interval_seconds()),
+        )?,
+        _ => delete_disburse_escrow_task(deps.storage, vault.id)?,
+    }
 
     Ok(response
         .add_submessages(get_disbursement_messages(
             deps.api,
             deps.storage,
+            &env,
             &vault,
-            amount_to_disburse.amount,
+            amount_to_disburse.clone(),
         )?)
         .add_submessages(get_fee_messages(
             deps.as_ref(),
@@ -124,9 +223,13 @@ mod disburse_escrow_tests {
     use crate::{
         constants::{AFTER_FAILED_AUTOMATION_REPLY_ID, ONE, TEN, TEN_DECIMAL},
         handlers::get_events_by_resource_id::get_events_by_resource_id_handler,
+        helpers::message::get_vault_reply_id,
         state::{
-            config::get_config,
-            disburse_escrow_tasks::{get_disburse_escrow_tasks, save_disburse_escrow_task},
+            config::{get_config, update_config},
+            disburse_escrow_tasks::{
+                get_disburse_escrow_task_due_date, get_disburse_escrow_tasks,
+                save_disburse_escrow_task,
+            },
             vaults::get_vault,
         },
         tests::{
@@ -134,7 +237,9 @@ mod disburse_escrow_tests {
             mocks::{calc_mock_dependencies, ADMIN, DENOM_UKUJI, DENOM_UUSK},
         },
         types::{
+            config::Config,
             destination::Destination,
+            escrow_vesting::{EscrowVesting, EscrowVestingSchedule},
             event::{Event, EventData},
             performance_assessment_strategy::PerformanceAssessmentStrategy,
             swap_adjustment_strategy::SwapAdjustmentStrategy,
@@ -179,32 +284,31 @@ mod disburse_escrow_tests {
     }
 
     #[test]
-    fn caches_vault_id_for_after_automation_handler() {
+    fn tags_disbursement_replies_with_vault_id() {
         let mut deps = calc_mock_dependencies();
         let env = mock_env();
         let info = mock_info(ADMIN, &[]);
 
-        instantiate_contract(deps.as_mut(), env.clone(), info);
+        instantiate_contract(deps.as_mut(), env.clone(), info.clone());
+
+        setup_vault(deps.as_mut(), env.clone(), Vault::default());
 
         let vault = setup_vault(
             deps.as_mut(),
             env.clone(),
             Vault {
+                status: VaultStatus::Inactive,
                 escrowed_amount: Coin::new(ONE.into(), DENOM_UUSK),
                 ..Vault::default()
             },
         );
 
-        save_disburse_escrow_task(
-            deps.as_mut().storage,
-            vault.id,
-            env.block.time.minus_seconds(10),
-        )
-        .unwrap();
-
-        let cached_vault_id = VAULT_ID_CACHE.load(deps.as_ref().storage).unwrap();
+        let response = disburse_escrow_handler(deps.as_mut(), env, info, vault.id).unwrap();
 
-        assert_eq!(vault.id, cached_vault_id)
+        assert!(response
+            .messages
+            .iter()
+            .any(|msg| msg.id == get_vault_reply_id(AFTER_FAILED_AUTOMATION_REPLY_ID, vault.id)));
     }
 
     #[test]
@@ -309,6 +413,7 @@ mod disburse_escrow_tests {
             env.clone(),
             Vault {
                 status: VaultStatus::Inactive,
+                escrow_level: Decimal::percent(5),
                 swapped_amount: Coin::new(TEN.into(), DENOM_UKUJI),
                 received_amount: Coin::new(TEN.into(), DENOM_UUSK),
                 deposited_amount: Coin::new(TEN.into(), DENOM_UKUJI),
@@ -352,6 +457,7 @@ mod disburse_escrow_tests {
             env.clone(),
             Vault {
                 status: VaultStatus::Inactive,
+                escrow_level: Decimal::percent(5),
                 swapped_amount: Coin::new(TEN.into(), DENOM_UKUJI),
                 received_amount: Coin::new((TEN + ONE).into(), DENOM_UUSK),
                 deposited_amount: Coin::new(TEN.into(), DENOM_UKUJI),
@@ -379,7 +485,7 @@ mod disburse_escrow_tests {
         );
 
         assert_eq!(
-            events.get(0).unwrap(),
+            events.first().unwrap(),
             &Event {
                 id: 1,
                 resource_id: vault.id,
@@ -482,4 +588,515 @@ mod disburse_escrow_tests {
         assert_eq!(disburse_escrow_tasks_before.len(), 1);
         assert_eq!(disburse_escrow_tasks_after.len(), 0);
     }
+
+    #[test]
+    fn when_sender_is_not_executor_and_permissionless_execution_is_disabled_fails() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                escrowed_amount: Coin::new(ONE.into(), DENOM_UUSK),
+                ..Vault::default()
+            },
+        );
+
+        save_disburse_escrow_task(deps.as_mut().storage, vault.id, env.block.time).unwrap();
+
+        let err = disburse_escrow_handler(deps.as_mut(), env, mock_info("keeper", &[]), vault.id)
+            .unwrap_err();
+
+        assert_eq!(err.to_string(), "Unauthorized");
+    }
+
+    #[test]
+    fn when_keeper_and_no_disburse_escrow_task_is_due_fails() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        update_config(
+            deps.as_mut().storage,
+            Config {
+                permissionless_execution: true,
+                ..Config::default()
+            },
+        )
+        .unwrap();
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                escrowed_amount: Coin::new(ONE.into(), DENOM_UUSK),
+                ..Vault::default()
+            },
+        );
+
+        let err = disburse_escrow_handler(deps.as_mut(), env, mock_info("keeper", &[]), vault.id)
+            .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "Error: no escrow disbursement is due for vault with id {}",
+                vault.id
+            )
+        );
+    }
+
+    #[test]
+    fn when_keeper_and_disburse_escrow_task_is_due_succeeds() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        update_config(
+            deps.as_mut().storage,
+            Config {
+                permissionless_execution: true,
+                ..Config::default()
+            },
+        )
+        .unwrap();
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                escrowed_amount: Coin::new(ONE.into(), DENOM_UUSK),
+                ..Vault::default()
+            },
+        );
+
+        save_disburse_escrow_task(
+            deps.as_mut().storage,
+            vault.id,
+            env.block.time.minus_seconds(10),
+        )
+        .unwrap();
+
+        disburse_escrow_handler(deps.as_mut(), env, mock_info("keeper", &[]), vault.id).unwrap();
+
+        let updated_vault = get_vault(deps.as_ref().storage, vault.id).unwrap();
+
+        assert!(updated_vault.escrowed_amount.amount.is_zero());
+    }
+
+    #[test]
+    fn with_high_water_mark_charges_fee_on_value_above_mark() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+        let info = mock_info(ADMIN, &[]);
+
+        instantiate_contract(deps.as_mut(), env.clone(), info.clone());
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                status: VaultStatus::Inactive,
+                escrow_level: Decimal::percent(5),
+                swapped_amount: Coin::new(TEN.into(), DENOM_UKUJI),
+                received_amount: Coin::new((TEN + TEN).into(), DENOM_UUSK),
+                deposited_amount: Coin::new(TEN.into(), DENOM_UKUJI),
+                escrowed_amount: Coin::new(TEN.into(), DENOM_UUSK),
+                performance_assessment_strategy: Some(
+                    PerformanceAssessmentStrategy::HighWaterMark {
+                        mark: Decimal::percent(150),
+                    },
+                ),
+                ..Vault::default()
+            },
+        );
+
+        disburse_escrow_handler(deps.as_mut(), env, info, vault.id).unwrap();
+
+        let events = get_events_by_resource_id_handler(deps.as_ref(), vault.id, None, None, None)
+            .unwrap()
+            .events;
+
+        let config = get_config(deps.as_ref().storage).unwrap();
+
+        assert_eq!(
+            events.first().unwrap().data,
+            EventData::DcaVaultEscrowDisbursed {
+                amount_disbursed: Coin::new(
+                    (TEN - TEN
+                        * Decimal::percent(50)
+                        * config.high_water_mark_performance_fee_percent)
+                        .into(),
+                    DENOM_UUSK
+                ),
+                performance_fee: Coin::new(
+                    (TEN * Decimal::percent(50) * config.high_water_mark_performance_fee_percent)
+                        .into(),
+                    DENOM_UUSK
+                ),
+            }
+        );
+    }
+
+    #[test]
+    fn with_high_water_mark_raises_mark_to_current_value() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+        let info = mock_info(ADMIN, &[]);
+
+        instantiate_contract(deps.as_mut(), env.clone(), info.clone());
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                status: VaultStatus::Inactive,
+                swapped_amount: Coin::new(TEN.into(), DENOM_UKUJI),
+                received_amount: Coin::new((TEN + TEN).into(), DENOM_UUSK),
+                deposited_amount: Coin::new(TEN.into(), DENOM_UKUJI),
+                escrowed_amount: Coin::new(ONE.into(), DENOM_UUSK),
+                performance_assessment_strategy: Some(
+                    PerformanceAssessmentStrategy::HighWaterMark {
+                        mark: Decimal::one(),
+                    },
+                ),
+                ..Vault::default()
+            },
+        );
+
+        disburse_escrow_handler(deps.as_mut(), env, info, vault.id).unwrap();
+
+        let updated_vault = get_vault(deps.as_ref().storage, vault.id).unwrap();
+
+        assert_eq!(
+            updated_vault.performance_assessment_strategy,
+            Some(PerformanceAssessmentStrategy::HighWaterMark {
+                mark: Decimal::percent(200),
+            })
+        );
+    }
+
+    #[test]
+    fn with_value_below_high_water_mark_charges_no_fee_and_keeps_mark() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+        let info = mock_info(ADMIN, &[]);
+
+        instantiate_contract(deps.as_mut(), env.clone(), info.clone());
+
+        let performance_assessment_strategy = PerformanceAssessmentStrategy::HighWaterMark {
+            mark: Decimal::percent(300),
+        };
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                status: VaultStatus::Inactive,
+                swapped_amount: Coin::new(TEN.into(), DENOM_UKUJI),
+                received_amount: Coin::new((TEN + TEN).into(), DENOM_UUSK),
+                deposited_amount: Coin::new(TEN.into(), DENOM_UKUJI),
+                destinations: vec![Destination::default()],
+                escrowed_amount: Coin::new(ONE.into(), DENOM_UUSK),
+                performance_assessment_strategy: Some(performance_assessment_strategy.clone()),
+                ..Vault::default()
+            },
+        );
+
+        let response = disburse_escrow_handler(deps.as_mut(), env, info, vault.id).unwrap();
+
+        assert!(response.messages.contains(&SubMsg::reply_always(
+            BankMsg::Send {
+                to_address: vault.destinations[0].address.to_string(),
+                amount: vec![vault.escrowed_amount]
+            },
+            AFTER_FAILED_AUTOMATION_REPLY_ID
+        )));
+
+        let updated_vault = get_vault(deps.as_ref().storage, vault.id).unwrap();
+
+        assert_eq!(
+            updated_vault.performance_assessment_strategy,
+            Some(performance_assessment_strategy)
+        );
+    }
+
+    #[test]
+    fn with_escrow_vesting_releases_first_tranche_and_schedules_next() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+        let info = mock_info(ADMIN, &[]);
+
+        instantiate_contract(deps.as_mut(), env.clone(), info.clone());
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                status: VaultStatus::Inactive,
+                deposited_amount: Coin::new(TEN.into(), DENOM_UKUJI),
+                escrowed_amount: Coin::new(TEN.into(), DENOM_UUSK),
+                performance_assessment_strategy: Some(
+                    PerformanceAssessmentStrategy::HighWaterMark {
+                        mark: Decimal::percent(500),
+                    },
+                ),
+                escrow_vesting: Some(EscrowVesting {
+                    schedule: EscrowVestingSchedule::Linear {
+                        tranches: 2,
+                        interval_seconds: 100,
+                    },
+                    released_tranches: 0,
+                }),
+                ..Vault::default()
+            },
+        );
+
+        let due_date = env.block.time.minus_seconds(10);
+
+        save_disburse_escrow_task(deps.as_mut().storage, vault.id, due_date).unwrap();
+
+        disburse_escrow_handler(deps.as_mut(), env.clone(), info, vault.id).unwrap();
+
+        let updated_vault = get_vault(deps.as_ref().storage, vault.id).unwrap();
+
+        assert_eq!(
+            updated_vault.escrowed_amount,
+            Coin::new((TEN / Uint128::new(2)).into(), DENOM_UUSK)
+        );
+        assert_eq!(updated_vault.escrow_vesting.unwrap().released_tranches, 1);
+        assert_eq!(
+            get_disburse_escrow_task_due_date(deps.as_ref().storage, vault.id)
+                .unwrap()
+                .map(|due_date| due_date.seconds()),
+            Some(due_date.plus_seconds(100).seconds())
+        );
+    }
+
+    #[test]
+    fn with_escrow_vesting_releases_remaining_escrow_on_last_tranche() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+        let info = mock_info(ADMIN, &[]);
+
+        instantiate_contract(deps.as_mut(), env.clone(), info.clone());
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                status: VaultStatus::Inactive,
+                deposited_amount: Coin::new(TEN.into(), DENOM_UKUJI),
+                escrowed_amount: Coin::new(ONE.into(), DENOM_UUSK),
+                performance_assessment_strategy: Some(
+                    PerformanceAssessmentStrategy::HighWaterMark {
+                        mark: Decimal::percent(500),
+                    },
+                ),
+                escrow_vesting: Some(EscrowVesting {
+                    schedule: EscrowVestingSchedule::Stepped {
+                        shares: vec![Decimal::percent(70), Decimal::percent(30)],
+                        interval_seconds: 100,
+                    },
+                    released_tranches: 1,
+                }),
+                ..Vault::default()
+            },
+        );
+
+        save_disburse_escrow_task(deps.as_mut().storage, vault.id, env.block.time).unwrap();
+
+        disburse_escrow_handler(deps.as_mut(), env.clone(), info, vault.id).unwrap();
+
+        let updated_vault = get_vault(deps.as_ref().storage, vault.id).unwrap();
+
+        assert!(updated_vault.escrowed_amount.amount.is_zero());
+        assert_eq!(
+            get_disburse_escrow_task_due_date(deps.as_ref().storage, vault.id).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn with_escrow_vesting_charges_performance_fee_pro_rata() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+        let info = mock_info(ADMIN, &[]);
+
+        instantiate_contract(deps.as_mut(), env.clone(), info.clone());
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                status: VaultStatus::Inactive,
+                escrow_level: Decimal::percent(5),
+                swapped_amount: Coin::new(TEN.into(), DENOM_UKUJI),
+                received_amount: Coin::new((TEN + TEN).into(), DENOM_UUSK),
+                deposited_amount: Coin::new(TEN.into(), DENOM_UKUJI),
+                escrowed_amount: Coin::new(TEN.into(), DENOM_UUSK),
+                performance_assessment_strategy: Some(
+                    PerformanceAssessmentStrategy::CompareToStandardDca {
+                        swapped_amount: Coin::new(TEN.into(), DENOM_UKUJI),
+                        received_amount: Coin::new(TEN.into(), DENOM_UUSK),
+                    },
+                ),
+                escrow_vesting: Some(EscrowVesting {
+                    schedule: EscrowVestingSchedule::Linear {
+                        tranches: 4,
+                        interval_seconds: 100,
+                    },
+                    released_tranches: 0,
+                }),
+                ..Vault::default()
+            },
+        );
+
+        disburse_escrow_handler(deps.as_mut(), env, info, vault.id).unwrap();
+
+        let events = get_events_by_resource_id_handler(deps.as_ref(), vault.id, None, None, None)
+            .unwrap()
+            .events;
+
+        let performance_fee = TEN * Decimal::percent(20) * Decimal::percent(25);
+
+        assert_eq!(
+            events.first().unwrap().data,
+            EventData::DcaVaultEscrowDisbursed {
+                amount_disbursed: Coin::new(
+                    (TEN * Decimal::percent(25) - performance_fee).into(),
+                    DENOM_UUSK
+                ),
+                performance_fee: Coin::new(performance_fee.into(), DENOM_UUSK),
+            }
+        );
+    }
+
+    #[test]
+    fn with_escrow_vesting_and_high_water_mark_charges_fee_only_on_new_highs() {
+        let mut deps = calc_mock_dependencies();
+        let mut env = mock_env();
+        let info = mock_info(ADMIN, &[]);
+
+        instantiate_contract(deps.as_mut(), env.clone(), info.clone());
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                status: VaultStatus::Inactive,
+                escrow_level: Decimal::percent(5),
+                swapped_amount: Coin::new(TEN.into(), DENOM_UKUJI),
+                received_amount: Coin::new((TEN + TEN).into(), DENOM_UUSK),
+                deposited_amount: Coin::new(TEN.into(), DENOM_UKUJI),
+                escrowed_amount: Coin::new(TEN.into(), DENOM_UUSK),
+                performance_assessment_strategy: Some(
+                    PerformanceAssessmentStrategy::HighWaterMark {
+                        mark: Decimal::percent(150),
+                    },
+                ),
+                escrow_vesting: Some(EscrowVesting {
+                    schedule: EscrowVestingSchedule::Linear {
+                        tranches: 4,
+                        interval_seconds: 100,
+                    },
+                    released_tranches: 0,
+                }),
+                ..Vault::default()
+            },
+        );
+
+        for _ in 0..4 {
+            disburse_escrow_handler(deps.as_mut(), env.clone(), info.clone(), vault.id).unwrap();
+            env.block.time = env.block.time.plus_seconds(100);
+        }
+
+        let config = get_config(deps.as_ref().storage).unwrap();
+
+        let performance_fee =
+            TEN * Decimal::percent(50) * config.high_water_mark_performance_fee_percent;
+
+        let performance_fees =
+            get_events_by_resource_id_handler(deps.as_ref(), vault.id, None, None, None)
+                .unwrap()
+                .events
+                .into_iter()
+                .flat_map(|event| match event.data {
+                    EventData::DcaVaultEscrowDisbursed {
+                        performance_fee, ..
+                    } => Some(performance_fee.amount),
+                    _ => None,
+                })
+                .collect::<Vec<Uint128>>();
+
+        assert_eq!(
+            performance_fees,
+            vec![
+                performance_fee,
+                Uint128::zero(),
+                Uint128::zero(),
+                Uint128::zero()
+            ]
+        );
+
+        let updated_vault = get_vault(deps.as_ref().storage, vault.id).unwrap();
+
+        assert!(updated_vault.escrowed_amount.amount.is_zero());
+        assert_eq!(
+            updated_vault.performance_assessment_strategy,
+            Some(PerformanceAssessmentStrategy::HighWaterMark {
+                mark: Decimal::percent(200),
+            })
+        );
+    }
+
+    #[test]
+    fn with_escrow_vesting_and_high_water_mark_raises_mark_on_each_tranche() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+        let info = mock_info(ADMIN, &[]);
+
+        instantiate_contract(deps.as_mut(), env.clone(), info.clone());
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                status: VaultStatus::Inactive,
+                swapped_amount: Coin::new(TEN.into(), DENOM_UKUJI),
+                received_amount: Coin::new((TEN + TEN).into(), DENOM_UUSK),
+                deposited_amount: Coin::new(TEN.into(), DENOM_UKUJI),
+                escrowed_amount: Coin::new(TEN.into(), DENOM_UUSK),
+                performance_assessment_strategy: Some(
+                    PerformanceAssessmentStrategy::HighWaterMark {
+                        mark: Decimal::percent(150),
+                    },
+                ),
+                escrow_vesting: Some(EscrowVesting {
+                    schedule: EscrowVestingSchedule::Linear {
+                        tranches: 2,
+                        interval_seconds: 100,
+                    },
+                    released_tranches: 0,
+                }),
+                ..Vault::default()
+            },
+        );
+
+        disburse_escrow_handler(deps.as_mut(), env, info, vault.id).unwrap();
+
+        let updated_vault = get_vault(deps.as_ref().storage, vault.id).unwrap();
+
+        assert_eq!(
+            updated_vault.performance_assessment_strategy,
+            Some(PerformanceAssessmentStrategy::HighWaterMark {
+                mark: Decimal::percent(200),
+            })
+        );
+    }
 }
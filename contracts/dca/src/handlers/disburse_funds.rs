@@ -1,12 +1,23 @@
 use crate::error::ContractError;
 use crate::helpers::disbursement::get_disbursement_messages;
-use crate::helpers::fees::{get_automation_fee_rate, get_fee_messages, get_swap_fee_rate};
+use crate::helpers::fees::{
+    deduct_keeper_bounty, deduct_referral_fee, get_automation_fee_rate,
+    get_basket_asset_swap_fee_rate, get_fee_messages,
+};
 use crate::helpers::math::checked_mul;
+use crate::helpers::message::split_vault_reply_id;
+use crate::helpers::vault::{end_vault, schedule_execution_retry};
 use crate::msg::ExecuteMsg;
-use crate::state::cache::{SWAP_CACHE, VAULT_ID_CACHE};
+use crate::state::cache::{
+    SwapCache, BASKET_SWAP_CACHE, CATCH_UP_AMOUNT_CACHE, KEEPER_CACHE, PARTIAL_SWAP_CACHE,
+    RETRY_ATTEMPT_CACHE, SWAP_CACHE,
+};
+use crate::state::config::get_config;
 use crate::state::events::create_event;
 use crate::state::triggers::delete_trigger;
 use crate::state::vaults::{get_vault, update_vault};
+use crate::types::basket::BasketAsset;
+use crate::types::end_conditions::EndConditions;
 use crate::types::event::{EventBuilder, EventData, ExecutionSkippedReason};
 use crate::types::vault::{Vault, VaultStatus};
 use cosmwasm_std::{to_json_binary, SubMsg, SubMsgResult, Uint128, WasmMsg};
@@ -14,21 +25,40 @@ use cosmwasm_std::{to_json_binary, SubMsg, SubMsgResult, Uint128, WasmMsg};
 use cosmwasm_std::{Attribute, Coin, DepsMut, Env, Reply, Response};
 use shared::balance::query_balance;
 use shared::coin::{add_to, subtract};
+use shared::cw20::into_bank_msg;
 
 pub fn disburse_funds_handler(
     deps: DepsMut,
     env: &Env,
     reply: Reply,
 ) -> Result<Response, ContractError> {
-    let vault_id = VAULT_ID_CACHE.load(deps.storage)?;
+    let (_, vault_id) = split_vault_reply_id(reply.id);
     let mut vault = get_vault(deps.storage, vault_id)?;
 
+    let mut basket_swap_cache = match vault.basket {
+        Some(_) => Some(BASKET_SWAP_CACHE.load(deps.storage, vault.id.into())?),
+        None => None,
+    };
+
+    let target_denom = basket_swap_cache
+        .as_mut()
+        .and_then(|cache| cache.pop_front())
+        .unwrap_or_else(|| vault.target_denom.clone());
+
+    let is_final_swap = basket_swap_cache
+        .as_ref()
+        .is_none_or(|cache| cache.is_empty());
+
+    let partial_execution_scheduled_amount =
+        PARTIAL_SWAP_CACHE.may_load(deps.storage, vault.id.into())?;
+    PARTIAL_SWAP_CACHE.remove(deps.storage, vault.id.into());
+
     let mut attributes = Vec::<Attribute>::new();
     let mut sub_msgs = Vec::<SubMsg>::new();
 
     match reply.result {
         SubMsgResult::Ok(_) => {
-            let swap_cache = SWAP_CACHE.load(deps.storage)?;
+            let swap_cache = SWAP_CACHE.load(deps.storage, vault.id.into())?;
 
             let swap_denom_balance = query_balance(
                 deps.api,
@@ -40,7 +70,7 @@ pub fn disburse_funds_handler(
             let receive_denom_balance = query_balance(
                 deps.api,
                 &deps.querier,
-                &vault.target_denom,
+                &target_denom,
                 &env.contract.address,
             )?;
 
@@ -48,7 +78,8 @@ pub fn disburse_funds_handler(
             let coin_received =
                 subtract(&receive_denom_balance, &swap_cache.receive_denom_balance)?;
 
-            let swap_fee_rate = get_swap_fee_rate(deps.storage, &vault.swap_adjustment_strategy)?;
+            let swap_fee_rate =
+                get_basket_asset_swap_fee_rate(deps.storage, &vault, &coin_received.denom)?;
             let automation_fee_rate = get_automation_fee_rate(deps.storage, &vault)?;
 
             let swap_fee = checked_mul(coin_received.amount, swap_fee_rate)?;
@@ -57,26 +88,121 @@ pub fn disburse_funds_handler(
             let total_fee = swap_fee + automation_fee;
             let total_after_all_fees = coin_received.amount - total_fee;
 
+            let fee_amounts = match vault.referrer.clone() {
+                Some(referrer) => {
+                    let (fee_amounts, referral_fee) = deduct_referral_fee(
+                        deps.storage,
+                        &referrer,
+                        vec![swap_fee, automation_fee],
+                        &coin_received.denom,
+                    )?;
+
+                    if !referral_fee.amount.is_zero() {
+                        sub_msgs.push(SubMsg::new(into_bank_msg(
+                            deps.api,
+                            referrer.as_ref(),
+                            vec![referral_fee.clone()],
+                        )?));
+
+                        attributes.push(Attribute::new("referrer", referrer.to_string()));
+                        attributes.push(Attribute::new("referral_fee", referral_fee.to_string()));
+                    }
+
+                    fee_amounts
+                }
+                None => vec![swap_fee, automation_fee],
+            };
+
+            let fee_amounts = match KEEPER_CACHE.may_load(deps.storage, vault.id.into())? {
+                Some(keeper) => {
+                    let (fee_amounts, keeper_bounty) = deduct_keeper_bounty(
+                        fee_amounts,
+                        get_config(deps.storage)?.keeper_bounty_percent,
+                    );
+
+                    if !keeper_bounty.is_zero() {
+                        sub_msgs.push(SubMsg::new(into_bank_msg(
+                            deps.api,
+                            keeper.as_ref(),
+                            vec![Coin::new(keeper_bounty.into(), coin_received.denom.clone())],
+                        )?));
+
+                        attributes.push(Attribute::new("keeper", keeper.to_string()));
+                        attributes.push(Attribute::new(
+                            "keeper_bounty",
+                            Coin::new(keeper_bounty.into(), coin_received.denom.clone())
+                                .to_string(),
+                        ));
+                    }
+
+                    fee_amounts
+                }
+                None => fee_amounts,
+            };
+
             sub_msgs.append(&mut get_fee_messages(
                 deps.as_ref(),
                 env.clone(),
-                vec![swap_fee, automation_fee],
+                fee_amounts,
                 coin_received.denom.clone(),
                 false,
             )?);
 
-            let amount_to_escrow = total_after_all_fees * vault.escrow_level;
+            let amount_to_escrow = if coin_received.denom == vault.escrowed_amount.denom {
+                total_after_all_fees * vault.escrow_level
+            } else {
+                Uint128::zero()
+            };
             let total_after_escrow = total_after_all_fees - amount_to_escrow;
 
+            let caught_up_amount = CATCH_UP_AMOUNT_CACHE.may_load(deps.storage, vault.id.into())?;
+            CATCH_UP_AMOUNT_CACHE.remove(deps.storage, vault.id.into());
+
             vault.balance.amount -= coin_sent.amount;
 
             vault = update_vault(
                 deps.storage,
                 Vault {
                     swapped_amount: add_to(&vault.swapped_amount, coin_sent.amount),
-                    received_amount: add_to(&vault.received_amount, total_after_all_fees),
+                    received_amount: match vault.basket {
+                        Some(_) => vault.received_amount,
+                        None => add_to(&vault.received_amount, total_after_all_fees),
+                    },
+                    basket: vault.basket.map(|basket| {
+                        basket
+                            .into_iter()
+                            .map(|asset| {
+                                if asset.target_denom == coin_received.denom {
+                                    BasketAsset {
+                                        received_amount: add_to(
+                                            &asset.received_amount,
+                                            total_after_all_fees,
+                                        ),
+                                        ..asset
+                                    }
+                                } else {
+                                    asset
+                                }
+                            })
+                            .collect()
+                    }),
                     escrowed_amount: add_to(&vault.escrowed_amount, amount_to_escrow),
-                    status: if vault.balance.amount.is_zero() {
+                    catch_up_schedule: match caught_up_amount {
+                        Some(caught_up_amount) => vault
+                            .catch_up_schedule
+                            .as_ref()
+                            .and_then(|schedule| schedule.after_execution(caught_up_amount)),
+                        None => vault.catch_up_schedule,
+                    },
+                    end_conditions: vault.end_conditions.map(|end_conditions| EndConditions {
+                        executions: if is_final_swap {
+                            end_conditions.executions + 1
+                        } else {
+                            end_conditions.executions
+                        },
+                        ..end_conditions
+                    }),
+                    status: if vault.balance.amount.is_zero() && !vault.recurring_deposits {
                         VaultStatus::Inactive
                     } else {
                         vault.status
@@ -86,8 +212,14 @@ pub fn disburse_funds_handler(
             )?;
 
             sub_msgs.append(
-                &mut get_disbursement_messages(deps.api, deps.storage, &vault, total_after_escrow)?
-                    .into(),
+                &mut get_disbursement_messages(
+                    deps.api,
+                    deps.storage,
+                    env,
+                    &vault,
+                    Coin::new(total_after_escrow.into(), coin_received.denom.clone()),
+                )?
+                .into(),
             );
 
             create_event(
@@ -95,10 +227,18 @@ pub fn disburse_funds_handler(
                 EventBuilder::new(
                     vault.id,
                     env.block.clone(),
-                    EventData::DcaVaultExecutionCompleted {
-                        sent: coin_sent.clone(),
-                        received: coin_received.clone(),
-                        fee: Coin::new(total_fee.into(), coin_received.denom.clone()),
+                    match partial_execution_scheduled_amount {
+                        Some(scheduled_amount) => EventData::DcaVaultExecutionPartiallyCompleted {
+                            sent: coin_sent.clone(),
+                            scheduled: scheduled_amount,
+                            received: coin_received.clone(),
+                            fee: Coin::new(total_fee.into(), coin_received.denom.clone()),
+                        },
+                        None => EventData::DcaVaultExecutionCompleted {
+                            sent: coin_sent.clone(),
+                            received: coin_received.clone(),
+                            fee: Coin::new(total_fee.into(), coin_received.denom.clone()),
+                        },
                     },
                 ),
             )?;
@@ -124,6 +264,65 @@ pub fn disburse_funds_handler(
                 "execution_skipped",
                 "slippage_tolerance_exceeded",
             ));
+
+            if vault.basket.is_none() {
+                let retry_attempt = RETRY_ATTEMPT_CACHE
+                    .may_load(deps.storage, vault.id.into())?
+                    .unwrap_or_default();
+
+                if let Some(retry_time) =
+                    schedule_execution_retry(deps.storage, env, &vault, retry_attempt)?
+                {
+                    attributes.push(Attribute::new("retry_scheduled", retry_time.to_string()));
+                }
+            }
+        }
+    }
+
+    if let Some(basket_swap_cache) = basket_swap_cache.filter(|cache| !cache.is_empty()) {
+        if let Some(next_target_denom) = basket_swap_cache.front() {
+            SWAP_CACHE.save(
+                deps.storage,
+                vault.id.into(),
+                &SwapCache {
+                    swap_denom_balance: query_balance(
+                        deps.api,
+                        &deps.querier,
+                        &vault.get_swap_denom(),
+                        &env.contract.address,
+                    )?,
+                    receive_denom_balance: query_balance(
+                        deps.api,
+                        &deps.querier,
+                        next_target_denom,
+                        &env.contract.address,
+                    )?,
+                },
+            )?;
+        }
+
+        BASKET_SWAP_CACHE.save(deps.storage, vault.id.into(), &basket_swap_cache)?;
+    }
+
+    if is_final_swap {
+        SWAP_CACHE.remove(deps.storage, vault.id.into());
+        BASKET_SWAP_CACHE.remove(deps.storage, vault.id.into());
+        RETRY_ATTEMPT_CACHE.remove(deps.storage, vault.id.into());
+        CATCH_UP_AMOUNT_CACHE.remove(deps.storage, vault.id.into());
+        KEEPER_CACHE.remove(deps.storage, vault.id.into());
+
+        if let Some(condition) = vault.get_reached_end_condition(env.block.time) {
+            attributes.push(Attribute::new(
+                "end_condition_reached",
+                format!("{:?}", condition),
+            ));
+
+            let (_, mut end_vault_msgs) = end_vault(deps.storage, env, vault, condition)?;
+            sub_msgs.append(&mut end_vault_msgs);
+
+            return Ok(Response::new()
+                .add_attributes(attributes)
+                .add_submessages(sub_msgs));
         }
     }
 
@@ -152,9 +351,14 @@ mod disburse_funds_tests {
         handlers::get_events_by_resource_id::get_events_by_resource_id_handler,
         helpers::vault::get_swap_amount,
         state::{
-            cache::{SwapCache, SWAP_CACHE},
-            config::get_config,
+            cache::{
+                SwapCache, CATCH_UP_AMOUNT_CACHE, KEEPER_CACHE, RETRY_ATTEMPT_CACHE, SWAP_CACHE,
+            },
+            config::{get_config, update_config},
+            custom_swap_fees::save_custom_swap_fee,
+            referrals::{get_referral_fees, save_referral_fee_caps},
             swap_adjustments::update_swap_adjustment,
+            triggers::get_trigger,
             vaults::get_vault,
         },
         tests::{
@@ -165,20 +369,26 @@ mod disburse_funds_tests {
             mocks::{ADMIN, DENOM_UKUJI, DENOM_UUSK},
         },
         types::{
+            config::Config,
             destination::Destination,
+            end_conditions::EndCondition,
             event::{Event, EventBuilder, EventData, ExecutionSkippedReason},
             fee_collector::FeeCollector,
+            missed_execution_policy::CatchUpSchedule,
             performance_assessment_strategy::PerformanceAssessmentStrategy,
             position_type::PositionType,
+            retry_policy::RetryPolicy,
             swap_adjustment_strategy::{BaseDenom, SwapAdjustmentStrategy},
+            trigger::TriggerConfiguration,
             vault::{Vault, VaultStatus},
         },
     };
     use cosmwasm_std::{
-        testing::{mock_dependencies, mock_env, mock_info},
-        BankMsg, Coin, Decimal, Reply, SubMsg, SubMsgResponse, SubMsgResult, Uint128,
+        testing::{mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage},
+        Addr, BankMsg, Coin, CosmosMsg, Decimal, OwnedDeps, Reply, SubMsg, SubMsgResponse,
+        SubMsgResult, Uint128,
     };
-    use std::str::FromStr;
+    use std::{collections::VecDeque, str::FromStr};
 
     #[test]
     fn with_successful_swap_returns_funds_to_destination() {
@@ -200,6 +410,7 @@ mod disburse_funds_tests {
         SWAP_CACHE
             .save(
                 deps.as_mut().storage,
+                vault.id.into(),
                 &SwapCache {
                     swap_denom_balance: vault.balance.clone(),
                     receive_denom_balance: Coin::new(0, vault.target_denom.clone()),
@@ -229,17 +440,21 @@ mod disburse_funds_tests {
 
         let automation_fee = get_config(&deps.storage).unwrap().automation_fee_percent;
 
-        let automation_fees = vault.destinations.iter().filter(|d| d.msg.is_some()).fold(
-            Coin::new(0, vault.target_denom.clone()),
-            |mut accum, destination| {
-                let allocation_amount =
-                    checked_mul(receive_amount - fee, destination.allocation).unwrap();
-                let allocation_automation_fee =
-                    checked_mul(allocation_amount, automation_fee).unwrap();
-                accum.amount = accum.amount.checked_add(allocation_automation_fee).unwrap();
-                accum
-            },
-        );
+        let automation_fees = vault
+            .destinations
+            .iter()
+            .filter(|d| d.action.incurs_automation_fee())
+            .fold(
+                Coin::new(0, vault.target_denom.clone()),
+                |mut accum, destination| {
+                    let allocation_amount =
+                        checked_mul(receive_amount - fee, destination.allocation).unwrap();
+                    let allocation_automation_fee =
+                        checked_mul(allocation_amount, automation_fee).unwrap();
+                    accum.amount = accum.amount.checked_add(allocation_automation_fee).unwrap();
+                    accum
+                },
+            );
 
         let disbursal_amount = receive_amount - fee - automation_fees.amount;
 
@@ -267,6 +482,7 @@ mod disburse_funds_tests {
         SWAP_CACHE
             .save(
                 deps.as_mut().storage,
+                vault.id.into(),
                 &SwapCache {
                     swap_denom_balance: vault.balance.clone(),
                     receive_denom_balance: Coin::new(0, vault.target_denom.clone()),
@@ -328,6 +544,7 @@ mod disburse_funds_tests {
         SWAP_CACHE
             .save(
                 deps.as_mut().storage,
+                vault.id.into(),
                 &SwapCache {
                     swap_denom_balance: vault.balance.clone(),
                     receive_denom_balance: Coin::new(0, vault.target_denom.clone()),
@@ -357,16 +574,17 @@ mod disburse_funds_tests {
         let swap_fee = config.default_swap_fee_percent * receive_amount;
         let total_after_swap_fee = receive_amount - swap_fee;
 
-        let automation_fee = vault.destinations.iter().filter(|d| d.msg.is_some()).fold(
-            Uint128::zero(),
-            |acc, destination| {
+        let automation_fee = vault
+            .destinations
+            .iter()
+            .filter(|d| d.action.incurs_automation_fee())
+            .fold(Uint128::zero(), |acc, destination| {
                 let allocation_amount =
                     checked_mul(total_after_swap_fee, destination.allocation).unwrap();
                 let allocation_automation_fee =
                     checked_mul(allocation_amount, config.automation_fee_percent).unwrap();
                 acc.checked_add(allocation_automation_fee).unwrap()
-            },
-        );
+            });
 
         for fee_collector in config.fee_collectors.iter() {
             assert!(response.messages.contains(&SubMsg::new(BankMsg::Send {
@@ -403,6 +621,7 @@ mod disburse_funds_tests {
         SWAP_CACHE
             .save(
                 deps.as_mut().storage,
+                vault.id.into(),
                 &SwapCache {
                     swap_denom_balance: vault.balance.clone(),
                     receive_denom_balance: Coin::new(0, vault.target_denom.clone()),
@@ -442,6 +661,121 @@ mod disburse_funds_tests {
         );
     }
 
+    #[test]
+    fn with_successful_swap_advances_catch_up_schedule() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                catch_up_schedule: Some(CatchUpSchedule {
+                    remaining_amount: ONE * Uint128::new(3),
+                    executions_remaining: 3,
+                }),
+                ..Vault::default()
+            },
+        );
+
+        SWAP_CACHE
+            .save(
+                deps.as_mut().storage,
+                vault.id.into(),
+                &SwapCache {
+                    swap_denom_balance: vault.balance.clone(),
+                    receive_denom_balance: Coin::new(0, vault.target_denom.clone()),
+                },
+            )
+            .unwrap();
+
+        CATCH_UP_AMOUNT_CACHE
+            .save(deps.as_mut().storage, vault.id.into(), &ONE)
+            .unwrap();
+
+        deps.querier.update_balance(
+            "cosmos2contract",
+            vec![
+                Coin::new(
+                    (vault.balance.amount - vault.swap_amount - ONE).into(),
+                    vault.get_swap_denom(),
+                ),
+                Coin::new(ONE.into(), vault.target_denom.clone()),
+            ],
+        );
+
+        disburse_funds_handler(
+            deps.as_mut(),
+            &env,
+            Reply {
+                id: AFTER_SWAP_REPLY_ID,
+                result: SubMsgResult::Ok(SubMsgResponse {
+                    events: vec![],
+                    data: None,
+                }),
+            },
+        )
+        .unwrap();
+
+        let updated_vault = get_vault(&deps.storage, vault.id).unwrap();
+
+        assert_eq!(
+            updated_vault.catch_up_schedule,
+            Some(CatchUpSchedule {
+                remaining_amount: ONE * Uint128::new(2),
+                executions_remaining: 2,
+            })
+        );
+        assert!(CATCH_UP_AMOUNT_CACHE
+            .may_load(deps.as_ref().storage, vault.id.into())
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn with_failed_swap_does_not_advance_catch_up_schedule() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let catch_up_schedule = CatchUpSchedule {
+            remaining_amount: ONE * Uint128::new(3),
+            executions_remaining: 3,
+        };
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                catch_up_schedule: Some(catch_up_schedule.clone()),
+                ..Vault::default()
+            },
+        );
+
+        CATCH_UP_AMOUNT_CACHE
+            .save(deps.as_mut().storage, vault.id.into(), &ONE)
+            .unwrap();
+
+        disburse_funds_handler(
+            deps.as_mut(),
+            &env,
+            Reply {
+                id: AFTER_SWAP_REPLY_ID,
+                result: SubMsgResult::Err("Generic failure".to_string()),
+            },
+        )
+        .unwrap();
+
+        let updated_vault = get_vault(&deps.storage, vault.id).unwrap();
+
+        assert_eq!(updated_vault.catch_up_schedule, Some(catch_up_schedule));
+        assert!(CATCH_UP_AMOUNT_CACHE
+            .may_load(deps.as_ref().storage, vault.id.into())
+            .unwrap()
+            .is_none());
+    }
+
     #[test]
     fn with_successful_swap_adjusts_swapped_amount_stat() {
         let mut deps = mock_dependencies();
@@ -454,6 +788,7 @@ mod disburse_funds_tests {
         SWAP_CACHE
             .save(
                 deps.as_mut().storage,
+                vault.id.into(),
                 &SwapCache {
                     swap_denom_balance: vault.balance.clone(),
                     receive_denom_balance: Coin::new(0, vault.target_denom.clone()),
@@ -511,6 +846,7 @@ mod disburse_funds_tests {
         SWAP_CACHE
             .save(
                 deps.as_mut().storage,
+                vault.id.into(),
                 &SwapCache {
                     swap_denom_balance: vault.balance.clone(),
                     receive_denom_balance: Coin::new(0, vault.target_denom.clone()),
@@ -544,7 +880,7 @@ mod disburse_funds_tests {
         vault
             .destinations
             .iter()
-            .filter(|d| d.msg.is_some())
+            .filter(|d| d.action.incurs_automation_fee())
             .for_each(|destination| {
                 let allocation_amount =
                     checked_mul(receive_amount - fee, destination.allocation).unwrap();
@@ -578,6 +914,7 @@ mod disburse_funds_tests {
         SWAP_CACHE
             .save(
                 deps.as_mut().storage,
+                vault.id.into(),
                 &SwapCache {
                     swap_denom_balance: vault.balance.clone(),
                     receive_denom_balance: Coin::new(0, vault.target_denom.clone()),
@@ -676,6 +1013,7 @@ mod disburse_funds_tests {
         SWAP_CACHE
             .save(
                 deps.as_mut().storage,
+                vault.id.into(),
                 &SwapCache {
                     swap_denom_balance: vault.balance.clone(),
                     receive_denom_balance: Coin::new(0, vault.target_denom.clone()),
@@ -780,6 +1118,7 @@ mod disburse_funds_tests {
         SWAP_CACHE
             .save(
                 deps.as_mut().storage,
+                vault.id.into(),
                 &SwapCache {
                     swap_denom_balance: vault.balance.clone(),
                     receive_denom_balance: Coin::new(0, vault.target_denom.clone()),
@@ -972,6 +1311,7 @@ mod disburse_funds_tests {
         SWAP_CACHE
             .save(
                 deps.as_mut().storage,
+                vault.id.into(),
                 &SwapCache {
                     swap_denom_balance: vault.balance.clone(),
                     receive_denom_balance: Coin::new(0, vault.target_denom.clone()),
@@ -1022,6 +1362,7 @@ mod disburse_funds_tests {
         SWAP_CACHE
             .save(
                 deps.as_mut().storage,
+                vault.id.into(),
                 &SwapCache {
                     swap_denom_balance: vault.balance.clone(),
                     receive_denom_balance: Coin::new(0, vault.target_denom.clone()),
@@ -1080,6 +1421,7 @@ mod disburse_funds_tests {
         SWAP_CACHE
             .save(
                 deps.as_mut().storage,
+                vault.id.into(),
                 &SwapCache {
                     swap_denom_balance: vault.balance.clone(),
                     receive_denom_balance: Coin::new(0, vault.target_denom.clone()),
@@ -1137,6 +1479,7 @@ mod disburse_funds_tests {
         SWAP_CACHE
             .save(
                 deps.as_mut().storage,
+                vault.id.into(),
                 &SwapCache {
                     swap_denom_balance: vault.balance.clone(),
                     receive_denom_balance: Coin::new(0, vault.target_denom.clone()),
@@ -1197,6 +1540,7 @@ mod disburse_funds_tests {
         SWAP_CACHE
             .save(
                 deps.as_mut().storage,
+                vault.id.into(),
                 &SwapCache {
                     swap_denom_balance: vault.balance.clone(),
                     receive_denom_balance: Coin::new(0, vault.target_denom.clone()),
@@ -1257,6 +1601,7 @@ mod disburse_funds_tests {
         SWAP_CACHE
             .save(
                 deps.as_mut().storage,
+                vault.id.into(),
                 &SwapCache {
                     swap_denom_balance: vault.balance.clone(),
                     receive_denom_balance: Coin::new(0, vault.target_denom.clone()),
@@ -1285,4 +1630,801 @@ mod disburse_funds_tests {
         let vault = get_vault(&deps.storage, vault.id).unwrap();
         assert!(vault.trigger.is_some());
     }
+
+    fn setup_basket_vault(deps: DepsMut, env: Env) -> Vault {
+        setup_vault(
+            deps,
+            env,
+            Vault {
+                destinations: vec![Destination::default()],
+                basket: Some(vec![
+                    BasketAsset {
+                        target_denom: DENOM_UUSK.to_string(),
+                        weight: Decimal::percent(60),
+                        route: None,
+                        received_amount: Coin::new(0, DENOM_UUSK),
+                    },
+                    BasketAsset {
+                        target_denom: "uatom".to_string(),
+                        weight: Decimal::percent(40),
+                        route: None,
+                        received_amount: Coin::new(0, "uatom"),
+                    },
+                ]),
+                ..Vault::default()
+            },
+        )
+    }
+
+    fn settle_first_basket_swap(
+        deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+        env: &Env,
+        vault: &Vault,
+    ) -> Response {
+        BASKET_SWAP_CACHE
+            .save(
+                deps.as_mut().storage,
+                vault.id.into(),
+                &VecDeque::from(vec![DENOM_UUSK.to_string(), "uatom".to_string()]),
+            )
+            .unwrap();
+
+        SWAP_CACHE
+            .save(
+                deps.as_mut().storage,
+                vault.id.into(),
+                &SwapCache {
+                    swap_denom_balance: vault.balance.clone(),
+                    receive_denom_balance: Coin::new(0, DENOM_UUSK),
+                },
+            )
+            .unwrap();
+
+        deps.querier.update_balance(
+            "cosmos2contract",
+            vec![
+                Coin::new((vault.balance.amount - ONE).into(), vault.get_swap_denom()),
+                Coin::new(ONE.into(), DENOM_UUSK),
+            ],
+        );
+
+        disburse_funds_handler(
+            deps.as_mut(),
+            env,
+            Reply {
+                id: AFTER_SWAP_REPLY_ID,
+                result: SubMsgResult::Ok(SubMsgResponse {
+                    events: vec![],
+                    data: None,
+                }),
+            },
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn with_basket_vault_updates_received_amount_of_swapped_asset() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_basket_vault(deps.as_mut(), env.clone());
+
+        settle_first_basket_swap(&mut deps, &env, &vault);
+
+        let updated_vault = get_vault(&deps.storage, vault.id).unwrap();
+        let basket = updated_vault.basket.unwrap();
+
+        let fee = get_config(&deps.storage).unwrap().default_swap_fee_percent * ONE;
+
+        assert_eq!(
+            basket[0].received_amount,
+            Coin::new((ONE - fee).into(), DENOM_UUSK)
+        );
+        assert_eq!(basket[1].received_amount, Coin::new(0, "uatom"));
+        assert_eq!(updated_vault.received_amount, vault.received_amount);
+        assert_eq!(
+            updated_vault.swapped_amount,
+            Coin::new(ONE.into(), vault.get_swap_denom())
+        );
+    }
+
+    #[test]
+    fn with_basket_vault_disburses_swapped_asset_to_destinations() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_basket_vault(deps.as_mut(), env.clone());
+
+        let response = settle_first_basket_swap(&mut deps, &env, &vault);
+
+        let fee = get_config(&deps.storage).unwrap().default_swap_fee_percent * ONE;
+
+        assert!(response.messages.contains(&SubMsg::reply_always(
+            BankMsg::Send {
+                to_address: vault.destinations[0].address.to_string(),
+                amount: vec![Coin::new((ONE - fee).into(), DENOM_UUSK)],
+            },
+            AFTER_FAILED_AUTOMATION_REPLY_ID
+        )));
+    }
+
+    #[test]
+    fn with_basket_vault_caches_next_basket_swap() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_basket_vault(deps.as_mut(), env.clone());
+
+        settle_first_basket_swap(&mut deps, &env, &vault);
+
+        assert_eq!(
+            BASKET_SWAP_CACHE
+                .load(deps.as_ref().storage, vault.id.into())
+                .unwrap(),
+            VecDeque::from(vec!["uatom".to_string()])
+        );
+        assert_eq!(
+            SWAP_CACHE
+                .load(deps.as_ref().storage, vault.id.into())
+                .unwrap(),
+            SwapCache {
+                swap_denom_balance: Coin::new(
+                    (vault.balance.amount - ONE).into(),
+                    vault.get_swap_denom()
+                ),
+                receive_denom_balance: Coin::new(0, "uatom"),
+            }
+        );
+    }
+
+    fn settle_second_basket_swap(
+        deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+        env: &Env,
+        vault: &Vault,
+    ) -> Response {
+        BASKET_SWAP_CACHE
+            .save(
+                deps.as_mut().storage,
+                vault.id.into(),
+                &VecDeque::from(vec!["uatom".to_string()]),
+            )
+            .unwrap();
+
+        SWAP_CACHE
+            .save(
+                deps.as_mut().storage,
+                vault.id.into(),
+                &SwapCache {
+                    swap_denom_balance: vault.balance.clone(),
+                    receive_denom_balance: Coin::new(0, "uatom"),
+                },
+            )
+            .unwrap();
+
+        deps.querier.update_balance(
+            "cosmos2contract",
+            vec![
+                Coin::new((vault.balance.amount - ONE).into(), vault.get_swap_denom()),
+                Coin::new(ONE.into(), "uatom"),
+            ],
+        );
+
+        disburse_funds_handler(
+            deps.as_mut(),
+            env,
+            Reply {
+                id: AFTER_SWAP_REPLY_ID,
+                result: SubMsgResult::Ok(SubMsgResponse {
+                    events: vec![],
+                    data: None,
+                }),
+            },
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn with_basket_vault_uses_swap_fee_of_swapped_asset_pair() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_basket_vault(deps.as_mut(), env.clone());
+
+        save_custom_swap_fee(
+            deps.as_mut().storage,
+            DENOM_UKUJI,
+            Some("uatom"),
+            Decimal::percent(3),
+        )
+        .unwrap();
+
+        settle_second_basket_swap(&mut deps, &env, &vault);
+
+        let basket = get_vault(&deps.storage, vault.id).unwrap().basket.unwrap();
+
+        assert_eq!(
+            basket[1].received_amount,
+            Coin::new((ONE - Decimal::percent(3) * ONE).into(), "uatom")
+        );
+    }
+
+    #[test]
+    fn with_basket_vault_only_escrows_funds_in_escrow_denom() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_basket_vault(deps.as_mut(), env.clone());
+        let vault = update_vault(
+            deps.as_mut().storage,
+            Vault {
+                escrow_level: Decimal::percent(5),
+                ..vault
+            },
+        )
+        .unwrap();
+
+        settle_second_basket_swap(&mut deps, &env, &vault);
+
+        let updated_vault = get_vault(&deps.storage, vault.id).unwrap();
+
+        assert_eq!(updated_vault.escrowed_amount, vault.escrowed_amount);
+        assert_eq!(
+            updated_vault.basket.unwrap()[1].received_amount,
+            Coin::new(
+                (ONE - get_config(&deps.storage).unwrap().default_swap_fee_percent * ONE).into(),
+                "uatom"
+            )
+        );
+    }
+
+    fn settle_swap(
+        deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+        env: &Env,
+        vault: &Vault,
+    ) -> Response {
+        SWAP_CACHE
+            .save(
+                deps.as_mut().storage,
+                vault.id.into(),
+                &SwapCache {
+                    swap_denom_balance: vault.balance.clone(),
+                    receive_denom_balance: Coin::new(0, vault.target_denom.clone()),
+                },
+            )
+            .unwrap();
+
+        deps.querier.update_balance(
+            "cosmos2contract",
+            vec![
+                Coin::new((vault.balance.amount - ONE).into(), vault.get_swap_denom()),
+                Coin::new(ONE.into(), vault.target_denom.clone()),
+            ],
+        );
+
+        disburse_funds_handler(
+            deps.as_mut(),
+            env,
+            Reply {
+                id: AFTER_SWAP_REPLY_ID,
+                result: SubMsgResult::Ok(SubMsgResponse {
+                    events: vec![],
+                    data: None,
+                }),
+            },
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn with_successful_swap_increments_executions() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                end_conditions: Some(EndConditions {
+                    end_time: None,
+                    max_executions: Some(3),
+                    received_amount_goal: None,
+                    executions: 1,
+                }),
+                ..Vault::default()
+            },
+        );
+
+        settle_swap(&mut deps, &env, &vault);
+
+        let updated_vault = get_vault(&deps.storage, vault.id).unwrap();
+
+        assert_eq!(updated_vault.end_conditions.unwrap().executions, 2);
+        assert_eq!(updated_vault.status, VaultStatus::Active);
+    }
+
+    #[test]
+    fn with_max_executions_reached_ends_vault_and_refunds_balance() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                end_conditions: Some(EndConditions {
+                    end_time: None,
+                    max_executions: Some(1),
+                    received_amount_goal: None,
+                    executions: 0,
+                }),
+                ..Vault::default()
+            },
+        );
+
+        let response = settle_swap(&mut deps, &env, &vault);
+
+        let updated_vault = get_vault(&deps.storage, vault.id).unwrap();
+
+        assert!(response.messages.contains(&SubMsg::new(BankMsg::Send {
+            to_address: vault.owner.to_string(),
+            amount: vec![Coin::new(
+                (vault.balance.amount - ONE).into(),
+                vault.get_swap_denom()
+            )],
+        })));
+        assert_eq!(updated_vault.status, VaultStatus::Inactive);
+        assert_eq!(updated_vault.balance.amount, Uint128::zero());
+        assert_eq!(updated_vault.trigger, None);
+    }
+
+    #[test]
+    fn with_received_amount_goal_reached_ends_vault() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                end_conditions: Some(EndConditions {
+                    end_time: None,
+                    max_executions: None,
+                    received_amount_goal: Some(ONE / Uint128::new(2)),
+                    executions: 0,
+                }),
+                ..Vault::default()
+            },
+        );
+
+        settle_swap(&mut deps, &env, &vault);
+
+        let updated_vault = get_vault(&deps.storage, vault.id).unwrap();
+
+        let events = get_events_by_resource_id_handler(deps.as_ref(), vault.id, None, None, None)
+            .unwrap()
+            .events;
+
+        assert_eq!(updated_vault.status, VaultStatus::Inactive);
+        assert!(events.iter().any(|event| event.data
+            == EventData::DcaVaultEndConditionReached {
+                condition: EndCondition::ReceivedAmountGoalReached,
+            }));
+    }
+
+    #[test]
+    fn with_partial_execution_publishes_execution_partially_completed_event() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        let scheduled_amount = Coin::new((ONE * Uint128::new(2)).into(), vault.get_swap_denom());
+
+        PARTIAL_SWAP_CACHE
+            .save(deps.as_mut().storage, vault.id.into(), &scheduled_amount)
+            .unwrap();
+
+        settle_swap(&mut deps, &env, &vault);
+
+        let events = get_events_by_resource_id_handler(deps.as_ref(), vault.id, None, None, None)
+            .unwrap()
+            .events;
+
+        assert!(events.iter().any(|event| matches!(
+            &event.data,
+            EventData::DcaVaultExecutionPartiallyCompleted { sent, scheduled, .. }
+                if sent == &Coin::new(ONE.into(), vault.get_swap_denom())
+                    && scheduled == &scheduled_amount
+        )));
+        assert!(!events
+            .iter()
+            .any(|event| matches!(event.data, EventData::DcaVaultExecutionCompleted { .. })));
+        assert_eq!(
+            PARTIAL_SWAP_CACHE
+                .may_load(deps.as_ref().storage, vault.id.into())
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn with_failed_swap_and_retry_policy_schedules_retry_trigger() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                retry_policy: Some(RetryPolicy {
+                    retry_delay_minutes: 15,
+                    max_attempts: 2,
+                }),
+                trigger: Some(TriggerConfiguration::Time {
+                    target_time: env.block.time.plus_seconds(24 * 60 * 60),
+                }),
+                ..Vault::default()
+            },
+        );
+
+        RETRY_ATTEMPT_CACHE
+            .save(deps.as_mut().storage, vault.id.into(), &1)
+            .unwrap();
+
+        let reply = Reply {
+            id: AFTER_SWAP_REPLY_ID,
+            result: SubMsgResult::Err("failed for slippage".to_string()),
+        };
+
+        disburse_funds_handler(deps.as_mut(), &env, reply).unwrap();
+
+        assert_eq!(
+            get_trigger(deps.as_ref().storage, vault.id)
+                .unwrap()
+                .unwrap()
+                .configuration,
+            TriggerConfiguration::Retry {
+                target_time: env.block.time.plus_seconds(15 * 60),
+                attempt: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn with_failed_swap_and_exhausted_retries_keeps_normal_schedule() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let target_time = env.block.time.plus_seconds(24 * 60 * 60);
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                retry_policy: Some(RetryPolicy {
+                    retry_delay_minutes: 15,
+                    max_attempts: 2,
+                }),
+                trigger: Some(TriggerConfiguration::Time { target_time }),
+                ..Vault::default()
+            },
+        );
+
+        RETRY_ATTEMPT_CACHE
+            .save(deps.as_mut().storage, vault.id.into(), &2)
+            .unwrap();
+
+        let reply = Reply {
+            id: AFTER_SWAP_REPLY_ID,
+            result: SubMsgResult::Err("failed for slippage".to_string()),
+        };
+
+        disburse_funds_handler(deps.as_mut(), &env, reply).unwrap();
+
+        assert_eq!(
+            get_trigger(deps.as_ref().storage, vault.id)
+                .unwrap()
+                .unwrap()
+                .configuration,
+            TriggerConfiguration::Time { target_time }
+        );
+    }
+
+    #[test]
+    fn with_successful_swap_and_keeper_sends_bounty_from_fees() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        update_config(
+            deps.as_mut().storage,
+            Config {
+                permissionless_execution: true,
+                keeper_bounty_percent: Decimal::percent(20),
+                ..Config::default()
+            },
+        )
+        .unwrap();
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+        let receive_amount = Uint128::new(234312312);
+
+        KEEPER_CACHE
+            .save(
+                deps.as_mut().storage,
+                vault.id.into(),
+                &Addr::unchecked("keeper"),
+            )
+            .unwrap();
+
+        SWAP_CACHE
+            .save(
+                deps.as_mut().storage,
+                vault.id.into(),
+                &SwapCache {
+                    swap_denom_balance: vault.balance.clone(),
+                    receive_denom_balance: Coin::new(0, vault.target_denom.clone()),
+                },
+            )
+            .unwrap();
+
+        deps.querier.update_balance(
+            "cosmos2contract",
+            vec![Coin::new(receive_amount.into(), vault.target_denom.clone())],
+        );
+
+        let response = disburse_funds_handler(
+            deps.as_mut(),
+            &env,
+            Reply {
+                id: AFTER_SWAP_REPLY_ID,
+                result: SubMsgResult::Ok(SubMsgResponse {
+                    events: vec![],
+                    data: None,
+                }),
+            },
+        )
+        .unwrap();
+
+        let config = get_config(&deps.storage).unwrap();
+        let swap_fee = config.default_swap_fee_percent * receive_amount;
+        let keeper_bounty = swap_fee * config.keeper_bounty_percent;
+
+        assert!(response.messages.contains(&SubMsg::new(BankMsg::Send {
+            to_address: "keeper".to_string(),
+            amount: vec![Coin::new(keeper_bounty.into(), vault.target_denom.clone())],
+        })));
+        assert!(response.messages.contains(&SubMsg::new(BankMsg::Send {
+            to_address: config.fee_collectors[0].address.to_string(),
+            amount: vec![Coin::new(
+                (swap_fee - keeper_bounty).into(),
+                vault.target_denom.clone()
+            )],
+        })));
+        assert_eq!(
+            KEEPER_CACHE
+                .may_load(deps.as_ref().storage, vault.id.into())
+                .unwrap(),
+            None
+        );
+    }
+
+    fn settle_referred_swap(
+        deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+        env: &Env,
+        vault: &Vault,
+        receive_amount: Uint128,
+    ) -> Response {
+        deps.querier.update_balance(
+            "cosmos2contract",
+            vec![Coin::new(receive_amount.into(), vault.target_denom.clone())],
+        );
+
+        SWAP_CACHE
+            .save(
+                deps.as_mut().storage,
+                vault.id.into(),
+                &SwapCache {
+                    swap_denom_balance: vault.balance.clone(),
+                    receive_denom_balance: Coin::new(0, vault.target_denom.clone()),
+                },
+            )
+            .unwrap();
+
+        disburse_funds_handler(
+            deps.as_mut(),
+            env,
+            Reply {
+                id: AFTER_SWAP_REPLY_ID,
+                result: SubMsgResult::Ok(SubMsgResponse {
+                    events: vec![],
+                    data: None,
+                }),
+            },
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn with_successful_swap_and_referrer_sends_referral_fee_from_fees() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        update_config(
+            deps.as_mut().storage,
+            Config {
+                referral_fee_percent: Decimal::percent(25),
+                ..Config::default()
+            },
+        )
+        .unwrap();
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                referrer: Some(Addr::unchecked("referrer")),
+                ..Vault::default()
+            },
+        );
+        let receive_amount = Uint128::new(234312312);
+
+        save_referral_fee_caps(
+            deps.as_mut().storage,
+            &Addr::unchecked("referrer"),
+            &[Coin::new(u128::MAX, vault.target_denom.clone())],
+        )
+        .unwrap();
+
+        let response = settle_referred_swap(&mut deps, &env, &vault, receive_amount);
+
+        let config = get_config(&deps.storage).unwrap();
+        let swap_fee = config.default_swap_fee_percent * receive_amount;
+        let referral_fee = swap_fee * config.referral_fee_percent;
+
+        assert!(response.messages.contains(&SubMsg::new(BankMsg::Send {
+            to_address: "referrer".to_string(),
+            amount: vec![Coin::new(referral_fee.into(), vault.target_denom.clone())],
+        })));
+        assert!(response.messages.contains(&SubMsg::new(BankMsg::Send {
+            to_address: config.fee_collectors[0].address.to_string(),
+            amount: vec![Coin::new(
+                (swap_fee - referral_fee).into(),
+                vault.target_denom.clone()
+            )],
+        })));
+        assert_eq!(
+            get_referral_fees(deps.as_ref().storage, &Addr::unchecked("referrer")).unwrap(),
+            vec![Coin::new(referral_fee.into(), vault.target_denom)]
+        );
+    }
+
+    #[test]
+    fn with_unregistered_referrer_sends_no_referral_fee() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        update_config(
+            deps.as_mut().storage,
+            Config {
+                referral_fee_percent: Decimal::percent(25),
+                ..Config::default()
+            },
+        )
+        .unwrap();
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                referrer: Some(Addr::unchecked("referrer")),
+                ..Vault::default()
+            },
+        );
+        let receive_amount = Uint128::new(234312312);
+
+        let response = settle_referred_swap(&mut deps, &env, &vault, receive_amount);
+
+        let config = get_config(&deps.storage).unwrap();
+        let swap_fee = config.default_swap_fee_percent * receive_amount;
+
+        assert!(!response.messages.iter().any(|message| matches!(
+            &message.msg,
+            CosmosMsg::Bank(BankMsg::Send { to_address, .. }) if to_address == "referrer"
+        )));
+        assert!(response.messages.contains(&SubMsg::new(BankMsg::Send {
+            to_address: config.fee_collectors[0].address.to_string(),
+            amount: vec![Coin::new(swap_fee.into(), vault.target_denom.clone())],
+        })));
+        assert!(
+            get_referral_fees(deps.as_ref().storage, &Addr::unchecked("referrer"))
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn with_referral_fee_cap_limits_referral_fee() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let referrer = Addr::unchecked("referrer");
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        update_config(
+            deps.as_mut().storage,
+            Config {
+                referral_fee_percent: Decimal::percent(25),
+                ..Config::default()
+            },
+        )
+        .unwrap();
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                referrer: Some(referrer.clone()),
+                ..Vault::default()
+            },
+        );
+        let receive_amount = Uint128::new(234312312);
+
+        save_referral_fee_caps(
+            deps.as_mut().storage,
+            &referrer,
+            &[Coin::new(1000, vault.target_denom.clone())],
+        )
+        .unwrap();
+
+        let response = settle_referred_swap(&mut deps, &env, &vault, receive_amount);
+
+        let config = get_config(&deps.storage).unwrap();
+        let swap_fee = config.default_swap_fee_percent * receive_amount;
+
+        assert!(response.messages.contains(&SubMsg::new(BankMsg::Send {
+            to_address: referrer.to_string(),
+            amount: vec![Coin::new(1000, vault.target_denom.clone())],
+        })));
+        assert!(response.messages.contains(&SubMsg::new(BankMsg::Send {
+            to_address: config.fee_collectors[0].address.to_string(),
+            amount: vec![Coin::new(
+                (swap_fee - Uint128::new(1000)).into(),
+                vault.target_denom.clone()
+            )],
+        })));
+
+        let vault = get_vault(deps.as_ref().storage, vault.id).unwrap();
+        let response = settle_referred_swap(&mut deps, &env, &vault, receive_amount);
+
+        assert!(!response.messages.iter().any(|message| matches!(
+            &message.msg,
+            CosmosMsg::Bank(BankMsg::Send { to_address, .. }) if *to_address == referrer
+        )));
+        assert!(!response
+            .attributes
+            .iter()
+            .any(|attribute| attribute.key == "referral_fee"));
+    }
 }
@@ -0,0 +1,544 @@
+use crate::constants::AFTER_AGGREGATED_SWAP_REPLY_ID;
+use crate::error::ContractError;
+use crate::helpers::time::get_next_target_time;
+use crate::msg::ExecuteMsg;
+use crate::state::cache::{AggregatedSwapCache, AGGREGATED_SWAP_CACHE};
+use crate::state::config::get_config;
+use crate::state::events::create_event;
+use crate::state::triggers::save_trigger;
+use crate::state::vaults::get_vault;
+use crate::types::event::{EventBuilder, EventData, ExecutionSkippedReason};
+use crate::types::trigger::{Trigger, TriggerConfiguration};
+use cosmwasm_std::{
+    to_json_binary, Addr, Binary, Coin, Decimal, DepsMut, Env, MessageInfo, Reply, Response,
+    StdResult, SubMsg, SubMsgResult, Uint128, WasmMsg,
+};
+use exchange::msg::ExecuteMsg as ExchangeExecuteMsg;
+use shared::balance::query_balance;
+use shared::coin::subtract;
+use shared::cw20::into_execute_msg;
+
+pub fn execute_aggregated_swap_handler(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    vault_ids: Vec<Uint128>,
+    swap_amounts: Vec<Uint128>,
+    minimum_receive_amount: Coin,
+    route: Option<Binary>,
+    asset_price: Decimal,
+    keeper: Option<Addr>,
+) -> Result<Response, ContractError> {
+    if info.sender != env.contract.address {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if vault_ids.is_empty() || vault_ids.len() != swap_amounts.len() {
+        return Err(ContractError::CustomError {
+            val: String::from("a swap amount must be provided for every vault id"),
+        });
+    }
+
+    let vault = get_vault(deps.storage, vault_ids[0])?;
+
+    let swap_amount = Coin::new(
+        swap_amounts.iter().sum::<Uint128>().into(),
+        vault.get_swap_denom(),
+    );
+
+    AGGREGATED_SWAP_CACHE.save(
+        deps.storage,
+        &AggregatedSwapCache {
+            vault_ids: vault_ids.clone(),
+            swap_amounts,
+            swap_denom_balance: query_balance(
+                deps.api,
+                &deps.querier,
+                &swap_amount.denom,
+                &env.contract.address,
+            )?,
+            receive_denom_balance: query_balance(
+                deps.api,
+                &deps.querier,
+                &minimum_receive_amount.denom,
+                &env.contract.address,
+            )?,
+            asset_price,
+            keeper,
+        },
+    )?;
+
+    let config = get_config(deps.storage)?;
+
+    Ok(Response::new()
+        .add_attribute("execute_aggregated_swap", "true")
+        .add_attribute(
+            "vault_ids",
+            vault_ids
+                .iter()
+                .map(|vault_id| vault_id.to_string())
+                .collect::<Vec<String>>()
+                .join(","),
+        )
+        .add_attribute("swap_amount", swap_amount.to_string())
+        .add_attribute("minimum_receive_amount", minimum_receive_amount.to_string())
+        .add_submessage(SubMsg::reply_always(
+            into_execute_msg(
+                deps.api,
+                config.exchange_contract_address,
+                to_json_binary(&ExchangeExecuteMsg::Swap {
+                    minimum_receive_amount,
+                    route,
+                })?,
+                swap_amount,
+            )?,
+            AFTER_AGGREGATED_SWAP_REPLY_ID,
+        )))
+}
+
+pub fn handle_aggregated_swap_result(
+    deps: DepsMut,
+    env: Env,
+    reply: Reply,
+) -> Result<Response, ContractError> {
+    let cache = AGGREGATED_SWAP_CACHE.load(deps.storage)?;
+    AGGREGATED_SWAP_CACHE.remove(deps.storage);
+
+    let response = Response::new().add_attribute("aggregated_swap_result", "true");
+
+    match reply.result {
+        SubMsgResult::Ok(_) => {
+            let swap_denom_balance = query_balance(
+                deps.api,
+                &deps.querier,
+                &cache.swap_denom_balance.denom,
+                &env.contract.address,
+            )?;
+
+            let receive_denom_balance = query_balance(
+                deps.api,
+                &deps.querier,
+                &cache.receive_denom_balance.denom,
+                &env.contract.address,
+            )?;
+
+            let coin_sent = subtract(&cache.swap_denom_balance, &swap_denom_balance)?;
+            let coin_received = subtract(&receive_denom_balance, &cache.receive_denom_balance)?;
+
+            let total_swap_amount = cache.swap_amounts.iter().sum::<Uint128>();
+
+            let sub_msgs = cache
+                .vault_ids
+                .iter()
+                .zip(cache.swap_amounts.iter())
+                .map(|(vault_id, swap_amount)| {
+                    Ok(SubMsg::new(WasmMsg::Execute {
+                        contract_addr: env.contract.address.to_string(),
+                        msg: to_json_binary(&ExecuteMsg::SettleExecution {
+                            vault_id: *vault_id,
+                            sent: Coin::new(
+                                coin_sent
+                                    .amount
+                                    .multiply_ratio(*swap_amount, total_swap_amount)
+                                    .into(),
+                                coin_sent.denom.clone(),
+                            ),
+                            received: Coin::new(
+                                coin_received
+                                    .amount
+                                    .multiply_ratio(*swap_amount, total_swap_amount)
+                                    .into(),
+                                coin_received.denom.clone(),
+                            ),
+                            asset_price: cache.asset_price,
+                            residual: Coin::new(0, coin_sent.denom.clone()),
+                            keeper: cache.keeper.clone(),
+                        })?,
+                        funds: vec![],
+                    }))
+                })
+                .collect::<StdResult<Vec<SubMsg>>>()?;
+
+            Ok(response
+                .add_attribute("swapped_amount", coin_sent.to_string())
+                .add_attribute("received_amount", coin_received.to_string())
+                .add_submessages(sub_msgs))
+        }
+        SubMsgResult::Err(_) => {
+            for vault_id in cache.vault_ids.iter() {
+                let vault = get_vault(deps.storage, *vault_id)?;
+
+                if vault.is_active() {
+                    save_trigger(
+                        deps.storage,
+                        Trigger {
+                            vault_id: vault.id,
+                            configuration: TriggerConfiguration::Time {
+                                target_time: get_next_target_time(
+                                    env.block.time,
+                                    vault.started_at.unwrap_or(env.block.time),
+                                    vault.time_interval.clone(),
+                                ),
+                            },
+                        },
+                    )?;
+                }
+
+                create_event(
+                    deps.storage,
+                    EventBuilder::new(
+                        *vault_id,
+                        env.block.clone(),
+                        EventData::DcaVaultExecutionSkipped {
+                            reason: ExecutionSkippedReason::SlippageToleranceExceeded,
+                        },
+                    ),
+                )?;
+            }
+
+            Ok(response.add_attribute("execution_skipped", "slippage_tolerance_exceeded"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod execute_aggregated_swap_tests {
+    use super::*;
+    use crate::{
+        constants::ONE,
+        handlers::get_events_by_resource_id::get_events_by_resource_id_handler,
+        state::triggers::get_trigger,
+        tests::{
+            helpers::{instantiate_contract, setup_vault},
+            mocks::{calc_mock_dependencies, ADMIN, DENOM_UKUJI, DENOM_UUSK},
+        },
+        types::vault::Vault,
+    };
+    use cosmwasm_std::{
+        from_json,
+        testing::{mock_dependencies, mock_env, mock_info},
+        CosmosMsg, SubMsgResponse,
+    };
+
+    #[test]
+    fn with_sender_other_than_contract_should_fail() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let err = execute_aggregated_swap_handler(
+            deps.as_mut(),
+            env,
+            mock_info(ADMIN, &[]),
+            vec![Uint128::one()],
+            vec![ONE],
+            Coin::new(0, DENOM_UUSK),
+            None,
+            Decimal::one(),
+            None,
+        )
+        .unwrap_err();
+
+        assert_eq!(err.to_string(), "Unauthorized");
+    }
+
+    #[test]
+    fn should_send_single_swap_for_combined_amount() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+        let other_vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        let response = execute_aggregated_swap_handler(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(env.contract.address.as_ref(), &[]),
+            vec![vault.id, other_vault.id],
+            vec![ONE, ONE],
+            Coin::new(ONE.into(), DENOM_UUSK),
+            vault.route.clone(),
+            Decimal::one(),
+            None,
+        )
+        .unwrap();
+
+        let config = get_config(deps.as_ref().storage).unwrap();
+
+        assert_eq!(
+            response.messages,
+            vec![SubMsg::reply_always(
+                WasmMsg::Execute {
+                    contract_addr: config.exchange_contract_address.to_string(),
+                    msg: to_json_binary(&ExchangeExecuteMsg::Swap {
+                        minimum_receive_amount: Coin::new(ONE.into(), DENOM_UUSK),
+                        route: vault.route,
+                    })
+                    .unwrap(),
+                    funds: vec![Coin::new((ONE + ONE).into(), DENOM_UKUJI)],
+                },
+                AFTER_AGGREGATED_SWAP_REPLY_ID,
+            )]
+        );
+        assert_eq!(
+            AGGREGATED_SWAP_CACHE
+                .load(deps.as_ref().storage)
+                .unwrap()
+                .vault_ids,
+            vec![vault.id, other_vault.id]
+        );
+    }
+
+    #[test]
+    fn with_successful_swap_should_settle_received_amount_pro_rata() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        AGGREGATED_SWAP_CACHE
+            .save(
+                deps.as_mut().storage,
+                &AggregatedSwapCache {
+                    vault_ids: vec![Uint128::one(), Uint128::new(2)],
+                    swap_amounts: vec![ONE, ONE * Uint128::new(3)],
+                    swap_denom_balance: Coin::new((ONE * Uint128::new(4)).into(), DENOM_UKUJI),
+                    receive_denom_balance: Coin::new(0, DENOM_UUSK),
+                    asset_price: Decimal::one(),
+                    keeper: None,
+                },
+            )
+            .unwrap();
+
+        deps.querier.update_balance(
+            env.contract.address.clone(),
+            vec![Coin::new((ONE * Uint128::new(2)).into(), DENOM_UUSK)],
+        );
+
+        let response = handle_aggregated_swap_result(
+            deps.as_mut(),
+            env.clone(),
+            Reply {
+                id: AFTER_AGGREGATED_SWAP_REPLY_ID,
+                result: SubMsgResult::Ok(SubMsgResponse {
+                    events: vec![],
+                    data: None,
+                }),
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            response.messages,
+            vec![
+                SubMsg::new(WasmMsg::Execute {
+                    contract_addr: env.contract.address.to_string(),
+                    msg: to_json_binary(&ExecuteMsg::SettleExecution {
+                        vault_id: Uint128::one(),
+                        sent: Coin::new(ONE.into(), DENOM_UKUJI),
+                        received: Coin::new((ONE / Uint128::new(2)).into(), DENOM_UUSK),
+                        asset_price: Decimal::one(),
+                        residual: Coin::new(0, DENOM_UKUJI),
+                        keeper: None,
+                    })
+                    .unwrap(),
+                    funds: vec![],
+                }),
+                SubMsg::new(WasmMsg::Execute {
+                    contract_addr: env.contract.address.to_string(),
+                    msg: to_json_binary(&ExecuteMsg::SettleExecution {
+                        vault_id: Uint128::new(2),
+                        sent: Coin::new((ONE * Uint128::new(3)).into(), DENOM_UKUJI),
+                        received: Coin::new(
+                            (ONE * Uint128::new(3) / Uint128::new(2)).into(),
+                            DENOM_UUSK
+                        ),
+                        asset_price: Decimal::one(),
+                        residual: Coin::new(0, DENOM_UKUJI),
+                        keeper: None,
+                    })
+                    .unwrap(),
+                    funds: vec![],
+                }),
+            ]
+        );
+        assert!(AGGREGATED_SWAP_CACHE
+            .may_load(deps.as_ref().storage)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn with_partially_consumed_swap_should_settle_sent_amount_pro_rata() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        AGGREGATED_SWAP_CACHE
+            .save(
+                deps.as_mut().storage,
+                &AggregatedSwapCache {
+                    vault_ids: vec![Uint128::one(), Uint128::new(2)],
+                    swap_amounts: vec![ONE, ONE * Uint128::new(3)],
+                    swap_denom_balance: Coin::new((ONE * Uint128::new(4)).into(), DENOM_UKUJI),
+                    receive_denom_balance: Coin::new(0, DENOM_UUSK),
+                    asset_price: Decimal::one(),
+                    keeper: None,
+                },
+            )
+            .unwrap();
+
+        deps.querier.update_balance(
+            env.contract.address.clone(),
+            vec![
+                Coin::new(ONE.into(), DENOM_UKUJI),
+                Coin::new((ONE * Uint128::new(3)).into(), DENOM_UUSK),
+            ],
+        );
+
+        let response = handle_aggregated_swap_result(
+            deps.as_mut(),
+            env.clone(),
+            Reply {
+                id: AFTER_AGGREGATED_SWAP_REPLY_ID,
+                result: SubMsgResult::Ok(SubMsgResponse {
+                    events: vec![],
+                    data: None,
+                }),
+            },
+        )
+        .unwrap();
+
+        let sent_amounts = response
+            .messages
+            .iter()
+            .map(|message| match &message.msg {
+                CosmosMsg::Wasm(WasmMsg::Execute { msg, .. }) => {
+                    match from_json::<ExecuteMsg>(msg).unwrap() {
+                        ExecuteMsg::SettleExecution { sent, .. } => sent,
+                        _ => panic!("expected a settle execution message"),
+                    }
+                }
+                _ => panic!("expected a wasm execute message"),
+            })
+            .collect::<Vec<Coin>>();
+
+        assert_eq!(
+            sent_amounts,
+            vec![
+                Coin::new(
+                    (ONE * Uint128::new(3) / Uint128::new(4)).into(),
+                    DENOM_UKUJI
+                ),
+                Coin::new(
+                    (ONE * Uint128::new(9) / Uint128::new(4)).into(),
+                    DENOM_UKUJI
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn with_failed_swap_should_publish_skipped_event_for_each_vault() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+        let other_vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        AGGREGATED_SWAP_CACHE
+            .save(
+                deps.as_mut().storage,
+                &AggregatedSwapCache {
+                    vault_ids: vec![vault.id, other_vault.id],
+                    swap_amounts: vec![ONE, ONE],
+                    swap_denom_balance: Coin::new(0, DENOM_UKUJI),
+                    receive_denom_balance: Coin::new(0, DENOM_UUSK),
+                    asset_price: Decimal::one(),
+                    keeper: None,
+                },
+            )
+            .unwrap();
+
+        handle_aggregated_swap_result(
+            deps.as_mut(),
+            env,
+            Reply {
+                id: AFTER_AGGREGATED_SWAP_REPLY_ID,
+                result: SubMsgResult::Err("slippage exceeded".to_string()),
+            },
+        )
+        .unwrap();
+
+        for vault_id in [vault.id, other_vault.id] {
+            let events =
+                get_events_by_resource_id_handler(deps.as_ref(), vault_id, None, None, None)
+                    .unwrap()
+                    .events;
+
+            assert_eq!(
+                events[0].data,
+                EventData::DcaVaultExecutionSkipped {
+                    reason: ExecutionSkippedReason::SlippageToleranceExceeded,
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn with_failed_swap_should_reschedule_time_trigger_for_each_vault() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+        let other_vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        AGGREGATED_SWAP_CACHE
+            .save(
+                deps.as_mut().storage,
+                &AggregatedSwapCache {
+                    vault_ids: vec![vault.id, other_vault.id],
+                    swap_amounts: vec![ONE, ONE],
+                    swap_denom_balance: Coin::new(0, DENOM_UKUJI),
+                    receive_denom_balance: Coin::new(0, DENOM_UUSK),
+                    asset_price: Decimal::one(),
+                    keeper: None,
+                },
+            )
+            .unwrap();
+
+        handle_aggregated_swap_result(
+            deps.as_mut(),
+            env.clone(),
+            Reply {
+                id: AFTER_AGGREGATED_SWAP_REPLY_ID,
+                result: SubMsgResult::Err("slippage exceeded".to_string()),
+            },
+        )
+        .unwrap();
+
+        for vault in [vault, other_vault] {
+            assert_eq!(
+                get_trigger(deps.as_ref().storage, vault.id)
+                    .unwrap()
+                    .unwrap()
+                    .configuration,
+                TriggerConfiguration::Time {
+                    target_time: get_next_target_time(
+                        env.block.time,
+                        vault.started_at.unwrap_or(env.block.time),
+                        vault.time_interval,
+                    ),
+                }
+            );
+        }
+    }
+}
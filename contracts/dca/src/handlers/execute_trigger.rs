@@ -1,34 +1,63 @@
 use crate::constants::AFTER_SWAP_REPLY_ID;
 use crate::error::ContractError;
-use crate::helpers::price::{get_expected_receive_amount, get_slippage, get_twap_to_now};
-use crate::helpers::time::get_next_target_time;
-use crate::helpers::validation::{assert_contract_is_not_paused, assert_target_time_is_in_past};
-use crate::helpers::vault::{get_swap_amount, simulate_standard_dca_execution};
+use crate::handlers::recurring_deposit::pull_recurring_deposit;
+use crate::helpers::fees::get_keeper;
+use crate::helpers::message::get_vault_reply_id;
+use crate::helpers::price::{
+    get_expected_receive_amount, get_largest_swap_amount_within_slippage_tolerance, get_slippage,
+    get_twap_to_now,
+};
+use crate::helpers::time::{get_missed_interval_count, get_next_target_time};
+use crate::helpers::validation::{
+    assert_contract_is_not_paused, assert_target_time_is_in_past, assert_trigger_is_due,
+};
+use crate::helpers::vault::{
+    end_vault, get_catch_up_schedule, get_swap_amount, schedule_execution_retry,
+    simulate_standard_dca_execution,
+};
 use crate::msg::ExecuteMsg;
-use crate::state::cache::{SwapCache, SWAP_CACHE, VAULT_ID_CACHE};
+use crate::state::cache::{
+    SwapCache, BASKET_SWAP_CACHE, CATCH_UP_AMOUNT_CACHE, KEEPER_CACHE, PARTIAL_SWAP_CACHE,
+    RESIDUAL_SWAP_AMOUNT_CACHE, RETRY_ATTEMPT_CACHE, SWAP_CACHE,
+};
 use crate::state::config::get_config;
 use crate::state::events::create_event;
 use crate::state::triggers::{delete_trigger, save_trigger};
 use crate::state::vaults::{get_vault, update_vault};
+use crate::types::basket::{get_basket_swap_amounts, BasketAsset};
 use crate::types::event::{EventBuilder, EventData, ExecutionSkippedReason};
+use crate::types::performance_assessment_strategy::PerformanceAssessmentStrategy;
 use crate::types::swap_adjustment_strategy::SwapAdjustmentStrategy;
 use crate::types::trigger::{Trigger, TriggerConfiguration};
 use crate::types::vault::{Vault, VaultStatus};
-use cosmwasm_std::{to_json_binary, Binary, Coin, Decimal, SubMsg, WasmMsg};
+use cosmwasm_std::{to_json_binary, Binary, Coin, Decimal, StdResult, Storage, SubMsg, WasmMsg};
 #[cfg(not(feature = "library"))]
-use cosmwasm_std::{DepsMut, Env, Response, Uint128};
+use cosmwasm_std::{DepsMut, Env, MessageInfo, Response, Uint128};
 use exchange::msg::{ExecuteMsg as ExchangeExecuteMsg, Order, QueryMsg as ExchangeQueryMsg};
 use shared::balance::query_balance;
 use shared::cw20::into_execute_msg;
+use std::cmp::min;
+use std::collections::VecDeque;
 
 pub fn execute_trigger_handler(
     deps: DepsMut,
     env: Env,
+    info: MessageInfo,
     trigger_id: Uint128,
     mut route: Option<Binary>,
 ) -> Result<Response, ContractError> {
     assert_contract_is_not_paused(deps.storage)?;
 
+    let keeper = get_keeper(deps.storage, &env, &info.sender)?;
+
+    match keeper {
+        Some(keeper) => {
+            assert_trigger_is_due(deps.storage, env.block.time, trigger_id)?;
+            KEEPER_CACHE.save(deps.storage, trigger_id.into(), &keeper)?;
+        }
+        None => KEEPER_CACHE.remove(deps.storage, trigger_id.into()),
+    }
+
     let mut vault = get_vault(deps.storage, trigger_id)?;
 
     let mut response = Response::new()
@@ -56,9 +85,23 @@ pub fn execute_trigger_handler(
         });
     }
 
+    let mut missed_executions = None;
+    let mut retry_attempt = 0;
+
     match vault.trigger {
-        Some(TriggerConfiguration::Time { target_time }) => {
+        Some(TriggerConfiguration::Time { target_time })
+        | Some(TriggerConfiguration::Retry { target_time, .. }) => {
             assert_target_time_is_in_past(env.block.time, target_time)?;
+
+            missed_executions = Some(get_missed_interval_count(
+                env.block.time,
+                target_time,
+                &vault.time_interval,
+            ));
+
+            if let Some(TriggerConfiguration::Retry { attempt, .. }) = vault.trigger {
+                retry_attempt = attempt;
+            }
         }
         Some(TriggerConfiguration::Price { order_idx, .. }) => {
             let config = get_config(deps.storage)?;
@@ -108,10 +151,86 @@ pub fn execute_trigger_handler(
         )?;
     }
 
+    if let Some(condition) = vault.get_reached_end_condition(env.block.time) {
+        response = response.add_attribute("end_condition_reached", format!("{:?}", condition));
+
+        let (_, sub_msgs) = end_vault(deps.storage, &env, vault, condition)?;
+
+        return Ok(response
+            .add_submessages(sub_msgs)
+            .add_attribute("execution_skipped", "end_condition_reached"));
+    }
+
+    if let (true, Some(_), Some(trigger_configuration)) = (
+        vault.recurring_deposits && vault.balance.amount < vault.swap_amount,
+        missed_executions,
+        vault.trigger.clone(),
+    ) {
+        return pull_recurring_deposit(deps, &env, response, vault, trigger_configuration, route);
+    }
+
     let config = get_config(deps.storage)?;
 
     let adjusted_swap_amount = get_swap_amount(&deps.as_ref(), &env, &vault)?;
 
+    let catch_up_schedule = get_catch_up_schedule(
+        &vault,
+        &adjusted_swap_amount,
+        missed_executions.unwrap_or_default(),
+    );
+
+    if catch_up_schedule != vault.catch_up_schedule {
+        vault = update_vault(
+            deps.storage,
+            Vault {
+                catch_up_schedule: catch_up_schedule.clone(),
+                ..vault
+            },
+        )?;
+    }
+
+    let mut swap_amount = catch_up_schedule.map_or(adjusted_swap_amount.clone(), |schedule| {
+        Coin::new(
+            min(
+                adjusted_swap_amount.amount + schedule.next_amount(),
+                vault.balance.amount,
+            )
+            .into(),
+            vault.get_swap_denom(),
+        )
+    });
+
+    if let Some(missed_executions) = missed_executions {
+        response = response.add_attribute("missed_executions", missed_executions.to_string());
+    }
+
+    if let Some(residual_swap_amount) =
+        RESIDUAL_SWAP_AMOUNT_CACHE.may_load(deps.storage, vault.id.into())?
+    {
+        RESIDUAL_SWAP_AMOUNT_CACHE.remove(deps.storage, vault.id.into());
+
+        response = response.add_attribute("residual_swap_amount", residual_swap_amount.to_string());
+        swap_amount = residual_swap_amount;
+    }
+
+    if let Some(basket) = vault.basket.clone() {
+        cache_catch_up_amount(
+            deps.storage,
+            vault.id,
+            swap_amount.amount - adjusted_swap_amount.amount,
+        )?;
+
+        return execute_basket_swaps(
+            deps,
+            env,
+            response,
+            vault,
+            basket,
+            swap_amount,
+            missed_executions,
+        );
+    }
+
     if route.is_some() {
         let expected_receive_amount_new_route = get_expected_receive_amount(
             &deps.querier,
@@ -164,13 +283,31 @@ pub fn execute_trigger_handler(
                 base_denom: vault.target_denom.clone(),
                 quote_denom: vault.get_swap_denom(),
                 asset_price: twap_price,
+                missed_executions,
             },
         ),
     )?;
 
     response = response.add_attribute("twap_price", twap_price.to_string());
 
-    if let Some(SwapAdjustmentStrategy::RiskWeightedAverage { .. }) = vault.swap_adjustment_strategy
+    if let Some(PerformanceAssessmentStrategy::CompareToLumpSum { start_price: None }) =
+        vault.performance_assessment_strategy
+    {
+        vault = update_vault(
+            deps.storage,
+            Vault {
+                performance_assessment_strategy: Some(
+                    PerformanceAssessmentStrategy::CompareToLumpSum {
+                        start_price: Some(twap_price),
+                    },
+                ),
+                ..vault
+            },
+        )?;
+    }
+
+    if let (Some(SwapAdjustmentStrategy::RiskWeightedAverage { .. }), 0) =
+        (&vault.swap_adjustment_strategy, retry_attempt)
     {
         (vault, response) = simulate_standard_dca_execution(
             response,
@@ -183,8 +320,7 @@ pub fn execute_trigger_handler(
     }
 
     let should_execute_again = vault.is_active()
-        || vault.performance_assessment_strategy.clone().map_or(
-            false,
+        || vault.performance_assessment_strategy.clone().is_some_and(
             |performance_assessment_strategy| {
                 performance_assessment_strategy.should_continue(&vault)
             },
@@ -220,7 +356,7 @@ pub fn execute_trigger_handler(
         return Ok(response.add_attribute("execution_skipped", "vault_is_inactive"));
     }
 
-    if adjusted_swap_amount.amount.is_zero() {
+    if swap_amount.amount.is_zero() {
         create_event(
             deps.storage,
             EventBuilder::new(
@@ -254,33 +390,80 @@ pub fn execute_trigger_handler(
             .add_attribute("twap_price", twap_price.to_string()));
     };
 
+    if swap_amount.amount > adjusted_swap_amount.amount
+        && !get_slippage(
+            &deps.querier,
+            config.exchange_contract_address.clone(),
+            swap_amount.clone(),
+            vault.target_denom.clone(),
+            twap_price,
+            route.clone(),
+        )
+        .is_ok_and(|slippage| slippage <= vault.slippage_tolerance)
+    {
+        response = response.add_attribute("catch_up_skipped", "slippage_tolerance_exceeded");
+        swap_amount = adjusted_swap_amount.clone();
+    }
+
     let get_slippage_result = get_slippage(
         &deps.querier,
         config.exchange_contract_address.clone(),
-        adjusted_swap_amount.clone(),
+        swap_amount.clone(),
         vault.target_denom.clone(),
         twap_price,
         route.clone(),
     );
 
+    let mut partial_execution_scheduled_amount = None;
+
     match get_slippage_result {
         Ok(slippage) => {
             if slippage > vault.slippage_tolerance {
-                create_event(
-                    deps.storage,
-                    EventBuilder::new(
-                        vault.id,
-                        env.block,
-                        EventData::DcaVaultExecutionSkipped {
-                            reason: ExecutionSkippedReason::SlippageToleranceExceeded,
-                        },
-                    ),
-                )?;
+                let partial_swap_amount = if vault.allow_partial_executions {
+                    get_largest_swap_amount_within_slippage_tolerance(
+                        &deps.querier,
+                        config.exchange_contract_address.clone(),
+                        swap_amount.clone(),
+                        vault.target_denom.clone(),
+                        twap_price,
+                        route.clone(),
+                        vault.slippage_tolerance,
+                    )?
+                } else {
+                    Coin::new(0, vault.get_swap_denom())
+                };
+
+                if partial_swap_amount.amount.is_zero() {
+                    create_event(
+                        deps.storage,
+                        EventBuilder::new(
+                            vault.id,
+                            env.block.clone(),
+                            EventData::DcaVaultExecutionSkipped {
+                                reason: ExecutionSkippedReason::SlippageToleranceExceeded,
+                            },
+                        ),
+                    )?;
+
+                    if let Some(retry_time) =
+                        schedule_execution_retry(deps.storage, &env, &vault, retry_attempt)?
+                    {
+                        response =
+                            response.add_attribute("retry_scheduled", retry_time.to_string());
+                    }
+
+                    return Ok(response
+                        .add_attribute("execution_skipped", "slippage_tolerance_exceeded")
+                        .add_attribute("twap_price", twap_price.to_string())
+                        .add_attribute("slippage", slippage.to_string()));
+                }
+
+                response = response
+                    .add_attribute("partial_execution", "true")
+                    .add_attribute("partial_swap_amount", partial_swap_amount.to_string());
 
-                return Ok(response
-                    .add_attribute("execution_skipped", "slippage_tolerance_exceeded")
-                    .add_attribute("twap_price", twap_price.to_string())
-                    .add_attribute("slippage", slippage.to_string()));
+                partial_execution_scheduled_amount = Some(swap_amount);
+                swap_amount = partial_swap_amount;
             }
         }
         Err(_) => {
@@ -288,21 +471,43 @@ pub fn execute_trigger_handler(
                 deps.storage,
                 EventBuilder::new(
                     vault.id,
-                    env.block,
+                    env.block.clone(),
                     EventData::DcaVaultExecutionSkipped {
                         reason: ExecutionSkippedReason::SlippageQueryError,
                     },
                 ),
             )?;
 
+            if let Some(retry_time) =
+                schedule_execution_retry(deps.storage, &env, &vault, retry_attempt)?
+            {
+                response = response.add_attribute("retry_scheduled", retry_time.to_string());
+            }
+
             return Ok(response.add_attribute("execution_skipped", "slippage_query_error"));
         }
     }
 
-    VAULT_ID_CACHE.save(deps.storage, &vault.id)?;
+    cache_catch_up_amount(
+        deps.storage,
+        vault.id,
+        swap_amount
+            .amount
+            .saturating_sub(adjusted_swap_amount.amount),
+    )?;
+
+    match partial_execution_scheduled_amount {
+        Some(scheduled_amount) => {
+            PARTIAL_SWAP_CACHE.save(deps.storage, vault.id.into(), &scheduled_amount)?
+        }
+        None => PARTIAL_SWAP_CACHE.remove(deps.storage, vault.id.into()),
+    }
+
+    RETRY_ATTEMPT_CACHE.save(deps.storage, vault.id.into(), &retry_attempt)?;
 
     SWAP_CACHE.save(
         deps.storage,
+        vault.id.into(),
         &SwapCache {
             swap_denom_balance: query_balance(
                 deps.api,
@@ -323,8 +528,7 @@ pub fn execute_trigger_handler(
         vault
             .minimum_receive_amount
             .map_or(Uint128::zero(), |minimum_receive_amount| {
-                Decimal::from_ratio(adjusted_swap_amount.amount, vault.swap_amount)
-                    * minimum_receive_amount
+                Decimal::from_ratio(swap_amount.amount, vault.swap_amount) * minimum_receive_amount
             });
 
     Ok(response.add_submessage(SubMsg::reply_always(
@@ -338,12 +542,204 @@ pub fn execute_trigger_handler(
                 },
                 route,
             })?,
-            adjusted_swap_amount,
+            swap_amount,
         )?,
-        AFTER_SWAP_REPLY_ID,
+        get_vault_reply_id(AFTER_SWAP_REPLY_ID, vault.id),
     )))
 }
 
+fn cache_catch_up_amount(
+    store: &mut dyn Storage,
+    vault_id: Uint128,
+    caught_up_amount: Uint128,
+) -> StdResult<()> {
+    if caught_up_amount.is_zero() {
+        CATCH_UP_AMOUNT_CACHE.remove(store, vault_id.into());
+        return Ok(());
+    }
+
+    CATCH_UP_AMOUNT_CACHE.save(store, vault_id.into(), &caught_up_amount)
+}
+
+fn execute_basket_swaps(
+    deps: DepsMut,
+    env: Env,
+    mut response: Response,
+    vault: Vault,
+    basket: Vec<BasketAsset>,
+    swap_amount: Coin,
+    missed_executions: Option<u64>,
+) -> Result<Response, ContractError> {
+    let config = get_config(deps.storage)?;
+
+    let mut twap_prices = Vec::<Decimal>::new();
+
+    for asset in basket.iter() {
+        let twap_price = get_twap_to_now(
+            &deps.querier,
+            config.exchange_contract_address.clone(),
+            vault.get_swap_denom(),
+            asset.target_denom.clone(),
+            config.twap_period,
+            asset.route.clone(),
+        )?;
+
+        create_event(
+            deps.storage,
+            EventBuilder::new(
+                vault.id,
+                env.block.to_owned(),
+                EventData::DcaVaultExecutionTriggered {
+                    base_denom: asset.target_denom.clone(),
+                    quote_denom: vault.get_swap_denom(),
+                    asset_price: twap_price,
+                    missed_executions,
+                },
+            ),
+        )?;
+
+        response = response.add_attribute(
+            format!("twap_price_{}", asset.target_denom),
+            twap_price.to_string(),
+        );
+
+        twap_prices.push(twap_price);
+    }
+
+    if !vault.is_active() {
+        return Ok(response.add_attribute("execution_skipped", "vault_is_inactive"));
+    }
+
+    save_trigger(
+        deps.storage,
+        Trigger {
+            vault_id: vault.id,
+            configuration: TriggerConfiguration::Time {
+                target_time: get_next_target_time(
+                    env.block.time,
+                    vault.started_at.unwrap_or(env.block.time),
+                    vault.time_interval.clone(),
+                ),
+            },
+        },
+    )?;
+
+    if swap_amount.amount.is_zero() {
+        create_event(
+            deps.storage,
+            EventBuilder::new(
+                vault.id,
+                env.block,
+                EventData::DcaVaultExecutionSkipped {
+                    reason: ExecutionSkippedReason::SwapAmountAdjustedToZero,
+                },
+            ),
+        )?;
+
+        return Ok(response.add_attribute("execution_skipped", "swap_amount_adjusted_to_zero"));
+    }
+
+    let mut basket_swap_cache = VecDeque::<String>::new();
+    let mut swap_messages = Vec::<SubMsg>::new();
+
+    for ((asset, amount), twap_price) in basket
+        .iter()
+        .zip(get_basket_swap_amounts(&basket, swap_amount.amount))
+        .zip(twap_prices)
+    {
+        if amount.is_zero() {
+            continue;
+        }
+
+        let asset_swap_amount = Coin::new(amount.into(), vault.get_swap_denom());
+
+        let skipped_reason = match get_slippage(
+            &deps.querier,
+            config.exchange_contract_address.clone(),
+            asset_swap_amount.clone(),
+            asset.target_denom.clone(),
+            twap_price,
+            asset.route.clone(),
+        ) {
+            Ok(slippage) if slippage > vault.slippage_tolerance => {
+                Some(ExecutionSkippedReason::SlippageToleranceExceeded)
+            }
+            Ok(_) => None,
+            Err(_) => Some(ExecutionSkippedReason::SlippageQueryError),
+        };
+
+        if let Some(reason) = skipped_reason {
+            response = response.add_attribute(
+                format!("execution_skipped_{}", asset.target_denom),
+                match reason {
+                    ExecutionSkippedReason::SlippageQueryError => "slippage_query_error",
+                    _ => "slippage_tolerance_exceeded",
+                },
+            );
+
+            create_event(
+                deps.storage,
+                EventBuilder::new(
+                    vault.id,
+                    env.block.clone(),
+                    EventData::DcaVaultExecutionSkipped { reason },
+                ),
+            )?;
+
+            continue;
+        }
+
+        basket_swap_cache.push_back(asset.target_denom.clone());
+
+        let minimum_receive_amount = amount
+            .checked_div_floor(twap_price * (Decimal::one() + vault.slippage_tolerance))
+            .unwrap_or_default();
+
+        swap_messages.push(SubMsg::reply_always(
+            into_execute_msg(
+                deps.api,
+                config.exchange_contract_address.clone(),
+                to_json_binary(&ExchangeExecuteMsg::Swap {
+                    minimum_receive_amount: Coin::new(
+                        minimum_receive_amount.into(),
+                        asset.target_denom.clone(),
+                    ),
+                    route: asset.route.clone(),
+                })?,
+                asset_swap_amount,
+            )?,
+            get_vault_reply_id(AFTER_SWAP_REPLY_ID, vault.id),
+        ));
+    }
+
+    let Some(first_target_denom) = basket_swap_cache.front() else {
+        return Ok(response);
+    };
+
+    SWAP_CACHE.save(
+        deps.storage,
+        vault.id.into(),
+        &SwapCache {
+            swap_denom_balance: query_balance(
+                deps.api,
+                &deps.querier,
+                &vault.get_swap_denom(),
+                &env.contract.address,
+            )?,
+            receive_denom_balance: query_balance(
+                deps.api,
+                &deps.querier,
+                first_target_denom,
+                &env.contract.address,
+            )?,
+        },
+    )?;
+
+    BASKET_SWAP_CACHE.save(deps.storage, vault.id.into(), &basket_swap_cache)?;
+
+    Ok(response.add_submessages(swap_messages))
+}
+
 #[cfg(test)]
 mod execute_trigger_tests {
     use super::*;
@@ -355,20 +751,31 @@ mod execute_trigger_tests {
     use crate::helpers::vault::get_swap_amount;
     use crate::msg::ExecuteMsg;
     use crate::state::config::update_config;
+    use crate::state::disburse_escrow_tasks::get_disburse_escrow_task_due_date;
     use crate::state::swap_adjustments::update_swap_adjustment;
     use crate::state::triggers::delete_trigger;
+    use crate::state::triggers::get_trigger;
     use crate::state::vaults::get_vault;
     use crate::tests::helpers::{instantiate_contract, setup_vault};
+    use crate::tests::mocks::CalcMockQuerier;
     use crate::tests::mocks::{calc_mock_dependencies, ADMIN, DENOM_UKUJI, DENOM_UUSK};
     use crate::types::config::Config;
+    use crate::types::end_conditions::{EndCondition, EndConditions};
     use crate::types::event::{Event, EventData, ExecutionSkippedReason};
+    use crate::types::missed_execution_policy::{CatchUpSchedule, MissedExecutionPolicy};
     use crate::types::performance_assessment_strategy::PerformanceAssessmentStrategy;
     use crate::types::position_type::PositionType;
+    use crate::types::retry_policy::RetryPolicy;
     use crate::types::swap_adjustment_strategy::{BaseDenom, SwapAdjustmentStrategy};
+    use crate::types::time_interval::TimeInterval;
     use crate::types::trigger::TriggerConfiguration;
     use crate::types::vault::{Vault, VaultStatus};
-    use cosmwasm_std::testing::{mock_env, mock_info};
-    use cosmwasm_std::{to_json_binary, Coin, Decimal, SubMsg, Uint128, WasmMsg};
+    use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage};
+    use cosmwasm_std::{
+        from_json, to_json_binary, BankMsg, Coin, ContractResult, CosmosMsg, Decimal, Decimal256,
+        SubMsg, SystemResult, Timestamp, Uint128, WasmMsg, WasmQuery,
+    };
+    use cosmwasm_std::{Addr, OwnedDeps};
 
     #[test]
     fn when_contract_is_paused_should_fail() {
@@ -389,7 +796,9 @@ mod execute_trigger_tests {
         )
         .unwrap();
 
-        let err = execute_trigger_handler(deps.as_mut(), env, vault.id, None).unwrap_err();
+        let err =
+            execute_trigger_handler(deps.as_mut(), env, mock_info(ADMIN, &[]), vault.id, None)
+                .unwrap_err();
 
         assert_eq!(err.to_string(), "Error: contract is paused");
     }
@@ -411,7 +820,9 @@ mod execute_trigger_tests {
             },
         );
 
-        let err = execute_trigger_handler(deps.as_mut(), env, vault.id, None).unwrap_err();
+        let err =
+            execute_trigger_handler(deps.as_mut(), env, mock_info(ADMIN, &[]), vault.id, None)
+                .unwrap_err();
 
         assert_eq!(
             err.to_string(),
@@ -436,7 +847,8 @@ mod execute_trigger_tests {
             },
         );
 
-        execute_trigger_handler(deps.as_mut(), env, vault.id, None).unwrap_err();
+        execute_trigger_handler(deps.as_mut(), env, mock_info(ADMIN, &[]), vault.id, None)
+            .unwrap_err();
 
         let updated_vault = get_vault(deps.as_ref().storage, vault.id).unwrap();
 
@@ -463,7 +875,9 @@ mod execute_trigger_tests {
 
         delete_trigger(deps.as_mut().storage, vault.id).unwrap();
 
-        let err = execute_trigger_handler(deps.as_mut(), env, vault.id, None).unwrap_err();
+        let err =
+            execute_trigger_handler(deps.as_mut(), env, mock_info(ADMIN, &[]), vault.id, None)
+                .unwrap_err();
 
         assert_eq!(
             err.to_string(),
@@ -483,7 +897,9 @@ mod execute_trigger_tests {
 
         env.block.time = env.block.time.minus_seconds(10);
 
-        let err = execute_trigger_handler(deps.as_mut(), env, vault.id, None).unwrap_err();
+        let err =
+            execute_trigger_handler(deps.as_mut(), env, mock_info(ADMIN, &[]), vault.id, None)
+                .unwrap_err();
 
         assert_eq!(
             err.to_string(),
@@ -508,7 +924,7 @@ mod execute_trigger_tests {
             },
         );
 
-        execute_trigger_handler(deps.as_mut(), env, vault.id, None).unwrap();
+        execute_trigger_handler(deps.as_mut(), env, mock_info(ADMIN, &[]), vault.id, None).unwrap();
 
         let updated_vault = get_vault(deps.as_ref().storage, vault.id).unwrap();
 
@@ -533,7 +949,14 @@ mod execute_trigger_tests {
             },
         );
 
-        execute_trigger_handler(deps.as_mut(), env.clone(), vault.id, None).unwrap();
+        execute_trigger_handler(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADMIN, &[]),
+            vault.id,
+            None,
+        )
+        .unwrap();
 
         let updated_vault = get_vault(deps.as_ref().storage, vault.id).unwrap();
 
@@ -551,7 +974,14 @@ mod execute_trigger_tests {
 
         let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
 
-        execute_trigger_handler(deps.as_mut(), env.clone(), vault.id, None).unwrap();
+        execute_trigger_handler(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADMIN, &[]),
+            vault.id,
+            None,
+        )
+        .unwrap();
 
         let events = get_events_by_resource_id_handler(deps.as_ref(), vault.id, None, None, None)
             .unwrap()
@@ -567,7 +997,8 @@ mod execute_trigger_tests {
                 data: EventData::DcaVaultExecutionTriggered {
                     base_denom: vault.target_denom.clone(),
                     quote_denom: vault.get_swap_denom(),
-                    asset_price: Decimal::one()
+                    asset_price: Decimal::one(),
+                    missed_executions: Some(0)
                 }
             }
         );
@@ -595,7 +1026,9 @@ mod execute_trigger_tests {
             },
         );
 
-        let response = execute_trigger_handler(deps.as_mut(), env, vault.id, None).unwrap();
+        let response =
+            execute_trigger_handler(deps.as_mut(), env, mock_info(ADMIN, &[]), vault.id, None)
+                .unwrap();
 
         let config = get_config(deps.as_ref().storage).unwrap();
 
@@ -631,12 +1064,11 @@ mod execute_trigger_tests {
             },
         );
 
-        execute_trigger_handler(deps.as_mut(), env, vault.id, None).unwrap();
+        execute_trigger_handler(deps.as_mut(), env, mock_info(ADMIN, &[]), vault.id, None).unwrap();
 
         let updated_vault = get_vault(deps.as_ref().storage, vault.id).unwrap();
 
-        let fee_rate = get_swap_fee_rate(deps.as_mut().storage, &vault.swap_adjustment_strategy)
-            .unwrap()
+        let fee_rate = get_swap_fee_rate(deps.as_mut().storage, &vault).unwrap()
             + get_automation_fee_rate(deps.as_mut().storage, &vault).unwrap();
 
         let received_amount_before_fee = vault.swap_amount * Decimal::percent(95);
@@ -650,6 +1082,7 @@ mod execute_trigger_tests {
             match performance_assessment_strategy.clone() {
                 PerformanceAssessmentStrategy::CompareToStandardDca { swapped_amount, .. } =>
                     swapped_amount,
+                _ => panic!("expected standard DCA performance assessment"),
             },
             Coin::new(vault.swap_amount.into(), vault.get_swap_denom()),
         );
@@ -658,6 +1091,7 @@ mod execute_trigger_tests {
                 PerformanceAssessmentStrategy::CompareToStandardDca {
                     received_amount, ..
                 } => received_amount,
+                _ => panic!("expected standard DCA performance assessment"),
             },
             Coin::new(received_amount_after_fee.into(), vault.target_denom)
         );
@@ -689,7 +1123,7 @@ mod execute_trigger_tests {
             },
         );
 
-        execute_trigger_handler(deps.as_mut(), env, vault.id, None).unwrap();
+        execute_trigger_handler(deps.as_mut(), env, mock_info(ADMIN, &[]), vault.id, None).unwrap();
 
         let updated_vault = get_vault(deps.as_ref().storage, vault.id).unwrap();
 
@@ -699,6 +1133,70 @@ mod execute_trigger_tests {
         );
     }
 
+    #[test]
+    fn with_compare_to_lump_sum_performance_assessment_records_start_price() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+        let info = mock_info(ADMIN, &[]);
+
+        instantiate_contract(deps.as_mut(), env.clone(), info);
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                performance_assessment_strategy: Some(
+                    PerformanceAssessmentStrategy::CompareToLumpSum { start_price: None },
+                ),
+                swap_adjustment_strategy: Some(SwapAdjustmentStrategy::default()),
+                ..Vault::default()
+            },
+        );
+
+        execute_trigger_handler(deps.as_mut(), env, mock_info(ADMIN, &[]), vault.id, None).unwrap();
+
+        let updated_vault = get_vault(deps.as_ref().storage, vault.id).unwrap();
+
+        assert_eq!(
+            updated_vault.performance_assessment_strategy,
+            Some(PerformanceAssessmentStrategy::CompareToLumpSum {
+                start_price: Some(Decimal::one()),
+            })
+        );
+    }
+
+    #[test]
+    fn with_compare_to_lump_sum_performance_assessment_keeps_recorded_start_price() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+        let info = mock_info(ADMIN, &[]);
+
+        instantiate_contract(deps.as_mut(), env.clone(), info);
+
+        let performance_assessment_strategy = PerformanceAssessmentStrategy::CompareToLumpSum {
+            start_price: Some(Decimal::percent(150)),
+        };
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                performance_assessment_strategy: Some(performance_assessment_strategy.clone()),
+                swap_adjustment_strategy: Some(SwapAdjustmentStrategy::default()),
+                ..Vault::default()
+            },
+        );
+
+        execute_trigger_handler(deps.as_mut(), env, mock_info(ADMIN, &[]), vault.id, None).unwrap();
+
+        let updated_vault = get_vault(deps.as_ref().storage, vault.id).unwrap();
+
+        assert_eq!(
+            updated_vault.performance_assessment_strategy,
+            Some(performance_assessment_strategy)
+        );
+    }
+
     #[test]
     fn with_swap_adjusted_to_zero_should_publish_execution_skipped_event() {
         let mut deps = calc_mock_dependencies();
@@ -722,7 +1220,14 @@ mod execute_trigger_tests {
 
         deps.querier.update_fin_price(&THREE_DECIMAL);
 
-        execute_trigger_handler(deps.as_mut(), env.clone(), vault.id, None).unwrap();
+        execute_trigger_handler(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADMIN, &[]),
+            vault.id,
+            None,
+        )
+        .unwrap();
 
         let events = get_events_by_resource_id_handler(deps.as_ref(), vault.id, None, None, None)
             .unwrap()
@@ -762,7 +1267,9 @@ mod execute_trigger_tests {
 
         deps.querier.update_fin_price(&THREE_DECIMAL);
 
-        let response = execute_trigger_handler(deps.as_mut(), env, vault.id, None).unwrap();
+        let response =
+            execute_trigger_handler(deps.as_mut(), env, mock_info(ADMIN, &[]), vault.id, None)
+                .unwrap();
 
         assert!(response.messages.is_empty());
     }
@@ -805,7 +1312,9 @@ mod execute_trigger_tests {
                 .unwrap();
             });
 
-        let response = execute_trigger_handler(deps.as_mut(), env, vault.id, None).unwrap();
+        let response =
+            execute_trigger_handler(deps.as_mut(), env, mock_info(ADMIN, &[]), vault.id, None)
+                .unwrap();
 
         let config = get_config(deps.as_ref().storage).unwrap();
 
@@ -853,7 +1362,7 @@ mod execute_trigger_tests {
 
         deps.querier.update_fin_price(&HALF_DECIMAL);
 
-        execute_trigger_handler(deps.as_mut(), env, vault.id, None).unwrap();
+        execute_trigger_handler(deps.as_mut(), env, mock_info(ADMIN, &[]), vault.id, None).unwrap();
 
         let updated_vault = get_vault(deps.as_ref().storage, vault.id).unwrap();
 
@@ -884,10 +1393,17 @@ mod execute_trigger_tests {
             },
         );
 
-        execute_trigger_handler(deps.as_mut(), env.clone(), vault.id, None).unwrap();
-
-        let events = get_events_by_resource_id_handler(deps.as_ref(), vault.id, None, None, None)
-            .unwrap()
+        execute_trigger_handler(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADMIN, &[]),
+            vault.id,
+            None,
+        )
+        .unwrap();
+
+        let events = get_events_by_resource_id_handler(deps.as_ref(), vault.id, None, None, None)
+            .unwrap()
             .events;
 
         assert_eq!(
@@ -928,7 +1444,14 @@ mod execute_trigger_tests {
 
         deps.querier.update_fin_price(&HALF_DECIMAL);
 
-        execute_trigger_handler(deps.as_mut(), env.clone(), vault.id, None).unwrap();
+        execute_trigger_handler(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADMIN, &[]),
+            vault.id,
+            None,
+        )
+        .unwrap();
 
         let events = get_events_by_resource_id_handler(deps.as_ref(), vault.id, None, None, None)
             .unwrap()
@@ -966,12 +1489,11 @@ mod execute_trigger_tests {
             },
         );
 
-        execute_trigger_handler(deps.as_mut(), env, vault.id, None).unwrap();
+        execute_trigger_handler(deps.as_mut(), env, mock_info(ADMIN, &[]), vault.id, None).unwrap();
 
         let updated_vault = get_vault(deps.as_ref().storage, vault.id).unwrap();
 
-        let fee_rate = get_swap_fee_rate(deps.as_ref().storage, &vault.swap_adjustment_strategy)
-            .unwrap()
+        let fee_rate = get_swap_fee_rate(deps.as_ref().storage, &vault).unwrap()
             + get_automation_fee_rate(deps.as_ref().storage, &vault).unwrap();
 
         let received_amount_before_fee = vault.swap_amount * Decimal::percent(95);
@@ -985,6 +1507,7 @@ mod execute_trigger_tests {
             match performance_assessment_strategy.clone() {
                 PerformanceAssessmentStrategy::CompareToStandardDca { swapped_amount, .. } =>
                     swapped_amount,
+                _ => panic!("expected standard DCA performance assessment"),
             },
             Coin::new(vault.swap_amount.into(), vault.get_swap_denom()),
         );
@@ -993,6 +1516,7 @@ mod execute_trigger_tests {
                 PerformanceAssessmentStrategy::CompareToStandardDca {
                     received_amount, ..
                 } => received_amount,
+                _ => panic!("expected standard DCA performance assessment"),
             },
             Coin::new(received_amount_after_fee.into(), vault.target_denom)
         );
@@ -1028,7 +1552,14 @@ mod execute_trigger_tests {
 
         deps.querier.update_fin_price(&HALF_DECIMAL);
 
-        let response = execute_trigger_handler(deps.as_mut(), env.clone(), vault.id, None).unwrap();
+        let response = execute_trigger_handler(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADMIN, &[]),
+            vault.id,
+            None,
+        )
+        .unwrap();
 
         assert!(response.messages.contains(&SubMsg::new(WasmMsg::Execute {
             contract_addr: env.contract.address.to_string(),
@@ -1065,7 +1596,9 @@ mod execute_trigger_tests {
             },
         );
 
-        let response = execute_trigger_handler(deps.as_mut(), env, vault.id, None).unwrap();
+        let response =
+            execute_trigger_handler(deps.as_mut(), env, mock_info(ADMIN, &[]), vault.id, None)
+                .unwrap();
 
         assert!(response.messages.is_empty());
     }
@@ -1080,7 +1613,14 @@ mod execute_trigger_tests {
 
         let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
 
-        execute_trigger_handler(deps.as_mut(), env.clone(), vault.id, None).unwrap();
+        execute_trigger_handler(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADMIN, &[]),
+            vault.id,
+            None,
+        )
+        .unwrap();
 
         let updated_vault = get_vault(deps.as_ref().storage, vault.id).unwrap();
 
@@ -1118,7 +1658,14 @@ mod execute_trigger_tests {
             },
         );
 
-        execute_trigger_handler(deps.as_mut(), env.clone(), vault.id, None).unwrap();
+        execute_trigger_handler(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADMIN, &[]),
+            vault.id,
+            None,
+        )
+        .unwrap();
 
         let updated_vault = get_vault(deps.as_ref().storage, vault.id).unwrap();
 
@@ -1156,7 +1703,7 @@ mod execute_trigger_tests {
             },
         );
 
-        execute_trigger_handler(deps.as_mut(), env, vault.id, None).unwrap();
+        execute_trigger_handler(deps.as_mut(), env, mock_info(ADMIN, &[]), vault.id, None).unwrap();
 
         let updated_vault = get_vault(deps.as_ref().storage, vault.id).unwrap();
 
@@ -1191,7 +1738,14 @@ mod execute_trigger_tests {
             },
         );
 
-        execute_trigger_handler(deps.as_mut(), env.clone(), vault.id, None).unwrap();
+        execute_trigger_handler(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADMIN, &[]),
+            vault.id,
+            None,
+        )
+        .unwrap();
 
         let updated_vault = get_vault(deps.as_ref().storage, vault.id).unwrap();
 
@@ -1239,7 +1793,7 @@ mod execute_trigger_tests {
             },
         );
 
-        execute_trigger_handler(deps.as_mut(), env, vault.id, None).unwrap();
+        execute_trigger_handler(deps.as_mut(), env, mock_info(ADMIN, &[]), vault.id, None).unwrap();
 
         let updated_vault = get_vault(deps.as_ref().storage, vault.id).unwrap();
 
@@ -1257,7 +1811,9 @@ mod execute_trigger_tests {
 
         let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
 
-        let response = execute_trigger_handler(deps.as_mut(), env, vault.id, None).unwrap();
+        let response =
+            execute_trigger_handler(deps.as_mut(), env, mock_info(ADMIN, &[]), vault.id, None)
+                .unwrap();
 
         let config = get_config(deps.as_ref().storage).unwrap();
 
@@ -1281,6 +1837,56 @@ mod execute_trigger_tests {
         )
     }
 
+    #[test]
+    fn with_residual_swap_amount_should_only_swap_the_residual() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+        let info = mock_info(ADMIN, &[]);
+
+        instantiate_contract(deps.as_mut(), env.clone(), info);
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        let residual_swap_amount = Coin::new((ONE / TWO_MICRONS).into(), DENOM_UKUJI);
+
+        RESIDUAL_SWAP_AMOUNT_CACHE
+            .save(
+                deps.as_mut().storage,
+                vault.id.into(),
+                &residual_swap_amount,
+            )
+            .unwrap();
+
+        let response =
+            execute_trigger_handler(deps.as_mut(), env, mock_info(ADMIN, &[]), vault.id, None)
+                .unwrap();
+
+        let config = get_config(deps.as_ref().storage).unwrap();
+
+        assert_eq!(
+            response.messages.first().unwrap(),
+            &SubMsg::reply_always(
+                WasmMsg::Execute {
+                    contract_addr: config.exchange_contract_address.to_string(),
+                    msg: to_json_binary(&ExchangeExecuteMsg::Swap {
+                        minimum_receive_amount: Coin {
+                            amount: Uint128::zero(),
+                            denom: vault.target_denom.clone(),
+                        },
+                        route: vault.route
+                    })
+                    .unwrap(),
+                    funds: vec![residual_swap_amount]
+                },
+                AFTER_SWAP_REPLY_ID,
+            )
+        );
+        assert!(RESIDUAL_SWAP_AMOUNT_CACHE
+            .may_load(deps.as_ref().storage, vault.id.into())
+            .unwrap()
+            .is_none());
+    }
+
     #[test]
     fn should_create_reduced_swap_message_when_balance_is_low() {
         let mut deps = calc_mock_dependencies();
@@ -1299,7 +1905,9 @@ mod execute_trigger_tests {
             },
         );
 
-        let response = execute_trigger_handler(deps.as_mut(), env, vault.id, None).unwrap();
+        let response =
+            execute_trigger_handler(deps.as_mut(), env, mock_info(ADMIN, &[]), vault.id, None)
+                .unwrap();
 
         let config = get_config(deps.as_ref().storage).unwrap();
 
@@ -1340,7 +1948,14 @@ mod execute_trigger_tests {
             },
         );
 
-        let response = execute_trigger_handler(deps.as_mut(), env.clone(), vault.id, None).unwrap();
+        let response = execute_trigger_handler(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADMIN, &[]),
+            vault.id,
+            None,
+        )
+        .unwrap();
 
         let config = get_config(deps.as_ref().storage).unwrap();
 
@@ -1382,7 +1997,14 @@ mod execute_trigger_tests {
             },
         );
 
-        execute_trigger_handler(deps.as_mut(), env.clone(), vault.id, None).unwrap();
+        execute_trigger_handler(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADMIN, &[]),
+            vault.id,
+            None,
+        )
+        .unwrap();
 
         let events = get_events_by_resource_id_handler(deps.as_ref(), vault.id, None, None, None)
             .unwrap()
@@ -1422,7 +2044,14 @@ mod execute_trigger_tests {
             },
         );
 
-        execute_trigger_handler(deps.as_mut(), env.clone(), vault.id, None).unwrap();
+        execute_trigger_handler(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADMIN, &[]),
+            vault.id,
+            None,
+        )
+        .unwrap();
 
         let updated_vault = get_vault(deps.as_ref().storage, vault.id).unwrap();
 
@@ -1461,7 +2090,9 @@ mod execute_trigger_tests {
             },
         );
 
-        let response = execute_trigger_handler(deps.as_mut(), env, vault.id, None).unwrap();
+        let response =
+            execute_trigger_handler(deps.as_mut(), env, mock_info(ADMIN, &[]), vault.id, None)
+                .unwrap();
 
         let config = get_config(deps.as_ref().storage).unwrap();
 
@@ -1484,4 +2115,1139 @@ mod execute_trigger_tests {
             )
         );
     }
+
+    fn basket_vault() -> Vault {
+        Vault {
+            basket: Some(vec![
+                BasketAsset {
+                    target_denom: DENOM_UUSK.to_string(),
+                    weight: Decimal::percent(60),
+                    route: None,
+                    received_amount: Coin::new(0, DENOM_UUSK),
+                },
+                BasketAsset {
+                    target_denom: "uatom".to_string(),
+                    weight: Decimal::percent(40),
+                    route: None,
+                    received_amount: Coin::new(0, "uatom"),
+                },
+            ]),
+            ..Vault::default()
+        }
+    }
+
+    #[test]
+    fn with_basket_vault_should_create_swap_message_per_asset() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), basket_vault());
+
+        let response =
+            execute_trigger_handler(deps.as_mut(), env, mock_info(ADMIN, &[]), vault.id, None)
+                .unwrap();
+
+        let config = get_config(deps.as_ref().storage).unwrap();
+
+        let swap_messages = [
+            (DENOM_UUSK, Decimal::percent(60), Uint128::new(545454)),
+            ("uatom", Decimal::percent(40), Uint128::new(363636)),
+        ]
+        .into_iter()
+        .map(|(denom, weight, minimum_receive_amount)| {
+            SubMsg::reply_always(
+                WasmMsg::Execute {
+                    contract_addr: config.exchange_contract_address.to_string(),
+                    msg: to_json_binary(&ExchangeExecuteMsg::Swap {
+                        minimum_receive_amount: Coin::new(minimum_receive_amount.into(), denom),
+                        route: None,
+                    })
+                    .unwrap(),
+                    funds: vec![Coin::new(
+                        (vault.swap_amount * weight).into(),
+                        vault.get_swap_denom(),
+                    )],
+                },
+                AFTER_SWAP_REPLY_ID,
+            )
+        })
+        .collect::<Vec<SubMsg>>();
+
+        assert_eq!(response.messages, swap_messages);
+    }
+
+    #[test]
+    fn with_basket_vault_should_cache_scheduled_basket_swaps() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), basket_vault());
+
+        execute_trigger_handler(deps.as_mut(), env, mock_info(ADMIN, &[]), vault.id, None).unwrap();
+
+        let swap_cache = SWAP_CACHE
+            .load(deps.as_ref().storage, vault.id.into())
+            .unwrap();
+
+        assert_eq!(
+            BASKET_SWAP_CACHE
+                .load(deps.as_ref().storage, vault.id.into())
+                .unwrap(),
+            VecDeque::from(vec![DENOM_UUSK.to_string(), "uatom".to_string()])
+        );
+        assert_eq!(
+            swap_cache.receive_denom_balance.denom,
+            DENOM_UUSK.to_string()
+        );
+    }
+
+    #[test]
+    fn with_basket_vault_should_derive_minimum_receive_amounts_from_twap_price() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), basket_vault());
+
+        deps.querier.update_wasm(|query| {
+            SystemResult::Ok(ContractResult::Ok(match query {
+                WasmQuery::Smart { msg, .. } => match from_json::<ExchangeQueryMsg>(msg).unwrap() {
+                    ExchangeQueryMsg::GetTwapToNow { .. } => {
+                        to_json_binary(&Decimal256::percent(200)).unwrap()
+                    }
+                    ExchangeQueryMsg::GetExpectedReceiveAmount {
+                        swap_amount,
+                        target_denom,
+                        ..
+                    } => to_json_binary(&Coin {
+                        amount: swap_amount.amount * Decimal::percent(50),
+                        denom: target_denom,
+                    })
+                    .unwrap(),
+                    _ => panic!("unexpected exchange query"),
+                },
+                _ => panic!("unexpected query"),
+            }))
+        });
+
+        let response =
+            execute_trigger_handler(deps.as_mut(), env, mock_info(ADMIN, &[]), vault.id, None)
+                .unwrap();
+
+        let minimum_receive_amounts = response
+            .messages
+            .iter()
+            .map(|sub_msg| match &sub_msg.msg {
+                CosmosMsg::Wasm(WasmMsg::Execute { msg, .. }) => {
+                    match from_json::<ExchangeExecuteMsg>(msg).unwrap() {
+                        ExchangeExecuteMsg::Swap {
+                            minimum_receive_amount,
+                            ..
+                        } => minimum_receive_amount,
+                        _ => panic!("expected swap message"),
+                    }
+                }
+                _ => panic!("expected wasm execute message"),
+            })
+            .collect::<Vec<Coin>>();
+
+        assert_eq!(
+            minimum_receive_amounts,
+            vec![Coin::new(272727, DENOM_UUSK), Coin::new(181818, "uatom"),]
+        );
+    }
+
+    #[test]
+    fn with_basket_vault_should_skip_asset_exceeding_slippage_tolerance() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), basket_vault());
+
+        deps.querier.update_wasm(|query| {
+            SystemResult::Ok(ContractResult::Ok(match query {
+                WasmQuery::Smart { msg, .. } => match from_json::<ExchangeQueryMsg>(msg).unwrap() {
+                    ExchangeQueryMsg::GetTwapToNow { .. } => {
+                        to_json_binary(&Decimal256::percent(100)).unwrap()
+                    }
+                    ExchangeQueryMsg::GetExpectedReceiveAmount {
+                        swap_amount,
+                        target_denom,
+                        ..
+                    } => to_json_binary(&Coin {
+                        amount: swap_amount.amount
+                            * if target_denom == DENOM_UUSK {
+                                Decimal::percent(95)
+                            } else {
+                                Decimal::percent(50)
+                            },
+                        denom: target_denom,
+                    })
+                    .unwrap(),
+                    _ => panic!("unexpected exchange query"),
+                },
+                _ => panic!("unexpected query"),
+            }))
+        });
+
+        let response = execute_trigger_handler(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADMIN, &[]),
+            vault.id,
+            None,
+        )
+        .unwrap();
+
+        let events = get_events_by_resource_id_handler(deps.as_ref(), vault.id, None, None, None)
+            .unwrap()
+            .events;
+
+        assert_eq!(response.messages.len(), 1);
+        assert_eq!(
+            BASKET_SWAP_CACHE
+                .load(deps.as_ref().storage, vault.id.into())
+                .unwrap(),
+            VecDeque::from(vec![DENOM_UUSK.to_string()])
+        );
+        assert!(events.contains(&Event {
+            id: 3,
+            resource_id: vault.id,
+            timestamp: env.block.time,
+            block_height: env.block.height,
+            data: EventData::DcaVaultExecutionSkipped {
+                reason: ExecutionSkippedReason::SlippageToleranceExceeded
+            }
+        }));
+    }
+
+    #[test]
+    fn with_end_time_reached_should_end_vault_and_refund_balance() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                end_conditions: Some(EndConditions {
+                    end_time: Some(env.block.time),
+                    max_executions: None,
+                    received_amount_goal: None,
+                    executions: 0,
+                }),
+                ..Vault::default()
+            },
+        );
+
+        let response =
+            execute_trigger_handler(deps.as_mut(), env, mock_info(ADMIN, &[]), vault.id, None)
+                .unwrap();
+
+        let updated_vault = get_vault(deps.as_ref().storage, vault.id).unwrap();
+
+        assert_eq!(
+            response.messages,
+            vec![SubMsg::new(BankMsg::Send {
+                to_address: vault.owner.to_string(),
+                amount: vec![vault.balance.clone()],
+            })]
+        );
+        assert_eq!(updated_vault.status, VaultStatus::Inactive);
+        assert_eq!(updated_vault.balance.amount, Uint128::zero());
+        assert_eq!(updated_vault.trigger, None);
+    }
+
+    #[test]
+    fn with_end_time_reached_should_publish_end_condition_reached_event() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                end_conditions: Some(EndConditions {
+                    end_time: Some(env.block.time),
+                    max_executions: None,
+                    received_amount_goal: None,
+                    executions: 0,
+                }),
+                ..Vault::default()
+            },
+        );
+
+        execute_trigger_handler(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADMIN, &[]),
+            vault.id,
+            None,
+        )
+        .unwrap();
+
+        let events = get_events_by_resource_id_handler(deps.as_ref(), vault.id, None, None, None)
+            .unwrap()
+            .events;
+
+        assert!(events.contains(
+            &EventBuilder::new(
+                vault.id,
+                env.block,
+                EventData::DcaVaultEndConditionReached {
+                    condition: EndCondition::EndTimeReached,
+                },
+            )
+            .build(1)
+        ));
+    }
+
+    #[test]
+    fn with_end_time_reached_and_escrowed_funds_should_save_disburse_escrow_task() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                escrowed_amount: Coin::new(ONE.into(), DENOM_UUSK),
+                end_conditions: Some(EndConditions {
+                    end_time: Some(env.block.time),
+                    max_executions: None,
+                    received_amount_goal: None,
+                    executions: 0,
+                }),
+                ..Vault::default()
+            },
+        );
+
+        execute_trigger_handler(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADMIN, &[]),
+            vault.id,
+            None,
+        )
+        .unwrap();
+
+        let due_date = get_disburse_escrow_task_due_date(deps.as_ref().storage, vault.id).unwrap();
+
+        assert_eq!(
+            due_date,
+            Some(Timestamp::from_seconds(
+                vault
+                    .get_expected_execution_completed_date(env.block.time)
+                    .seconds()
+            ))
+        );
+    }
+
+    fn vault_with_missed_executions(
+        deps: DepsMut,
+        env: Env,
+        missed_execution_policy: MissedExecutionPolicy,
+        balance: Uint128,
+    ) -> (Vault, Env) {
+        let vault = setup_vault(
+            deps,
+            env.clone(),
+            Vault {
+                balance: Coin::new(balance.into(), DENOM_UKUJI),
+                missed_execution_policy,
+                ..Vault::default()
+            },
+        );
+
+        let mut later_env = env;
+        later_env.block.time = later_env
+            .block
+            .time
+            .plus_seconds(3 * 24 * 60 * 60 + 60 * 60);
+
+        (vault, later_env)
+    }
+
+    fn get_sent_swap_amount(response: &Response) -> Uint128 {
+        match &response.messages.first().unwrap().msg {
+            CosmosMsg::Wasm(WasmMsg::Execute { funds, .. }) => funds[0].amount,
+            _ => panic!("expected a swap message"),
+        }
+    }
+
+    #[test]
+    fn with_missed_executions_should_publish_missed_executions_in_triggered_event() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let (vault, env) =
+            vault_with_missed_executions(deps.as_mut(), env, MissedExecutionPolicy::Skip, TEN);
+
+        let response =
+            execute_trigger_handler(deps.as_mut(), env, mock_info(ADMIN, &[]), vault.id, None)
+                .unwrap();
+
+        let events = get_events_by_resource_id_handler(deps.as_ref(), vault.id, None, None, None)
+            .unwrap()
+            .events;
+
+        assert!(events.iter().any(|event| event.data
+            == EventData::DcaVaultExecutionTriggered {
+                base_denom: vault.target_denom.clone(),
+                quote_denom: vault.get_swap_denom(),
+                asset_price: Decimal::one(),
+                missed_executions: Some(3),
+            }));
+        assert_eq!(get_sent_swap_amount(&response), vault.swap_amount);
+    }
+
+    #[test]
+    fn with_catch_up_policy_should_swap_missed_amount() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let (vault, env) =
+            vault_with_missed_executions(deps.as_mut(), env, MissedExecutionPolicy::CatchUp, TEN);
+
+        let response =
+            execute_trigger_handler(deps.as_mut(), env, mock_info(ADMIN, &[]), vault.id, None)
+                .unwrap();
+
+        let updated_vault = get_vault(deps.as_ref().storage, vault.id).unwrap();
+
+        assert_eq!(
+            get_sent_swap_amount(&response),
+            vault.swap_amount * Uint128::new(4)
+        );
+        assert_eq!(
+            updated_vault.catch_up_schedule,
+            Some(CatchUpSchedule {
+                remaining_amount: vault.swap_amount * Uint128::new(3),
+                executions_remaining: 1,
+            })
+        );
+        assert_eq!(
+            CATCH_UP_AMOUNT_CACHE
+                .load(deps.as_ref().storage, vault.id.into())
+                .unwrap(),
+            vault.swap_amount * Uint128::new(3)
+        );
+    }
+
+    #[test]
+    fn with_catch_up_policy_and_every_block_interval_should_not_catch_up() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                time_interval: TimeInterval::EveryBlock,
+                missed_execution_policy: MissedExecutionPolicy::CatchUp,
+                ..Vault::default()
+            },
+        );
+
+        let mut env = env;
+        env.block.time = env.block.time.plus_seconds(6);
+
+        let response =
+            execute_trigger_handler(deps.as_mut(), env, mock_info(ADMIN, &[]), vault.id, None)
+                .unwrap();
+
+        assert_eq!(get_sent_swap_amount(&response), vault.swap_amount);
+        assert_eq!(
+            get_vault(deps.as_ref().storage, vault.id)
+                .unwrap()
+                .catch_up_schedule,
+            None
+        );
+    }
+
+    #[test]
+    fn with_catch_up_policy_should_cap_missed_amount_at_balance() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let (vault, env) = vault_with_missed_executions(
+            deps.as_mut(),
+            env,
+            MissedExecutionPolicy::CatchUp,
+            ONE * Uint128::new(2),
+        );
+
+        let response =
+            execute_trigger_handler(deps.as_mut(), env, mock_info(ADMIN, &[]), vault.id, None)
+                .unwrap();
+
+        assert_eq!(get_sent_swap_amount(&response), vault.balance.amount);
+    }
+
+    #[test]
+    fn with_spread_policy_should_spread_missed_amount_over_executions() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let (vault, env) = vault_with_missed_executions(
+            deps.as_mut(),
+            env,
+            MissedExecutionPolicy::Spread { executions: 3 },
+            TEN,
+        );
+
+        let response =
+            execute_trigger_handler(deps.as_mut(), env, mock_info(ADMIN, &[]), vault.id, None)
+                .unwrap();
+
+        let updated_vault = get_vault(deps.as_ref().storage, vault.id).unwrap();
+
+        assert_eq!(
+            get_sent_swap_amount(&response),
+            vault.swap_amount * Uint128::new(2)
+        );
+        assert_eq!(
+            updated_vault.catch_up_schedule,
+            Some(CatchUpSchedule {
+                remaining_amount: vault.swap_amount * Uint128::new(3),
+                executions_remaining: 3,
+            })
+        );
+        assert_eq!(
+            CATCH_UP_AMOUNT_CACHE
+                .load(deps.as_ref().storage, vault.id.into())
+                .unwrap(),
+            vault.swap_amount
+        );
+    }
+
+    #[test]
+    fn with_catch_up_policy_and_slippage_exceeded_should_swap_regular_amount() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let (vault, env) =
+            vault_with_missed_executions(deps.as_mut(), env, MissedExecutionPolicy::CatchUp, TEN);
+
+        deps.querier.update_wasm(|query| {
+            SystemResult::Ok(ContractResult::Ok(match query {
+                WasmQuery::Smart { msg, .. } => match from_json::<ExchangeQueryMsg>(msg).unwrap() {
+                    ExchangeQueryMsg::GetTwapToNow { .. } => {
+                        to_json_binary(&Decimal256::percent(100)).unwrap()
+                    }
+                    ExchangeQueryMsg::GetExpectedReceiveAmount {
+                        swap_amount,
+                        target_denom,
+                        ..
+                    } => to_json_binary(&Coin {
+                        amount: swap_amount.amount
+                            * if swap_amount.amount > ONE {
+                                Decimal::percent(50)
+                            } else {
+                                Decimal::percent(95)
+                            },
+                        denom: target_denom,
+                    })
+                    .unwrap(),
+                    _ => panic!("unexpected exchange query"),
+                },
+                _ => panic!("unexpected query"),
+            }))
+        });
+
+        let response =
+            execute_trigger_handler(deps.as_mut(), env, mock_info(ADMIN, &[]), vault.id, None)
+                .unwrap();
+
+        let updated_vault = get_vault(deps.as_ref().storage, vault.id).unwrap();
+
+        assert_eq!(get_sent_swap_amount(&response), vault.swap_amount);
+        assert_eq!(
+            updated_vault.catch_up_schedule,
+            Some(CatchUpSchedule {
+                remaining_amount: vault.swap_amount * Uint128::new(3),
+                executions_remaining: 1,
+            })
+        );
+        assert!(CATCH_UP_AMOUNT_CACHE
+            .may_load(deps.as_ref().storage, vault.id.into())
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn with_partial_executions_and_slippage_exceeded_should_swap_reduced_amount() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                allow_partial_executions: true,
+                ..Vault::default()
+            },
+        );
+
+        deps.querier.update_wasm(|query| {
+            SystemResult::Ok(ContractResult::Ok(match query {
+                WasmQuery::Smart { msg, .. } => match from_json::<ExchangeQueryMsg>(msg).unwrap() {
+                    ExchangeQueryMsg::GetTwapToNow { .. } => {
+                        to_json_binary(&Decimal256::percent(100)).unwrap()
+                    }
+                    ExchangeQueryMsg::GetExpectedReceiveAmount {
+                        swap_amount,
+                        target_denom,
+                        ..
+                    } => to_json_binary(&Coin {
+                        amount: swap_amount.amount
+                            * if swap_amount.amount > ONE / Uint128::new(2) {
+                                Decimal::percent(50)
+                            } else {
+                                Decimal::percent(95)
+                            },
+                        denom: target_denom,
+                    })
+                    .unwrap(),
+                    _ => panic!("unexpected exchange query"),
+                },
+                _ => panic!("unexpected query"),
+            }))
+        });
+
+        let response =
+            execute_trigger_handler(deps.as_mut(), env, mock_info(ADMIN, &[]), vault.id, None)
+                .unwrap();
+
+        assert_eq!(get_sent_swap_amount(&response), ONE / Uint128::new(2));
+        assert_eq!(
+            PARTIAL_SWAP_CACHE
+                .load(deps.as_ref().storage, vault.id.into())
+                .unwrap(),
+            Coin::new(vault.swap_amount.into(), vault.get_swap_denom())
+        );
+    }
+
+    #[test]
+    fn with_partial_executions_and_no_amount_within_slippage_tolerance_should_skip_execution() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                allow_partial_executions: true,
+                ..Vault::default()
+            },
+        );
+
+        deps.querier.update_wasm(|query| {
+            SystemResult::Ok(ContractResult::Ok(match query {
+                WasmQuery::Smart { msg, .. } => match from_json::<ExchangeQueryMsg>(msg).unwrap() {
+                    ExchangeQueryMsg::GetTwapToNow { .. } => {
+                        to_json_binary(&Decimal256::percent(100)).unwrap()
+                    }
+                    ExchangeQueryMsg::GetExpectedReceiveAmount {
+                        swap_amount,
+                        target_denom,
+                        ..
+                    } => to_json_binary(&Coin {
+                        amount: swap_amount.amount
+                            * if swap_amount.amount > Uint128::zero() {
+                                Decimal::percent(50)
+                            } else {
+                                Decimal::percent(95)
+                            },
+                        denom: target_denom,
+                    })
+                    .unwrap(),
+                    _ => panic!("unexpected exchange query"),
+                },
+                _ => panic!("unexpected query"),
+            }))
+        });
+
+        let response =
+            execute_trigger_handler(deps.as_mut(), env, mock_info(ADMIN, &[]), vault.id, None)
+                .unwrap();
+
+        let events = get_events_by_resource_id_handler(deps.as_ref(), vault.id, None, None, None)
+            .unwrap()
+            .events;
+
+        assert!(response.messages.is_empty());
+        assert!(events.iter().any(|event| event.data
+            == EventData::DcaVaultExecutionSkipped {
+                reason: ExecutionSkippedReason::SlippageToleranceExceeded
+            }));
+    }
+
+    #[test]
+    fn without_partial_executions_and_slippage_exceeded_should_skip_execution() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        deps.querier.update_wasm(|query| {
+            SystemResult::Ok(ContractResult::Ok(match query {
+                WasmQuery::Smart { msg, .. } => match from_json::<ExchangeQueryMsg>(msg).unwrap() {
+                    ExchangeQueryMsg::GetTwapToNow { .. } => {
+                        to_json_binary(&Decimal256::percent(100)).unwrap()
+                    }
+                    ExchangeQueryMsg::GetExpectedReceiveAmount {
+                        swap_amount,
+                        target_denom,
+                        ..
+                    } => to_json_binary(&Coin {
+                        amount: swap_amount.amount
+                            * if swap_amount.amount > ONE / Uint128::new(2) {
+                                Decimal::percent(50)
+                            } else {
+                                Decimal::percent(95)
+                            },
+                        denom: target_denom,
+                    })
+                    .unwrap(),
+                    _ => panic!("unexpected exchange query"),
+                },
+                _ => panic!("unexpected query"),
+            }))
+        });
+
+        let response =
+            execute_trigger_handler(deps.as_mut(), env, mock_info(ADMIN, &[]), vault.id, None)
+                .unwrap();
+
+        assert!(response.messages.is_empty());
+        assert_eq!(
+            PARTIAL_SWAP_CACHE
+                .may_load(deps.as_ref().storage, vault.id.into())
+                .unwrap(),
+            None
+        );
+    }
+
+    fn mock_slippage_tolerance_exceeded(
+        deps: &mut OwnedDeps<MockStorage, MockApi, CalcMockQuerier>,
+    ) {
+        deps.querier.update_wasm(|query| {
+            SystemResult::Ok(ContractResult::Ok(match query {
+                WasmQuery::Smart { msg, .. } => match from_json::<ExchangeQueryMsg>(msg).unwrap() {
+                    ExchangeQueryMsg::GetTwapToNow { .. } => {
+                        to_json_binary(&Decimal256::percent(100)).unwrap()
+                    }
+                    ExchangeQueryMsg::GetExpectedReceiveAmount {
+                        swap_amount,
+                        target_denom,
+                        ..
+                    } => to_json_binary(&Coin {
+                        amount: swap_amount.amount * Decimal::percent(50),
+                        denom: target_denom,
+                    })
+                    .unwrap(),
+                    _ => panic!("unexpected exchange query"),
+                },
+                _ => panic!("unexpected query"),
+            }))
+        });
+    }
+
+    #[test]
+    fn with_retry_policy_and_slippage_exceeded_should_schedule_retry_trigger() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                retry_policy: Some(RetryPolicy {
+                    retry_delay_minutes: 30,
+                    max_attempts: 3,
+                }),
+                ..Vault::default()
+            },
+        );
+
+        mock_slippage_tolerance_exceeded(&mut deps);
+
+        let response = execute_trigger_handler(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADMIN, &[]),
+            vault.id,
+            None,
+        )
+        .unwrap();
+
+        assert!(response.messages.is_empty());
+        assert_eq!(
+            get_trigger(deps.as_ref().storage, vault.id)
+                .unwrap()
+                .unwrap()
+                .configuration,
+            TriggerConfiguration::Retry {
+                target_time: env.block.time.plus_seconds(30 * 60),
+                attempt: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn with_default_retry_policy_and_slippage_exceeded_should_schedule_retry_trigger() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        update_config(
+            deps.as_mut().storage,
+            Config {
+                default_retry_policy: Some(RetryPolicy {
+                    retry_delay_minutes: 10,
+                    max_attempts: 1,
+                }),
+                ..Config::default()
+            },
+        )
+        .unwrap();
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        mock_slippage_tolerance_exceeded(&mut deps);
+
+        execute_trigger_handler(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADMIN, &[]),
+            vault.id,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(
+            get_trigger(deps.as_ref().storage, vault.id)
+                .unwrap()
+                .unwrap()
+                .configuration,
+            TriggerConfiguration::Retry {
+                target_time: env.block.time.plus_seconds(10 * 60),
+                attempt: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn without_retry_policy_and_slippage_exceeded_should_keep_normal_schedule() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        mock_slippage_tolerance_exceeded(&mut deps);
+
+        execute_trigger_handler(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADMIN, &[]),
+            vault.id,
+            None,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            get_trigger(deps.as_ref().storage, vault.id)
+                .unwrap()
+                .unwrap()
+                .configuration,
+            TriggerConfiguration::Time { .. }
+        ));
+    }
+
+    #[test]
+    fn with_retry_trigger_at_max_attempts_should_fall_back_to_normal_schedule() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                retry_policy: Some(RetryPolicy {
+                    retry_delay_minutes: 30,
+                    max_attempts: 3,
+                }),
+                trigger: Some(TriggerConfiguration::Retry {
+                    target_time: env.block.time,
+                    attempt: 3,
+                }),
+                ..Vault::default()
+            },
+        );
+
+        mock_slippage_tolerance_exceeded(&mut deps);
+
+        execute_trigger_handler(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADMIN, &[]),
+            vault.id,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(
+            get_trigger(deps.as_ref().storage, vault.id)
+                .unwrap()
+                .unwrap()
+                .configuration,
+            TriggerConfiguration::Time {
+                target_time: get_next_target_time(
+                    env.block.time,
+                    env.block.time,
+                    vault.time_interval.clone(),
+                ),
+            }
+        );
+    }
+
+    #[test]
+    fn with_retry_trigger_should_execute_swap_and_cache_attempt() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                retry_policy: Some(RetryPolicy {
+                    retry_delay_minutes: 30,
+                    max_attempts: 3,
+                }),
+                trigger: Some(TriggerConfiguration::Retry {
+                    target_time: env.block.time,
+                    attempt: 2,
+                }),
+                ..Vault::default()
+            },
+        );
+
+        let response = execute_trigger_handler(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADMIN, &[]),
+            vault.id,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(get_sent_swap_amount(&response), vault.swap_amount);
+        assert_eq!(
+            RETRY_ATTEMPT_CACHE
+                .load(deps.as_ref().storage, vault.id.into())
+                .unwrap(),
+            2
+        );
+    }
+
+    #[test]
+    fn with_permissionless_execution_should_cache_keeper() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        update_config(
+            deps.as_mut().storage,
+            Config {
+                permissionless_execution: true,
+                keeper_bounty_percent: Decimal::percent(20),
+                ..Config::default()
+            },
+        )
+        .unwrap();
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        execute_trigger_handler(deps.as_mut(), env, mock_info("keeper", &[]), vault.id, None)
+            .unwrap();
+
+        assert_eq!(
+            KEEPER_CACHE
+                .load(deps.as_ref().storage, vault.id.into())
+                .unwrap(),
+            Addr::unchecked("keeper")
+        );
+    }
+
+    #[test]
+    fn with_permissionless_execution_should_not_cache_executor_as_keeper() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        update_config(
+            deps.as_mut().storage,
+            Config {
+                permissionless_execution: true,
+                keeper_bounty_percent: Decimal::percent(20),
+                ..Config::default()
+            },
+        )
+        .unwrap();
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        execute_trigger_handler(
+            deps.as_mut(),
+            env,
+            mock_info("executor", &[]),
+            vault.id,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(
+            KEEPER_CACHE
+                .may_load(deps.as_ref().storage, vault.id.into())
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn without_permissionless_execution_should_not_cache_keeper() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        execute_trigger_handler(deps.as_mut(), env, mock_info("keeper", &[]), vault.id, None)
+            .unwrap();
+
+        assert_eq!(
+            KEEPER_CACHE
+                .may_load(deps.as_ref().storage, vault.id.into())
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn with_keeper_and_stale_trigger_should_fail() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        update_config(
+            deps.as_mut().storage,
+            Config {
+                permissionless_execution: true,
+                ..Config::default()
+            },
+        )
+        .unwrap();
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                trigger: Some(TriggerConfiguration::Time {
+                    target_time: env.block.time.plus_seconds(60),
+                }),
+                ..Vault::default()
+            },
+        );
+
+        let err =
+            execute_trigger_handler(deps.as_mut(), env, mock_info("keeper", &[]), vault.id, None)
+                .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Error: trigger execution time has not yet elapsed"
+        );
+    }
+
+    #[test]
+    fn with_keeper_and_already_executed_trigger_should_fail() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        update_config(
+            deps.as_mut().storage,
+            Config {
+                permissionless_execution: true,
+                ..Config::default()
+            },
+        )
+        .unwrap();
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        execute_trigger_handler(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("keeper", &[]),
+            vault.id,
+            None,
+        )
+        .unwrap();
+
+        let err =
+            execute_trigger_handler(deps.as_mut(), env, mock_info("keeper", &[]), vault.id, None)
+                .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Error: trigger execution time has not yet elapsed"
+        );
+    }
 }
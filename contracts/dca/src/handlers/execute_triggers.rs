@@ -0,0 +1,872 @@
+use crate::constants::{
+    AFTER_BATCHED_AGGREGATED_SWAP_REPLY_ID, AFTER_BATCHED_SETTLEMENT_REPLY_ID,
+    AFTER_BATCHED_TRIGGER_EXECUTION_REPLY_ID,
+};
+use crate::error::ContractError;
+use crate::helpers::aggregation::get_aggregated_swaps;
+use crate::helpers::fees::get_keeper;
+use crate::helpers::message::{get_vault_reply_id, split_vault_reply_id};
+use crate::helpers::netting::get_netted_executions;
+use crate::helpers::validation::{assert_contract_is_not_paused, assert_trigger_batch_is_valid};
+use crate::msg::ExecuteMsg;
+use crate::state::cache::{
+    BATCHED_AGGREGATED_SWAP_CACHE, BATCHED_SETTLEMENT_CACHE, BATCHED_TRIGGER_EXECUTION_CACHE,
+    BATCH_KEEPER_CACHE, RESIDUAL_SWAP_AMOUNT_CACHE,
+};
+use crate::state::events::create_event;
+use crate::state::vaults::get_vault;
+use crate::types::event::{EventBuilder, EventData, ExecutionSkippedReason};
+use cosmwasm_std::{
+    to_json_binary, Binary, DepsMut, Env, MessageInfo, Reply, Response, StdResult, SubMsg,
+    SubMsgResult, Uint128, WasmMsg,
+};
+
+pub fn execute_triggers_handler(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    trigger_ids: Vec<Uint128>,
+    routes: Option<Vec<Option<Binary>>>,
+) -> Result<Response, ContractError> {
+    assert_contract_is_not_paused(deps.storage)?;
+    assert_trigger_batch_is_valid(&trigger_ids, &routes)?;
+
+    let routes = routes.unwrap_or_else(|| vec![None; trigger_ids.len()]);
+
+    let netting_groups = get_netted_executions(deps.as_ref(), &env, &trigger_ids)?;
+    let keeper = get_keeper(deps.storage, &env, &info.sender)?;
+
+    let mut sub_msgs = Vec::<SubMsg>::new();
+
+    for netted_executions in netting_groups.iter() {
+        let batch_id = netted_executions[0].vault_id;
+
+        BATCHED_SETTLEMENT_CACHE.save(
+            deps.storage,
+            batch_id.into(),
+            &netted_executions
+                .iter()
+                .map(|netted_execution| netted_execution.vault_id)
+                .collect::<Vec<Uint128>>(),
+        )?;
+
+        sub_msgs.push(SubMsg::reply_always(
+            WasmMsg::Execute {
+                contract_addr: env.contract.address.to_string(),
+                msg: to_json_binary(&ExecuteMsg::SettleNettedExecutions {
+                    executions: netted_executions.clone(),
+                    keeper: keeper.clone(),
+                })?,
+                funds: vec![],
+            },
+            get_vault_reply_id(AFTER_BATCHED_SETTLEMENT_REPLY_ID, batch_id),
+        ));
+    }
+
+    let netted_executions = netting_groups.concat();
+
+    let (trigger_ids, routes): (Vec<Uint128>, Vec<Option<Binary>>) = trigger_ids
+        .into_iter()
+        .zip(routes)
+        .filter(|(trigger_id, _)| {
+            !netted_executions.iter().any(|netted_execution| {
+                netted_execution.vault_id == *trigger_id
+                    && netted_execution.residual.amount.is_zero()
+            })
+        })
+        .unzip();
+
+    let get_residual_swap_amount = |trigger_id: &Uint128| {
+        netted_executions
+            .iter()
+            .find(|netted_execution| netted_execution.vault_id == *trigger_id)
+            .map(|netted_execution| netted_execution.residual.clone())
+    };
+
+    let aggregated_swaps = get_aggregated_swaps(
+        deps.as_ref(),
+        &env,
+        &trigger_ids
+            .iter()
+            .zip(routes.iter())
+            .filter(|(_, route)| route.is_none())
+            .map(|(trigger_id, _)| {
+                (
+                    *trigger_id,
+                    get_residual_swap_amount(trigger_id).map(|residual| residual.amount),
+                )
+            })
+            .collect::<Vec<(Uint128, Option<Uint128>)>>(),
+    )?;
+
+    for aggregated_swap in aggregated_swaps.iter() {
+        let batch_id = aggregated_swap.vault_ids[0];
+
+        BATCHED_AGGREGATED_SWAP_CACHE.save(
+            deps.storage,
+            batch_id.into(),
+            &aggregated_swap.vault_ids,
+        )?;
+
+        sub_msgs.push(SubMsg::reply_always(
+            WasmMsg::Execute {
+                contract_addr: env.contract.address.to_string(),
+                msg: to_json_binary(&ExecuteMsg::ExecuteAggregatedSwap {
+                    vault_ids: aggregated_swap.vault_ids.clone(),
+                    swap_amounts: aggregated_swap.swap_amounts.clone(),
+                    minimum_receive_amount: aggregated_swap.minimum_receive_amount.clone(),
+                    route: aggregated_swap.route.clone(),
+                    asset_price: aggregated_swap.asset_price,
+                    keeper: keeper.clone(),
+                })?,
+                funds: vec![],
+            },
+            get_vault_reply_id(AFTER_BATCHED_AGGREGATED_SWAP_REPLY_ID, batch_id),
+        ));
+    }
+
+    let (trigger_ids, routes): (Vec<Uint128>, Vec<Option<Binary>>) = trigger_ids
+        .into_iter()
+        .zip(routes)
+        .filter(|(trigger_id, _)| {
+            !aggregated_swaps
+                .iter()
+                .any(|aggregated_swap| aggregated_swap.vault_ids.contains(trigger_id))
+        })
+        .unzip();
+
+    for trigger_id in trigger_ids.iter() {
+        if let Some(residual_swap_amount) = get_residual_swap_amount(trigger_id) {
+            RESIDUAL_SWAP_AMOUNT_CACHE.save(
+                deps.storage,
+                (*trigger_id).into(),
+                &residual_swap_amount,
+            )?;
+        }
+    }
+
+    if !trigger_ids.is_empty() {
+        BATCH_KEEPER_CACHE.save(deps.storage, &info.sender)?;
+
+        let mut pending_trigger_ids = BATCHED_TRIGGER_EXECUTION_CACHE
+            .may_load(deps.storage)?
+            .unwrap_or_default();
+
+        pending_trigger_ids.extend(trigger_ids.iter());
+
+        BATCHED_TRIGGER_EXECUTION_CACHE.save(deps.storage, &pending_trigger_ids)?;
+    }
+
+    let mut trigger_sub_msgs = trigger_ids
+        .iter()
+        .zip(routes)
+        .map(|(trigger_id, route)| {
+            Ok(SubMsg::reply_always(
+                WasmMsg::Execute {
+                    contract_addr: env.contract.address.to_string(),
+                    msg: to_json_binary(&ExecuteMsg::ExecuteTrigger {
+                        trigger_id: *trigger_id,
+                        route,
+                    })?,
+                    funds: vec![],
+                },
+                AFTER_BATCHED_TRIGGER_EXECUTION_REPLY_ID,
+            ))
+        })
+        .collect::<StdResult<Vec<SubMsg>>>()?;
+
+    sub_msgs.append(&mut trigger_sub_msgs);
+
+    Ok(Response::new()
+        .add_attribute("execute_triggers", "true")
+        .add_attribute(
+            "trigger_ids",
+            trigger_ids
+                .iter()
+                .map(|trigger_id| trigger_id.to_string())
+                .collect::<Vec<String>>()
+                .join(","),
+        )
+        .add_attribute(
+            "netted_vault_ids",
+            netted_executions
+                .iter()
+                .map(|netted_execution| netted_execution.vault_id.to_string())
+                .collect::<Vec<String>>()
+                .join(","),
+        )
+        .add_attribute(
+            "aggregated_vault_ids",
+            aggregated_swaps
+                .iter()
+                .flat_map(|aggregated_swap| aggregated_swap.vault_ids.iter())
+                .map(|vault_id| vault_id.to_string())
+                .collect::<Vec<String>>()
+                .join(","),
+        )
+        .add_submessages(sub_msgs))
+}
+
+pub fn handle_batched_trigger_execution_result(
+    deps: DepsMut,
+    env: Env,
+    reply: Reply,
+) -> Result<Response, ContractError> {
+    let mut pending_trigger_ids = BATCHED_TRIGGER_EXECUTION_CACHE.load(deps.storage)?;
+
+    let vault_id = pending_trigger_ids
+        .pop_front()
+        .ok_or(ContractError::CustomError {
+            val: String::from("no pending batched trigger executions"),
+        })?;
+
+    if pending_trigger_ids.is_empty() {
+        BATCHED_TRIGGER_EXECUTION_CACHE.remove(deps.storage);
+        BATCH_KEEPER_CACHE.remove(deps.storage);
+    } else {
+        BATCHED_TRIGGER_EXECUTION_CACHE.save(deps.storage, &pending_trigger_ids)?;
+    }
+
+    RESIDUAL_SWAP_AMOUNT_CACHE.remove(deps.storage, vault_id.into());
+
+    let response = Response::new()
+        .add_attribute("batched_trigger_execution", "true")
+        .add_attribute("vault_id", vault_id);
+
+    match reply.result {
+        SubMsgResult::Ok(_) => Ok(response.add_attribute("execution_result", "success")),
+        SubMsgResult::Err(msg) => {
+            save_execution_skipped_event(deps, &env, vault_id, &msg)?;
+
+            Ok(response.add_attribute("execution_result", format!("failure: {}", msg)))
+        }
+    }
+}
+
+pub fn handle_batched_settlement_result(
+    mut deps: DepsMut,
+    env: Env,
+    reply: Reply,
+) -> Result<Response, ContractError> {
+    let (_, batch_id) = split_vault_reply_id(reply.id);
+
+    let vault_ids = BATCHED_SETTLEMENT_CACHE.load(deps.storage, batch_id.into())?;
+    BATCHED_SETTLEMENT_CACHE.remove(deps.storage, batch_id.into());
+
+    let response = Response::new()
+        .add_attribute("batched_settlement", "true")
+        .add_attribute(
+            "vault_ids",
+            vault_ids
+                .iter()
+                .map(|vault_id| vault_id.to_string())
+                .collect::<Vec<String>>()
+                .join(","),
+        );
+
+    match reply.result {
+        SubMsgResult::Ok(_) => Ok(response.add_attribute("execution_result", "success")),
+        SubMsgResult::Err(msg) => {
+            for vault_id in vault_ids {
+                save_execution_skipped_event(deps.branch(), &env, vault_id, &msg)?;
+            }
+
+            Ok(response.add_attribute("execution_result", format!("failure: {}", msg)))
+        }
+    }
+}
+
+pub fn handle_batched_aggregated_swap_result(
+    mut deps: DepsMut,
+    env: Env,
+    reply: Reply,
+) -> Result<Response, ContractError> {
+    let (_, batch_id) = split_vault_reply_id(reply.id);
+
+    let vault_ids = BATCHED_AGGREGATED_SWAP_CACHE.load(deps.storage, batch_id.into())?;
+    BATCHED_AGGREGATED_SWAP_CACHE.remove(deps.storage, batch_id.into());
+
+    let response = Response::new()
+        .add_attribute("batched_aggregated_swap", "true")
+        .add_attribute(
+            "vault_ids",
+            vault_ids
+                .iter()
+                .map(|vault_id| vault_id.to_string())
+                .collect::<Vec<String>>()
+                .join(","),
+        );
+
+    match reply.result {
+        SubMsgResult::Ok(_) => Ok(response.add_attribute("execution_result", "success")),
+        SubMsgResult::Err(msg) => {
+            for vault_id in vault_ids {
+                save_execution_skipped_event(deps.branch(), &env, vault_id, &msg)?;
+            }
+
+            Ok(response.add_attribute("execution_result", format!("failure: {}", msg)))
+        }
+    }
+}
+
+fn save_execution_skipped_event(
+    deps: DepsMut,
+    env: &Env,
+    vault_id: Uint128,
+    msg: &str,
+) -> Result<(), ContractError> {
+    if get_vault(deps.storage, vault_id).is_ok() {
+        create_event(
+            deps.storage,
+            EventBuilder::new(
+                vault_id,
+                env.block.clone(),
+                EventData::DcaVaultExecutionSkipped {
+                    reason: ExecutionSkippedReason::UnknownError {
+                        msg: msg.to_string(),
+                    },
+                },
+            ),
+        )?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod execute_triggers_tests {
+    use super::*;
+    use crate::{
+        constants::{ONE, TEN},
+        handlers::get_events_by_resource_id::get_events_by_resource_id_handler,
+        state::config::update_config,
+        tests::{
+            helpers::{instantiate_contract, setup_vault},
+            mocks::{calc_mock_dependencies, ADMIN, DENOM_UKUJI, DENOM_UUSK},
+        },
+        types::{config::Config, netted_execution::NettedExecution, vault::Vault},
+    };
+    use cosmwasm_std::{
+        testing::{mock_dependencies, mock_env, mock_info},
+        Coin, Decimal, SubMsgResponse,
+    };
+
+    #[test]
+    fn when_contract_is_paused_should_fail() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        update_config(
+            deps.as_mut().storage,
+            Config {
+                paused: true,
+                ..Config::default()
+            },
+        )
+        .unwrap();
+
+        let err = execute_triggers_handler(
+            deps.as_mut(),
+            env,
+            mock_info(ADMIN, &[]),
+            vec![Uint128::one()],
+            None,
+        )
+        .unwrap_err();
+
+        assert_eq!(err.to_string(), "Error: contract is paused");
+    }
+
+    #[test]
+    fn with_no_trigger_ids_should_fail() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let err = execute_triggers_handler(deps.as_mut(), env, mock_info(ADMIN, &[]), vec![], None)
+            .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Error: at least one trigger id must be provided"
+        );
+    }
+
+    #[test]
+    fn with_mismatched_routes_should_fail() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let err = execute_triggers_handler(
+            deps.as_mut(),
+            env,
+            mock_info(ADMIN, &[]),
+            vec![Uint128::one(), Uint128::new(2)],
+            Some(vec![None]),
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Error: a route must be provided for every trigger id"
+        );
+    }
+
+    #[test]
+    fn with_duplicate_trigger_ids_should_fail() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let err = execute_triggers_handler(
+            deps.as_mut(),
+            env,
+            mock_info(ADMIN, &[]),
+            vec![Uint128::one(), Uint128::one()],
+            None,
+        )
+        .unwrap_err();
+
+        assert_eq!(err.to_string(), "Error: trigger ids must be unique");
+    }
+
+    #[test]
+    fn should_execute_each_trigger_in_its_own_sub_message() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let route = Some(to_json_binary(&vec![1, 2]).unwrap());
+
+        let response = execute_triggers_handler(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADMIN, &[]),
+            vec![Uint128::one(), Uint128::new(2)],
+            Some(vec![route.clone(), None]),
+        )
+        .unwrap();
+
+        assert_eq!(
+            response.messages,
+            vec![
+                SubMsg::reply_always(
+                    WasmMsg::Execute {
+                        contract_addr: env.contract.address.to_string(),
+                        msg: to_json_binary(&ExecuteMsg::ExecuteTrigger {
+                            trigger_id: Uint128::one(),
+                            route,
+                        })
+                        .unwrap(),
+                        funds: vec![],
+                    },
+                    AFTER_BATCHED_TRIGGER_EXECUTION_REPLY_ID,
+                ),
+                SubMsg::reply_always(
+                    WasmMsg::Execute {
+                        contract_addr: env.contract.address.to_string(),
+                        msg: to_json_binary(&ExecuteMsg::ExecuteTrigger {
+                            trigger_id: Uint128::new(2),
+                            route: None,
+                        })
+                        .unwrap(),
+                        funds: vec![],
+                    },
+                    AFTER_BATCHED_TRIGGER_EXECUTION_REPLY_ID,
+                ),
+            ]
+        );
+        assert_eq!(
+            BATCHED_TRIGGER_EXECUTION_CACHE
+                .load(deps.as_ref().storage)
+                .unwrap(),
+            vec![Uint128::one(), Uint128::new(2)]
+        );
+    }
+
+    #[test]
+    fn with_failed_execution_should_publish_skipped_event_for_failing_vault_only() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let first_vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+        let second_vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        execute_triggers_handler(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADMIN, &[]),
+            vec![first_vault.id, second_vault.id],
+            None,
+        )
+        .unwrap();
+
+        handle_batched_trigger_execution_result(
+            deps.as_mut(),
+            env.clone(),
+            Reply {
+                id: AFTER_BATCHED_TRIGGER_EXECUTION_REPLY_ID,
+                result: SubMsgResult::Ok(SubMsgResponse {
+                    events: vec![],
+                    data: None,
+                }),
+            },
+        )
+        .unwrap();
+
+        handle_batched_trigger_execution_result(
+            deps.as_mut(),
+            env.clone(),
+            Reply {
+                id: AFTER_BATCHED_TRIGGER_EXECUTION_REPLY_ID,
+                result: SubMsgResult::Err("trigger execution time has not yet elapsed".to_string()),
+            },
+        )
+        .unwrap();
+
+        let first_vault_events =
+            get_events_by_resource_id_handler(deps.as_ref(), first_vault.id, None, None, None)
+                .unwrap()
+                .events;
+
+        let second_vault_events =
+            get_events_by_resource_id_handler(deps.as_ref(), second_vault.id, None, None, None)
+                .unwrap()
+                .events;
+
+        assert!(first_vault_events.is_empty());
+        assert!(second_vault_events.iter().any(|event| event.data
+            == EventData::DcaVaultExecutionSkipped {
+                reason: ExecutionSkippedReason::UnknownError {
+                    msg: "trigger execution time has not yet elapsed".to_string()
+                }
+            }));
+        assert_eq!(
+            BATCHED_TRIGGER_EXECUTION_CACHE
+                .may_load(deps.as_ref().storage)
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn with_fully_opposing_vaults_should_settle_internally_without_swapping() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        let opposing_vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                balance: Coin::new(TEN.into(), DENOM_UUSK),
+                target_denom: DENOM_UKUJI.to_string(),
+                ..Vault::default()
+            },
+        );
+
+        let response = execute_triggers_handler(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADMIN, &[]),
+            vec![vault.id, opposing_vault.id],
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(
+            response.messages,
+            vec![SubMsg::reply_always(
+                WasmMsg::Execute {
+                    contract_addr: env.contract.address.to_string(),
+                    msg: to_json_binary(&ExecuteMsg::SettleNettedExecutions {
+                        executions: vec![
+                            NettedExecution {
+                                vault_id: vault.id,
+                                sent: Coin::new(ONE.into(), DENOM_UKUJI),
+                                received: Coin::new(ONE.into(), DENOM_UUSK),
+                                residual: Coin::new(0, DENOM_UKUJI),
+                                asset_price: Decimal::one(),
+                            },
+                            NettedExecution {
+                                vault_id: opposing_vault.id,
+                                sent: Coin::new(ONE.into(), DENOM_UUSK),
+                                received: Coin::new(ONE.into(), DENOM_UKUJI),
+                                residual: Coin::new(0, DENOM_UUSK),
+                                asset_price: Decimal::one(),
+                            },
+                        ],
+                        keeper: None,
+                    })
+                    .unwrap(),
+                    funds: vec![],
+                },
+                get_vault_reply_id(AFTER_BATCHED_SETTLEMENT_REPLY_ID, vault.id),
+            )]
+        );
+        assert_eq!(
+            BATCHED_SETTLEMENT_CACHE
+                .load(deps.as_ref().storage, vault.id.into())
+                .unwrap(),
+            vec![vault.id, opposing_vault.id]
+        );
+        assert!(BATCHED_TRIGGER_EXECUTION_CACHE
+            .may_load(deps.as_ref().storage)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn with_partially_opposing_vaults_should_only_swap_the_residual() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        let opposing_vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                balance: Coin::new(TEN.into(), DENOM_UUSK),
+                target_denom: DENOM_UKUJI.to_string(),
+                swap_amount: ONE / Uint128::new(4),
+                ..Vault::default()
+            },
+        );
+
+        let response = execute_triggers_handler(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADMIN, &[]),
+            vec![vault.id, opposing_vault.id],
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(response.messages.len(), 2);
+        assert_eq!(
+            response.messages[1],
+            SubMsg::reply_always(
+                WasmMsg::Execute {
+                    contract_addr: env.contract.address.to_string(),
+                    msg: to_json_binary(&ExecuteMsg::ExecuteTrigger {
+                        trigger_id: vault.id,
+                        route: None,
+                    })
+                    .unwrap(),
+                    funds: vec![],
+                },
+                AFTER_BATCHED_TRIGGER_EXECUTION_REPLY_ID,
+            )
+        );
+        assert_eq!(
+            RESIDUAL_SWAP_AMOUNT_CACHE
+                .load(deps.as_ref().storage, vault.id.into())
+                .unwrap(),
+            Coin::new((ONE - ONE / Uint128::new(4)).into(), DENOM_UKUJI)
+        );
+        assert_eq!(
+            BATCHED_TRIGGER_EXECUTION_CACHE
+                .load(deps.as_ref().storage)
+                .unwrap(),
+            vec![vault.id]
+        );
+    }
+
+    #[test]
+    fn with_vaults_sharing_pair_and_route_should_aggregate_into_single_swap() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+        let other_vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        let response = execute_triggers_handler(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADMIN, &[]),
+            vec![vault.id, other_vault.id],
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(
+            response.messages,
+            vec![SubMsg::reply_always(
+                WasmMsg::Execute {
+                    contract_addr: env.contract.address.to_string(),
+                    msg: to_json_binary(&ExecuteMsg::ExecuteAggregatedSwap {
+                        vault_ids: vec![vault.id, other_vault.id],
+                        swap_amounts: vec![ONE, ONE],
+                        minimum_receive_amount: Coin::new(0, DENOM_UUSK),
+                        route: vault.route,
+                        asset_price: Decimal::one(),
+                        keeper: None,
+                    })
+                    .unwrap(),
+                    funds: vec![],
+                },
+                get_vault_reply_id(AFTER_BATCHED_AGGREGATED_SWAP_REPLY_ID, vault.id),
+            )]
+        );
+        assert_eq!(
+            BATCHED_AGGREGATED_SWAP_CACHE
+                .load(deps.as_ref().storage, vault.id.into())
+                .unwrap(),
+            vec![vault.id, other_vault.id]
+        );
+        assert!(BATCHED_TRIGGER_EXECUTION_CACHE
+            .may_load(deps.as_ref().storage)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn with_route_override_should_not_aggregate_vault() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+        let other_vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        let response = execute_triggers_handler(
+            deps.as_mut(),
+            env,
+            mock_info(ADMIN, &[]),
+            vec![vault.id, other_vault.id],
+            Some(vec![vault.route.clone(), None]),
+        )
+        .unwrap();
+
+        assert_eq!(response.messages.len(), 2);
+        assert!(response
+            .messages
+            .iter()
+            .all(|msg| msg.id == AFTER_BATCHED_TRIGGER_EXECUTION_REPLY_ID));
+    }
+
+    #[test]
+    fn with_failed_settlement_leg_should_publish_skipped_event_for_both_legs() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+        let opposing_vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                balance: Coin::new(TEN.into(), DENOM_UUSK),
+                target_denom: DENOM_UKUJI.to_string(),
+                ..Vault::default()
+            },
+        );
+
+        BATCHED_SETTLEMENT_CACHE
+            .save(
+                deps.as_mut().storage,
+                vault.id.into(),
+                &vec![vault.id, opposing_vault.id],
+            )
+            .unwrap();
+
+        handle_batched_settlement_result(
+            deps.as_mut(),
+            env,
+            Reply {
+                id: get_vault_reply_id(AFTER_BATCHED_SETTLEMENT_REPLY_ID, vault.id),
+                result: SubMsgResult::Err("settlement failed".to_string()),
+            },
+        )
+        .unwrap();
+
+        for vault_id in [vault.id, opposing_vault.id] {
+            let events =
+                get_events_by_resource_id_handler(deps.as_ref(), vault_id, None, None, None)
+                    .unwrap()
+                    .events;
+
+            assert_eq!(
+                events.last().unwrap().data,
+                EventData::DcaVaultExecutionSkipped {
+                    reason: ExecutionSkippedReason::UnknownError {
+                        msg: "settlement failed".to_string()
+                    }
+                }
+            );
+        }
+        assert!(BATCHED_SETTLEMENT_CACHE
+            .may_load(deps.as_ref().storage, vault.id.into())
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn with_failed_aggregated_swap_should_publish_skipped_event_for_each_vault() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+        let other_vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        BATCHED_AGGREGATED_SWAP_CACHE
+            .save(
+                deps.as_mut().storage,
+                vault.id.into(),
+                &vec![vault.id, other_vault.id],
+            )
+            .unwrap();
+
+        handle_batched_aggregated_swap_result(
+            deps.as_mut(),
+            env,
+            Reply {
+                id: get_vault_reply_id(AFTER_BATCHED_AGGREGATED_SWAP_REPLY_ID, vault.id),
+                result: SubMsgResult::Err("swap failed".to_string()),
+            },
+        )
+        .unwrap();
+
+        for vault_id in [vault.id, other_vault.id] {
+            let events =
+                get_events_by_resource_id_handler(deps.as_ref(), vault_id, None, None, None)
+                    .unwrap()
+                    .events;
+
+            assert_eq!(
+                events.last().unwrap().data,
+                EventData::DcaVaultExecutionSkipped {
+                    reason: ExecutionSkippedReason::UnknownError {
+                        msg: "swap failed".to_string()
+                    }
+                }
+            );
+        }
+
+        assert!(BATCHED_AGGREGATED_SWAP_CACHE
+            .may_load(deps.as_ref().storage, vault.id.into())
+            .unwrap()
+            .is_none());
+    }
+}
@@ -1,14 +1,98 @@
 use crate::{
-    msg::DisburseEscrowTasksResponse, state::disburse_escrow_tasks::get_disburse_escrow_tasks,
+    msg::{DisburseEscrowTask, DisburseEscrowTasksResponse},
+    state::{
+        disburse_escrow_tasks::{get_disburse_escrow_task_due_date, get_disburse_escrow_tasks},
+        vaults::get_vault,
+    },
 };
-use cosmwasm_std::{Deps, Env, StdResult};
+use cosmwasm_std::{Deps, Env, StdError, StdResult};
 
 pub fn get_disburse_escrow_tasks_handler(
     deps: Deps,
     env: Env,
     limit: Option<u16>,
 ) -> StdResult<DisburseEscrowTasksResponse> {
-    let tasks = get_disburse_escrow_tasks(deps.storage, env.block.time, limit)?;
+    let vault_ids = get_disburse_escrow_tasks(deps.storage, env.block.time, limit)?;
 
-    Ok(DisburseEscrowTasksResponse { vault_ids: tasks })
+    let tasks = vault_ids
+        .iter()
+        .map(|vault_id| {
+            let vault = get_vault(deps.storage, *vault_id)?;
+
+            Ok(DisburseEscrowTask {
+                vault_id: *vault_id,
+                tranche: vault
+                    .escrow_vesting
+                    .map_or(0, |escrow_vesting| escrow_vesting.released_tranches),
+                due_date: get_disburse_escrow_task_due_date(deps.storage, *vault_id)?.ok_or(
+                    StdError::generic_err(format!(
+                        "no escrow disbursement task found for vault {}",
+                        vault_id
+                    )),
+                )?,
+            })
+        })
+        .collect::<StdResult<Vec<DisburseEscrowTask>>>()?;
+
+    Ok(DisburseEscrowTasksResponse { vault_ids, tasks })
+}
+
+#[cfg(test)]
+mod get_disburse_escrow_tasks_tests {
+    use super::get_disburse_escrow_tasks_handler;
+    use crate::{
+        msg::DisburseEscrowTask,
+        state::disburse_escrow_tasks::save_disburse_escrow_task,
+        tests::{
+            helpers::{instantiate_contract, setup_vault},
+            mocks::ADMIN,
+        },
+        types::{
+            escrow_vesting::{EscrowVesting, EscrowVestingSchedule},
+            vault::Vault,
+        },
+    };
+    use cosmwasm_std::{
+        testing::{mock_dependencies, mock_env, mock_info},
+        Timestamp,
+    };
+
+    #[test]
+    fn returns_next_tranche_of_due_tasks() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                escrow_vesting: Some(EscrowVesting {
+                    schedule: EscrowVestingSchedule::Linear {
+                        tranches: 3,
+                        interval_seconds: 100,
+                    },
+                    released_tranches: 2,
+                }),
+                ..Vault::default()
+            },
+        );
+
+        let due_date = Timestamp::from_seconds(env.block.time.seconds() - 10);
+
+        save_disburse_escrow_task(deps.as_mut().storage, vault.id, due_date).unwrap();
+
+        let response = get_disburse_escrow_tasks_handler(deps.as_ref(), env, None).unwrap();
+
+        assert_eq!(response.vault_ids, vec![vault.id]);
+        assert_eq!(
+            response.tasks,
+            vec![DisburseEscrowTask {
+                vault_id: vault.id,
+                tranche: 2,
+                due_date,
+            }]
+        );
+    }
 }
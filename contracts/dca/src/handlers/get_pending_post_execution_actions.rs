@@ -0,0 +1,16 @@
+use crate::{
+    msg::PendingPostExecutionActionsResponse,
+    state::post_execution_action_retries::get_pending_post_execution_actions,
+};
+use cosmwasm_std::{Deps, StdResult, Uint128};
+
+pub fn get_pending_post_execution_actions_handler(
+    deps: Deps,
+    vault_id: Uint128,
+) -> StdResult<PendingPostExecutionActionsResponse> {
+    let actions = get_pending_post_execution_actions(deps.storage, vault_id)?;
+
+    Ok(PendingPostExecutionActionsResponse {
+        actions: actions.into_iter().collect(),
+    })
+}
@@ -0,0 +1,12 @@
+use crate::{
+    msg::ReferralFeesResponse,
+    state::referrals::{get_referral_fee_caps, get_referral_fees},
+};
+use cosmwasm_std::{Addr, Deps, StdResult};
+
+pub fn get_referral_fees_handler(deps: Deps, referrer: Addr) -> StdResult<ReferralFeesResponse> {
+    Ok(ReferralFeesResponse {
+        fees: get_referral_fees(deps.storage, &referrer)?,
+        caps: get_referral_fee_caps(deps.storage, &referrer)?,
+    })
+}
@@ -2,6 +2,7 @@ use crate::{
     helpers::{fees::get_performance_fee, price::get_twap_to_now, vault::get_performance_factor},
     msg::VaultPerformanceResponse,
     state::{config::get_config, vaults::get_vault},
+    types::performance_assessment_strategy::PerformanceAssessmentStrategy,
 };
 use cosmwasm_std::{Deps, StdError, StdResult, Uint128};
 
@@ -31,8 +32,12 @@ pub fn get_vault_performance_handler(
         }),
         |_| {
             Ok(VaultPerformanceResponse {
-                fee: get_performance_fee(&vault, current_price)?,
+                fee: get_performance_fee(&config, &vault, current_price)?,
                 factor: get_performance_factor(&vault, current_price)?,
+                high_water_mark: match vault.performance_assessment_strategy {
+                    Some(PerformanceAssessmentStrategy::HighWaterMark { mark }) => Some(mark),
+                    _ => None,
+                },
             })
         },
     )
@@ -115,4 +120,34 @@ mod get_vault_performance_tests {
             )
         );
     }
+
+    #[test]
+    fn reports_high_water_mark() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env,
+            Vault {
+                deposited_amount: Coin::new(TEN.into(), DENOM_UKUJI),
+                swapped_amount: Coin::new(TEN.into(), DENOM_UKUJI),
+                received_amount: Coin::new((TEN + TEN).into(), DENOM_UUSK),
+                escrowed_amount: Coin::new(TEN.into(), DENOM_UUSK),
+                performance_assessment_strategy: Some(
+                    PerformanceAssessmentStrategy::HighWaterMark {
+                        mark: Decimal::percent(160),
+                    },
+                ),
+                ..Vault::default()
+            },
+        );
+
+        let response = get_vault_performance_handler(deps.as_ref(), vault.id).unwrap();
+
+        assert_eq!(response.high_water_mark, Some(Decimal::percent(160)));
+        assert_eq!(response.factor, Decimal::percent(125));
+    }
 }
@@ -1,8 +1,12 @@
 use crate::{
     error::ContractError,
+    helpers::{disbursement::save_sent_ibc_transfer, message::split_vault_reply_id},
     state::{
-        cache::{POST_EXECUTION_ACTION_CACHE, VAULT_ID_CACHE},
+        cache::POST_EXECUTION_ACTION_CACHE,
         events::create_event,
+        post_execution_action_retries::{
+            push_pending_post_execution_action, PendingPostExecutionAction,
+        },
         vaults::get_vault,
     },
     types::event::{EventBuilder, EventData},
@@ -15,7 +19,7 @@ pub fn handle_failed_automation_handler(
     env: Env,
     reply: Reply,
 ) -> Result<Response, ContractError> {
-    let vault_id = VAULT_ID_CACHE.load(deps.storage)?;
+    let (_, vault_id) = split_vault_reply_id(reply.id);
     let vault = get_vault(deps.storage, vault_id)?;
 
     let mut cache = POST_EXECUTION_ACTION_CACHE.load(deps.storage, vault_id.into())?;
@@ -25,26 +29,68 @@ pub fn handle_failed_automation_handler(
     let destination_num = vault.destinations.len() - cache.len();
 
     Ok(match reply.result {
-        SubMsgResult::Ok(_) => Response::new()
-            .add_attribute(format!("destination_msg_{}", destination_num), "succeeded"),
+        SubMsgResult::Ok(sub_msg_response) => {
+            let mut response = Response::new()
+                .add_attribute(format!("destination_msg_{}", destination_num), "succeeded");
+
+            if let Some(sequence) = save_sent_ibc_transfer(
+                deps.storage,
+                vault_id,
+                &entry.msg.msg,
+                &sub_msg_response.events,
+                entry.fallback_address,
+            )? {
+                response = response.add_attribute(
+                    format!("destination_msg_{}_ibc_sequence", destination_num),
+                    sequence.to_string(),
+                );
+            }
+
+            response
+        }
         SubMsgResult::Err(_) => {
             create_event(
                 deps.storage,
                 EventBuilder::new(
                     vault_id,
-                    env.block,
+                    env.block.clone(),
                     EventData::DcaVaultPostExecutionActionFailed {
-                        msg: entry.msg,
+                        msg: entry.msg.clone(),
                         funds: entry.funds.clone(),
                     },
                 ),
             )?;
 
-            Response::new()
-                .add_attribute(format!("destination_msg_{}", destination_num), "failed")
+            let response = Response::new()
+                .add_attribute(format!("destination_msg_{}", destination_num), "failed");
+
+            if vault.retry_failed_post_execution_actions {
+                push_pending_post_execution_action(
+                    deps.storage,
+                    vault_id,
+                    PendingPostExecutionAction {
+                        msg: entry.msg,
+                        funds: entry.funds,
+                        fallback_address: entry.fallback_address,
+                        attempts: 0,
+                        failed_at: env.block.time,
+                    },
+                )?;
+
+                return Ok(response.add_attribute(
+                    format!("destination_msg_{}_queued_for_retry", destination_num),
+                    "true",
+                ));
+            }
+
+            response
+                .add_attribute(
+                    format!("destination_msg_{}_refunded_to", destination_num),
+                    entry.fallback_address.to_string(),
+                )
                 .add_submessage(SubMsg::new(into_bank_msg(
                     deps.api,
-                    vault.owner.as_ref(),
+                    entry.fallback_address.as_ref(),
                     entry.funds,
                 )?))
         }
@@ -57,22 +103,28 @@ mod handle_failed_automation_handler_tests {
     use crate::{
         constants::AFTER_FAILED_AUTOMATION_REPLY_ID,
         handlers::get_events_by_resource_id::get_events_by_resource_id_handler,
-        helpers::disbursement::get_disbursement_messages,
-        state::cache::{PostExecutionActionCacheEntry, POST_EXECUTION_ACTION_CACHE},
+        helpers::{disbursement::get_disbursement_messages, message::get_vault_reply_id},
+        state::{
+            cache::{PostExecutionActionCacheEntry, POST_EXECUTION_ACTION_CACHE},
+            ibc_transfers::{get_ibc_transfer, IbcTransfer},
+            post_execution_action_retries::{
+                get_pending_post_execution_actions, PendingPostExecutionAction,
+            },
+        },
         tests::{
             helpers::{instantiate_contract, setup_vault},
             mocks::ADMIN,
         },
         types::{
-            destination::Destination,
+            destination::{Destination, DestinationAction},
             event::{EventBuilder, EventData},
             vault::Vault,
         },
     };
     use cosmwasm_std::{
         testing::{mock_dependencies, mock_env, mock_info},
-        to_json_binary, Addr, BankMsg, Coin, Decimal, Reply, SubMsg, SubMsgResponse, SubMsgResult,
-        WasmMsg,
+        to_json_binary, Addr, BankMsg, Coin, Decimal, Event, Reply, SubMsg, SubMsgResponse,
+        SubMsgResult, WasmMsg,
     };
     use std::collections::VecDeque;
 
@@ -85,19 +137,19 @@ mod handle_failed_automation_handler_tests {
             Destination {
                 address: Addr::unchecked("owner"),
                 allocation: Decimal::percent(30),
-                msg: None,
+                action: DestinationAction::Send,
             },
             Destination {
                 address: Addr::unchecked("contract"),
                 allocation: Decimal::percent(80),
-                msg: Some(
-                    to_json_binary(&WasmMsg::Execute {
+                action: DestinationAction::ContractCall {
+                    msg: to_json_binary(&WasmMsg::Execute {
                         contract_addr: "contract".to_string(),
                         msg: to_json_binary("test").unwrap(),
                         funds: vec![],
                     })
                     .unwrap(),
-                ),
+                },
             },
         ];
 
@@ -113,8 +165,9 @@ mod handle_failed_automation_handler_tests {
         get_disbursement_messages(
             &deps.api.clone(),
             deps.as_mut().storage,
+            &env,
             &vault,
-            vault.swap_amount,
+            Coin::new(vault.swap_amount.into(), vault.target_denom.clone()),
         )
         .unwrap();
 
@@ -141,7 +194,12 @@ mod handle_failed_automation_handler_tests {
                 msg: SubMsg::reply_always(
                     WasmMsg::Execute {
                         contract_addr: destinations[1].address.to_string(),
-                        msg: destinations[1].msg.clone().unwrap(),
+                        msg: to_json_binary(&WasmMsg::Execute {
+                            contract_addr: "contract".to_string(),
+                            msg: to_json_binary("test").unwrap(),
+                            funds: vec![],
+                        })
+                        .unwrap(),
                         funds: vec![Coin::new(
                             (vault.swap_amount * destinations[1].allocation).into(),
                             vault.target_denom.clone()
@@ -153,6 +211,7 @@ mod handle_failed_automation_handler_tests {
                     (vault.swap_amount * destinations[1].allocation).into(),
                     vault.target_denom
                 )],
+                fallback_address: vault.owner.clone(),
             }])
         );
     }
@@ -166,19 +225,19 @@ mod handle_failed_automation_handler_tests {
             Destination {
                 address: Addr::unchecked("owner"),
                 allocation: Decimal::percent(30),
-                msg: None,
+                action: DestinationAction::Send,
             },
             Destination {
                 address: Addr::unchecked("contract"),
                 allocation: Decimal::percent(80),
-                msg: Some(
-                    to_json_binary(&WasmMsg::Execute {
+                action: DestinationAction::ContractCall {
+                    msg: to_json_binary(&WasmMsg::Execute {
                         contract_addr: "contract".to_string(),
                         msg: to_json_binary("test").unwrap(),
                         funds: vec![],
                     })
                     .unwrap(),
-                ),
+                },
             },
         ];
 
@@ -194,8 +253,9 @@ mod handle_failed_automation_handler_tests {
         get_disbursement_messages(
             &deps.api.clone(),
             deps.as_mut().storage,
+            &env,
             &vault,
-            vault.swap_amount,
+            Coin::new(vault.swap_amount.into(), vault.target_denom.clone()),
         )
         .unwrap();
 
@@ -224,19 +284,19 @@ mod handle_failed_automation_handler_tests {
             Destination {
                 address: Addr::unchecked("owner"),
                 allocation: Decimal::percent(30),
-                msg: None,
+                action: DestinationAction::Send,
             },
             Destination {
                 address: Addr::unchecked("contract"),
                 allocation: Decimal::percent(80),
-                msg: Some(
-                    to_json_binary(&WasmMsg::Execute {
+                action: DestinationAction::ContractCall {
+                    msg: to_json_binary(&WasmMsg::Execute {
                         contract_addr: "contract".to_string(),
                         msg: to_json_binary("test").unwrap(),
                         funds: vec![],
                     })
                     .unwrap(),
-                ),
+                },
             },
         ];
 
@@ -252,8 +312,9 @@ mod handle_failed_automation_handler_tests {
         get_disbursement_messages(
             &deps.api.clone(),
             deps.as_mut().storage,
+            &env,
             &vault,
-            vault.swap_amount,
+            Coin::new(vault.swap_amount.into(), vault.target_denom.clone()),
         )
         .unwrap();
 
@@ -277,7 +338,12 @@ mod handle_failed_automation_handler_tests {
                 msg: SubMsg::reply_always(
                     WasmMsg::Execute {
                         contract_addr: destinations[1].address.to_string(),
-                        msg: destinations[1].msg.clone().unwrap(),
+                        msg: to_json_binary(&WasmMsg::Execute {
+                            contract_addr: "contract".to_string(),
+                            msg: to_json_binary("test").unwrap(),
+                            funds: vec![],
+                        })
+                        .unwrap(),
                         funds: vec![Coin::new(
                             (vault.swap_amount * destinations[1].allocation).into(),
                             vault.target_denom.clone()
@@ -289,6 +355,7 @@ mod handle_failed_automation_handler_tests {
                     (vault.swap_amount * destinations[1].allocation).into(),
                     vault.target_denom
                 )],
+                fallback_address: vault.owner.clone(),
             }])
         );
     }
@@ -304,26 +371,26 @@ mod handle_failed_automation_handler_tests {
             Destination {
                 address: Addr::unchecked("contract1"),
                 allocation: Decimal::percent(30),
-                msg: Some(
-                    to_json_binary(&WasmMsg::Execute {
+                action: DestinationAction::ContractCall {
+                    msg: to_json_binary(&WasmMsg::Execute {
                         contract_addr: "contract2".to_string(),
                         msg: to_json_binary("test").unwrap(),
                         funds: vec![],
                     })
                     .unwrap(),
-                ),
+                },
             },
             Destination {
                 address: Addr::unchecked("contract2"),
                 allocation: Decimal::percent(80),
-                msg: Some(
-                    to_json_binary(&WasmMsg::Execute {
+                action: DestinationAction::ContractCall {
+                    msg: to_json_binary(&WasmMsg::Execute {
                         contract_addr: "contract2".to_string(),
                         msg: to_json_binary("test").unwrap(),
                         funds: vec![],
                     })
                     .unwrap(),
-                ),
+                },
             },
         ];
 
@@ -339,8 +406,9 @@ mod handle_failed_automation_handler_tests {
         get_disbursement_messages(
             &deps.api.clone(),
             deps.as_mut().storage,
+            &env,
             &vault,
-            vault.swap_amount,
+            Coin::new(vault.swap_amount.into(), vault.target_denom.clone()),
         )
         .unwrap();
 
@@ -367,7 +435,12 @@ mod handle_failed_automation_handler_tests {
                     msg: SubMsg::reply_always(
                         WasmMsg::Execute {
                             contract_addr: destinations[0].address.to_string(),
-                            msg: destinations[0].msg.clone().unwrap(),
+                            msg: to_json_binary(&WasmMsg::Execute {
+                                contract_addr: "contract2".to_string(),
+                                msg: to_json_binary("test").unwrap(),
+                                funds: vec![],
+                            })
+                            .unwrap(),
                             funds: vec![Coin::new(
                                 (vault.swap_amount * destinations[0].allocation).into(),
                                 vault.target_denom.clone()
@@ -396,26 +469,26 @@ mod handle_failed_automation_handler_tests {
             Destination {
                 address: Addr::unchecked("contract1"),
                 allocation: Decimal::percent(30),
-                msg: Some(
-                    to_json_binary(&WasmMsg::Execute {
+                action: DestinationAction::ContractCall {
+                    msg: to_json_binary(&WasmMsg::Execute {
                         contract_addr: "contract2".to_string(),
                         msg: to_json_binary("test").unwrap(),
                         funds: vec![],
                     })
                     .unwrap(),
-                ),
+                },
             },
             Destination {
                 address: Addr::unchecked("contract2"),
                 allocation: Decimal::percent(80),
-                msg: Some(
-                    to_json_binary(&WasmMsg::Execute {
+                action: DestinationAction::ContractCall {
+                    msg: to_json_binary(&WasmMsg::Execute {
                         contract_addr: "contract2".to_string(),
                         msg: to_json_binary("test").unwrap(),
                         funds: vec![],
                     })
                     .unwrap(),
-                ),
+                },
             },
         ];
 
@@ -431,8 +504,9 @@ mod handle_failed_automation_handler_tests {
         get_disbursement_messages(
             &deps.api.clone(),
             deps.as_mut().storage,
+            &env,
             &vault,
-            vault.swap_amount,
+            Coin::new(vault.swap_amount.into(), vault.target_denom.clone()),
         )
         .unwrap();
 
@@ -457,4 +531,221 @@ mod handle_failed_automation_handler_tests {
             })]
         );
     }
+
+    #[test]
+    fn sends_funds_to_destination_address_on_failed_delegation() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let destinations = vec![Destination {
+            address: Addr::unchecked("delegator"),
+            allocation: Decimal::percent(100),
+            action: DestinationAction::Delegate {
+                validator: Addr::unchecked("validator"),
+            },
+        }];
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                destinations: destinations.clone(),
+                ..Vault::default()
+            },
+        );
+
+        get_disbursement_messages(
+            &deps.api.clone(),
+            deps.as_mut().storage,
+            &env,
+            &vault,
+            Coin::new(vault.swap_amount.into(), vault.target_denom.clone()),
+        )
+        .unwrap();
+
+        let response = handle_failed_automation_handler(
+            deps.as_mut(),
+            env,
+            Reply {
+                id: AFTER_FAILED_AUTOMATION_REPLY_ID,
+                result: SubMsgResult::Err("error".to_string()),
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            response.messages,
+            vec![SubMsg::new(BankMsg::Send {
+                to_address: destinations[0].address.to_string(),
+                amount: vec![Coin::new(vault.swap_amount.into(), vault.target_denom)],
+            })]
+        );
+    }
+
+    #[test]
+    fn saves_pending_ibc_transfer_on_success() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                destinations: vec![Destination {
+                    address: Addr::unchecked("owner"),
+                    allocation: Decimal::percent(100),
+                    action: DestinationAction::IbcTransfer {
+                        channel: "channel-0".to_string(),
+                        receiver: "osmo1receiver".to_string(),
+                        timeout: 600,
+                    },
+                }],
+                ..Vault::default()
+            },
+        );
+
+        get_disbursement_messages(
+            &deps.api.clone(),
+            deps.as_mut().storage,
+            &env,
+            &vault,
+            Coin::new(vault.swap_amount.into(), vault.target_denom.clone()),
+        )
+        .unwrap();
+
+        handle_failed_automation_handler(
+            deps.as_mut(),
+            env,
+            Reply {
+                id: AFTER_FAILED_AUTOMATION_REPLY_ID,
+                result: SubMsgResult::Ok(SubMsgResponse {
+                    events: vec![Event::new("send_packet")
+                        .add_attribute("packet_src_channel", "channel-0")
+                        .add_attribute("packet_sequence", "12")],
+                    data: None,
+                }),
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            get_ibc_transfer(deps.as_ref().storage, "channel-0", 12).unwrap(),
+            Some(IbcTransfer {
+                vault_id: vault.id,
+                channel_id: "channel-0".to_string(),
+                sequence: 12,
+                funds: Coin::new(vault.swap_amount.into(), vault.target_denom),
+                refund_address: vault.owner,
+            })
+        );
+    }
+
+    #[test]
+    fn queues_action_for_retry_on_failure_when_enabled() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                destinations: vec![Destination {
+                    address: Addr::unchecked("contract"),
+                    allocation: Decimal::percent(100),
+                    action: DestinationAction::ContractCall {
+                        msg: to_json_binary("test").unwrap(),
+                    },
+                }],
+                retry_failed_post_execution_actions: true,
+                ..Vault::default()
+            },
+        );
+
+        get_disbursement_messages(
+            &deps.api.clone(),
+            deps.as_mut().storage,
+            &env,
+            &vault,
+            Coin::new(vault.swap_amount.into(), vault.target_denom.clone()),
+        )
+        .unwrap();
+
+        let entry = POST_EXECUTION_ACTION_CACHE
+            .load(deps.as_ref().storage, vault.id.into())
+            .unwrap()[0]
+            .clone();
+
+        let response = handle_failed_automation_handler(
+            deps.as_mut(),
+            env.clone(),
+            Reply {
+                id: AFTER_FAILED_AUTOMATION_REPLY_ID,
+                result: SubMsgResult::Err("error".to_string()),
+            },
+        )
+        .unwrap();
+
+        assert!(response.messages.is_empty());
+        assert_eq!(
+            get_pending_post_execution_actions(deps.as_ref().storage, vault.id).unwrap(),
+            VecDeque::from(vec![PendingPostExecutionAction {
+                msg: entry.msg,
+                funds: entry.funds,
+                fallback_address: entry.fallback_address,
+                attempts: 0,
+                failed_at: env.block.time,
+            }])
+        );
+    }
+
+    #[test]
+    fn with_interleaved_vaults_uses_vault_id_from_reply_id() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+        let other_vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        for vault in [&vault, &other_vault] {
+            get_disbursement_messages(
+                &deps.api.clone(),
+                deps.as_mut().storage,
+                &env,
+                vault,
+                Coin::new(vault.swap_amount.into(), vault.target_denom.clone()),
+            )
+            .unwrap();
+        }
+
+        handle_failed_automation_handler(
+            deps.as_mut(),
+            env,
+            Reply {
+                id: get_vault_reply_id(AFTER_FAILED_AUTOMATION_REPLY_ID, vault.id),
+                result: SubMsgResult::Ok(SubMsgResponse {
+                    events: vec![],
+                    data: None,
+                }),
+            },
+        )
+        .unwrap();
+
+        assert!(POST_EXECUTION_ACTION_CACHE
+            .load(deps.as_ref().storage, vault.id.into())
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            POST_EXECUTION_ACTION_CACHE
+                .load(deps.as_ref().storage, other_vault.id.into())
+                .unwrap()
+                .len(),
+            1
+        );
+    }
 }
@@ -0,0 +1,265 @@
+use crate::{
+    error::ContractError,
+    msg::IbcLifecycleComplete,
+    state::{
+        events::create_event,
+        ibc_transfers::{delete_ibc_transfer, get_ibc_transfer},
+    },
+    types::event::{EventBuilder, EventData},
+};
+use cosmwasm_std::{DepsMut, Env, Response, SubMsg};
+use shared::cw20::into_bank_msg;
+
+pub fn ibc_lifecycle_complete_handler(
+    deps: DepsMut,
+    env: Env,
+    msg: IbcLifecycleComplete,
+) -> Result<Response, ContractError> {
+    let (channel, sequence, failure_reason) = match msg {
+        IbcLifecycleComplete::IbcAck {
+            channel,
+            sequence,
+            ack,
+            success,
+        } => (
+            channel,
+            sequence,
+            if success {
+                None
+            } else {
+                Some(format!("ibc transfer failed with ack {}", ack))
+            },
+        ),
+        IbcLifecycleComplete::IbcTimeout { channel, sequence } => (
+            channel,
+            sequence,
+            Some(String::from("ibc transfer timed out")),
+        ),
+    };
+
+    let transfer = get_ibc_transfer(deps.storage, &channel, sequence)?.ok_or_else(|| {
+        ContractError::CustomError {
+            val: format!(
+                "no pending ibc transfer for channel {} and sequence {}",
+                channel, sequence
+            ),
+        }
+    })?;
+
+    delete_ibc_transfer(deps.storage, &channel, sequence);
+
+    let response = Response::new()
+        .add_attribute("ibc_lifecycle_complete", "true")
+        .add_attribute("vault_id", transfer.vault_id)
+        .add_attribute("channel_id", channel.clone())
+        .add_attribute("sequence", sequence.to_string());
+
+    match failure_reason {
+        None => Ok(response.add_attribute("ibc_transfer", "succeeded")),
+        Some(reason) => {
+            create_event(
+                deps.storage,
+                EventBuilder::new(
+                    transfer.vault_id,
+                    env.block,
+                    EventData::DcaVaultIbcTransferFailed {
+                        channel_id: channel,
+                        sequence,
+                        funds: vec![transfer.funds.clone()],
+                        reason,
+                    },
+                ),
+            )?;
+
+            Ok(response
+                .add_attribute("ibc_transfer", "failed")
+                .add_attribute("refunded_to", transfer.refund_address.to_string())
+                .add_submessage(SubMsg::new(into_bank_msg(
+                    deps.api,
+                    transfer.refund_address.as_ref(),
+                    vec![transfer.funds],
+                )?)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod ibc_lifecycle_complete_handler_tests {
+    use super::ibc_lifecycle_complete_handler;
+    use crate::{
+        handlers::get_events_by_resource_id::get_events_by_resource_id_handler,
+        msg::IbcLifecycleComplete,
+        state::ibc_transfers::{get_ibc_transfer, save_ibc_transfer, IbcTransfer},
+        tests::{
+            helpers::instantiate_contract,
+            mocks::{ADMIN, DENOM_UKUJI, USER},
+        },
+        types::event::{EventBuilder, EventData},
+    };
+    use cosmwasm_std::{
+        testing::{mock_dependencies, mock_env, mock_info},
+        Addr, BankMsg, Coin, DepsMut, SubMsg, Uint128,
+    };
+
+    fn save_transfer(deps: DepsMut) -> IbcTransfer {
+        let transfer = IbcTransfer {
+            vault_id: Uint128::one(),
+            channel_id: "channel-0".to_string(),
+            sequence: 7,
+            funds: Coin::new(1000, DENOM_UKUJI),
+            refund_address: Addr::unchecked(USER),
+        };
+
+        save_ibc_transfer(deps.storage, &transfer).unwrap();
+
+        transfer
+    }
+
+    #[test]
+    fn with_no_pending_transfer_fails() {
+        let mut deps = mock_dependencies();
+
+        let err = ibc_lifecycle_complete_handler(
+            deps.as_mut(),
+            mock_env(),
+            IbcLifecycleComplete::IbcTimeout {
+                channel: "channel-0".to_string(),
+                sequence: 7,
+            },
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Error: no pending ibc transfer for channel channel-0 and sequence 7"
+        );
+    }
+
+    #[test]
+    fn with_successful_ack_removes_pending_transfer_without_refund() {
+        let mut deps = mock_dependencies();
+        let transfer = save_transfer(deps.as_mut());
+
+        let response = ibc_lifecycle_complete_handler(
+            deps.as_mut(),
+            mock_env(),
+            IbcLifecycleComplete::IbcAck {
+                channel: transfer.channel_id.clone(),
+                sequence: transfer.sequence,
+                ack: "AQ==".to_string(),
+                success: true,
+            },
+        )
+        .unwrap();
+
+        assert!(response.messages.is_empty());
+        assert_eq!(
+            get_ibc_transfer(
+                deps.as_ref().storage,
+                &transfer.channel_id,
+                transfer.sequence
+            )
+            .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn with_failed_ack_refunds_refund_address() {
+        let mut deps = mock_dependencies();
+        let transfer = save_transfer(deps.as_mut());
+
+        let response = ibc_lifecycle_complete_handler(
+            deps.as_mut(),
+            mock_env(),
+            IbcLifecycleComplete::IbcAck {
+                channel: transfer.channel_id.clone(),
+                sequence: transfer.sequence,
+                ack: "error".to_string(),
+                success: false,
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            response.messages,
+            vec![SubMsg::new(BankMsg::Send {
+                to_address: transfer.refund_address.to_string(),
+                amount: vec![transfer.funds],
+            })]
+        );
+    }
+
+    #[test]
+    fn with_timeout_refunds_refund_address() {
+        let mut deps = mock_dependencies();
+        let transfer = save_transfer(deps.as_mut());
+
+        let response = ibc_lifecycle_complete_handler(
+            deps.as_mut(),
+            mock_env(),
+            IbcLifecycleComplete::IbcTimeout {
+                channel: transfer.channel_id.clone(),
+                sequence: transfer.sequence,
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            response.messages,
+            vec![SubMsg::new(BankMsg::Send {
+                to_address: transfer.refund_address.to_string(),
+                amount: vec![transfer.funds],
+            })]
+        );
+        assert_eq!(
+            get_ibc_transfer(
+                deps.as_ref().storage,
+                &transfer.channel_id,
+                transfer.sequence
+            )
+            .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn with_timeout_creates_ibc_transfer_failed_event() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let transfer = save_transfer(deps.as_mut());
+
+        ibc_lifecycle_complete_handler(
+            deps.as_mut(),
+            env.clone(),
+            IbcLifecycleComplete::IbcTimeout {
+                channel: transfer.channel_id.clone(),
+                sequence: transfer.sequence,
+            },
+        )
+        .unwrap();
+
+        let events =
+            get_events_by_resource_id_handler(deps.as_ref(), transfer.vault_id, None, None, None)
+                .unwrap()
+                .events;
+
+        assert_eq!(
+            events[0],
+            EventBuilder::new(
+                transfer.vault_id,
+                env.block,
+                EventData::DcaVaultIbcTransferFailed {
+                    channel_id: transfer.channel_id,
+                    sequence: transfer.sequence,
+                    funds: vec![transfer.funds],
+                    reason: "ibc transfer timed out".to_string(),
+                },
+            )
+            .build(1)
+        );
+    }
+}
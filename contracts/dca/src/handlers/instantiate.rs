@@ -2,9 +2,13 @@ use crate::{
     contract::{CONTRACT_NAME, CONTRACT_VERSION},
     error::ContractError,
     helpers::validation::{
-        assert_addresses_are_valid, assert_fee_collector_addresses_are_valid,
-        assert_fee_collector_allocations_add_up_to_one, assert_fee_level_is_valid,
+        assert_addresses_are_valid, assert_escrow_level_bounds_are_valid,
+        assert_fee_collector_addresses_are_valid, assert_fee_collector_allocations_add_up_to_one,
+        assert_fee_level_is_valid,
+        assert_high_water_mark_performance_fee_percent_is_no_greater_than_100_percent,
+        assert_keeper_bounty_percent_is_no_greater_than_100_percent,
         assert_no_more_than_10_fee_collectors, assert_page_limit_is_valid,
+        assert_referral_fee_percent_is_no_greater_than_100_percent, assert_retry_policy_is_valid,
         assert_risk_weighted_average_escrow_level_is_no_greater_than_100_percent,
         assert_slippage_tolerance_is_less_than_or_equal_to_one, assert_twap_period_is_valid,
     },
@@ -32,6 +36,17 @@ pub fn instantiate_handler(deps: DepsMut, msg: InstantiateMsg) -> Result<Respons
         msg.risk_weighted_average_escrow_level,
     )?;
 
+    assert_keeper_bounty_percent_is_no_greater_than_100_percent(msg.keeper_bounty_percent)?;
+    assert_referral_fee_percent_is_no_greater_than_100_percent(msg.referral_fee_percent)?;
+    assert_high_water_mark_performance_fee_percent_is_no_greater_than_100_percent(
+        msg.high_water_mark_performance_fee_percent,
+    )?;
+    assert_escrow_level_bounds_are_valid(msg.minimum_escrow_level, msg.maximum_escrow_level)?;
+
+    if let Some(default_retry_policy) = &msg.default_retry_policy {
+        assert_retry_policy_is_valid(default_retry_policy)?;
+    }
+
     update_config(
         deps.storage,
         Config {
@@ -47,6 +62,16 @@ pub fn instantiate_handler(deps: DepsMut, msg: InstantiateMsg) -> Result<Respons
             twap_period: msg.twap_period,
             default_slippage_tolerance: msg.default_slippage_tolerance,
             exchange_contract_address: msg.exchange_contract_address,
+            default_retry_policy: msg.default_retry_policy.clone(),
+            permissionless_execution: msg.permissionless_execution,
+            keeper_bounty_percent: msg.keeper_bounty_percent,
+            referral_fee_percent: msg.referral_fee_percent,
+            high_water_mark_performance_fee_percent: msg.high_water_mark_performance_fee_percent,
+            minimum_escrow_level: msg.minimum_escrow_level,
+            maximum_escrow_level: msg.maximum_escrow_level,
+            post_execution_action_max_retry_attempts: msg.post_execution_action_max_retry_attempts,
+            post_execution_action_max_retry_age_seconds: msg
+                .post_execution_action_max_retry_age_seconds,
         },
     )?;
 
@@ -92,6 +117,15 @@ mod instantiate_tests {
             twap_period: 30,
             default_slippage_tolerance: Decimal::percent(2),
             exchange_contract_address: Addr::unchecked(EXCHANGE_CONTRACT_ADDRESS),
+            default_retry_policy: None,
+            permissionless_execution: false,
+            keeper_bounty_percent: Decimal::zero(),
+            referral_fee_percent: Decimal::zero(),
+            high_water_mark_performance_fee_percent: Decimal::percent(20),
+            minimum_escrow_level: Decimal::zero(),
+            maximum_escrow_level: Decimal::percent(100),
+            post_execution_action_max_retry_attempts: 5,
+            post_execution_action_max_retry_age_seconds: 7 * 24 * 60 * 60,
         };
 
         let result = instantiate(deps.as_mut(), env, info, instantiate_message).unwrap();
@@ -127,6 +161,15 @@ mod instantiate_tests {
             twap_period: 30,
             default_slippage_tolerance: Decimal::percent(2),
             exchange_contract_address: Addr::unchecked(EXCHANGE_CONTRACT_ADDRESS),
+            default_retry_policy: None,
+            permissionless_execution: false,
+            keeper_bounty_percent: Decimal::zero(),
+            referral_fee_percent: Decimal::zero(),
+            high_water_mark_performance_fee_percent: Decimal::percent(20),
+            minimum_escrow_level: Decimal::zero(),
+            maximum_escrow_level: Decimal::percent(100),
+            post_execution_action_max_retry_attempts: 5,
+            post_execution_action_max_retry_age_seconds: 7 * 24 * 60 * 60,
         };
 
         let result = instantiate(deps.as_mut(), env, info, instantiate_message).unwrap_err();
@@ -159,6 +202,15 @@ mod instantiate_tests {
             twap_period: 30,
             default_slippage_tolerance: Decimal::percent(2),
             exchange_contract_address: Addr::unchecked(EXCHANGE_CONTRACT_ADDRESS),
+            default_retry_policy: None,
+            permissionless_execution: false,
+            keeper_bounty_percent: Decimal::zero(),
+            referral_fee_percent: Decimal::zero(),
+            high_water_mark_performance_fee_percent: Decimal::percent(20),
+            minimum_escrow_level: Decimal::zero(),
+            maximum_escrow_level: Decimal::percent(100),
+            post_execution_action_max_retry_attempts: 5,
+            post_execution_action_max_retry_age_seconds: 7 * 24 * 60 * 60,
         };
 
         let result = instantiate(deps.as_mut(), env, info, instantiate_message).unwrap_err();
@@ -188,6 +240,15 @@ mod instantiate_tests {
             twap_period: 30,
             default_slippage_tolerance: Decimal::percent(2),
             exchange_contract_address: Addr::unchecked(EXCHANGE_CONTRACT_ADDRESS),
+            default_retry_policy: None,
+            permissionless_execution: false,
+            keeper_bounty_percent: Decimal::zero(),
+            referral_fee_percent: Decimal::zero(),
+            high_water_mark_performance_fee_percent: Decimal::percent(20),
+            minimum_escrow_level: Decimal::zero(),
+            maximum_escrow_level: Decimal::percent(100),
+            post_execution_action_max_retry_attempts: 5,
+            post_execution_action_max_retry_age_seconds: 7 * 24 * 60 * 60,
         };
 
         let result = instantiate(deps.as_mut(), env, info, instantiate_message).unwrap_err();
@@ -1,14 +1,21 @@
+pub mod accept_vault_ownership;
 pub mod cancel_vault;
+pub mod create_custom_swap_fee;
 pub mod create_vault;
 pub mod deposit;
 pub mod disburse_escrow;
 pub mod disburse_funds;
+pub mod execute_aggregated_swap;
 pub mod execute_trigger;
+pub mod execute_triggers;
 pub mod get_config;
+pub mod get_custom_swap_fees;
 pub mod get_disburse_escrow_tasks;
 pub mod get_events;
 pub mod get_events_by_resource_id;
 pub mod get_pairs;
+pub mod get_pending_post_execution_actions;
+pub mod get_referral_fees;
 pub mod get_time_trigger_ids;
 pub mod get_trigger_id_by_fin_limit_order_idx;
 pub mod get_vault;
@@ -16,9 +23,20 @@ pub mod get_vault_performance;
 pub mod get_vaults;
 pub mod get_vaults_by_address;
 pub mod handle_failed_automation;
+pub mod ibc_lifecycle_complete;
 pub mod instantiate;
 pub mod migrate;
+pub mod pause_vault;
+pub mod recurring_deposit;
+pub mod remove_custom_swap_fee;
+pub mod resume_vault;
+pub mod retry_post_execution_action;
+pub mod settle_execution;
+pub mod simulate_vault;
+pub mod transfer_vault_ownership;
 pub mod update_config;
+pub mod update_referral_fee_caps;
 pub mod update_swap_adjustment_handler;
 pub mod update_vault;
+pub mod withdraw_from_vault;
 pub mod z_delegate;
@@ -0,0 +1,210 @@
+use crate::error::ContractError;
+use crate::helpers::validation::{assert_vault_can_be_paused, asset_sender_is_vault_owner};
+use crate::state::events::create_event;
+use crate::state::triggers::delete_trigger;
+use crate::state::vaults::{get_vault, update_vault};
+use crate::types::event::{EventBuilder, EventData};
+use crate::types::vault::{Vault, VaultStatus};
+use cosmwasm_std::{DepsMut, Env, MessageInfo, Response, Uint128};
+
+pub fn pause_vault_handler(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    vault_id: Uint128,
+) -> Result<Response, ContractError> {
+    let vault = get_vault(deps.storage, vault_id)?;
+
+    asset_sender_is_vault_owner(vault.owner.clone(), info.sender)?;
+    assert_vault_can_be_paused(&vault)?;
+
+    delete_trigger(deps.storage, vault.id)?;
+
+    let vault = update_vault(
+        deps.storage,
+        Vault {
+            status: VaultStatus::Paused,
+            ..vault
+        },
+    )?;
+
+    create_event(
+        deps.storage,
+        EventBuilder::new(vault.id, env.block, EventData::DcaVaultPaused {}),
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("pause_vault", "true")
+        .add_attribute("vault_id", vault.id)
+        .add_attribute("owner", vault.owner))
+}
+
+#[cfg(test)]
+mod pause_vault_tests {
+    use super::*;
+    use crate::constants::ONE;
+    use crate::handlers::get_events_by_resource_id::get_events_by_resource_id_handler;
+    use crate::handlers::get_vault::get_vault_handler;
+    use crate::state::triggers::get_time_triggers;
+    use crate::tests::helpers::{instantiate_contract, setup_vault};
+    use crate::tests::mocks::{ADMIN, DENOM_UUSK, USER};
+    use crate::types::event::{EventBuilder, EventData};
+    use crate::types::vault::{Vault, VaultStatus};
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+    use cosmwasm_std::Coin;
+
+    #[test]
+    fn should_set_vault_status_to_paused() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        pause_vault_handler(deps.as_mut(), env, mock_info(USER, &[]), vault.id).unwrap();
+
+        let updated_vault = get_vault_handler(deps.as_ref(), vault.id).unwrap().vault;
+
+        assert_eq!(vault.status, VaultStatus::Active);
+        assert_eq!(updated_vault.status, VaultStatus::Paused);
+    }
+
+    #[test]
+    fn should_delete_the_trigger() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        pause_vault_handler(deps.as_mut(), env.clone(), mock_info(USER, &[]), vault.id).unwrap();
+
+        let updated_vault = get_vault_handler(deps.as_ref(), vault.id).unwrap().vault;
+
+        let trigger_ids =
+            get_time_triggers(deps.as_ref().storage, env.block.time, Some(100)).unwrap();
+
+        assert_ne!(vault.trigger, None);
+        assert_eq!(updated_vault.trigger, None);
+        assert!(!trigger_ids.contains(&vault.id));
+    }
+
+    #[test]
+    fn should_keep_balance_and_escrow() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                escrowed_amount: Coin::new(ONE.into(), DENOM_UUSK),
+                ..Vault::default()
+            },
+        );
+
+        let response =
+            pause_vault_handler(deps.as_mut(), env, mock_info(USER, &[]), vault.id).unwrap();
+
+        let updated_vault = get_vault_handler(deps.as_ref(), vault.id).unwrap().vault;
+
+        assert!(response.messages.is_empty());
+        assert_eq!(updated_vault.balance, vault.balance);
+        assert_eq!(updated_vault.escrowed_amount, vault.escrowed_amount);
+    }
+
+    #[test]
+    fn should_publish_vault_paused_event() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        pause_vault_handler(deps.as_mut(), env.clone(), mock_info(USER, &[]), vault.id).unwrap();
+
+        let events = get_events_by_resource_id_handler(deps.as_ref(), vault.id, None, None, None)
+            .unwrap()
+            .events;
+
+        assert!(events.contains(
+            &EventBuilder::new(vault.id, env.block, EventData::DcaVaultPaused {}).build(1)
+        ));
+    }
+
+    #[test]
+    fn for_vault_with_different_owner_should_fail() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        let err = pause_vault_handler(
+            deps.as_mut(),
+            env,
+            mock_info("not-the-owner", &[]),
+            vault.id,
+        )
+        .unwrap_err();
+
+        assert_eq!(err.to_string(), "Unauthorized");
+    }
+
+    #[test]
+    fn for_inactive_vault_should_fail() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                status: VaultStatus::Inactive,
+                ..Vault::default()
+            },
+        );
+
+        let err =
+            pause_vault_handler(deps.as_mut(), env, mock_info(USER, &[]), vault.id).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Error: vault is Inactive, only active vaults can be paused"
+        );
+    }
+
+    #[test]
+    fn for_already_paused_vault_should_fail() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                status: VaultStatus::Paused,
+                trigger: None,
+                ..Vault::default()
+            },
+        );
+
+        let err =
+            pause_vault_handler(deps.as_mut(), env, mock_info(USER, &[]), vault.id).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Error: vault is Paused, only active vaults can be paused"
+        );
+    }
+}
@@ -0,0 +1,569 @@
+use crate::{
+    constants::{AFTER_RECURRING_DEPOSIT_REPLY_ID, RECURRING_DEPOSIT_MAX_FAILED_ATTEMPTS},
+    error::ContractError,
+    helpers::{authz::create_authz_exec_message, time::get_next_target_time},
+    msg::ExecuteMsg,
+    state::{
+        cache::{RecurringDepositCache, RECURRING_DEPOSIT_CACHE},
+        events::create_event,
+        recurring_deposit_failures::{
+            delete_recurring_deposit_failed_attempts, get_recurring_deposit_failed_attempts,
+            save_recurring_deposit_failed_attempts,
+        },
+        triggers::{delete_trigger, save_trigger},
+        vaults::{get_vault, update_vault},
+    },
+    types::{
+        event::{EventBuilder, EventData, ExecutionSkippedReason},
+        trigger::{Trigger, TriggerConfiguration},
+        vault::{Vault, VaultStatus},
+    },
+};
+use cosmos_sdk_proto::cosmos::{bank::v1beta1::MsgSend, base::v1beta1::Coin as ProtoCoin};
+use cosmwasm_std::{
+    to_json_binary, Binary, Coin, DepsMut, Env, Reply, Response, StdResult, Storage, SubMsg,
+    SubMsgResult, WasmMsg,
+};
+use shared::coin::add;
+
+pub fn pull_recurring_deposit(
+    deps: DepsMut,
+    env: &Env,
+    response: Response,
+    vault: Vault,
+    trigger_configuration: TriggerConfiguration,
+    route: Option<Binary>,
+) -> Result<Response, ContractError> {
+    let amount = Coin::new(
+        (vault.swap_amount - vault.balance.amount).into(),
+        vault.get_swap_denom(),
+    );
+
+    let wallet_balance = deps
+        .querier
+        .query_balance(vault.owner.clone(), amount.denom.clone())?;
+
+    if wallet_balance.amount < amount.amount {
+        return skip_for_insufficient_wallet_funds(deps.storage, env, response, vault);
+    }
+
+    save_trigger(
+        deps.storage,
+        Trigger {
+            vault_id: vault.id,
+            configuration: trigger_configuration,
+        },
+    )?;
+
+    RECURRING_DEPOSIT_CACHE.save(
+        deps.storage,
+        &RecurringDepositCache {
+            vault_id: vault.id,
+            amount: amount.clone(),
+            route,
+        },
+    )?;
+
+    Ok(response
+        .add_attribute("recurring_deposit", amount.to_string())
+        .add_submessage(SubMsg::reply_always(
+            create_authz_exec_message(
+                env.contract.address.clone(),
+                String::from("/cosmos.bank.v1beta1.MsgSend"),
+                MsgSend {
+                    from_address: vault.owner.to_string(),
+                    to_address: env.contract.address.to_string(),
+                    amount: vec![ProtoCoin {
+                        denom: amount.denom,
+                        amount: amount.amount.to_string(),
+                    }],
+                },
+            ),
+            AFTER_RECURRING_DEPOSIT_REPLY_ID,
+        )))
+}
+
+pub fn handle_recurring_deposit_result(
+    deps: DepsMut,
+    env: Env,
+    reply: Reply,
+) -> Result<Response, ContractError> {
+    let cache = RECURRING_DEPOSIT_CACHE.load(deps.storage)?;
+    RECURRING_DEPOSIT_CACHE.remove(deps.storage);
+
+    let vault = get_vault(deps.storage, cache.vault_id)?;
+
+    let response = Response::new()
+        .add_attribute("recurring_deposit_result", "true")
+        .add_attribute("vault_id", vault.id);
+
+    match reply.result {
+        SubMsgResult::Ok(_) => {
+            delete_recurring_deposit_failed_attempts(deps.storage, vault.id);
+
+            let vault = update_vault(
+                deps.storage,
+                Vault {
+                    balance: add(vault.balance.clone(), cache.amount.clone())?,
+                    deposited_amount: add(vault.deposited_amount.clone(), cache.amount.clone())?,
+                    status: if vault.is_inactive() {
+                        VaultStatus::Active
+                    } else {
+                        vault.status
+                    },
+                    ..vault
+                },
+            )?;
+
+            create_event(
+                deps.storage,
+                EventBuilder::new(
+                    vault.id,
+                    env.block.clone(),
+                    EventData::DcaVaultFundsDeposited {
+                        amount: cache.amount.clone(),
+                    },
+                ),
+            )?;
+
+            Ok(response
+                .add_attribute("deposited_amount", cache.amount.to_string())
+                .add_submessage(SubMsg::new(WasmMsg::Execute {
+                    contract_addr: env.contract.address.to_string(),
+                    msg: to_json_binary(&ExecuteMsg::ExecuteTrigger {
+                        trigger_id: vault.id,
+                        route: cache.route,
+                    })?,
+                    funds: vec![],
+                })))
+        }
+        SubMsgResult::Err(_) => {
+            delete_trigger(deps.storage, vault.id)?;
+            skip_for_insufficient_wallet_funds(deps.storage, &env, response, vault)
+        }
+    }
+}
+
+fn skip_for_insufficient_wallet_funds(
+    store: &mut dyn Storage,
+    env: &Env,
+    response: Response,
+    vault: Vault,
+) -> Result<Response, ContractError> {
+    create_event(
+        store,
+        EventBuilder::new(
+            vault.id,
+            env.block.clone(),
+            EventData::DcaVaultExecutionSkipped {
+                reason: ExecutionSkippedReason::InsufficientWalletFunds,
+            },
+        ),
+    )?;
+
+    let failed_attempts = increment_failed_attempts(store, &vault)?;
+
+    let response = response
+        .add_attribute("execution_skipped", "insufficient_wallet_funds")
+        .add_attribute(
+            "recurring_deposit_failed_attempts",
+            failed_attempts.to_string(),
+        );
+
+    if failed_attempts >= RECURRING_DEPOSIT_MAX_FAILED_ATTEMPTS {
+        delete_recurring_deposit_failed_attempts(store, vault.id);
+
+        update_vault(
+            store,
+            Vault {
+                status: VaultStatus::Inactive,
+                ..vault
+            },
+        )?;
+
+        return Ok(response.add_attribute("vault_deactivated", "true"));
+    }
+
+    save_trigger(
+        store,
+        Trigger {
+            vault_id: vault.id,
+            configuration: TriggerConfiguration::Time {
+                target_time: get_next_target_time(
+                    env.block.time,
+                    vault.started_at.unwrap_or(env.block.time),
+                    vault.time_interval.clone(),
+                ),
+            },
+        },
+    )?;
+
+    Ok(response)
+}
+
+fn increment_failed_attempts(store: &mut dyn Storage, vault: &Vault) -> StdResult<u32> {
+    let failed_attempts = get_recurring_deposit_failed_attempts(store, vault.id)? + 1;
+
+    save_recurring_deposit_failed_attempts(store, vault.id, failed_attempts)?;
+
+    Ok(failed_attempts)
+}
+
+#[cfg(test)]
+mod pull_recurring_deposit_tests {
+    use crate::{
+        constants::{AFTER_RECURRING_DEPOSIT_REPLY_ID, ONE, RECURRING_DEPOSIT_MAX_FAILED_ATTEMPTS},
+        handlers::{
+            execute_trigger::execute_trigger_handler,
+            get_events_by_resource_id::get_events_by_resource_id_handler,
+        },
+        helpers::authz::create_authz_exec_message,
+        state::{
+            cache::RECURRING_DEPOSIT_CACHE,
+            recurring_deposit_failures::save_recurring_deposit_failed_attempts,
+            triggers::get_trigger, vaults::get_vault,
+        },
+        tests::{
+            helpers::{instantiate_contract, setup_vault},
+            mocks::{calc_mock_dependencies, ADMIN, DENOM_UKUJI, USER},
+        },
+        types::{
+            event::{EventBuilder, EventData, ExecutionSkippedReason},
+            trigger::TriggerConfiguration,
+            vault::{Vault, VaultStatus},
+        },
+    };
+    use cosmos_sdk_proto::cosmos::{bank::v1beta1::MsgSend, base::v1beta1::Coin as ProtoCoin};
+    use cosmwasm_std::{
+        testing::{mock_dependencies, mock_env, mock_info},
+        Coin, SubMsg,
+    };
+
+    fn subscription_vault() -> Vault {
+        Vault {
+            balance: Coin::new(0, DENOM_UKUJI),
+            recurring_deposits: true,
+            ..Vault::default()
+        }
+    }
+
+    #[test]
+    fn pulls_swap_amount_from_owner_wallet() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        deps.querier
+            .update_balance(USER, vec![Coin::new(ONE.into(), DENOM_UKUJI)]);
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), subscription_vault());
+
+        let response = execute_trigger_handler(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADMIN, &[]),
+            vault.id,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(
+            response.messages,
+            vec![SubMsg::reply_always(
+                create_authz_exec_message(
+                    env.contract.address.clone(),
+                    String::from("/cosmos.bank.v1beta1.MsgSend"),
+                    MsgSend {
+                        from_address: vault.owner.to_string(),
+                        to_address: env.contract.address.to_string(),
+                        amount: vec![ProtoCoin {
+                            denom: DENOM_UKUJI.to_string(),
+                            amount: vault.swap_amount.to_string(),
+                        }],
+                    },
+                ),
+                AFTER_RECURRING_DEPOSIT_REPLY_ID
+            )]
+        );
+        assert_eq!(
+            RECURRING_DEPOSIT_CACHE
+                .load(deps.as_ref().storage)
+                .unwrap()
+                .amount,
+            Coin::new(vault.swap_amount.into(), DENOM_UKUJI)
+        );
+    }
+
+    #[test]
+    fn keeps_trigger_due_while_deposit_is_pulled() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        deps.querier
+            .update_balance(USER, vec![Coin::new(ONE.into(), DENOM_UKUJI)]);
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), subscription_vault());
+
+        execute_trigger_handler(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADMIN, &[]),
+            vault.id,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(
+            get_trigger(deps.as_ref().storage, vault.id)
+                .unwrap()
+                .unwrap()
+                .configuration,
+            TriggerConfiguration::Time {
+                target_time: env.block.time
+            }
+        );
+    }
+
+    #[test]
+    fn with_insufficient_wallet_funds_skips_execution() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), subscription_vault());
+
+        let response = execute_trigger_handler(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(ADMIN, &[]),
+            vault.id,
+            None,
+        )
+        .unwrap();
+
+        assert!(response.messages.is_empty());
+
+        let events = get_events_by_resource_id_handler(deps.as_ref(), vault.id, None, None, None)
+            .unwrap()
+            .events;
+
+        assert!(events.contains(
+            &EventBuilder::new(
+                vault.id,
+                env.block.clone(),
+                EventData::DcaVaultExecutionSkipped {
+                    reason: ExecutionSkippedReason::InsufficientWalletFunds,
+                },
+            )
+            .build(1)
+        ));
+        assert!(match get_trigger(deps.as_ref().storage, vault.id)
+            .unwrap()
+            .unwrap()
+            .configuration
+        {
+            TriggerConfiguration::Time { target_time } => target_time > env.block.time,
+            _ => false,
+        });
+    }
+
+    #[test]
+    fn with_repeated_insufficient_wallet_funds_deactivates_vault() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), subscription_vault());
+
+        save_recurring_deposit_failed_attempts(
+            deps.as_mut().storage,
+            vault.id,
+            RECURRING_DEPOSIT_MAX_FAILED_ATTEMPTS - 1,
+        )
+        .unwrap();
+
+        execute_trigger_handler(deps.as_mut(), env, mock_info(ADMIN, &[]), vault.id, None).unwrap();
+
+        let vault = get_vault(deps.as_ref().storage, vault.id).unwrap();
+
+        assert_eq!(vault.status, VaultStatus::Inactive);
+        assert_eq!(vault.trigger, None);
+    }
+
+    #[test]
+    fn with_sufficient_vault_balance_does_not_pull_deposit() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                recurring_deposits: true,
+                ..Vault::default()
+            },
+        );
+
+        let response =
+            execute_trigger_handler(deps.as_mut(), env, mock_info(ADMIN, &[]), vault.id, None)
+                .unwrap();
+
+        assert!(!response
+            .attributes
+            .iter()
+            .any(|attribute| attribute.key == "recurring_deposit"));
+        assert!(RECURRING_DEPOSIT_CACHE
+            .may_load(deps.as_ref().storage)
+            .unwrap()
+            .is_none());
+    }
+}
+
+#[cfg(test)]
+mod handle_recurring_deposit_result_tests {
+    use super::handle_recurring_deposit_result;
+    use crate::{
+        constants::{AFTER_RECURRING_DEPOSIT_REPLY_ID, ONE},
+        msg::ExecuteMsg,
+        state::{
+            cache::{RecurringDepositCache, RECURRING_DEPOSIT_CACHE},
+            recurring_deposit_failures::{
+                get_recurring_deposit_failed_attempts, save_recurring_deposit_failed_attempts,
+            },
+            vaults::get_vault,
+        },
+        tests::{
+            helpers::{instantiate_contract, setup_vault},
+            mocks::{ADMIN, DENOM_UKUJI},
+        },
+        types::vault::Vault,
+    };
+    use cosmwasm_std::{
+        testing::{mock_dependencies, mock_env, mock_info},
+        to_json_binary, Coin, Reply, SubMsg, SubMsgResponse, SubMsgResult, WasmMsg,
+    };
+
+    #[test]
+    fn on_success_credits_vault_and_executes_trigger() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                balance: Coin::new(0, DENOM_UKUJI),
+                recurring_deposits: true,
+                ..Vault::default()
+            },
+        );
+
+        RECURRING_DEPOSIT_CACHE
+            .save(
+                deps.as_mut().storage,
+                &RecurringDepositCache {
+                    vault_id: vault.id,
+                    amount: Coin::new(ONE.into(), DENOM_UKUJI),
+                    route: None,
+                },
+            )
+            .unwrap();
+
+        save_recurring_deposit_failed_attempts(deps.as_mut().storage, vault.id, 1).unwrap();
+
+        let response = handle_recurring_deposit_result(
+            deps.as_mut(),
+            env.clone(),
+            Reply {
+                id: AFTER_RECURRING_DEPOSIT_REPLY_ID,
+                result: SubMsgResult::Ok(SubMsgResponse {
+                    events: vec![],
+                    data: None,
+                }),
+            },
+        )
+        .unwrap();
+
+        let updated_vault = get_vault(deps.as_ref().storage, vault.id).unwrap();
+
+        assert_eq!(updated_vault.balance, Coin::new(ONE.into(), DENOM_UKUJI));
+        assert_eq!(
+            updated_vault.deposited_amount,
+            Coin::new((vault.deposited_amount.amount + ONE).into(), DENOM_UKUJI)
+        );
+        assert_eq!(
+            response.messages,
+            vec![SubMsg::new(WasmMsg::Execute {
+                contract_addr: env.contract.address.to_string(),
+                msg: to_json_binary(&ExecuteMsg::ExecuteTrigger {
+                    trigger_id: vault.id,
+                    route: None,
+                })
+                .unwrap(),
+                funds: vec![],
+            })]
+        );
+        assert_eq!(
+            get_recurring_deposit_failed_attempts(deps.as_ref().storage, vault.id).unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn on_failure_records_failed_attempt() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                balance: Coin::new(0, DENOM_UKUJI),
+                recurring_deposits: true,
+                ..Vault::default()
+            },
+        );
+
+        RECURRING_DEPOSIT_CACHE
+            .save(
+                deps.as_mut().storage,
+                &RecurringDepositCache {
+                    vault_id: vault.id,
+                    amount: Coin::new(ONE.into(), DENOM_UKUJI),
+                    route: None,
+                },
+            )
+            .unwrap();
+
+        let response = handle_recurring_deposit_result(
+            deps.as_mut(),
+            env,
+            Reply {
+                id: AFTER_RECURRING_DEPOSIT_REPLY_ID,
+                result: SubMsgResult::Err("authorization not found".to_string()),
+            },
+        )
+        .unwrap();
+
+        assert!(response.messages.is_empty());
+        assert_eq!(
+            get_recurring_deposit_failed_attempts(deps.as_ref().storage, vault.id).unwrap(),
+            1
+        );
+        assert_eq!(
+            get_vault(deps.as_ref().storage, vault.id).unwrap().balance,
+            Coin::new(0, DENOM_UKUJI)
+        );
+    }
+}
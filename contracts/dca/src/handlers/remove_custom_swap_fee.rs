@@ -0,0 +1,123 @@
+use crate::{
+    error::ContractError,
+    helpers::validation::assert_sender_is_admin,
+    state::custom_swap_fees::{
+        delete_custom_swap_fee, get_custom_swap_fee, get_custom_swap_fee_key,
+    },
+};
+use cosmwasm_std::{DepsMut, MessageInfo, Response};
+
+pub fn remove_custom_swap_fee_handler(
+    deps: DepsMut,
+    info: MessageInfo,
+    denom: String,
+    paired_denom: Option<String>,
+) -> Result<Response, ContractError> {
+    assert_sender_is_admin(deps.storage, info.sender)?;
+
+    let key = get_custom_swap_fee_key(&denom, paired_denom.as_deref());
+
+    if get_custom_swap_fee(deps.storage, &denom, paired_denom.as_deref())?.is_none() {
+        return Err(ContractError::CustomError {
+            val: format!("custom swap fee for {} not found", key),
+        });
+    }
+
+    delete_custom_swap_fee(deps.storage, &denom, paired_denom.as_deref());
+
+    Ok(Response::new()
+        .add_attribute("remove_custom_swap_fee", "true")
+        .add_attribute("key", key))
+}
+
+#[cfg(test)]
+mod remove_custom_swap_fee_tests {
+    use super::remove_custom_swap_fee_handler;
+    use crate::{
+        state::custom_swap_fees::{get_custom_swap_fee, save_custom_swap_fee},
+        tests::{
+            helpers::instantiate_contract,
+            mocks::{ADMIN, DENOM_UKUJI, DENOM_UUSK, USER},
+        },
+    };
+    use cosmwasm_std::{
+        testing::{mock_dependencies, mock_env, mock_info},
+        Decimal,
+    };
+
+    #[test]
+    fn with_non_admin_sender_fails() {
+        let mut deps = mock_dependencies();
+
+        instantiate_contract(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]));
+
+        let err = remove_custom_swap_fee_handler(
+            deps.as_mut(),
+            mock_info(USER, &[]),
+            DENOM_UKUJI.to_string(),
+            None,
+        )
+        .unwrap_err();
+
+        assert_eq!(err.to_string(), "Unauthorized");
+    }
+
+    #[test]
+    fn with_no_custom_swap_fee_fails() {
+        let mut deps = mock_dependencies();
+
+        instantiate_contract(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]));
+
+        let err = remove_custom_swap_fee_handler(
+            deps.as_mut(),
+            mock_info(ADMIN, &[]),
+            DENOM_UKUJI.to_string(),
+            Some(DENOM_UUSK.to_string()),
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Error: custom swap fee for ukuji,uusk not found"
+        );
+    }
+
+    #[test]
+    fn removes_only_the_matching_custom_swap_fee() {
+        let mut deps = mock_dependencies();
+
+        instantiate_contract(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]));
+
+        save_custom_swap_fee(
+            deps.as_mut().storage,
+            DENOM_UKUJI,
+            None,
+            Decimal::percent(1),
+        )
+        .unwrap();
+        save_custom_swap_fee(
+            deps.as_mut().storage,
+            DENOM_UKUJI,
+            Some(DENOM_UUSK),
+            Decimal::percent(2),
+        )
+        .unwrap();
+
+        remove_custom_swap_fee_handler(
+            deps.as_mut(),
+            mock_info(ADMIN, &[]),
+            DENOM_UUSK.to_string(),
+            Some(DENOM_UKUJI.to_string()),
+        )
+        .unwrap();
+
+        assert_eq!(
+            get_custom_swap_fee(deps.as_ref().storage, DENOM_UKUJI, Some(DENOM_UUSK)).unwrap(),
+            None
+        );
+        assert_eq!(
+            get_custom_swap_fee(deps.as_ref().storage, DENOM_UKUJI, None).unwrap(),
+            Some(Decimal::percent(1))
+        );
+    }
+}
@@ -0,0 +1,224 @@
+use crate::error::ContractError;
+use crate::helpers::time::get_next_target_time;
+use crate::helpers::validation::{assert_vault_can_be_resumed, asset_sender_is_vault_owner};
+use crate::state::events::create_event;
+use crate::state::triggers::save_trigger;
+use crate::state::vaults::{get_vault, update_vault};
+use crate::types::event::{EventBuilder, EventData};
+use crate::types::trigger::{Trigger, TriggerConfiguration};
+use crate::types::vault::{Vault, VaultStatus};
+use cosmwasm_std::{DepsMut, Env, MessageInfo, Response, Uint128};
+
+pub fn resume_vault_handler(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    vault_id: Uint128,
+) -> Result<Response, ContractError> {
+    let vault = get_vault(deps.storage, vault_id)?;
+
+    asset_sender_is_vault_owner(vault.owner.clone(), info.sender)?;
+    assert_vault_can_be_resumed(&vault)?;
+
+    let vault = update_vault(
+        deps.storage,
+        Vault {
+            status: VaultStatus::Active,
+            ..vault
+        },
+    )?;
+
+    let target_time = get_next_target_time(
+        env.block.time,
+        vault.started_at.unwrap_or(env.block.time),
+        vault.time_interval.clone(),
+    );
+
+    save_trigger(
+        deps.storage,
+        Trigger {
+            vault_id: vault.id,
+            configuration: TriggerConfiguration::Time { target_time },
+        },
+    )?;
+
+    create_event(
+        deps.storage,
+        EventBuilder::new(vault.id, env.block, EventData::DcaVaultResumed {}),
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("resume_vault", "true")
+        .add_attribute("vault_id", vault.id)
+        .add_attribute("owner", vault.owner)
+        .add_attribute("target_time", target_time.to_string()))
+}
+
+#[cfg(test)]
+mod resume_vault_tests {
+    use super::*;
+    use crate::handlers::get_events_by_resource_id::get_events_by_resource_id_handler;
+    use crate::handlers::get_vault::get_vault_handler;
+    use crate::handlers::pause_vault::pause_vault_handler;
+    use crate::tests::helpers::{instantiate_contract, setup_vault};
+    use crate::tests::mocks::{ADMIN, USER};
+    use crate::types::event::{EventBuilder, EventData};
+    use crate::types::vault::{Vault, VaultStatus};
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+    use cosmwasm_std::Timestamp;
+
+    #[test]
+    fn should_set_vault_status_to_active() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                status: VaultStatus::Paused,
+                trigger: None,
+                ..Vault::default()
+            },
+        );
+
+        resume_vault_handler(deps.as_mut(), env, mock_info(USER, &[]), vault.id).unwrap();
+
+        let updated_vault = get_vault_handler(deps.as_ref(), vault.id).unwrap().vault;
+
+        assert_eq!(updated_vault.status, VaultStatus::Active);
+    }
+
+    #[test]
+    fn should_save_trigger_at_next_target_time_from_started_at() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let started_at = env.block.time.minus_seconds(60 * 60 * 24 * 3 + 60);
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                status: VaultStatus::Paused,
+                started_at: Some(started_at),
+                trigger: None,
+                ..Vault::default()
+            },
+        );
+
+        resume_vault_handler(deps.as_mut(), env.clone(), mock_info(USER, &[]), vault.id).unwrap();
+
+        let updated_vault = get_vault_handler(deps.as_ref(), vault.id).unwrap().vault;
+
+        assert_eq!(
+            updated_vault.trigger,
+            Some(TriggerConfiguration::Time {
+                target_time: get_next_target_time(env.block.time, started_at, vault.time_interval)
+            })
+        );
+        assert_eq!(
+            updated_vault.trigger,
+            Some(TriggerConfiguration::Time {
+                target_time: Timestamp::from_seconds(started_at.seconds() + 60 * 60 * 24 * 4)
+            })
+        );
+    }
+
+    #[test]
+    fn should_publish_vault_resumed_event() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                status: VaultStatus::Paused,
+                trigger: None,
+                ..Vault::default()
+            },
+        );
+
+        resume_vault_handler(deps.as_mut(), env.clone(), mock_info(USER, &[]), vault.id).unwrap();
+
+        let events = get_events_by_resource_id_handler(deps.as_ref(), vault.id, None, None, None)
+            .unwrap()
+            .events;
+
+        assert!(events.contains(
+            &EventBuilder::new(vault.id, env.block, EventData::DcaVaultResumed {}).build(1)
+        ));
+    }
+
+    #[test]
+    fn after_pause_should_keep_balance() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        pause_vault_handler(deps.as_mut(), env.clone(), mock_info(USER, &[]), vault.id).unwrap();
+        resume_vault_handler(deps.as_mut(), env, mock_info(USER, &[]), vault.id).unwrap();
+
+        let updated_vault = get_vault_handler(deps.as_ref(), vault.id).unwrap().vault;
+
+        assert_eq!(updated_vault.status, VaultStatus::Active);
+        assert_eq!(updated_vault.balance, vault.balance);
+        assert!(updated_vault.trigger.is_some());
+    }
+
+    #[test]
+    fn for_vault_with_different_owner_should_fail() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                status: VaultStatus::Paused,
+                trigger: None,
+                ..Vault::default()
+            },
+        );
+
+        let err = resume_vault_handler(
+            deps.as_mut(),
+            env,
+            mock_info("not-the-owner", &[]),
+            vault.id,
+        )
+        .unwrap_err();
+
+        assert_eq!(err.to_string(), "Unauthorized");
+    }
+
+    #[test]
+    fn for_vault_that_is_not_paused_should_fail() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        let err =
+            resume_vault_handler(deps.as_mut(), env, mock_info(USER, &[]), vault.id).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Error: vault is Active, only paused vaults can be resumed"
+        );
+    }
+}
@@ -0,0 +1,629 @@
+use crate::{
+    constants::AFTER_POST_EXECUTION_ACTION_RETRY_REPLY_ID,
+    error::ContractError,
+    helpers::{
+        disbursement::save_sent_ibc_transfer, ibc::refresh_ibc_transfer_timeout,
+        validation::assert_sender_is_executor,
+    },
+    state::{
+        cache::POST_EXECUTION_ACTION_RETRY_CACHE,
+        config::get_config,
+        events::create_event,
+        post_execution_action_retries::{
+            pop_pending_post_execution_action, push_pending_post_execution_action,
+            PendingPostExecutionAction,
+        },
+        vaults::get_vault,
+    },
+    types::{
+        config::Config,
+        event::{EventBuilder, EventData},
+    },
+};
+use cosmwasm_std::{DepsMut, Env, MessageInfo, Reply, Response, SubMsg, SubMsgResult, Uint128};
+use shared::cw20::into_bank_msg;
+
+pub fn retry_post_execution_action_handler(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    vault_id: Uint128,
+) -> Result<Response, ContractError> {
+    let vault = get_vault(deps.storage, vault_id)?;
+    let config = get_config(deps.storage)?;
+
+    if info.sender != vault.owner && !config.permissionless_execution {
+        assert_sender_is_executor(deps.storage, &env, &info.sender)?;
+    }
+
+    let action = pop_pending_post_execution_action(deps.storage, vault_id)?.ok_or_else(|| {
+        ContractError::CustomError {
+            val: format!(
+                "vault with id {} has no pending post execution actions",
+                vault_id
+            ),
+        }
+    })?;
+
+    let response = Response::new()
+        .add_attribute("retry_post_execution_action", "true")
+        .add_attribute("vault_id", vault_id);
+
+    if should_refund(&config, &env, &action) {
+        return Ok(response
+            .add_attribute("refunded_to", action.fallback_address.to_string())
+            .add_submessage(SubMsg::new(into_bank_msg(
+                deps.api,
+                action.fallback_address.as_ref(),
+                action.funds,
+            )?)));
+    }
+
+    POST_EXECUTION_ACTION_RETRY_CACHE.save(deps.storage, &(vault_id, action.clone()))?;
+
+    Ok(response
+        .add_attribute("attempt", (action.attempts + 1).to_string())
+        .add_submessage(SubMsg::reply_always(
+            refresh_ibc_transfer_timeout(action.msg.msg, action.failed_at, env.block.time),
+            AFTER_POST_EXECUTION_ACTION_RETRY_REPLY_ID,
+        )))
+}
+
+pub fn handle_post_execution_action_retry_result(
+    deps: DepsMut,
+    env: Env,
+    reply: Reply,
+) -> Result<Response, ContractError> {
+    let (vault_id, mut action) = POST_EXECUTION_ACTION_RETRY_CACHE.load(deps.storage)?;
+    POST_EXECUTION_ACTION_RETRY_CACHE.remove(deps.storage);
+
+    action.attempts += 1;
+
+    let response = Response::new().add_attribute("vault_id", vault_id);
+
+    match reply.result {
+        SubMsgResult::Ok(sub_msg_response) => {
+            let mut response = response.add_attribute("post_execution_action", "succeeded");
+
+            if let Some(sequence) = save_sent_ibc_transfer(
+                deps.storage,
+                vault_id,
+                &action.msg.msg,
+                &sub_msg_response.events,
+                action.fallback_address,
+            )? {
+                response = response.add_attribute("ibc_sequence", sequence.to_string());
+            }
+
+            Ok(response)
+        }
+        SubMsgResult::Err(_) => {
+            create_event(
+                deps.storage,
+                EventBuilder::new(
+                    vault_id,
+                    env.block.clone(),
+                    EventData::DcaVaultPostExecutionActionFailed {
+                        msg: action.msg.clone(),
+                        funds: action.funds.clone(),
+                    },
+                ),
+            )?;
+
+            let response = response.add_attribute("post_execution_action", "failed");
+
+            if should_refund(&get_config(deps.storage)?, &env, &action) {
+                return Ok(response
+                    .add_attribute("refunded_to", action.fallback_address.to_string())
+                    .add_submessage(SubMsg::new(into_bank_msg(
+                        deps.api,
+                        action.fallback_address.as_ref(),
+                        action.funds,
+                    )?)));
+            }
+
+            push_pending_post_execution_action(deps.storage, vault_id, action)?;
+
+            Ok(response.add_attribute("queued_for_retry", "true"))
+        }
+    }
+}
+
+fn should_refund(config: &Config, env: &Env, action: &PendingPostExecutionAction) -> bool {
+    action.attempts >= config.post_execution_action_max_retry_attempts
+        || action
+            .failed_at
+            .plus_seconds(config.post_execution_action_max_retry_age_seconds)
+            <= env.block.time
+}
+
+#[cfg(test)]
+mod retry_post_execution_action_handler_tests {
+    use super::retry_post_execution_action_handler;
+    use crate::{
+        constants::AFTER_POST_EXECUTION_ACTION_RETRY_REPLY_ID,
+        helpers::ibc::{create_ibc_transfer_message, decode_ibc_transfer_message},
+        state::{
+            cache::POST_EXECUTION_ACTION_RETRY_CACHE,
+            config::{get_config, update_config},
+            post_execution_action_retries::{
+                get_pending_post_execution_actions, push_pending_post_execution_action,
+                PendingPostExecutionAction,
+            },
+        },
+        tests::{
+            helpers::{instantiate_contract, setup_vault},
+            mocks::{ADMIN, USER},
+        },
+        types::{config::Config, vault::Vault},
+    };
+    use cosmwasm_std::{
+        testing::{mock_dependencies, mock_env, mock_info},
+        to_json_binary, BankMsg, Coin, SubMsg, Timestamp, WasmMsg,
+    };
+
+    fn pending_action(
+        vault: &Vault,
+        attempts: u32,
+        failed_at: Timestamp,
+    ) -> PendingPostExecutionAction {
+        let funds = vec![Coin::new(
+            vault.swap_amount.into(),
+            vault.target_denom.clone(),
+        )];
+
+        PendingPostExecutionAction {
+            msg: SubMsg::new(WasmMsg::Execute {
+                contract_addr: "contract".to_string(),
+                msg: to_json_binary("test").unwrap(),
+                funds: funds.clone(),
+            }),
+            funds,
+            fallback_address: vault.owner.clone(),
+            attempts,
+            failed_at,
+        }
+    }
+
+    #[test]
+    fn with_no_pending_actions_fails() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        let err =
+            retry_post_execution_action_handler(deps.as_mut(), env, mock_info(USER, &[]), vault.id)
+                .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "Error: vault with id {} has no pending post execution actions",
+                vault.id
+            )
+        );
+    }
+
+    #[test]
+    fn with_unauthorised_sender_fails() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        push_pending_post_execution_action(
+            deps.as_mut().storage,
+            vault.id,
+            pending_action(&vault, 0, env.block.time),
+        )
+        .unwrap();
+
+        let err = retry_post_execution_action_handler(
+            deps.as_mut(),
+            env,
+            mock_info("not-owner", &[]),
+            vault.id,
+        )
+        .unwrap_err();
+
+        assert_eq!(err.to_string(), "Unauthorized");
+    }
+
+    #[test]
+    fn replays_pending_action() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+        let action = pending_action(&vault, 0, env.block.time);
+
+        push_pending_post_execution_action(deps.as_mut().storage, vault.id, action.clone())
+            .unwrap();
+
+        let response =
+            retry_post_execution_action_handler(deps.as_mut(), env, mock_info(USER, &[]), vault.id)
+                .unwrap();
+
+        assert_eq!(
+            response.messages,
+            vec![SubMsg::reply_always(
+                action.msg.msg.clone(),
+                AFTER_POST_EXECUTION_ACTION_RETRY_REPLY_ID
+            )]
+        );
+        assert_eq!(
+            POST_EXECUTION_ACTION_RETRY_CACHE
+                .load(deps.as_ref().storage)
+                .unwrap(),
+            (vault.id, action)
+        );
+        assert!(
+            get_pending_post_execution_actions(deps.as_ref().storage, vault.id)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn with_max_attempts_reached_refunds_fallback_address() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+        let action = pending_action(
+            &vault,
+            get_config(deps.as_ref().storage)
+                .unwrap()
+                .post_execution_action_max_retry_attempts,
+            env.block.time,
+        );
+
+        push_pending_post_execution_action(deps.as_mut().storage, vault.id, action.clone())
+            .unwrap();
+
+        let response = retry_post_execution_action_handler(
+            deps.as_mut(),
+            env,
+            mock_info(ADMIN, &[]),
+            vault.id,
+        )
+        .unwrap();
+
+        assert_eq!(
+            response.messages,
+            vec![SubMsg::new(BankMsg::Send {
+                to_address: vault.owner.to_string(),
+                amount: action.funds,
+            })]
+        );
+    }
+
+    #[test]
+    fn with_expired_action_refunds_fallback_address() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+        let action = pending_action(
+            &vault,
+            0,
+            env.block.time.minus_seconds(
+                get_config(deps.as_ref().storage)
+                    .unwrap()
+                    .post_execution_action_max_retry_age_seconds,
+            ),
+        );
+
+        push_pending_post_execution_action(deps.as_mut().storage, vault.id, action.clone())
+            .unwrap();
+
+        let response =
+            retry_post_execution_action_handler(deps.as_mut(), env, mock_info(USER, &[]), vault.id)
+                .unwrap();
+
+        assert_eq!(
+            response.messages,
+            vec![SubMsg::new(BankMsg::Send {
+                to_address: vault.owner.to_string(),
+                amount: action.funds,
+            })]
+        );
+        assert!(POST_EXECUTION_ACTION_RETRY_CACHE
+            .may_load(deps.as_ref().storage)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn with_configured_max_attempts_reached_refunds_fallback_address() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let config = get_config(deps.as_ref().storage).unwrap();
+
+        update_config(
+            deps.as_mut().storage,
+            Config {
+                post_execution_action_max_retry_attempts: 1,
+                ..config
+            },
+        )
+        .unwrap();
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+        let action = pending_action(&vault, 1, env.block.time);
+
+        push_pending_post_execution_action(deps.as_mut().storage, vault.id, action.clone())
+            .unwrap();
+
+        let response =
+            retry_post_execution_action_handler(deps.as_mut(), env, mock_info(USER, &[]), vault.id)
+                .unwrap();
+
+        assert_eq!(
+            response.messages,
+            vec![SubMsg::new(BankMsg::Send {
+                to_address: vault.owner.to_string(),
+                amount: action.funds,
+            })]
+        );
+    }
+
+    #[test]
+    fn with_ibc_transfer_recomputes_timeout() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        let failed_at = env.block.time.minus_seconds(3600);
+        let funds = Coin::new(vault.swap_amount.into(), vault.target_denom.clone());
+
+        push_pending_post_execution_action(
+            deps.as_mut().storage,
+            vault.id,
+            PendingPostExecutionAction {
+                msg: SubMsg::new(create_ibc_transfer_message(
+                    &env.contract.address,
+                    "channel-0".to_string(),
+                    "osmo1receiver".to_string(),
+                    funds.clone(),
+                    failed_at.plus_seconds(600),
+                )),
+                funds: vec![funds],
+                fallback_address: vault.owner.clone(),
+                attempts: 0,
+                failed_at,
+            },
+        )
+        .unwrap();
+
+        let response = retry_post_execution_action_handler(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(USER, &[]),
+            vault.id,
+        )
+        .unwrap();
+
+        assert_eq!(
+            decode_ibc_transfer_message(&response.messages[0].msg)
+                .unwrap()
+                .timeout_timestamp,
+            env.block.time.plus_seconds(600).nanos()
+        );
+    }
+}
+
+#[cfg(test)]
+mod handle_post_execution_action_retry_result_tests {
+    use super::handle_post_execution_action_retry_result;
+    use crate::{
+        constants::AFTER_POST_EXECUTION_ACTION_RETRY_REPLY_ID,
+        state::{
+            cache::POST_EXECUTION_ACTION_RETRY_CACHE,
+            config::get_config,
+            post_execution_action_retries::{
+                get_pending_post_execution_actions, PendingPostExecutionAction,
+            },
+        },
+        tests::{
+            helpers::{instantiate_contract, setup_vault},
+            mocks::ADMIN,
+        },
+        types::vault::Vault,
+    };
+    use cosmwasm_std::{
+        testing::{mock_dependencies, mock_env, mock_info},
+        to_json_binary, BankMsg, Coin, DepsMut, Reply, SubMsg, SubMsgResponse, SubMsgResult,
+        Timestamp, WasmMsg,
+    };
+
+    fn cache_action(deps: DepsMut, vault: &Vault, attempts: u32, failed_at: Timestamp) {
+        let funds = vec![Coin::new(
+            vault.swap_amount.into(),
+            vault.target_denom.clone(),
+        )];
+
+        POST_EXECUTION_ACTION_RETRY_CACHE
+            .save(
+                deps.storage,
+                &(
+                    vault.id,
+                    PendingPostExecutionAction {
+                        msg: SubMsg::new(WasmMsg::Execute {
+                            contract_addr: "contract".to_string(),
+                            msg: to_json_binary("test").unwrap(),
+                            funds: funds.clone(),
+                        }),
+                        funds,
+                        fallback_address: vault.owner.clone(),
+                        attempts,
+                        failed_at,
+                    },
+                ),
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn on_success_does_not_requeue_action() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        cache_action(deps.as_mut(), &vault, 0, env.block.time);
+
+        let response = handle_post_execution_action_retry_result(
+            deps.as_mut(),
+            env,
+            Reply {
+                id: AFTER_POST_EXECUTION_ACTION_RETRY_REPLY_ID,
+                result: SubMsgResult::Ok(SubMsgResponse {
+                    events: vec![],
+                    data: None,
+                }),
+            },
+        )
+        .unwrap();
+
+        assert!(response.messages.is_empty());
+        assert!(
+            get_pending_post_execution_actions(deps.as_ref().storage, vault.id)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn on_failure_requeues_action_with_incremented_attempts() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        cache_action(deps.as_mut(), &vault, 0, env.block.time);
+
+        handle_post_execution_action_retry_result(
+            deps.as_mut(),
+            env,
+            Reply {
+                id: AFTER_POST_EXECUTION_ACTION_RETRY_REPLY_ID,
+                result: SubMsgResult::Err("error".to_string()),
+            },
+        )
+        .unwrap();
+
+        let pending_actions =
+            get_pending_post_execution_actions(deps.as_ref().storage, vault.id).unwrap();
+
+        assert_eq!(pending_actions.len(), 1);
+        assert_eq!(pending_actions[0].attempts, 1);
+    }
+
+    #[test]
+    fn on_final_failure_refunds_fallback_address() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        let config = get_config(deps.as_ref().storage).unwrap();
+
+        cache_action(
+            deps.as_mut(),
+            &vault,
+            config.post_execution_action_max_retry_attempts - 1,
+            env.block.time,
+        );
+
+        let response = handle_post_execution_action_retry_result(
+            deps.as_mut(),
+            env,
+            Reply {
+                id: AFTER_POST_EXECUTION_ACTION_RETRY_REPLY_ID,
+                result: SubMsgResult::Err("error".to_string()),
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            response.messages,
+            vec![SubMsg::new(BankMsg::Send {
+                to_address: vault.owner.to_string(),
+                amount: vec![Coin::new(vault.swap_amount.into(), vault.target_denom)],
+            })]
+        );
+        assert!(
+            get_pending_post_execution_actions(deps.as_ref().storage, vault.id)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn on_failure_of_expired_action_refunds_fallback_address() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        let config = get_config(deps.as_ref().storage).unwrap();
+
+        cache_action(
+            deps.as_mut(),
+            &vault,
+            0,
+            env.block
+                .time
+                .minus_seconds(config.post_execution_action_max_retry_age_seconds),
+        );
+
+        let response = handle_post_execution_action_retry_result(
+            deps.as_mut(),
+            env,
+            Reply {
+                id: AFTER_POST_EXECUTION_ACTION_RETRY_REPLY_ID,
+                result: SubMsgResult::Err("error".to_string()),
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            response.messages,
+            vec![SubMsg::new(BankMsg::Send {
+                to_address: vault.owner.to_string(),
+                amount: vec![Coin::new(vault.swap_amount.into(), vault.target_denom)],
+            })]
+        );
+        assert!(
+            get_pending_post_execution_actions(deps.as_ref().storage, vault.id)
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
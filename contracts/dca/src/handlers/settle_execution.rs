@@ -0,0 +1,527 @@
+use crate::error::ContractError;
+use crate::helpers::disbursement::get_disbursement_messages;
+use crate::helpers::fees::{
+    deduct_keeper_bounty, deduct_referral_fee, get_automation_fee_rate, get_fee_messages,
+    get_swap_fee_rate,
+};
+use crate::helpers::math::checked_mul;
+use crate::helpers::time::get_next_target_time;
+use crate::helpers::vault::end_vault;
+use crate::msg::ExecuteMsg;
+use crate::state::config::get_config;
+use crate::state::events::create_event;
+use crate::state::triggers::{delete_trigger, save_trigger};
+use crate::state::vaults::{get_vault, update_vault};
+use crate::types::end_conditions::EndConditions;
+use crate::types::event::{EventBuilder, EventData};
+use crate::types::netted_execution::NettedExecution;
+use crate::types::trigger::{Trigger, TriggerConfiguration};
+use crate::types::vault::{Vault, VaultStatus};
+use cosmwasm_std::{
+    to_json_binary, Addr, Attribute, Coin, Decimal, DepsMut, Env, MessageInfo, Response, StdResult,
+    SubMsg, Uint128, WasmMsg,
+};
+use shared::coin::add_to;
+use shared::cw20::into_bank_msg;
+
+pub fn settle_execution_handler(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    vault_id: Uint128,
+    sent: Coin,
+    received: Coin,
+    asset_price: Decimal,
+    residual: Coin,
+    keeper: Option<Addr>,
+) -> Result<Response, ContractError> {
+    if info.sender != env.contract.address {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut vault = get_vault(deps.storage, vault_id)?;
+
+    let is_fully_netted = residual.amount.is_zero();
+
+    let mut attributes = vec![
+        Attribute::new("settle_execution", "true"),
+        Attribute::new("vault_id", vault.id),
+        Attribute::new("owner", vault.owner.clone()),
+    ];
+
+    let mut sub_msgs = Vec::<SubMsg>::new();
+
+    let swap_fee_rate = get_swap_fee_rate(deps.storage, &vault)?;
+    let automation_fee_rate = get_automation_fee_rate(deps.storage, &vault)?;
+
+    let swap_fee = checked_mul(received.amount, swap_fee_rate)?;
+    let total_after_swap_fee = received.amount - swap_fee;
+    let automation_fee = checked_mul(total_after_swap_fee, automation_fee_rate)?;
+    let total_fee = swap_fee + automation_fee;
+    let total_after_all_fees = received.amount - total_fee;
+
+    let fee_amounts = match vault.referrer.clone() {
+        Some(referrer) => {
+            let (fee_amounts, referral_fee) = deduct_referral_fee(
+                deps.storage,
+                &referrer,
+                vec![swap_fee, automation_fee],
+                &received.denom,
+            )?;
+
+            if !referral_fee.amount.is_zero() {
+                sub_msgs.push(SubMsg::new(into_bank_msg(
+                    deps.api,
+                    referrer.as_ref(),
+                    vec![referral_fee.clone()],
+                )?));
+
+                attributes.push(Attribute::new("referrer", referrer.to_string()));
+                attributes.push(Attribute::new("referral_fee", referral_fee.to_string()));
+            }
+
+            fee_amounts
+        }
+        None => vec![swap_fee, automation_fee],
+    };
+
+    let fee_amounts = match keeper {
+        Some(keeper) => {
+            let (fee_amounts, keeper_bounty) =
+                deduct_keeper_bounty(fee_amounts, get_config(deps.storage)?.keeper_bounty_percent);
+
+            if !keeper_bounty.is_zero() {
+                sub_msgs.push(SubMsg::new(into_bank_msg(
+                    deps.api,
+                    keeper.as_ref(),
+                    vec![Coin::new(keeper_bounty.into(), received.denom.clone())],
+                )?));
+
+                attributes.push(Attribute::new("keeper", keeper.to_string()));
+                attributes.push(Attribute::new(
+                    "keeper_bounty",
+                    Coin::new(keeper_bounty.into(), received.denom.clone()).to_string(),
+                ));
+            }
+
+            fee_amounts
+        }
+        None => fee_amounts,
+    };
+
+    sub_msgs.append(&mut get_fee_messages(
+        deps.as_ref(),
+        env.clone(),
+        fee_amounts,
+        received.denom.clone(),
+        false,
+    )?);
+
+    let amount_to_escrow = total_after_all_fees * vault.escrow_level;
+    let total_after_escrow = total_after_all_fees - amount_to_escrow;
+
+    vault.balance.amount -= sent.amount;
+
+    vault = update_vault(
+        deps.storage,
+        Vault {
+            swapped_amount: add_to(&vault.swapped_amount, sent.amount),
+            received_amount: add_to(&vault.received_amount, total_after_all_fees),
+            escrowed_amount: add_to(&vault.escrowed_amount, amount_to_escrow),
+            end_conditions: vault.end_conditions.map(|end_conditions| EndConditions {
+                executions: if is_fully_netted {
+                    end_conditions.executions + 1
+                } else {
+                    end_conditions.executions
+                },
+                ..end_conditions
+            }),
+            status: if vault.balance.amount.is_zero() && !vault.recurring_deposits {
+                VaultStatus::Inactive
+            } else {
+                vault.status
+            },
+            ..vault
+        },
+    )?;
+
+    sub_msgs.append(
+        &mut get_disbursement_messages(
+            deps.api,
+            deps.storage,
+            &env,
+            &vault,
+            Coin::new(total_after_escrow.into(), received.denom.clone()),
+        )?
+        .into(),
+    );
+
+    if is_fully_netted {
+        create_event(
+            deps.storage,
+            EventBuilder::new(
+                vault.id,
+                env.block.clone(),
+                EventData::DcaVaultExecutionTriggered {
+                    base_denom: vault.target_denom.clone(),
+                    quote_denom: vault.get_swap_denom(),
+                    asset_price,
+                    missed_executions: None,
+                },
+            ),
+        )?;
+    }
+
+    create_event(
+        deps.storage,
+        EventBuilder::new(
+            vault.id,
+            env.block.clone(),
+            EventData::DcaVaultExecutionCompleted {
+                sent: sent.clone(),
+                received: received.clone(),
+                fee: Coin::new(total_fee.into(), received.denom.clone()),
+            },
+        ),
+    )?;
+
+    attributes.push(Attribute::new("asset_price", asset_price.to_string()));
+    attributes.push(Attribute::new("swapped_amount", sent.to_string()));
+    attributes.push(Attribute::new("received_amount", received.to_string()));
+    attributes.push(Attribute::new("fee_amount", total_fee.to_string()));
+    attributes.push(Attribute::new("residual_swap_amount", residual.to_string()));
+
+    if !is_fully_netted {
+        return Ok(Response::new()
+            .add_attributes(attributes)
+            .add_submessages(sub_msgs));
+    }
+
+    delete_trigger(deps.storage, vault.id)?;
+
+    if let Some(condition) = vault.get_reached_end_condition(env.block.time) {
+        attributes.push(Attribute::new(
+            "end_condition_reached",
+            format!("{:?}", condition),
+        ));
+
+        let (_, mut end_vault_msgs) = end_vault(deps.storage, &env, vault, condition)?;
+        sub_msgs.append(&mut end_vault_msgs);
+
+        return Ok(Response::new()
+            .add_attributes(attributes)
+            .add_submessages(sub_msgs));
+    }
+
+    if vault.is_active() {
+        save_trigger(
+            deps.storage,
+            Trigger {
+                vault_id: vault.id,
+                configuration: TriggerConfiguration::Time {
+                    target_time: get_next_target_time(
+                        env.block.time,
+                        vault.started_at.unwrap_or(env.block.time),
+                        vault.time_interval.clone(),
+                    ),
+                },
+            },
+        )?;
+    } else if vault.should_not_continue() && vault.escrowed_amount.amount > Uint128::zero() {
+        sub_msgs.push(SubMsg::new(WasmMsg::Execute {
+            contract_addr: env.contract.address.to_string(),
+            msg: to_json_binary(&ExecuteMsg::DisburseEscrow { vault_id: vault.id })?,
+            funds: vec![],
+        }));
+    }
+
+    Ok(Response::new()
+        .add_attributes(attributes)
+        .add_submessages(sub_msgs))
+}
+
+pub fn settle_netted_executions_handler(
+    env: Env,
+    info: MessageInfo,
+    executions: Vec<NettedExecution>,
+    keeper: Option<Addr>,
+) -> Result<Response, ContractError> {
+    if info.sender != env.contract.address {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let sub_msgs = executions
+        .iter()
+        .map(|execution| {
+            Ok(SubMsg::new(WasmMsg::Execute {
+                contract_addr: env.contract.address.to_string(),
+                msg: to_json_binary(&ExecuteMsg::SettleExecution {
+                    vault_id: execution.vault_id,
+                    sent: execution.sent.clone(),
+                    received: execution.received.clone(),
+                    asset_price: execution.asset_price,
+                    residual: execution.residual.clone(),
+                    keeper: keeper.clone(),
+                })?,
+                funds: vec![],
+            }))
+        })
+        .collect::<StdResult<Vec<SubMsg>>>()?;
+
+    Ok(Response::new()
+        .add_attribute("settle_netted_executions", "true")
+        .add_attribute(
+            "vault_ids",
+            executions
+                .iter()
+                .map(|execution| execution.vault_id.to_string())
+                .collect::<Vec<String>>()
+                .join(","),
+        )
+        .add_submessages(sub_msgs))
+}
+
+#[cfg(test)]
+mod settle_execution_tests {
+    use super::*;
+    use crate::{
+        constants::{AFTER_FAILED_AUTOMATION_REPLY_ID, ONE, TEN},
+        handlers::get_events_by_resource_id::get_events_by_resource_id_handler,
+        helpers::message::get_vault_reply_id,
+        state::triggers::get_trigger,
+        tests::{
+            helpers::{instantiate_contract, setup_vault},
+            mocks::{calc_mock_dependencies, ADMIN, DENOM_UKUJI, DENOM_UUSK},
+        },
+        types::time_interval::TimeInterval,
+    };
+    use cosmwasm_std::testing::{mock_env, mock_info};
+
+    fn get_received_after_fees(deps: &DepsMut, vault: &Vault, received: Uint128) -> Uint128 {
+        let swap_fee =
+            checked_mul(received, get_swap_fee_rate(deps.storage, vault).unwrap()).unwrap();
+        let automation_fee = checked_mul(
+            received - swap_fee,
+            get_automation_fee_rate(deps.storage, vault).unwrap(),
+        )
+        .unwrap();
+
+        received - swap_fee - automation_fee
+    }
+
+    #[test]
+    fn with_sender_other_than_contract_should_fail() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        let err = settle_execution_handler(
+            deps.as_mut(),
+            env,
+            mock_info(ADMIN, &[]),
+            vault.id,
+            Coin::new(ONE.into(), DENOM_UKUJI),
+            Coin::new(ONE.into(), DENOM_UUSK),
+            Decimal::one(),
+            Coin::new(0, DENOM_UKUJI),
+            None,
+        )
+        .unwrap_err();
+
+        assert_eq!(err.to_string(), "Unauthorized");
+    }
+
+    #[test]
+    fn with_fully_netted_execution_should_update_vault_and_reschedule_trigger() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        let response = settle_execution_handler(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(env.contract.address.as_ref(), &[]),
+            vault.id,
+            Coin::new(ONE.into(), DENOM_UKUJI),
+            Coin::new(ONE.into(), DENOM_UUSK),
+            Decimal::one(),
+            Coin::new(0, DENOM_UKUJI),
+            None,
+        )
+        .unwrap();
+
+        let received_after_fees = get_received_after_fees(&deps.as_mut(), &vault, ONE);
+        let updated_vault = get_vault(deps.as_ref().storage, vault.id).unwrap();
+
+        assert_eq!(
+            updated_vault.balance,
+            Coin::new((TEN - ONE).into(), DENOM_UKUJI)
+        );
+        assert_eq!(
+            updated_vault.swapped_amount,
+            Coin::new(ONE.into(), DENOM_UKUJI)
+        );
+        assert_eq!(
+            updated_vault.received_amount,
+            Coin::new(received_after_fees.into(), DENOM_UUSK)
+        );
+        assert_eq!(
+            get_trigger(deps.as_ref().storage, vault.id)
+                .unwrap()
+                .unwrap()
+                .configuration,
+            TriggerConfiguration::Time {
+                target_time: get_next_target_time(
+                    env.block.time,
+                    env.block.time,
+                    TimeInterval::Daily
+                )
+            }
+        );
+        assert!(response
+            .messages
+            .iter()
+            .any(|msg| msg.id == get_vault_reply_id(AFTER_FAILED_AUTOMATION_REPLY_ID, vault.id)));
+
+        let events = get_events_by_resource_id_handler(deps.as_ref(), vault.id, None, None, None)
+            .unwrap()
+            .events;
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(
+            events[0].data,
+            EventData::DcaVaultExecutionTriggered {
+                base_denom: DENOM_UUSK.to_string(),
+                quote_denom: DENOM_UKUJI.to_string(),
+                asset_price: Decimal::one(),
+                missed_executions: None,
+            }
+        );
+        assert_eq!(
+            events[1].data,
+            EventData::DcaVaultExecutionCompleted {
+                sent: Coin::new(ONE.into(), DENOM_UKUJI),
+                received: Coin::new(ONE.into(), DENOM_UUSK),
+                fee: Coin::new((ONE - received_after_fees).into(), DENOM_UUSK),
+            }
+        );
+    }
+
+    #[test]
+    fn with_partially_netted_execution_should_leave_trigger_for_residual_swap() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        settle_execution_handler(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(env.contract.address.as_ref(), &[]),
+            vault.id,
+            Coin::new((ONE / Uint128::new(4)).into(), DENOM_UKUJI),
+            Coin::new((ONE / Uint128::new(4)).into(), DENOM_UUSK),
+            Decimal::one(),
+            Coin::new((ONE - ONE / Uint128::new(4)).into(), DENOM_UKUJI),
+            None,
+        )
+        .unwrap();
+
+        let updated_vault = get_vault(deps.as_ref().storage, vault.id).unwrap();
+
+        assert_eq!(
+            updated_vault.balance,
+            Coin::new((TEN - ONE / Uint128::new(4)).into(), DENOM_UKUJI)
+        );
+        assert_eq!(updated_vault.trigger, vault.trigger);
+
+        let events = get_events_by_resource_id_handler(deps.as_ref(), vault.id, None, None, None)
+            .unwrap()
+            .events;
+
+        assert_eq!(events.len(), 1);
+    }
+}
+
+#[cfg(test)]
+mod settle_netted_executions_tests {
+    use super::*;
+    use crate::{
+        constants::ONE,
+        tests::mocks::{ADMIN, DENOM_UKUJI, DENOM_UUSK},
+    };
+    use cosmwasm_std::testing::{mock_env, mock_info};
+
+    fn netted_executions() -> Vec<NettedExecution> {
+        vec![
+            NettedExecution {
+                vault_id: Uint128::one(),
+                sent: Coin::new(ONE.into(), DENOM_UKUJI),
+                received: Coin::new(ONE.into(), DENOM_UUSK),
+                residual: Coin::new(0, DENOM_UKUJI),
+                asset_price: Decimal::one(),
+            },
+            NettedExecution {
+                vault_id: Uint128::new(2),
+                sent: Coin::new(ONE.into(), DENOM_UUSK),
+                received: Coin::new(ONE.into(), DENOM_UKUJI),
+                residual: Coin::new(0, DENOM_UUSK),
+                asset_price: Decimal::one(),
+            },
+        ]
+    }
+
+    #[test]
+    fn with_sender_other_than_contract_should_fail() {
+        let err = settle_netted_executions_handler(
+            mock_env(),
+            mock_info(ADMIN, &[]),
+            netted_executions(),
+            None,
+        )
+        .unwrap_err();
+
+        assert_eq!(err.to_string(), "Unauthorized");
+    }
+
+    #[test]
+    fn should_settle_every_leg_without_catching_errors() {
+        let env = mock_env();
+
+        let response = settle_netted_executions_handler(
+            env.clone(),
+            mock_info(env.contract.address.as_str(), &[]),
+            netted_executions(),
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(
+            response.messages,
+            netted_executions()
+                .into_iter()
+                .map(|execution| SubMsg::new(WasmMsg::Execute {
+                    contract_addr: env.contract.address.to_string(),
+                    msg: to_json_binary(&ExecuteMsg::SettleExecution {
+                        vault_id: execution.vault_id,
+                        sent: execution.sent,
+                        received: execution.received,
+                        asset_price: execution.asset_price,
+                        residual: execution.residual,
+                        keeper: None,
+                    })
+                    .unwrap(),
+                    funds: vec![],
+                }))
+                .collect::<Vec<SubMsg>>()
+        );
+    }
+}
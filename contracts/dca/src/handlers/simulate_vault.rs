@@ -0,0 +1,569 @@
+use crate::{
+    constants::TWO_MICRONS,
+    handlers::create_vault::get_vault_builder,
+    helpers::{
+        fees::{get_automation_fee_rate, get_performance_fee_rate, get_swap_fee_rate},
+        price::get_twap_to_now,
+        time::get_next_target_time,
+        vault::get_swap_amount,
+    },
+    msg::{VaultSimulation, VaultSimulationResponse},
+    state::config::get_config,
+    types::{
+        basket::{get_basket_swap_amounts, BasketAssetParams},
+        deposit_conversion::DepositConversion,
+        destination::Destination,
+        end_conditions::{EndConditions, EndConditionsParams},
+        escrow_vesting::EscrowVestingSchedule,
+        missed_execution_policy::MissedExecutionPolicy,
+        performance_assessment_strategy::PerformanceAssessmentStrategyParams,
+        retry_policy::RetryPolicy,
+        swap_adjustment_strategy::{SwapAdjustmentStrategy, SwapAdjustmentStrategyParams},
+        time_interval::TimeInterval,
+        vault::Vault,
+    },
+};
+use cosmwasm_std::{Addr, Binary, Coin, Decimal, Deps, Env, StdResult, Timestamp, Uint128, Uint64};
+
+#[allow(clippy::too_many_arguments)]
+pub fn simulate_vault_handler(
+    deps: Deps,
+    env: Env,
+    deposit: Coin,
+    owner: Addr,
+    label: Option<String>,
+    destinations: Vec<Destination>,
+    target_denom: String,
+    route: Option<Binary>,
+    slippage_tolerance: Option<Decimal>,
+    minimum_receive_amount: Option<Uint128>,
+    swap_amount: Uint128,
+    time_interval: TimeInterval,
+    target_start_time_utc_seconds: Option<Uint64>,
+    target_receive_amount: Option<Uint128>,
+    performance_assessment_strategy_params: Option<PerformanceAssessmentStrategyParams>,
+    swap_adjustment_strategy_params: Option<SwapAdjustmentStrategyParams>,
+    basket: Option<Vec<BasketAssetParams>>,
+    end_conditions: Option<EndConditionsParams>,
+    missed_execution_policy: Option<MissedExecutionPolicy>,
+    allow_partial_executions: Option<bool>,
+    retry_policy: Option<RetryPolicy>,
+    retry_failed_post_execution_actions: Option<bool>,
+    recurring_deposits: Option<bool>,
+    deposit_conversion: Option<DepositConversion>,
+    referrer: Option<Addr>,
+    escrow_vesting: Option<EscrowVestingSchedule>,
+    escrow_level: Option<Decimal>,
+) -> StdResult<VaultSimulationResponse> {
+    let vault_builder = match get_vault_builder(
+        deps,
+        &env,
+        std::slice::from_ref(&deposit),
+        owner,
+        label,
+        destinations,
+        target_denom,
+        route,
+        slippage_tolerance,
+        minimum_receive_amount,
+        swap_amount,
+        time_interval,
+        target_start_time_utc_seconds,
+        target_receive_amount,
+        performance_assessment_strategy_params,
+        swap_adjustment_strategy_params,
+        basket,
+        end_conditions,
+        missed_execution_policy,
+        allow_partial_executions,
+        retry_policy,
+        retry_failed_post_execution_actions,
+        recurring_deposits,
+        &deposit_conversion,
+        referrer,
+        escrow_vesting,
+        escrow_level,
+    ) {
+        Ok(vault_builder) => vault_builder,
+        Err(err) => {
+            return Ok(VaultSimulationResponse {
+                simulation: None,
+                validation_error: Some(err.to_string()),
+            })
+        }
+    };
+
+    let expected_balance =
+        deposit_conversion.map_or(deposit, |conversion| conversion.minimum_receive_amount);
+
+    // price triggered vaults fund their limit order from the deposit
+    let expected_balance = match target_receive_amount {
+        Some(_) => Coin::new(
+            expected_balance.amount.saturating_sub(TWO_MICRONS).into(),
+            expected_balance.denom,
+        ),
+        None => expected_balance,
+    };
+
+    let vault = Vault {
+        balance: expected_balance.clone(),
+        deposited_amount: expected_balance,
+        ..vault_builder.build(Uint128::zero())
+    };
+
+    let config = get_config(deps.storage)?;
+
+    let start_time = match target_start_time_utc_seconds {
+        Some(seconds) => Timestamp::from_seconds(seconds.u64()),
+        None if vault.time_interval.is_calendar_schedule() => {
+            get_next_target_time(env.block.time, env.block.time, vault.time_interval.clone())
+        }
+        None => env.block.time,
+    };
+
+    let swap_amount = match &vault.basket {
+        Some(basket) => get_basket_swap_amounts(basket, vault.swap_amount)
+            .into_iter()
+            .sum(),
+        None => {
+            let adjusted_swap_amount = get_swap_amount(&deps, &env, &vault)?.amount;
+
+            // an adjustment of zero skips executions until it recovers, so estimate those
+            // executions at the unadjusted swap amount
+            if adjusted_swap_amount.is_zero() {
+                vault.swap_amount
+            } else {
+                adjusted_swap_amount
+            }
+        }
+    };
+
+    let receive_price = match (&vault.basket, target_receive_amount, &vault.end_conditions) {
+        (None, Some(target_receive_amount), _) => Some(Decimal::from_ratio(
+            vault.swap_amount,
+            target_receive_amount,
+        )),
+        (
+            None,
+            None,
+            Some(EndConditions {
+                received_amount_goal: Some(_),
+                ..
+            }),
+        ) => Some(get_twap_to_now(
+            &deps.querier,
+            config.exchange_contract_address.clone(),
+            vault.get_swap_denom(),
+            vault.target_denom.clone(),
+            config.twap_period,
+            vault.route.clone(),
+        )?),
+        _ => None,
+    };
+
+    let expected_executions =
+        vault.get_expected_execution_count(start_time, swap_amount, receive_price);
+
+    let expected_completion_date = match target_receive_amount {
+        Some(_) => None,
+        None => Some(vault.get_execution_completed_date(start_time, expected_executions)),
+    };
+
+    Ok(VaultSimulationResponse {
+        simulation: Some(VaultSimulation {
+            expected_executions,
+            expected_completion_date,
+            risk_weighted_average_model_id: match vault.swap_adjustment_strategy {
+                Some(SwapAdjustmentStrategy::RiskWeightedAverage { model_id, .. }) => {
+                    Some(model_id)
+                }
+                _ => None,
+            },
+            swap_fee_rate: get_swap_fee_rate(deps.storage, &vault)?,
+            automation_fee_rate: get_automation_fee_rate(deps.storage, &vault)?,
+            performance_fee_rate: vault
+                .performance_assessment_strategy
+                .as_ref()
+                .map(|strategy| get_performance_fee_rate(&config, &vault, strategy)),
+            escrow_level: vault.escrow_level,
+        }),
+        validation_error: None,
+    })
+}
+
+#[cfg(test)]
+mod simulate_vault_tests {
+    use super::simulate_vault_handler;
+    use crate::{
+        handlers::{create_vault::create_vault_handler, get_vault::get_vault_handler},
+        helpers::fees::{get_automation_fee_rate, get_swap_fee_rate},
+        msg::VaultSimulation,
+        state::{config::get_config, swap_adjustments::update_swap_adjustment},
+        tests::{
+            helpers::instantiate_contract,
+            mocks::{calc_mock_dependencies, ADMIN, DENOM_UKUJI, DENOM_UUSK, USER},
+        },
+        types::{
+            basket::BasketAssetParams,
+            end_conditions::EndConditionsParams,
+            performance_assessment_strategy::PerformanceAssessmentStrategyParams,
+            swap_adjustment_strategy::{SwapAdjustmentStrategy, SwapAdjustmentStrategyParams},
+            time_interval::TimeInterval,
+        },
+    };
+    use cosmwasm_std::{
+        testing::{mock_env, mock_info},
+        Addr, Coin, Decimal, Deps, Env, Timestamp, Uint128, Uint64,
+    };
+
+    #[test]
+    fn with_invalid_parameters_returns_validation_error() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let response = simulate_vault_handler(
+            deps.as_ref(),
+            env,
+            Coin::new(100000, DENOM_UUSK),
+            Addr::unchecked(USER),
+            None,
+            vec![],
+            DENOM_UKUJI.to_string(),
+            None,
+            None,
+            None,
+            Uint128::new(100000),
+            TimeInterval::Daily,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(Addr::unchecked(USER)),
+            None,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(response.simulation, None);
+        assert_eq!(
+            response.validation_error,
+            Some("Error: vault owner cannot be its own referrer".to_string())
+        );
+    }
+
+    #[test]
+    fn with_valid_parameters_matches_created_vault() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let deposit = Coin::new(1000000050, DENOM_UUSK);
+        let target_start_time = Timestamp::from_seconds(env.block.time.plus_seconds(10).seconds());
+
+        let response = simulate_vault_handler(
+            deps.as_ref(),
+            env.clone(),
+            deposit.clone(),
+            Addr::unchecked(USER),
+            None,
+            vec![],
+            DENOM_UKUJI.to_string(),
+            None,
+            None,
+            None,
+            Uint128::new(100000),
+            TimeInterval::Daily,
+            Some(target_start_time.seconds().into()),
+            None,
+            Some(PerformanceAssessmentStrategyParams::CompareToStandardDca),
+            Some(SwapAdjustmentStrategyParams::default()),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(response.validation_error, None);
+
+        let info = mock_info(USER, &[deposit]);
+
+        create_vault_handler(
+            deps.as_mut(),
+            env.clone(),
+            &info,
+            info.sender.clone(),
+            None,
+            vec![],
+            DENOM_UKUJI.to_string(),
+            None,
+            None,
+            None,
+            Uint128::new(100000),
+            TimeInterval::Daily,
+            Some(target_start_time.seconds().into()),
+            None,
+            Some(PerformanceAssessmentStrategyParams::CompareToStandardDca),
+            Some(SwapAdjustmentStrategyParams::default()),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+
+        let vault = get_vault_handler(deps.as_ref(), Uint128::one())
+            .unwrap()
+            .vault;
+
+        let simulation = response.simulation.unwrap();
+
+        assert_eq!(simulation.expected_executions, Uint128::new(10001));
+        assert_eq!(
+            simulation.expected_completion_date,
+            Some(vault.get_expected_execution_completed_date(target_start_time))
+        );
+        assert_eq!(simulation.risk_weighted_average_model_id, Some(90));
+        assert_eq!(
+            simulation.swap_fee_rate,
+            get_swap_fee_rate(deps.as_ref().storage, &vault).unwrap()
+        );
+        assert_eq!(
+            simulation.automation_fee_rate,
+            get_automation_fee_rate(deps.as_ref().storage, &vault).unwrap()
+        );
+        assert!(simulation.performance_fee_rate.is_some());
+        assert_eq!(simulation.escrow_level, vault.escrow_level);
+        assert_eq!(
+            simulation.escrow_level,
+            get_config(deps.as_ref().storage)
+                .unwrap()
+                .risk_weighted_average_escrow_level
+        );
+        assert_ne!(simulation.escrow_level, Decimal::zero());
+    }
+
+    fn simulate(
+        deps: Deps,
+        env: Env,
+        deposit: Coin,
+        target_denom: &str,
+        target_receive_amount: Option<Uint128>,
+        swap_adjustment_strategy_params: Option<SwapAdjustmentStrategyParams>,
+        basket: Option<Vec<BasketAssetParams>>,
+        end_conditions: Option<EndConditionsParams>,
+    ) -> VaultSimulation {
+        let response = simulate_vault_handler(
+            deps,
+            env,
+            deposit,
+            Addr::unchecked(USER),
+            None,
+            vec![],
+            target_denom.to_string(),
+            None,
+            None,
+            None,
+            Uint128::new(100),
+            TimeInterval::Daily,
+            None,
+            target_receive_amount,
+            swap_adjustment_strategy_params
+                .as_ref()
+                .map(|_| PerformanceAssessmentStrategyParams::CompareToStandardDca),
+            swap_adjustment_strategy_params,
+            basket,
+            end_conditions,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(response.validation_error, None);
+
+        response.simulation.unwrap()
+    }
+
+    #[test]
+    fn with_swap_adjustment_estimates_executions_at_adjusted_swap_amount() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        update_swap_adjustment(
+            deps.as_mut().storage,
+            SwapAdjustmentStrategy::default(),
+            Decimal::percent(50),
+            env.block.time,
+        )
+        .unwrap();
+
+        let simulation = simulate(
+            deps.as_ref(),
+            env.clone(),
+            Coin::new(1000, DENOM_UUSK),
+            DENOM_UKUJI,
+            None,
+            Some(SwapAdjustmentStrategyParams::default()),
+            None,
+            None,
+        );
+
+        assert_eq!(simulation.expected_executions, Uint128::new(20));
+        assert_eq!(
+            simulation.expected_completion_date,
+            Some(env.block.time.plus_seconds(20 * 24 * 60 * 60))
+        );
+    }
+
+    #[test]
+    fn with_basket_splits_each_execution_across_legs() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let simulation = simulate(
+            deps.as_ref(),
+            env.clone(),
+            Coin::new(1050, DENOM_UUSK),
+            DENOM_UKUJI,
+            None,
+            None,
+            Some(vec![
+                BasketAssetParams {
+                    target_denom: DENOM_UKUJI.to_string(),
+                    weight: Decimal::percent(60),
+                    route: None,
+                },
+                BasketAssetParams {
+                    target_denom: "uatom".to_string(),
+                    weight: Decimal::percent(40),
+                    route: None,
+                },
+            ]),
+            None,
+        );
+
+        assert_eq!(simulation.expected_executions, Uint128::new(11));
+        assert_eq!(
+            simulation.expected_completion_date,
+            Some(env.block.time.plus_seconds(11 * 24 * 60 * 60))
+        );
+    }
+
+    #[test]
+    fn with_max_executions_caps_expected_executions() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let simulation = simulate(
+            deps.as_ref(),
+            env.clone(),
+            Coin::new(1000, DENOM_UUSK),
+            DENOM_UKUJI,
+            None,
+            None,
+            None,
+            Some(EndConditionsParams {
+                end_time_utc_seconds: None,
+                max_executions: Some(Uint64::new(3)),
+                received_amount_goal: None,
+            }),
+        );
+
+        assert_eq!(simulation.expected_executions, Uint128::new(3));
+        assert_eq!(
+            simulation.expected_completion_date,
+            Some(env.block.time.plus_seconds(3 * 24 * 60 * 60))
+        );
+    }
+
+    #[test]
+    fn with_end_time_caps_expected_executions() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let simulation = simulate(
+            deps.as_ref(),
+            env.clone(),
+            Coin::new(1000, DENOM_UUSK),
+            DENOM_UKUJI,
+            None,
+            None,
+            None,
+            Some(EndConditionsParams {
+                end_time_utc_seconds: Some(Uint64::new(
+                    env.block.time.plus_seconds(4 * 24 * 60 * 60).seconds(),
+                )),
+                max_executions: None,
+                received_amount_goal: None,
+            }),
+        );
+
+        assert_eq!(simulation.expected_executions, Uint128::new(4));
+    }
+
+    #[test]
+    fn with_target_receive_amount_excludes_limit_order_funds_and_has_no_completion_date() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let simulation = simulate(
+            deps.as_ref(),
+            env,
+            Coin::new(1002, DENOM_UUSK),
+            DENOM_UKUJI,
+            Some(Uint128::new(50)),
+            None,
+            None,
+            Some(EndConditionsParams {
+                end_time_utc_seconds: None,
+                max_executions: None,
+                received_amount_goal: Some(Uint128::new(200)),
+            }),
+        );
+
+        assert_eq!(simulation.expected_executions, Uint128::new(4));
+        assert_eq!(simulation.expected_completion_date, None);
+    }
+}
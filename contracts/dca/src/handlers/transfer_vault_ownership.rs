@@ -0,0 +1,195 @@
+use crate::error::ContractError;
+use crate::helpers::validation::{
+    assert_address_is_valid, assert_vault_is_not_cancelled, asset_sender_is_vault_owner,
+};
+use crate::state::vault_ownership_transfers::{
+    save_vault_ownership_transfer, VaultOwnershipTransfer,
+};
+use crate::state::vaults::get_vault;
+use cosmwasm_std::{Addr, DepsMut, MessageInfo, Response, Uint128};
+
+pub fn transfer_vault_ownership_handler(
+    deps: DepsMut,
+    info: MessageInfo,
+    vault_id: Uint128,
+    new_owner: Addr,
+    update_destinations: bool,
+) -> Result<Response, ContractError> {
+    let vault = get_vault(deps.storage, vault_id)?;
+
+    asset_sender_is_vault_owner(vault.owner.clone(), info.sender)?;
+    assert_vault_is_not_cancelled(&vault)?;
+    assert_address_is_valid(deps.as_ref(), &new_owner, "new owner")?;
+
+    if new_owner == vault.owner {
+        return Err(ContractError::CustomError {
+            val: format!("{} already owns vault {}", new_owner, vault.id),
+        });
+    }
+
+    save_vault_ownership_transfer(
+        deps.storage,
+        vault.id,
+        &VaultOwnershipTransfer {
+            new_owner: new_owner.clone(),
+            update_destinations,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("transfer_vault_ownership", "true")
+        .add_attribute("vault_id", vault.id)
+        .add_attribute("owner", vault.owner)
+        .add_attribute("new_owner", new_owner)
+        .add_attribute("update_destinations", update_destinations.to_string()))
+}
+
+#[cfg(test)]
+mod transfer_vault_ownership_tests {
+    use super::*;
+    use crate::handlers::get_vault::get_vault_handler;
+    use crate::state::vault_ownership_transfers::get_vault_ownership_transfer;
+    use crate::tests::helpers::{instantiate_contract, setup_vault};
+    use crate::tests::mocks::{ADMIN, USER};
+    use crate::types::vault::{Vault, VaultStatus};
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+
+    #[test]
+    fn saves_pending_transfer_without_changing_owner() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env, Vault::default());
+
+        transfer_vault_ownership_handler(
+            deps.as_mut(),
+            mock_info(USER, &[]),
+            vault.id,
+            Addr::unchecked("new-owner"),
+            true,
+        )
+        .unwrap();
+
+        let updated_vault = get_vault_handler(deps.as_ref(), vault.id).unwrap().vault;
+
+        assert_eq!(updated_vault.owner, vault.owner);
+        assert_eq!(
+            get_vault_ownership_transfer(deps.as_ref().storage, vault.id).unwrap(),
+            Some(VaultOwnershipTransfer {
+                new_owner: Addr::unchecked("new-owner"),
+                update_destinations: true,
+            })
+        );
+    }
+
+    #[test]
+    fn overwrites_existing_pending_transfer() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env, Vault::default());
+
+        transfer_vault_ownership_handler(
+            deps.as_mut(),
+            mock_info(USER, &[]),
+            vault.id,
+            Addr::unchecked("new-owner"),
+            true,
+        )
+        .unwrap();
+
+        transfer_vault_ownership_handler(
+            deps.as_mut(),
+            mock_info(USER, &[]),
+            vault.id,
+            Addr::unchecked("other-owner"),
+            false,
+        )
+        .unwrap();
+
+        assert_eq!(
+            get_vault_ownership_transfer(deps.as_ref().storage, vault.id).unwrap(),
+            Some(VaultOwnershipTransfer {
+                new_owner: Addr::unchecked("other-owner"),
+                update_destinations: false,
+            })
+        );
+    }
+
+    #[test]
+    fn to_current_owner_fails() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env, Vault::default());
+
+        let err = transfer_vault_ownership_handler(
+            deps.as_mut(),
+            mock_info(USER, &[]),
+            vault.id,
+            vault.owner.clone(),
+            false,
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            format!("Error: {} already owns vault {}", vault.owner, vault.id)
+        );
+    }
+
+    #[test]
+    fn for_cancelled_vault_fails() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env,
+            Vault {
+                status: VaultStatus::Cancelled,
+                ..Vault::default()
+            },
+        );
+
+        let err = transfer_vault_ownership_handler(
+            deps.as_mut(),
+            mock_info(USER, &[]),
+            vault.id,
+            Addr::unchecked("new-owner"),
+            false,
+        )
+        .unwrap_err();
+
+        assert_eq!(err.to_string(), "Error: vault is already cancelled");
+    }
+
+    #[test]
+    fn for_vault_with_different_owner_fails() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env, Vault::default());
+
+        let err = transfer_vault_ownership_handler(
+            deps.as_mut(),
+            mock_info(ADMIN, &[]),
+            vault.id,
+            Addr::unchecked("new-owner"),
+            false,
+        )
+        .unwrap_err();
+
+        assert_eq!(err.to_string(), "Unauthorized");
+    }
+}
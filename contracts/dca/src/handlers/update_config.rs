@@ -1,18 +1,23 @@
 use crate::{
     error::ContractError,
     helpers::validation::{
-        assert_addresses_are_valid, assert_fee_collector_addresses_are_valid,
-        assert_fee_collector_allocations_add_up_to_one, assert_fee_level_is_valid,
+        assert_addresses_are_valid, assert_escrow_level_bounds_are_valid,
+        assert_fee_collector_addresses_are_valid, assert_fee_collector_allocations_add_up_to_one,
+        assert_fee_level_is_valid,
+        assert_high_water_mark_performance_fee_percent_is_no_greater_than_100_percent,
+        assert_keeper_bounty_percent_is_no_greater_than_100_percent,
         assert_no_more_than_10_fee_collectors, assert_page_limit_is_valid,
+        assert_referral_fee_percent_is_no_greater_than_100_percent, assert_retry_policy_is_valid,
         assert_risk_weighted_average_escrow_level_is_no_greater_than_100_percent,
         assert_sender_is_admin, assert_slippage_tolerance_is_less_than_or_equal_to_one,
         assert_twap_period_is_valid,
     },
     state::config::{get_config, update_config},
-    types::{config::Config, fee_collector::FeeCollector},
+    types::{config::Config, fee_collector::FeeCollector, retry_policy::RetryPolicy},
 };
 use cosmwasm_std::{Addr, Decimal, DepsMut, MessageInfo, Response};
 
+#[allow(clippy::too_many_arguments)]
 pub fn update_config_handler(
     deps: DepsMut,
     info: MessageInfo,
@@ -27,6 +32,15 @@ pub fn update_config_handler(
     twap_period: Option<u64>,
     default_slippage_tolerance: Option<Decimal>,
     exchange_contract_address: Option<Addr>,
+    default_retry_policy: Option<RetryPolicy>,
+    permissionless_execution: Option<bool>,
+    keeper_bounty_percent: Option<Decimal>,
+    referral_fee_percent: Option<Decimal>,
+    high_water_mark_performance_fee_percent: Option<Decimal>,
+    minimum_escrow_level: Option<Decimal>,
+    maximum_escrow_level: Option<Decimal>,
+    post_execution_action_max_retry_attempts: Option<u32>,
+    post_execution_action_max_retry_age_seconds: Option<u64>,
 ) -> Result<Response, ContractError> {
     assert_sender_is_admin(deps.storage, info.sender)?;
     let existing_config = get_config(deps.storage)?;
@@ -50,6 +64,20 @@ pub fn update_config_handler(
             .unwrap_or(existing_config.default_slippage_tolerance),
         exchange_contract_address: exchange_contract_address
             .unwrap_or(existing_config.exchange_contract_address),
+        default_retry_policy: default_retry_policy.or(existing_config.default_retry_policy),
+        permissionless_execution: permissionless_execution
+            .unwrap_or(existing_config.permissionless_execution),
+        keeper_bounty_percent: keeper_bounty_percent
+            .unwrap_or(existing_config.keeper_bounty_percent),
+        referral_fee_percent: referral_fee_percent.unwrap_or(existing_config.referral_fee_percent),
+        high_water_mark_performance_fee_percent: high_water_mark_performance_fee_percent
+            .unwrap_or(existing_config.high_water_mark_performance_fee_percent),
+        minimum_escrow_level: minimum_escrow_level.unwrap_or(existing_config.minimum_escrow_level),
+        maximum_escrow_level: maximum_escrow_level.unwrap_or(existing_config.maximum_escrow_level),
+        post_execution_action_max_retry_attempts: post_execution_action_max_retry_attempts
+            .unwrap_or(existing_config.post_execution_action_max_retry_attempts),
+        post_execution_action_max_retry_age_seconds: post_execution_action_max_retry_age_seconds
+            .unwrap_or(existing_config.post_execution_action_max_retry_age_seconds),
     };
 
     assert_fee_level_is_valid(&config.default_swap_fee_percent)?;
@@ -66,6 +94,17 @@ pub fn update_config_handler(
         config.risk_weighted_average_escrow_level,
     )?;
 
+    assert_keeper_bounty_percent_is_no_greater_than_100_percent(config.keeper_bounty_percent)?;
+    assert_referral_fee_percent_is_no_greater_than_100_percent(config.referral_fee_percent)?;
+    assert_high_water_mark_performance_fee_percent_is_no_greater_than_100_percent(
+        config.high_water_mark_performance_fee_percent,
+    )?;
+    assert_escrow_level_bounds_are_valid(config.minimum_escrow_level, config.maximum_escrow_level)?;
+
+    if let Some(default_retry_policy) = &config.default_retry_policy {
+        assert_retry_policy_is_valid(default_retry_policy)?;
+    }
+
     let config = update_config(deps.storage, config)?;
 
     Ok(Response::default()
@@ -109,6 +148,15 @@ mod update_config_tests {
             None,
             None,
             None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap();
 
@@ -146,12 +194,21 @@ mod update_config_tests {
             None,
             None,
             None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap();
 
         let config = get_config(deps.as_ref().storage).unwrap();
 
-        assert_eq!(config.executors, executors.unwrap());
+        assert_eq!(Some(config.executors), executors);
     }
 
     #[test]
@@ -175,6 +232,15 @@ mod update_config_tests {
             None,
             None,
             None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap();
 
@@ -204,6 +270,15 @@ mod update_config_tests {
             None,
             None,
             None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap_err();
 
@@ -231,6 +306,15 @@ mod update_config_tests {
             None,
             None,
             None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap();
 
@@ -260,6 +344,15 @@ mod update_config_tests {
             None,
             None,
             None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap_err();
 
@@ -289,6 +382,15 @@ mod update_config_tests {
             None,
             None,
             None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap();
 
@@ -332,12 +434,21 @@ mod update_config_tests {
             None,
             None,
             None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap();
 
         let config = get_config(deps.as_ref().storage).unwrap();
 
-        assert_eq!(config.fee_collectors, fee_collectors.unwrap());
+        assert_eq!(Some(config.fee_collectors), fee_collectors);
     }
 
     #[test]
@@ -370,6 +481,15 @@ mod update_config_tests {
             None,
             None,
             None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap_err();
 
@@ -400,6 +520,15 @@ mod update_config_tests {
             None,
             None,
             None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap();
 
@@ -432,6 +561,15 @@ mod update_config_tests {
             None,
             None,
             None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap_err();
 
@@ -441,6 +579,45 @@ mod update_config_tests {
         )
     }
 
+    #[test]
+    fn update_minimum_escrow_level_above_maximum_escrow_level_should_fail() {
+        let mut deps = mock_dependencies();
+        let info = mock_info(ADMIN, &[]);
+
+        instantiate_contract(deps.as_mut(), mock_env(), info.clone());
+
+        let err = update_config_handler(
+            deps.as_mut(),
+            info,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(Decimal::percent(30)),
+            Some(Decimal::percent(20)),
+            None,
+            None,
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Error: minimum escrow level must be less than or equal to maximum escrow level"
+        )
+    }
+
     #[test]
     fn with_default_slippage_tolerance_more_than_100_percent_should_fail() {
         let mut deps = mock_dependencies();
@@ -462,6 +639,15 @@ mod update_config_tests {
             None,
             Some(Decimal::percent(150)),
             None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap_err();
 
@@ -498,6 +684,15 @@ mod update_config_tests {
             None,
             None,
             None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap_err();
 
@@ -506,4 +701,43 @@ mod update_config_tests {
             "Error: no more than 10 fee collectors are allowed"
         )
     }
+
+    #[test]
+    fn update_post_execution_action_retry_limits_should_succeed() {
+        let mut deps = mock_dependencies();
+        let info = mock_info(ADMIN, &[]);
+
+        instantiate_contract(deps.as_mut(), mock_env(), info.clone());
+
+        update_config_handler(
+            deps.as_mut(),
+            info,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(2),
+            Some(60 * 60),
+        )
+        .unwrap();
+
+        let config = get_config(deps.as_ref().storage).unwrap();
+
+        assert_eq!(config.post_execution_action_max_retry_attempts, 2);
+        assert_eq!(config.post_execution_action_max_retry_age_seconds, 60 * 60);
+    }
 }
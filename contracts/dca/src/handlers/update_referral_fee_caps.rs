@@ -0,0 +1,116 @@
+use crate::{
+    error::ContractError,
+    helpers::validation::{assert_address_is_valid, assert_sender_is_admin},
+    state::referrals::save_referral_fee_caps,
+};
+use cosmwasm_std::{Addr, Coin, DepsMut, MessageInfo, Response};
+
+pub fn update_referral_fee_caps_handler(
+    deps: DepsMut,
+    info: MessageInfo,
+    referrer: Addr,
+    caps: Vec<Coin>,
+) -> Result<Response, ContractError> {
+    assert_sender_is_admin(deps.storage, info.sender)?;
+    assert_address_is_valid(deps.as_ref(), &referrer, "referrer")?;
+
+    for (index, cap) in caps.iter().enumerate() {
+        if caps[index + 1..]
+            .iter()
+            .any(|other| other.denom == cap.denom)
+        {
+            return Err(ContractError::CustomError {
+                val: format!("received multiple referral fee caps for {}", cap.denom),
+            });
+        }
+    }
+
+    save_referral_fee_caps(deps.storage, &referrer, &caps)?;
+
+    Ok(Response::new()
+        .add_attribute("update_referral_fee_caps", "true")
+        .add_attribute("referrer", referrer)
+        .add_attribute("caps", format!("{:?}", caps)))
+}
+
+#[cfg(test)]
+mod update_referral_fee_caps_tests {
+    use super::update_referral_fee_caps_handler;
+    use crate::{
+        state::referrals::get_referral_fee_caps,
+        tests::{
+            helpers::instantiate_contract,
+            mocks::{ADMIN, DENOM_UKUJI, DENOM_UUSK, USER},
+        },
+    };
+    use cosmwasm_std::{
+        testing::{mock_dependencies, mock_env, mock_info},
+        Addr, Coin,
+    };
+
+    #[test]
+    fn with_non_admin_sender_fails() {
+        let mut deps = mock_dependencies();
+
+        instantiate_contract(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]));
+
+        let err = update_referral_fee_caps_handler(
+            deps.as_mut(),
+            mock_info(USER, &[]),
+            Addr::unchecked("referrer"),
+            vec![Coin::new(100, DENOM_UKUJI)],
+        )
+        .unwrap_err();
+
+        assert_eq!(err.to_string(), "Unauthorized");
+    }
+
+    #[test]
+    fn with_duplicate_denoms_fails() {
+        let mut deps = mock_dependencies();
+
+        instantiate_contract(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]));
+
+        let err = update_referral_fee_caps_handler(
+            deps.as_mut(),
+            mock_info(ADMIN, &[]),
+            Addr::unchecked("referrer"),
+            vec![Coin::new(100, DENOM_UKUJI), Coin::new(200, DENOM_UKUJI)],
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Error: received multiple referral fee caps for ukuji"
+        );
+    }
+
+    #[test]
+    fn replaces_existing_caps() {
+        let mut deps = mock_dependencies();
+        let referrer = Addr::unchecked("referrer");
+
+        instantiate_contract(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]));
+
+        update_referral_fee_caps_handler(
+            deps.as_mut(),
+            mock_info(ADMIN, &[]),
+            referrer.clone(),
+            vec![Coin::new(100, DENOM_UKUJI)],
+        )
+        .unwrap();
+
+        update_referral_fee_caps_handler(
+            deps.as_mut(),
+            mock_info(ADMIN, &[]),
+            referrer.clone(),
+            vec![Coin::new(200, DENOM_UUSK)],
+        )
+        .unwrap();
+
+        assert_eq!(
+            get_referral_fee_caps(deps.as_ref().storage, &referrer).unwrap(),
+            vec![Coin::new(200, DENOM_UUSK)]
+        );
+    }
+}
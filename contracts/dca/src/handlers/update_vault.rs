@@ -3,9 +3,10 @@ use crate::{
     helpers::{
         time::get_next_target_time,
         validation::{
+            assert_contract_destination_callbacks_are_valid, assert_destination_actions_are_valid,
             assert_destination_allocations_add_up_to_one,
             assert_destination_callback_addresses_are_valid,
-            assert_destinations_limit_is_not_breached,
+            assert_destinations_limit_is_not_breached, assert_escrow_level_is_within_bounds,
             assert_label_is_no_longer_than_100_characters,
             assert_no_destination_allocations_are_zero,
             assert_slippage_tolerance_is_less_than_or_equal_to_one, assert_time_interval_is_valid,
@@ -14,12 +15,14 @@ use crate::{
         },
     },
     state::{
+        config::get_config,
+        disburse_escrow_tasks::get_disburse_escrow_task_due_date,
         events::create_event,
         triggers::{delete_trigger, save_trigger},
         vaults::{get_vault, update_vault},
     },
     types::{
-        destination::Destination,
+        destination::{Destination, DestinationAction},
         event::{EventBuilder, EventData},
         swap_adjustment_strategy::{SwapAdjustmentStrategy, SwapAdjustmentStrategyParams},
         time_interval::TimeInterval,
@@ -41,12 +44,31 @@ pub fn update_vault_handler(
     time_interval: Option<TimeInterval>,
     swap_adjustment_strategy: Option<SwapAdjustmentStrategyParams>,
     swap_amount: Option<Uint128>,
+    allow_partial_executions: Option<bool>,
+    retry_failed_post_execution_actions: Option<bool>,
+    recurring_deposits: Option<bool>,
+    escrow_level: Option<Decimal>,
 ) -> Result<Response, ContractError> {
     let mut vault = get_vault(deps.storage, vault_id)?;
 
     asset_sender_is_vault_owner(vault.owner.clone(), info.sender)?;
     assert_vault_is_not_cancelled(&vault)?;
 
+    if vault.basket.is_some()
+        && (minimum_receive_amount.is_some() || swap_adjustment_strategy.is_some())
+    {
+        return Err(ContractError::CustomError {
+            val: "basket vaults do not support price based options or swap adjustment strategies."
+                .to_string(),
+        });
+    }
+
+    if vault.basket.is_some() && allow_partial_executions.unwrap_or(false) {
+        return Err(ContractError::CustomError {
+            val: "basket vaults do not support partial executions.".to_string(),
+        });
+    }
+
     let mut response = Response::default()
         .add_attribute("update_vault", "true")
         .add_attribute("vault_id", vault.id)
@@ -140,7 +162,7 @@ pub fn update_vault_handler(
             destinations.push(Destination {
                 allocation: Decimal::percent(100),
                 address: vault.owner.clone(),
-                msg: None,
+                action: DestinationAction::Send,
             });
         }
 
@@ -148,6 +170,20 @@ pub fn update_vault_handler(
         assert_destination_callback_addresses_are_valid(deps.as_ref(), &destinations)?;
         assert_no_destination_allocations_are_zero(&destinations)?;
         assert_destination_allocations_add_up_to_one(&destinations)?;
+        assert_contract_destination_callbacks_are_valid(&destinations, &env.contract.address)?;
+        assert_destination_actions_are_valid(
+            deps.as_ref(),
+            &destinations,
+            &vault.basket.as_ref().map_or_else(
+                || vec![vault.target_denom.clone()],
+                |basket| {
+                    basket
+                        .iter()
+                        .map(|asset| asset.target_denom.clone())
+                        .collect()
+                },
+            ),
+        )?;
 
         updates.push(Update {
             field: "destinations".to_string(),
@@ -172,6 +208,86 @@ pub fn update_vault_handler(
         response = response.add_attribute("slippage_tolerance", slippage_tolerance.to_string());
     }
 
+    if let Some(allow_partial_executions) = allow_partial_executions {
+        updates.push(Update {
+            field: "allow_partial_executions".to_string(),
+            old_value: format!("{}", vault.allow_partial_executions),
+            new_value: format!("{}", allow_partial_executions),
+        });
+
+        vault.allow_partial_executions = allow_partial_executions;
+        response = response.add_attribute(
+            "allow_partial_executions",
+            allow_partial_executions.to_string(),
+        );
+    }
+
+    if let Some(retry_failed_post_execution_actions) = retry_failed_post_execution_actions {
+        updates.push(Update {
+            field: "retry_failed_post_execution_actions".to_string(),
+            old_value: format!("{}", vault.retry_failed_post_execution_actions),
+            new_value: format!("{}", retry_failed_post_execution_actions),
+        });
+
+        vault.retry_failed_post_execution_actions = retry_failed_post_execution_actions;
+        response = response.add_attribute(
+            "retry_failed_post_execution_actions",
+            retry_failed_post_execution_actions.to_string(),
+        );
+    }
+
+    if let Some(recurring_deposits) = recurring_deposits {
+        if recurring_deposits && matches!(vault.trigger, Some(TriggerConfiguration::Price { .. })) {
+            return Err(ContractError::CustomError {
+                val: String::from(
+                    "recurring deposits are only supported for time triggered vaults",
+                ),
+            });
+        }
+
+        updates.push(Update {
+            field: "recurring_deposits".to_string(),
+            old_value: format!("{}", vault.recurring_deposits),
+            new_value: format!("{}", recurring_deposits),
+        });
+
+        vault.recurring_deposits = recurring_deposits;
+        response = response.add_attribute("recurring_deposits", recurring_deposits.to_string());
+    }
+
+    if let Some(escrow_level) = escrow_level {
+        if vault.performance_assessment_strategy.is_none() {
+            return Err(ContractError::CustomError {
+                val:
+                    "escrow level can only be set for vaults with a performance assessment strategy"
+                        .to_string(),
+            });
+        }
+
+        // funds escrowed at the old level cannot be re-priced, so the level is fixed once the
+        // vault has executed
+        if !vault.swapped_amount.amount.is_zero()
+            || !vault.escrowed_amount.amount.is_zero()
+            || get_disburse_escrow_task_due_date(deps.storage, vault.id)?.is_some()
+        {
+            return Err(ContractError::CustomError {
+                val: "escrow level can only be updated before the vault's first execution"
+                    .to_string(),
+            });
+        }
+
+        assert_escrow_level_is_within_bounds(&get_config(deps.storage)?, escrow_level)?;
+
+        updates.push(Update {
+            field: "escrow_level".to_string(),
+            old_value: format!("{}", vault.escrow_level),
+            new_value: format!("{}", escrow_level),
+        });
+
+        vault.escrow_level = escrow_level;
+        response = response.add_attribute("escrow_level", escrow_level.to_string());
+    }
+
     if let Some(minimum_receive_amount) = minimum_receive_amount {
         updates.push(Update {
             field: "minimum_receive_amount".to_string(),
@@ -287,15 +403,19 @@ mod update_vault_tests {
         constants::{ONE, TEN},
         handlers::get_events_by_resource_id::get_events_by_resource_id_handler,
         helpers::time::get_next_target_time,
-        state::{config::update_config, vaults::get_vault},
+        state::{
+            config::update_config, disburse_escrow_tasks::save_disburse_escrow_task,
+            vaults::get_vault,
+        },
         tests::{
             helpers::{instantiate_contract, setup_vault},
             mocks::{ADMIN, USER},
         },
         types::{
             config::Config,
-            destination::Destination,
+            destination::{Destination, DestinationAction},
             event::{Event, EventData},
+            performance_assessment_strategy::PerformanceAssessmentStrategy,
             position_type::PositionType,
             swap_adjustment_strategy::{
                 BaseDenom, SwapAdjustmentStrategy, SwapAdjustmentStrategyParams,
@@ -308,7 +428,7 @@ mod update_vault_tests {
     };
     use cosmwasm_std::{
         testing::{mock_dependencies, mock_env, mock_info},
-        Addr, Decimal, Uint128,
+        Addr, Coin, Decimal, Uint128,
     };
 
     #[test]
@@ -331,6 +451,10 @@ mod update_vault_tests {
             None,
             None,
             None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap_err();
 
@@ -360,6 +484,10 @@ mod update_vault_tests {
             Some(TimeInterval::Custom { seconds: 12 }),
             None,
             None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap_err();
 
@@ -391,6 +519,10 @@ mod update_vault_tests {
             None,
             None,
             None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap_err();
 
@@ -429,6 +561,10 @@ mod update_vault_tests {
             None,
             None,
             None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap_err();
 
@@ -462,6 +598,10 @@ mod update_vault_tests {
             None,
             None,
             None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap_err();
 
@@ -478,7 +618,7 @@ mod update_vault_tests {
             Destination {
                 address: Addr::unchecked("random"),
                 allocation: Decimal::percent(10),
-                msg: None,
+                action: DestinationAction::Send,
             };
             11
         ];
@@ -495,6 +635,10 @@ mod update_vault_tests {
             None,
             None,
             None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap_err();
 
@@ -514,12 +658,12 @@ mod update_vault_tests {
             Destination {
                 address: Addr::unchecked("random"),
                 allocation: Decimal::percent(10),
-                msg: None,
+                action: DestinationAction::Send,
             },
             Destination {
                 address: Addr::unchecked("random"),
                 allocation: Decimal::percent(10),
-                msg: None,
+                action: DestinationAction::Send,
             },
         ];
 
@@ -535,6 +679,10 @@ mod update_vault_tests {
             None,
             None,
             None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap_err();
 
@@ -554,12 +702,12 @@ mod update_vault_tests {
             Destination {
                 address: Addr::unchecked("random"),
                 allocation: Decimal::percent(50),
-                msg: None,
+                action: DestinationAction::Send,
             },
             Destination {
                 address: Addr::unchecked("random"),
                 allocation: Decimal::percent(51),
-                msg: None,
+                action: DestinationAction::Send,
             },
         ];
 
@@ -575,6 +723,10 @@ mod update_vault_tests {
             None,
             None,
             None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap_err();
 
@@ -594,12 +746,12 @@ mod update_vault_tests {
             Destination {
                 address: Addr::unchecked("random"),
                 allocation: Decimal::percent(100),
-                msg: None,
+                action: DestinationAction::Send,
             },
             Destination {
                 address: Addr::unchecked("random"),
                 allocation: Decimal::zero(),
-                msg: None,
+                action: DestinationAction::Send,
             },
         ];
 
@@ -615,6 +767,10 @@ mod update_vault_tests {
             None,
             None,
             None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap_err();
 
@@ -660,6 +816,10 @@ mod update_vault_tests {
             None,
             Some(new_swap_adjustment_strategy.clone()),
             None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap_err();
 
@@ -709,6 +869,10 @@ mod update_vault_tests {
             None,
             new_swap_adjustment_strategy.clone(),
             None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap_err();
 
@@ -745,6 +909,10 @@ mod update_vault_tests {
             None,
             strategy.clone(),
             None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap_err();
 
@@ -775,6 +943,10 @@ mod update_vault_tests {
             None,
             None,
             Some(Uint128::new(3498473290)),
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap_err();
 
@@ -808,6 +980,10 @@ mod update_vault_tests {
                 increase_only: false,
             }),
             Some(Uint128::new(436753262)),
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap_err();
 
@@ -845,6 +1021,10 @@ mod update_vault_tests {
             None,
             None,
             Some(vault.swap_amount * Uint128::new(2)),
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap();
 
@@ -885,6 +1065,10 @@ mod update_vault_tests {
             None,
             None,
             Some(vault.swap_amount * Uint128::new(2)),
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap();
 
@@ -932,6 +1116,10 @@ mod update_vault_tests {
             None,
             None,
             Some(swap_amount),
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap();
 
@@ -980,6 +1168,10 @@ mod update_vault_tests {
             None,
             strategy,
             None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap();
 
@@ -1015,6 +1207,10 @@ mod update_vault_tests {
             None,
             None,
             None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap();
 
@@ -1033,12 +1229,12 @@ mod update_vault_tests {
             Destination {
                 address: Addr::unchecked("random"),
                 allocation: Decimal::percent(50),
-                msg: None,
+                action: DestinationAction::Send,
             },
             Destination {
                 address: Addr::unchecked("random"),
                 allocation: Decimal::percent(50),
-                msg: None,
+                action: DestinationAction::Send,
             },
         ];
 
@@ -1054,6 +1250,10 @@ mod update_vault_tests {
             None,
             None,
             None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap();
 
@@ -1081,6 +1281,10 @@ mod update_vault_tests {
             None,
             None,
             None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap();
 
@@ -1092,7 +1296,7 @@ mod update_vault_tests {
             vec![Destination {
                 address: vault.owner,
                 allocation: Decimal::percent(100),
-                msg: None,
+                action: DestinationAction::Send,
             }]
         );
     }
@@ -1117,6 +1321,10 @@ mod update_vault_tests {
             None,
             None,
             None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap();
 
@@ -1125,6 +1333,66 @@ mod update_vault_tests {
         assert_eq!(updated_vault.slippage_tolerance, slippage_tolerance);
     }
 
+    #[test]
+    fn updates_allow_partial_executions() {
+        let mut deps = mock_dependencies();
+
+        let vault = setup_vault(deps.as_mut(), mock_env(), Vault::default());
+
+        update_vault_handler(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            vault.id,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(true),
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+
+        let updated_vault = get_vault(deps.as_ref().storage, vault.id).unwrap();
+
+        assert!(updated_vault.allow_partial_executions);
+    }
+
+    #[test]
+    fn updates_retry_failed_post_execution_actions() {
+        let mut deps = mock_dependencies();
+
+        let vault = setup_vault(deps.as_mut(), mock_env(), Vault::default());
+
+        update_vault_handler(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(USER, &[]),
+            vault.id,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(true),
+            None,
+            None,
+        )
+        .unwrap();
+
+        let updated_vault = get_vault(deps.as_ref().storage, vault.id).unwrap();
+
+        assert!(updated_vault.retry_failed_post_execution_actions);
+    }
+
     #[test]
     fn updates_minimum_receive_amount() {
         let mut deps = mock_dependencies();
@@ -1145,6 +1413,10 @@ mod update_vault_tests {
             None,
             None,
             None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap();
 
@@ -1173,6 +1445,10 @@ mod update_vault_tests {
             Some(time_interval.clone()),
             None,
             None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap();
 
@@ -1202,6 +1478,10 @@ mod update_vault_tests {
             Some(time_interval.clone()),
             None,
             None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap();
 
@@ -1238,12 +1518,12 @@ mod update_vault_tests {
             Destination {
                 address: Addr::unchecked("random-1"),
                 allocation: Decimal::percent(50),
-                msg: None,
+                action: DestinationAction::Send,
             },
             Destination {
                 address: Addr::unchecked("random-2"),
                 allocation: Decimal::percent(50),
-                msg: None,
+                action: DestinationAction::Send,
             },
         ];
         let new_slippage_tolerance = Decimal::percent(12);
@@ -1261,6 +1541,10 @@ mod update_vault_tests {
             Some(new_time_interval.clone()),
             None,
             None,
+            None,
+            None,
+            None,
+            None,
         )
         .unwrap();
 
@@ -1321,4 +1605,327 @@ mod update_vault_tests {
             }
         )
     }
+
+    #[test]
+    fn updates_escrow_level_and_publishes_vault_updated_event() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                escrow_level: Decimal::percent(5),
+                performance_assessment_strategy: Some(PerformanceAssessmentStrategy::default()),
+                ..Vault::default()
+            },
+        );
+
+        update_vault_handler(
+            deps.as_mut(),
+            env,
+            mock_info(USER, &[]),
+            vault.id,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(Decimal::percent(10)),
+        )
+        .unwrap();
+
+        let updated_vault = get_vault(deps.as_ref().storage, vault.id).unwrap();
+
+        assert_eq!(updated_vault.escrow_level, Decimal::percent(10));
+
+        let events = get_events_by_resource_id_handler(deps.as_ref(), vault.id, None, None, None)
+            .unwrap()
+            .events;
+
+        assert_eq!(
+            events.last().unwrap().data,
+            EventData::DcaVaultUpdated {
+                updates: vec![Update {
+                    field: "escrow_level".to_string(),
+                    old_value: "0.05".to_string(),
+                    new_value: "0.1".to_string(),
+                }]
+            }
+        );
+    }
+
+    #[test]
+    fn with_recurring_deposits_on_price_triggered_vault_fails() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                trigger: Some(TriggerConfiguration::Price {
+                    target_price: Decimal::percent(200),
+                    order_idx: Uint128::new(28),
+                }),
+                ..Vault::default()
+            },
+        );
+
+        let err = update_vault_handler(
+            deps.as_mut(),
+            env,
+            mock_info(USER, &[]),
+            vault.id,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(true),
+            None,
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Error: recurring deposits are only supported for time triggered vaults"
+        );
+    }
+
+    #[test]
+    fn with_escrow_level_outside_of_bounds_fails() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        update_config(
+            deps.as_mut().storage,
+            Config {
+                minimum_escrow_level: Decimal::percent(1),
+                maximum_escrow_level: Decimal::percent(20),
+                ..Config::default()
+            },
+        )
+        .unwrap();
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                escrow_level: Decimal::percent(5),
+                performance_assessment_strategy: Some(PerformanceAssessmentStrategy::default()),
+                ..Vault::default()
+            },
+        );
+
+        let err = update_vault_handler(
+            deps.as_mut(),
+            env,
+            mock_info(USER, &[]),
+            vault.id,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(Decimal::percent(25)),
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Error: escrow level must be between 0.01 and 0.2"
+        );
+    }
+
+    #[test]
+    fn with_escrow_level_and_no_performance_assessment_strategy_fails() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        let err = update_vault_handler(
+            deps.as_mut(),
+            env,
+            mock_info(USER, &[]),
+            vault.id,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(Decimal::percent(10)),
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Error: escrow level can only be set for vaults with a performance assessment strategy"
+        );
+    }
+
+    #[test]
+    fn with_escrow_level_and_escrowed_funds_fails() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                status: VaultStatus::Inactive,
+                escrow_level: Decimal::percent(10),
+                escrowed_amount: Coin::new(ONE.into(), "ukuji"),
+                performance_assessment_strategy: Some(PerformanceAssessmentStrategy::default()),
+                ..Vault::default()
+            },
+        );
+
+        let err = update_vault_handler(
+            deps.as_mut(),
+            env,
+            mock_info(USER, &[]),
+            vault.id,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(Decimal::percent(1)),
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Error: escrow level can only be updated before the vault's first execution"
+        );
+        assert_eq!(
+            get_vault(deps.as_ref().storage, vault.id)
+                .unwrap()
+                .escrow_level,
+            Decimal::percent(10)
+        );
+    }
+
+    #[test]
+    fn with_escrow_level_after_first_execution_fails() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                escrow_level: Decimal::percent(10),
+                swapped_amount: Coin::new(ONE.into(), "ukuji"),
+                performance_assessment_strategy: Some(PerformanceAssessmentStrategy::default()),
+                ..Vault::default()
+            },
+        );
+
+        let err = update_vault_handler(
+            deps.as_mut(),
+            env,
+            mock_info(USER, &[]),
+            vault.id,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(Decimal::percent(1)),
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Error: escrow level can only be updated before the vault's first execution"
+        );
+    }
+
+    #[test]
+    fn with_escrow_level_and_pending_disburse_escrow_task_fails() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                escrow_level: Decimal::percent(10),
+                performance_assessment_strategy: Some(PerformanceAssessmentStrategy::default()),
+                ..Vault::default()
+            },
+        );
+
+        save_disburse_escrow_task(deps.as_mut().storage, vault.id, env.block.time).unwrap();
+
+        let err = update_vault_handler(
+            deps.as_mut(),
+            env,
+            mock_info(USER, &[]),
+            vault.id,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(Decimal::percent(1)),
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Error: escrow level can only be updated before the vault's first execution"
+        );
+    }
 }
@@ -0,0 +1,564 @@
+use crate::error::ContractError;
+use crate::helpers::validation::{
+    assert_contract_is_not_paused, assert_vault_is_not_cancelled,
+    assert_withdrawal_amount_is_valid, asset_sender_is_vault_owner,
+};
+use crate::helpers::vault::{get_retract_order_messages, get_risk_weighted_average_model_id};
+use crate::state::disburse_escrow_tasks::save_disburse_escrow_task;
+use crate::state::events::create_event;
+use crate::state::triggers::delete_trigger;
+use crate::state::vaults::{get_vault, update_vault};
+use crate::types::event::{EventBuilder, EventData};
+use crate::types::swap_adjustment_strategy::SwapAdjustmentStrategy;
+use crate::types::vault::{Vault, VaultStatus};
+use cosmwasm_std::{Coin, DepsMut, Env, MessageInfo, Response, SubMsg, Uint128};
+use shared::coin::subtract;
+use shared::cw20::into_bank_msg;
+
+pub fn withdraw_from_vault_handler(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    vault_id: Uint128,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    assert_contract_is_not_paused(deps.storage)?;
+
+    let vault = get_vault(deps.storage, vault_id)?;
+
+    asset_sender_is_vault_owner(vault.owner.clone(), info.sender)?;
+    assert_vault_is_not_cancelled(&vault)?;
+    assert_withdrawal_amount_is_valid(&vault, amount)?;
+
+    let withdrawal = Coin::new(amount.into(), vault.get_swap_denom());
+    let new_balance = subtract(&vault.balance, &withdrawal)?;
+
+    let vault = update_vault(
+        deps.storage,
+        Vault {
+            balance: new_balance.clone(),
+            deposited_amount: subtract(&vault.deposited_amount, &withdrawal)?,
+            status: if new_balance.amount.is_zero() {
+                VaultStatus::Inactive
+            } else {
+                vault.status
+            },
+            swap_adjustment_strategy: vault.swap_adjustment_strategy.clone().map(
+                |swap_adjustment_strategy| match swap_adjustment_strategy {
+                    SwapAdjustmentStrategy::RiskWeightedAverage {
+                        base_denom,
+                        position_type,
+                        ..
+                    } => SwapAdjustmentStrategy::RiskWeightedAverage {
+                        model_id: get_risk_weighted_average_model_id(
+                            &env.block.time,
+                            &new_balance,
+                            &vault.swap_amount,
+                            &vault.time_interval,
+                        ),
+                        base_denom,
+                        position_type,
+                    },
+                    _ => swap_adjustment_strategy,
+                },
+            ),
+            ..vault
+        },
+    )?;
+
+    let mut sub_msgs = vec![SubMsg::new(into_bank_msg(
+        deps.api,
+        vault.owner.as_ref(),
+        vec![withdrawal.clone()],
+    )?)];
+
+    if vault.should_not_continue() {
+        if vault.escrowed_amount.amount > Uint128::zero() {
+            save_disburse_escrow_task(
+                deps.storage,
+                vault.id,
+                vault.get_expected_execution_completed_date(env.block.time),
+            )?;
+        }
+
+        sub_msgs.append(&mut get_retract_order_messages(deps.storage, &vault)?);
+
+        delete_trigger(deps.storage, vault.id)?;
+    }
+
+    create_event(
+        deps.storage,
+        EventBuilder::new(
+            vault.id,
+            env.block,
+            EventData::DcaVaultFundsWithdrawn {
+                amount: withdrawal.clone(),
+            },
+        ),
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("withdraw_from_vault", "true")
+        .add_attribute("vault_id", vault.id)
+        .add_attribute("owner", vault.owner.clone())
+        .add_attribute("withdrawn_amount", withdrawal.to_string())
+        .add_submessages(sub_msgs))
+}
+
+#[cfg(test)]
+mod withdraw_from_vault_tests {
+    use super::*;
+    use crate::constants::{ONE, TEN};
+    use crate::handlers::get_events_by_resource_id::get_events_by_resource_id_handler;
+    use crate::handlers::get_vault::get_vault_handler;
+    use crate::state::config::{get_config, update_config};
+    use crate::state::disburse_escrow_tasks::get_disburse_escrow_tasks;
+    use crate::state::triggers::get_trigger;
+    use crate::tests::helpers::{instantiate_contract, setup_vault};
+    use crate::tests::mocks::{ADMIN, DENOM_UKUJI, DENOM_UUSK, USER};
+    use crate::types::config::Config;
+    use crate::types::event::{EventBuilder, EventData};
+    use crate::types::performance_assessment_strategy::PerformanceAssessmentStrategy;
+    use crate::types::position_type::PositionType;
+    use crate::types::swap_adjustment_strategy::{BaseDenom, SwapAdjustmentStrategy};
+    use crate::types::time_interval::TimeInterval;
+    use crate::types::vault::{Vault, VaultStatus};
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+    use cosmwasm_std::{BankMsg, Coin, SubMsg};
+
+    #[test]
+    fn sends_withdrawn_amount_to_owner() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        let response =
+            withdraw_from_vault_handler(deps.as_mut(), env, mock_info(USER, &[]), vault.id, ONE)
+                .unwrap();
+
+        assert!(response.messages.contains(&SubMsg::new(BankMsg::Send {
+            to_address: vault.owner.to_string(),
+            amount: vec![Coin::new(ONE.into(), DENOM_UKUJI)],
+        })));
+    }
+
+    #[test]
+    fn reduces_balance_and_deposited_amount() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        withdraw_from_vault_handler(deps.as_mut(), env, mock_info(USER, &[]), vault.id, ONE)
+            .unwrap();
+
+        let updated_vault = get_vault_handler(deps.as_ref(), vault.id).unwrap().vault;
+
+        assert_eq!(
+            updated_vault.balance,
+            Coin::new((vault.balance.amount - ONE).into(), DENOM_UKUJI)
+        );
+        assert_eq!(
+            updated_vault.deposited_amount,
+            Coin::new((vault.deposited_amount.amount - ONE).into(), DENOM_UKUJI)
+        );
+        assert_eq!(updated_vault.status, VaultStatus::Active);
+    }
+
+    #[test]
+    fn publishes_funds_withdrawn_event() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        withdraw_from_vault_handler(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(USER, &[]),
+            vault.id,
+            ONE,
+        )
+        .unwrap();
+
+        let events = get_events_by_resource_id_handler(deps.as_ref(), vault.id, None, None, None)
+            .unwrap()
+            .events;
+
+        assert!(events.contains(
+            &EventBuilder::new(
+                vault.id,
+                env.block,
+                EventData::DcaVaultFundsWithdrawn {
+                    amount: Coin::new(ONE.into(), DENOM_UKUJI),
+                },
+            )
+            .build(1)
+        ));
+    }
+
+    #[test]
+    fn withdrawing_entire_balance_sets_vault_to_inactive() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        withdraw_from_vault_handler(
+            deps.as_mut(),
+            env,
+            mock_info(USER, &[]),
+            vault.id,
+            vault.balance.amount,
+        )
+        .unwrap();
+
+        let updated_vault = get_vault_handler(deps.as_ref(), vault.id).unwrap().vault;
+
+        assert!(updated_vault.balance.amount.is_zero());
+        assert_eq!(updated_vault.status, VaultStatus::Inactive);
+    }
+
+    #[test]
+    fn withdrawing_entire_balance_deletes_trigger() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        assert!(get_trigger(deps.as_ref().storage, vault.id)
+            .unwrap()
+            .is_some());
+
+        withdraw_from_vault_handler(
+            deps.as_mut(),
+            env,
+            mock_info(USER, &[]),
+            vault.id,
+            vault.balance.amount,
+        )
+        .unwrap();
+
+        assert!(get_trigger(deps.as_ref().storage, vault.id)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn withdrawing_part_of_balance_keeps_trigger() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        withdraw_from_vault_handler(deps.as_mut(), env, mock_info(USER, &[]), vault.id, ONE)
+            .unwrap();
+
+        assert!(get_trigger(deps.as_ref().storage, vault.id)
+            .unwrap()
+            .is_some());
+    }
+
+    #[test]
+    fn withdrawing_entire_balance_with_escrow_saves_disburse_escrow_task() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                escrowed_amount: Coin::new(ONE.into(), DENOM_UUSK),
+                ..Vault::default()
+            },
+        );
+
+        withdraw_from_vault_handler(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(USER, &[]),
+            vault.id,
+            vault.balance.amount,
+        )
+        .unwrap();
+
+        let disburse_escrow_tasks = get_disburse_escrow_tasks(
+            deps.as_ref().storage,
+            env.block.time.plus_seconds(10),
+            Some(100),
+        )
+        .unwrap();
+
+        assert!(disburse_escrow_tasks.contains(&vault.id));
+    }
+
+    #[test]
+    fn withdrawing_entire_balance_keeps_trigger_while_standard_dca_continues() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                balance: Coin::new((ONE * Uint128::new(3)).into(), DENOM_UKUJI),
+                deposited_amount: Coin::new(TEN.into(), DENOM_UKUJI),
+                escrowed_amount: Coin::new(ONE.into(), DENOM_UUSK),
+                performance_assessment_strategy: Some(
+                    PerformanceAssessmentStrategy::CompareToStandardDca {
+                        swapped_amount: Coin::new((ONE * Uint128::new(5)).into(), DENOM_UKUJI),
+                        received_amount: Coin::new((ONE * Uint128::new(5)).into(), DENOM_UUSK),
+                    },
+                ),
+                ..Vault::default()
+            },
+        );
+
+        withdraw_from_vault_handler(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(USER, &[]),
+            vault.id,
+            vault.balance.amount,
+        )
+        .unwrap();
+
+        assert!(get_trigger(deps.as_ref().storage, vault.id)
+            .unwrap()
+            .is_some());
+        assert!(get_disburse_escrow_tasks(
+            deps.as_ref().storage,
+            env.block.time.plus_seconds(10),
+            Some(100),
+        )
+        .unwrap()
+        .is_empty());
+    }
+
+    #[test]
+    fn updates_risk_weighted_average_model_id() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                balance: Coin::new((ONE * Uint128::new(60)).into(), DENOM_UKUJI),
+                deposited_amount: Coin::new((ONE * Uint128::new(60)).into(), DENOM_UKUJI),
+                time_interval: TimeInterval::Daily,
+                swap_adjustment_strategy: Some(SwapAdjustmentStrategy::RiskWeightedAverage {
+                    model_id: 55,
+                    base_denom: BaseDenom::Bitcoin,
+                    position_type: PositionType::Enter,
+                }),
+                performance_assessment_strategy: Some(PerformanceAssessmentStrategy::default()),
+                ..Vault::default()
+            },
+        );
+
+        withdraw_from_vault_handler(
+            deps.as_mut(),
+            env,
+            mock_info(USER, &[]),
+            vault.id,
+            ONE * Uint128::new(50),
+        )
+        .unwrap();
+
+        let updated_vault = get_vault_handler(deps.as_ref(), vault.id).unwrap().vault;
+
+        assert_eq!(
+            updated_vault.swap_adjustment_strategy,
+            Some(SwapAdjustmentStrategy::RiskWeightedAverage {
+                model_id: 30,
+                base_denom: BaseDenom::Bitcoin,
+                position_type: PositionType::Enter,
+            })
+        );
+    }
+
+    #[test]
+    fn with_amount_larger_than_balance_fails() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        let err = withdraw_from_vault_handler(
+            deps.as_mut(),
+            env,
+            mock_info(USER, &[]),
+            vault.id,
+            vault.balance.amount + Uint128::one(),
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "Error: cannot withdraw {} from vault with balance {}",
+                vault.balance.amount + Uint128::one(),
+                vault.balance
+            )
+        );
+    }
+
+    #[test]
+    fn with_amount_larger_than_standard_dca_balance_fails() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                balance: Coin::new(TEN.into(), DENOM_UKUJI),
+                deposited_amount: Coin::new(TEN.into(), DENOM_UKUJI),
+                performance_assessment_strategy: Some(
+                    PerformanceAssessmentStrategy::CompareToStandardDca {
+                        swapped_amount: Coin::new((ONE * Uint128::new(5)).into(), DENOM_UKUJI),
+                        received_amount: Coin::new((ONE * Uint128::new(5)).into(), DENOM_UUSK),
+                    },
+                ),
+                ..Vault::default()
+            },
+        );
+
+        let err = withdraw_from_vault_handler(
+            deps.as_mut(),
+            env,
+            mock_info(USER, &[]),
+            vault.id,
+            ONE * Uint128::new(6),
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "Error: cannot withdraw {} from vault with standard DCA balance {}",
+                ONE * Uint128::new(6),
+                ONE * Uint128::new(5)
+            )
+        );
+    }
+
+    #[test]
+    fn with_zero_amount_fails() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        let err = withdraw_from_vault_handler(
+            deps.as_mut(),
+            env,
+            mock_info(USER, &[]),
+            vault.id,
+            Uint128::zero(),
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Error: withdrawal amount must be greater than 0"
+        );
+    }
+
+    #[test]
+    fn when_contract_is_paused_fails() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let config = get_config(deps.as_ref().storage).unwrap();
+
+        update_config(
+            deps.as_mut().storage,
+            Config {
+                paused: true,
+                ..config
+            },
+        )
+        .unwrap();
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        let err =
+            withdraw_from_vault_handler(deps.as_mut(), env, mock_info(USER, &[]), vault.id, ONE)
+                .unwrap_err();
+
+        assert_eq!(err.to_string(), "Error: contract is paused");
+    }
+
+    #[test]
+    fn for_cancelled_vault_fails() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                status: VaultStatus::Cancelled,
+                ..Vault::default()
+            },
+        );
+
+        let err =
+            withdraw_from_vault_handler(deps.as_mut(), env, mock_info(USER, &[]), vault.id, ONE)
+                .unwrap_err();
+
+        assert_eq!(err.to_string(), "Error: vault is already cancelled");
+    }
+
+    #[test]
+    fn for_vault_with_different_owner_fails() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        let err = withdraw_from_vault_handler(
+            deps.as_mut(),
+            env,
+            mock_info("not-the-owner", &[]),
+            vault.id,
+            ONE,
+        )
+        .unwrap_err();
+
+        assert_eq!(err.to_string(), "Unauthorized");
+    }
+}
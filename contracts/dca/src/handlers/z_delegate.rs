@@ -120,7 +120,7 @@ mod z_delegate_tests {
     #[test]
     fn sends_bank_message() {
         let amount_to_delegate = Coin::new(100, DENOM_UKUJI);
-        let info = mock_info(USER, &[amount_to_delegate.clone()]);
+        let info = mock_info(USER, std::slice::from_ref(&amount_to_delegate));
 
         let delegator_address = Addr::unchecked(info.sender.clone());
 
@@ -142,7 +142,7 @@ mod z_delegate_tests {
     #[test]
     fn sends_delegate_message() {
         let amount_to_delegate = Coin::new(100, DENOM_UKUJI);
-        let info = mock_info(USER, &[amount_to_delegate.clone()]);
+        let info = mock_info(USER, std::slice::from_ref(&amount_to_delegate));
 
         let delegator_address = Addr::unchecked(info.sender.clone());
         let validator_address = Addr::unchecked(VALIDATOR);
@@ -0,0 +1,315 @@
+use super::{
+    netting::get_internal_swap_amount,
+    price::{get_slippage, get_twap_to_now},
+};
+use crate::{
+    state::{config::get_config, vaults::get_vault},
+    types::vault::Vault,
+};
+use cosmwasm_std::{Binary, Coin, Decimal, Deps, Env, StdResult, Uint128};
+use std::collections::BTreeMap;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct AggregatedSwap {
+    pub vault_ids: Vec<Uint128>,
+    pub swap_amounts: Vec<Uint128>,
+    pub swap_amount: Coin,
+    pub minimum_receive_amount: Coin,
+    pub route: Option<Binary>,
+    pub asset_price: Decimal,
+}
+
+pub fn get_aggregated_swaps(
+    deps: Deps,
+    env: &Env,
+    candidates: &[(Uint128, Option<Uint128>)],
+) -> StdResult<Vec<AggregatedSwap>> {
+    let mut vaults_by_route =
+        BTreeMap::<(String, String, Option<Binary>), Vec<(Vault, Uint128)>>::new();
+
+    for (vault_id, swap_amount_override) in candidates {
+        if let Ok(vault) = get_vault(deps.storage, *vault_id) {
+            let swap_amount = get_internal_swap_amount(&vault, env.block.time);
+
+            if !swap_amount.is_zero() {
+                vaults_by_route
+                    .entry((
+                        vault.get_swap_denom(),
+                        vault.target_denom.clone(),
+                        vault.route.clone(),
+                    ))
+                    .or_default()
+                    .push((vault, swap_amount_override.unwrap_or(swap_amount)));
+            }
+        }
+    }
+
+    let config = get_config(deps.storage)?;
+
+    let mut aggregated_swaps = Vec::<AggregatedSwap>::new();
+
+    for ((swap_denom, target_denom, route), vaults) in vaults_by_route.into_iter() {
+        if vaults.len() < 2 {
+            continue;
+        }
+
+        let Ok(asset_price) = get_twap_to_now(
+            &deps.querier,
+            config.exchange_contract_address.clone(),
+            swap_denom.clone(),
+            target_denom.clone(),
+            config.twap_period,
+            route.clone(),
+        ) else {
+            continue;
+        };
+
+        let vaults = vaults
+            .into_iter()
+            .filter(|(vault, _)| !vault.price_threshold_exceeded(asset_price).unwrap_or(true))
+            .collect::<Vec<(Vault, Uint128)>>();
+
+        let Ok(slippage) = get_slippage(
+            &deps.querier,
+            config.exchange_contract_address.clone(),
+            Coin::new(
+                vaults
+                    .iter()
+                    .map(|(_, swap_amount)| *swap_amount)
+                    .sum::<Uint128>()
+                    .into(),
+                swap_denom.clone(),
+            ),
+            target_denom.clone(),
+            asset_price,
+            route.clone(),
+        ) else {
+            continue;
+        };
+
+        let vaults = vaults
+            .into_iter()
+            .filter(|(vault, _)| vault.slippage_tolerance >= slippage)
+            .collect::<Vec<(Vault, Uint128)>>();
+
+        if vaults.len() < 2 {
+            continue;
+        }
+
+        let swap_amounts = vaults
+            .iter()
+            .map(|(_, swap_amount)| *swap_amount)
+            .collect::<Vec<Uint128>>();
+
+        let total_swap_amount = swap_amounts.iter().sum::<Uint128>();
+
+        let minimum_receive_amount = vaults
+            .iter()
+            .filter_map(|(vault, _)| {
+                vault.minimum_receive_amount.map(|minimum_receive_amount| {
+                    minimum_receive_amount.multiply_ratio(total_swap_amount, vault.swap_amount)
+                })
+            })
+            .max()
+            .unwrap_or_default();
+
+        aggregated_swaps.push(AggregatedSwap {
+            vault_ids: vaults.iter().map(|(vault, _)| vault.id).collect(),
+            swap_amount: Coin::new(total_swap_amount.into(), swap_denom),
+            swap_amounts,
+            minimum_receive_amount: Coin::new(minimum_receive_amount.into(), target_denom),
+            route,
+            asset_price,
+        });
+    }
+
+    Ok(aggregated_swaps)
+}
+
+#[cfg(test)]
+mod get_aggregated_swaps_tests {
+    use super::*;
+    use crate::{
+        constants::{ONE, TEN},
+        tests::{
+            helpers::{instantiate_contract, setup_vault},
+            mocks::{calc_mock_dependencies, ADMIN, DENOM_UKUJI, DENOM_UUSK},
+        },
+    };
+    use cosmwasm_std::{
+        testing::{mock_env, mock_info},
+        to_json_binary,
+    };
+
+    #[test]
+    fn pools_vaults_sharing_pair_and_route() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        let other_vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                swap_amount: ONE * Uint128::new(2),
+                minimum_receive_amount: Some(ONE),
+                ..Vault::default()
+            },
+        );
+
+        let aggregated_swaps = get_aggregated_swaps(
+            deps.as_ref(),
+            &env,
+            &[(vault.id, None), (other_vault.id, None)],
+        )
+        .unwrap();
+
+        assert_eq!(
+            aggregated_swaps,
+            vec![AggregatedSwap {
+                vault_ids: vec![vault.id, other_vault.id],
+                swap_amounts: vec![ONE, ONE * Uint128::new(2)],
+                swap_amount: Coin::new((ONE * Uint128::new(3)).into(), DENOM_UKUJI),
+                minimum_receive_amount: Coin::new(
+                    (ONE * Uint128::new(3) / Uint128::new(2)).into(),
+                    DENOM_UUSK
+                ),
+                route: vault.route,
+                asset_price: Decimal::one(),
+            }]
+        );
+    }
+
+    #[test]
+    fn uses_swap_amount_overrides() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+        let other_vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        let aggregated_swaps = get_aggregated_swaps(
+            deps.as_ref(),
+            &env,
+            &[
+                (vault.id, Some(ONE / Uint128::new(2))),
+                (other_vault.id, None),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(
+            aggregated_swaps[0].swap_amounts,
+            vec![ONE / Uint128::new(2), ONE]
+        );
+    }
+
+    #[test]
+    fn uses_strictest_per_vault_minimum_receive_amount() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                minimum_receive_amount: Some(ONE / Uint128::new(2)),
+                ..Vault::default()
+            },
+        );
+
+        let other_vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                swap_amount: ONE * Uint128::new(3),
+                minimum_receive_amount: Some(ONE),
+                ..Vault::default()
+            },
+        );
+
+        let aggregated_swaps = get_aggregated_swaps(
+            deps.as_ref(),
+            &env,
+            &[(vault.id, None), (other_vault.id, None)],
+        )
+        .unwrap();
+
+        let total_received = aggregated_swaps[0].minimum_receive_amount.amount;
+
+        assert_eq!(total_received, ONE * Uint128::new(2));
+        assert!(total_received.multiply_ratio(ONE, ONE * Uint128::new(4)) >= ONE / Uint128::new(2));
+        assert!(total_received.multiply_ratio(ONE * Uint128::new(3), ONE * Uint128::new(4)) >= ONE);
+    }
+
+    #[test]
+    fn does_not_pool_vaults_with_different_routes() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        let other_vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                route: Some(to_json_binary(&vec![3]).unwrap()),
+                ..Vault::default()
+            },
+        );
+
+        let aggregated_swaps = get_aggregated_swaps(
+            deps.as_ref(),
+            &env,
+            &[(vault.id, None), (other_vault.id, None)],
+        )
+        .unwrap();
+
+        assert!(aggregated_swaps.is_empty());
+    }
+
+    #[test]
+    fn excludes_vaults_whose_price_threshold_is_exceeded() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+        let other_vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        let expensive_vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                minimum_receive_amount: Some(TEN),
+                ..Vault::default()
+            },
+        );
+
+        let aggregated_swaps = get_aggregated_swaps(
+            deps.as_ref(),
+            &env,
+            &[
+                (vault.id, None),
+                (other_vault.id, None),
+                (expensive_vault.id, None),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(
+            aggregated_swaps[0].vault_ids,
+            vec![vault.id, other_vault.id]
+        );
+    }
+}
@@ -1,84 +1,157 @@
-use super::math::checked_mul;
+use super::{
+    ibc::{create_ibc_transfer_message, decode_ibc_transfer_message},
+    math::checked_mul,
+    message::{get_attribute_in_event, get_vault_reply_id},
+};
 use crate::{
     constants::AFTER_FAILED_AUTOMATION_REPLY_ID,
-    state::cache::{PostExecutionActionCacheEntry, POST_EXECUTION_ACTION_CACHE},
-    types::vault::Vault,
+    msg::ExecuteMsg,
+    state::{
+        cache::{PostExecutionActionCacheEntry, POST_EXECUTION_ACTION_CACHE},
+        ibc_transfers::{save_ibc_transfer, IbcTransfer},
+    },
+    types::{
+        destination::{Destination, DestinationAction},
+        vault::Vault,
+    },
+};
+use cosmwasm_std::{
+    to_json_binary, Addr, Api, Coin, CosmosMsg, Env, Event, StdResult, Storage, SubMsg, Uint128,
 };
-use cosmwasm_std::{Api, Coin, StdResult, Storage, SubMsg, Uint128};
 use shared::cw20::{into_bank_msg, into_execute_msg};
 use std::collections::VecDeque;
 
 pub fn get_disbursement_messages(
     api: &dyn Api,
     store: &mut dyn Storage,
+    env: &Env,
     vault: &Vault,
-    amount_to_disburse: Uint128,
+    amount_to_disburse: Coin,
 ) -> StdResult<VecDeque<SubMsg>> {
     let mut post_execution_action_caches = VecDeque::<PostExecutionActionCacheEntry>::new();
+    let mut messages = VecDeque::<SubMsg>::new();
+
+    for destination in vault.destinations.iter() {
+        let allocation_amount = Coin::new(
+            checked_mul(amount_to_disburse.amount, destination.allocation)
+                .expect("amount to be distributed should be valid")
+                .into(),
+            amount_to_disburse.denom.clone(),
+        );
 
-    let messages = vault
-        .destinations
-        .iter()
-        .flat_map(|destination| {
-            let allocation_amount = Coin::new(
-                checked_mul(amount_to_disburse, destination.allocation)
-                    .expect("amount to be distributed should be valid")
-                    .into(),
-                vault.target_denom.clone(),
+        if allocation_amount.amount.gt(&Uint128::zero()) {
+            let msg = SubMsg::reply_always(
+                get_destination_action_msg(api, env, destination, allocation_amount.clone())?,
+                get_vault_reply_id(AFTER_FAILED_AUTOMATION_REPLY_ID, vault.id),
             );
 
-            if allocation_amount.amount.gt(&Uint128::zero()) {
-                let msg = destination.msg.clone().map_or(
-                    SubMsg::reply_always(
-                        into_bank_msg(
-                            api,
-                            destination.address.as_ref(),
-                            vec![allocation_amount.clone()],
-                        )
-                        .expect("valid bank msg"),
-                        AFTER_FAILED_AUTOMATION_REPLY_ID,
-                    ),
-                    |msg| {
-                        SubMsg::reply_always(
-                            into_execute_msg(
-                                api,
-                                destination.address.clone(),
-                                msg,
-                                allocation_amount.clone(),
-                            )
-                            .expect("valid wasm execute msg"),
-                            AFTER_FAILED_AUTOMATION_REPLY_ID,
-                        )
-                    },
-                );
-
-                post_execution_action_caches.push_back(PostExecutionActionCacheEntry {
-                    msg: msg.clone(),
-                    funds: vec![allocation_amount],
-                });
-
-                return Some(msg);
-            }
-
-            None
-        })
-        .collect::<VecDeque<SubMsg>>();
+            post_execution_action_caches.push_back(PostExecutionActionCacheEntry {
+                msg: msg.clone(),
+                funds: vec![allocation_amount],
+                fallback_address: destination.get_fallback_address(&vault.owner),
+            });
+
+            messages.push_back(msg);
+        }
+    }
 
     POST_EXECUTION_ACTION_CACHE.save(store, vault.id.into(), &post_execution_action_caches)?;
 
     Ok(messages)
 }
 
+fn get_destination_action_msg(
+    api: &dyn Api,
+    env: &Env,
+    destination: &Destination,
+    amount: Coin,
+) -> StdResult<CosmosMsg> {
+    match &destination.action {
+        DestinationAction::Send => into_bank_msg(api, destination.address.as_ref(), vec![amount]),
+        DestinationAction::Delegate { validator } => into_execute_msg(
+            api,
+            env.contract.address.clone(),
+            to_json_binary(&ExecuteMsg::ZDelegate {
+                delegator_address: destination.address.clone(),
+                validator_address: validator.clone(),
+            })?,
+            amount,
+        ),
+        DestinationAction::DepositToVault { vault_id } => into_execute_msg(
+            api,
+            env.contract.address.clone(),
+            to_json_binary(&ExecuteMsg::Deposit {
+                address: destination.address.clone(),
+                vault_id: *vault_id,
+                conversion: None,
+            })?,
+            amount,
+        ),
+        DestinationAction::IbcTransfer {
+            channel,
+            receiver,
+            timeout,
+        } => Ok(create_ibc_transfer_message(
+            &env.contract.address,
+            channel.clone(),
+            receiver.clone(),
+            amount,
+            env.block.time.plus_seconds(*timeout),
+        )),
+        DestinationAction::ContractCall { msg } => {
+            into_execute_msg(api, destination.address.clone(), msg.clone(), amount)
+        }
+    }
+}
+
+pub fn save_sent_ibc_transfer(
+    store: &mut dyn Storage,
+    vault_id: Uint128,
+    msg: &CosmosMsg,
+    events: &[Event],
+    refund_address: Addr,
+) -> StdResult<Option<u64>> {
+    if let Some((transfer, funds)) = decode_ibc_transfer_message(msg)
+        .and_then(|transfer| transfer.funds().map(|funds| (transfer, funds)))
+    {
+        if let Some(sequence) = get_attribute_in_event(events, "send_packet", "packet_sequence")
+            .ok()
+            .and_then(|sequence| sequence.parse::<u64>().ok())
+        {
+            save_ibc_transfer(
+                store,
+                &IbcTransfer {
+                    vault_id,
+                    channel_id: transfer.source_channel,
+                    sequence,
+                    funds,
+                    refund_address,
+                },
+            )?;
+
+            return Ok(Some(sequence));
+        }
+    }
+
+    Ok(None)
+}
+
 #[cfg(test)]
 mod get_disbursement_messages_tests {
     use super::get_disbursement_messages;
     use crate::{
         constants::{AFTER_FAILED_AUTOMATION_REPLY_ID, ONE},
+        helpers::ibc::{create_ibc_transfer_message, decode_ibc_transfer_message},
+        msg::ExecuteMsg,
         state::cache::POST_EXECUTION_ACTION_CACHE,
-        types::{destination::Destination, vault::Vault},
+        types::{
+            destination::{Destination, DestinationAction},
+            vault::Vault,
+        },
     };
     use cosmwasm_std::{
-        testing::mock_dependencies, to_json_binary, Addr, BankMsg, Coin, Decimal, SubMsg, WasmMsg,
+        testing::{mock_dependencies, mock_env},
+        to_json_binary, Addr, BankMsg, Coin, Decimal, SubMsg, Uint128, WasmMsg,
     };
 
     #[test]
@@ -88,7 +161,7 @@ mod get_disbursement_messages_tests {
         let destination = Destination {
             address: Addr::unchecked("test"),
             allocation: Decimal::percent(100),
-            msg: None,
+            action: DestinationAction::Send,
         };
 
         let vault = Vault {
@@ -96,9 +169,14 @@ mod get_disbursement_messages_tests {
             ..Vault::default()
         };
 
-        let messages =
-            get_disbursement_messages(&deps.api.clone(), deps.as_mut().storage, &vault, ONE)
-                .unwrap();
+        let messages = get_disbursement_messages(
+            &deps.api.clone(),
+            deps.as_mut().storage,
+            &mock_env(),
+            &vault,
+            Coin::new(ONE.into(), vault.target_denom.clone()),
+        )
+        .unwrap();
 
         assert!(messages.contains(&SubMsg::reply_always(
             BankMsg::Send {
@@ -117,19 +195,19 @@ mod get_disbursement_messages_tests {
             Destination {
                 address: Addr::unchecked("owner"),
                 allocation: Decimal::percent(30),
-                msg: None,
+                action: DestinationAction::Send,
             },
             Destination {
                 address: Addr::unchecked("contract"),
                 allocation: Decimal::percent(80),
-                msg: Some(
-                    to_json_binary(&WasmMsg::Execute {
+                action: DestinationAction::ContractCall {
+                    msg: to_json_binary(&WasmMsg::Execute {
                         contract_addr: "contract".to_string(),
                         msg: to_json_binary("test").unwrap(),
                         funds: vec![],
                     })
                     .unwrap(),
-                ),
+                },
             },
         ];
 
@@ -138,7 +216,14 @@ mod get_disbursement_messages_tests {
             ..Vault::default()
         };
 
-        get_disbursement_messages(&deps.api.clone(), deps.as_mut().storage, &vault, ONE).unwrap();
+        get_disbursement_messages(
+            &deps.api.clone(),
+            deps.as_mut().storage,
+            &mock_env(),
+            &vault,
+            Coin::new(ONE.into(), vault.target_denom.clone()),
+        )
+        .unwrap();
 
         let mut cache = POST_EXECUTION_ACTION_CACHE
             .load(deps.as_ref().storage, vault.id.into())
@@ -163,7 +248,12 @@ mod get_disbursement_messages_tests {
             SubMsg::reply_always(
                 WasmMsg::Execute {
                     contract_addr: destinations[1].address.to_string(),
-                    msg: destinations[1].msg.clone().unwrap(),
+                    msg: to_json_binary(&WasmMsg::Execute {
+                        contract_addr: "contract".to_string(),
+                        msg: to_json_binary("test").unwrap(),
+                        funds: vec![],
+                    })
+                    .unwrap(),
                     funds: vec![Coin::new(
                         (ONE * destinations[1].allocation).into(),
                         vault.target_denom
@@ -173,4 +263,166 @@ mod get_disbursement_messages_tests {
             )
         );
     }
+
+    #[test]
+    fn generates_delegate_callback_for_delegate_action() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let destination = Destination {
+            address: Addr::unchecked("delegator"),
+            allocation: Decimal::percent(100),
+            action: DestinationAction::Delegate {
+                validator: Addr::unchecked("validator"),
+            },
+        };
+
+        let vault = Vault {
+            destinations: vec![destination.clone()],
+            ..Vault::default()
+        };
+
+        let messages = get_disbursement_messages(
+            &deps.api.clone(),
+            deps.as_mut().storage,
+            &env,
+            &vault,
+            Coin::new(ONE.into(), vault.target_denom.clone()),
+        )
+        .unwrap();
+
+        assert!(messages.contains(&SubMsg::reply_always(
+            WasmMsg::Execute {
+                contract_addr: env.contract.address.to_string(),
+                msg: to_json_binary(&ExecuteMsg::ZDelegate {
+                    delegator_address: destination.address,
+                    validator_address: Addr::unchecked("validator"),
+                })
+                .unwrap(),
+                funds: vec![Coin::new(ONE.into(), vault.target_denom)],
+            },
+            AFTER_FAILED_AUTOMATION_REPLY_ID
+        )))
+    }
+
+    #[test]
+    fn generates_deposit_callback_for_deposit_to_vault_action() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let destination = Destination {
+            address: Addr::unchecked("owner"),
+            allocation: Decimal::percent(100),
+            action: DestinationAction::DepositToVault {
+                vault_id: Uint128::new(2),
+            },
+        };
+
+        let vault = Vault {
+            destinations: vec![destination.clone()],
+            ..Vault::default()
+        };
+
+        let messages = get_disbursement_messages(
+            &deps.api.clone(),
+            deps.as_mut().storage,
+            &env,
+            &vault,
+            Coin::new(ONE.into(), vault.target_denom.clone()),
+        )
+        .unwrap();
+
+        assert!(messages.contains(&SubMsg::reply_always(
+            WasmMsg::Execute {
+                contract_addr: env.contract.address.to_string(),
+                msg: to_json_binary(&ExecuteMsg::Deposit {
+                    address: destination.address,
+                    vault_id: Uint128::new(2),
+                    conversion: None,
+                })
+                .unwrap(),
+                funds: vec![Coin::new(ONE.into(), vault.target_denom)],
+            },
+            AFTER_FAILED_AUTOMATION_REPLY_ID
+        )))
+    }
+
+    #[test]
+    fn generates_ibc_transfer_for_ibc_transfer_action() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let destination = Destination {
+            address: Addr::unchecked("owner"),
+            allocation: Decimal::percent(100),
+            action: DestinationAction::IbcTransfer {
+                channel: "channel-0".to_string(),
+                receiver: "osmo1receiver".to_string(),
+                timeout: 600,
+            },
+        };
+
+        let vault = Vault {
+            destinations: vec![destination],
+            ..Vault::default()
+        };
+
+        let messages = get_disbursement_messages(
+            &deps.api.clone(),
+            deps.as_mut().storage,
+            &env,
+            &vault,
+            Coin::new(ONE.into(), vault.target_denom.clone()),
+        )
+        .unwrap();
+
+        assert!(messages.contains(&SubMsg::reply_always(
+            create_ibc_transfer_message(
+                &env.contract.address,
+                "channel-0".to_string(),
+                "osmo1receiver".to_string(),
+                Coin::new(ONE.into(), vault.target_denom),
+                env.block.time.plus_seconds(600),
+            ),
+            AFTER_FAILED_AUTOMATION_REPLY_ID
+        )));
+
+        let transfer = decode_ibc_transfer_message(&messages[0].msg).unwrap();
+
+        assert_eq!(
+            transfer.memo,
+            format!("{{\"ibc_callback\":\"{}\"}}", env.contract.address)
+        );
+    }
+
+    #[test]
+    fn caches_destination_address_as_fallback_for_delegate_action() {
+        let mut deps = mock_dependencies();
+
+        let vault = Vault {
+            destinations: vec![Destination {
+                address: Addr::unchecked("delegator"),
+                allocation: Decimal::percent(100),
+                action: DestinationAction::Delegate {
+                    validator: Addr::unchecked("validator"),
+                },
+            }],
+            ..Vault::default()
+        };
+
+        get_disbursement_messages(
+            &deps.api.clone(),
+            deps.as_mut().storage,
+            &mock_env(),
+            &vault,
+            Coin::new(ONE.into(), vault.target_denom.clone()),
+        )
+        .unwrap();
+
+        let cache = POST_EXECUTION_ACTION_CACHE
+            .load(deps.as_ref().storage, vault.id.into())
+            .unwrap();
+
+        assert_eq!(cache[0].fallback_address, Addr::unchecked("delegator"));
+    }
 }
@@ -1,8 +1,13 @@
-use super::math::checked_mul;
+use super::{math::checked_mul, vault::get_vault_value_ratio};
 use crate::{
-    state::config::get_config,
+    state::{
+        cache::BATCH_KEEPER_CACHE,
+        config::get_config,
+        custom_swap_fees::get_custom_swap_fee,
+        referrals::{add_referral_fee, get_remaining_referral_fee_cap},
+    },
     types::{
-        fee_collector::FeeCollector,
+        config::Config, fee_collector::FeeCollector,
         performance_assessment_strategy::PerformanceAssessmentStrategy,
         swap_adjustment_strategy::SwapAdjustmentStrategy, vault::Vault,
     },
@@ -12,7 +17,7 @@ use cosmos_sdk_proto::{
     traits::Message,
 };
 use cosmwasm_std::{
-    Binary, Coin, CosmosMsg, Decimal, Deps, Env, StdResult, Storage, SubMsg, Uint128,
+    Addr, Binary, Coin, CosmosMsg, Decimal, Deps, Env, StdResult, Storage, SubMsg, Uint128,
 };
 use shared::cw20::into_bank_msg;
 use std::cmp::min;
@@ -95,6 +100,86 @@ pub fn get_fee_messages(
         .collect::<Vec<SubMsg>>())
 }
 
+pub fn get_keeper(store: &dyn Storage, env: &Env, sender: &Addr) -> StdResult<Option<Addr>> {
+    let config = get_config(store)?;
+
+    if !config.permissionless_execution {
+        return Ok(None);
+    }
+
+    let keeper = if sender == env.contract.address {
+        match BATCH_KEEPER_CACHE.may_load(store)? {
+            Some(keeper) => keeper,
+            None => return Ok(None),
+        }
+    } else {
+        sender.clone()
+    };
+
+    if config.executors.contains(&keeper)
+        || keeper == config.admin
+        || keeper == env.contract.address
+    {
+        return Ok(None);
+    }
+
+    Ok(Some(keeper))
+}
+
+pub fn deduct_keeper_bounty(
+    fee_amounts: Vec<Uint128>,
+    keeper_bounty_percent: Decimal,
+) -> (Vec<Uint128>, Uint128) {
+    let bounties = fee_amounts
+        .iter()
+        .map(|fee| *fee * keeper_bounty_percent)
+        .collect::<Vec<Uint128>>();
+
+    (
+        fee_amounts
+            .iter()
+            .zip(bounties.iter())
+            .map(|(fee, bounty)| fee - bounty)
+            .collect(),
+        bounties.iter().sum(),
+    )
+}
+
+pub fn deduct_referral_fee(
+    store: &mut dyn Storage,
+    referrer: &Addr,
+    fee_amounts: Vec<Uint128>,
+    denom: &str,
+) -> StdResult<(Vec<Uint128>, Coin)> {
+    let config = get_config(store)?;
+
+    let mut remaining_cap = get_remaining_referral_fee_cap(store, referrer, denom)?;
+
+    let referral_fees = fee_amounts
+        .iter()
+        .map(|fee| {
+            let referral_fee = min(*fee * config.referral_fee_percent, remaining_cap);
+            remaining_cap -= referral_fee;
+            referral_fee
+        })
+        .collect::<Vec<Uint128>>();
+
+    let referral_fee = Coin::new(referral_fees.iter().sum::<Uint128>().into(), denom);
+
+    if !referral_fee.amount.is_zero() {
+        add_referral_fee(store, referrer, &referral_fee)?;
+    }
+
+    Ok((
+        fee_amounts
+            .iter()
+            .zip(referral_fees.iter())
+            .map(|(fee, referral_fee)| fee - referral_fee)
+            .collect(),
+        referral_fee,
+    ))
+}
+
 pub fn get_automation_fee_rate(storage: &dyn Storage, vault: &Vault) -> StdResult<Decimal> {
     let default_automation_fee_level = get_config(storage)?.automation_fee_percent;
 
@@ -102,28 +187,73 @@ pub fn get_automation_fee_rate(storage: &dyn Storage, vault: &Vault) -> StdResul
         vault
             .destinations
             .iter()
-            .filter(|destination| destination.msg.is_some())
+            .filter(|destination| destination.action.incurs_automation_fee())
             .map(|destination| destination.allocation)
             .sum(),
     )?)
 }
 
-pub fn get_swap_fee_rate(
+pub fn get_swap_fee_rate(storage: &dyn Storage, vault: &Vault) -> StdResult<Decimal> {
+    get_basket_asset_swap_fee_rate(storage, vault, &vault.target_denom)
+}
+
+pub fn get_basket_asset_swap_fee_rate(
     storage: &dyn Storage,
-    swap_adjustment_strategy: &Option<SwapAdjustmentStrategy>,
+    vault: &Vault,
+    target_denom: &str,
 ) -> StdResult<Decimal> {
     let config = get_config(storage)?;
 
-    Ok(match swap_adjustment_strategy {
+    let default_swap_fee_rate = match vault.swap_adjustment_strategy {
         Some(SwapAdjustmentStrategy::WeightedScale { .. }) => {
             config.weighted_scale_swap_fee_percent
         }
-        Some(_) => Decimal::zero(),
+        Some(_) => return Ok(Decimal::zero()),
         None => config.default_swap_fee_percent,
-    })
+    };
+
+    let swap_denom = vault.get_swap_denom();
+
+    if let Some(pair_fee_rate) = get_custom_swap_fee(storage, &swap_denom, Some(target_denom))? {
+        return Ok(pair_fee_rate);
+    }
+
+    Ok(
+        match (
+            get_custom_swap_fee(storage, &swap_denom, None)?,
+            get_custom_swap_fee(storage, target_denom, None)?,
+        ) {
+            (Some(swap_denom_fee_rate), Some(target_denom_fee_rate)) => {
+                min(swap_denom_fee_rate, target_denom_fee_rate)
+            }
+            (Some(fee_rate), None) | (None, Some(fee_rate)) => fee_rate,
+            (None, None) => default_swap_fee_rate,
+        },
+    )
+}
+
+pub fn get_performance_fee_rate(
+    config: &Config,
+    vault: &Vault,
+    strategy: &PerformanceAssessmentStrategy,
+) -> Decimal {
+    let performance_fee_rate = strategy.performance_fee_rate(config);
+
+    if vault.base_escrow_level.is_zero() {
+        return performance_fee_rate;
+    }
+
+    min(
+        performance_fee_rate * (vault.escrow_level / vault.base_escrow_level),
+        Decimal::one(),
+    )
 }
 
-pub fn get_performance_fee(vault: &Vault, current_price: Decimal) -> StdResult<Coin> {
+pub fn get_performance_fee(
+    config: &Config,
+    vault: &Vault,
+    current_price: Decimal,
+) -> StdResult<Coin> {
     Ok(vault.performance_assessment_strategy.clone().map_or(
         Coin::new(0, vault.target_denom.clone()),
         |strategy| match strategy.clone() {
@@ -147,7 +277,51 @@ pub fn get_performance_fee(vault: &Vault, current_price: Decimal) -> StdResult<C
                     denom: vault.target_denom.clone(),
                     amount: min(
                         vault.escrowed_amount.amount,
-                        added_value_in_terms_of_receive_denom * strategy.performance_fee_rate(),
+                        added_value_in_terms_of_receive_denom
+                            * get_performance_fee_rate(config, vault, &strategy),
+                    ),
+                }
+            }
+            PerformanceAssessmentStrategy::CompareToLumpSum { start_price } => {
+                let added_value_in_terms_of_receive_denom =
+                    start_price.map_or(Uint128::zero(), |start_price| {
+                        let vault_total_value = vault.deposited_amount.amount
+                            - vault.swapped_amount.amount
+                            + vault.received_amount.amount * current_price;
+
+                        let lump_sum_total_value =
+                            vault.deposited_amount.amount * (current_price / start_price);
+
+                        vault_total_value
+                            .checked_sub(lump_sum_total_value)
+                            .unwrap_or(Uint128::zero())
+                            * (Decimal::one() / current_price)
+                    });
+
+                Coin {
+                    denom: vault.target_denom.clone(),
+                    amount: min(
+                        vault.escrowed_amount.amount,
+                        added_value_in_terms_of_receive_denom
+                            * get_performance_fee_rate(config, vault, &strategy),
+                    ),
+                }
+            }
+            PerformanceAssessmentStrategy::HighWaterMark { mark } => {
+                let value_above_mark = vault.deposited_amount.amount
+                    * get_vault_value_ratio(vault, current_price)
+                        .checked_sub(mark)
+                        .unwrap_or(Decimal::zero());
+
+                let value_above_mark_in_terms_of_receive_denom =
+                    value_above_mark * (Decimal::one() / current_price);
+
+                Coin {
+                    denom: vault.target_denom.clone(),
+                    amount: min(
+                        vault.escrowed_amount.amount,
+                        value_above_mark_in_terms_of_receive_denom
+                            * get_performance_fee_rate(config, vault, &strategy),
                     ),
                 }
             }
@@ -157,15 +331,21 @@ pub fn get_performance_fee(vault: &Vault, current_price: Decimal) -> StdResult<C
 
 #[cfg(test)]
 mod tests {
-    use super::get_swap_fee_rate;
+    use super::{deduct_keeper_bounty, get_performance_fee_rate, get_swap_fee_rate};
     use crate::{
         constants::{ONE, TEN},
         helpers::fees::get_performance_fee,
-        state::config::get_config,
-        tests::{helpers::instantiate_contract, mocks::ADMIN},
+        state::{config::get_config, custom_swap_fees::save_custom_swap_fee},
+        tests::{
+            helpers::instantiate_contract,
+            mocks::{ADMIN, DENOM_UKUJI, DENOM_UUSK},
+        },
         types::{
+            config::Config,
             performance_assessment_strategy::PerformanceAssessmentStrategy,
-            swap_adjustment_strategy::SwapAdjustmentStrategy, vault::Vault,
+            position_type::PositionType,
+            swap_adjustment_strategy::{BaseDenom, SwapAdjustmentStrategy},
+            vault::Vault,
         },
     };
     use cosmwasm_std::{
@@ -174,6 +354,13 @@ mod tests {
     };
     use std::str::FromStr;
 
+    fn config() -> Config {
+        Config {
+            risk_weighted_average_escrow_level: Decimal::percent(5),
+            ..Config::default()
+        }
+    }
+
     fn get_vault(
         total_deposit: Uint128,
         swapped_amount: Uint128,
@@ -230,7 +417,7 @@ mod tests {
             standard_dca_received_amount,
         );
 
-        let fee = get_performance_fee(&vault, current_price).unwrap();
+        let fee = get_performance_fee(&config(), &vault, current_price).unwrap();
         assert_eq!(fee.amount, expected_fee);
     }
 
@@ -238,7 +425,7 @@ mod tests {
     fn non_zero_fee_is_in_vault_receive_denom() {
         let vault = get_vault(TEN, TEN, TEN, TEN + TEN, TEN);
 
-        let fee = get_performance_fee(&vault, Decimal::one()).unwrap();
+        let fee = get_performance_fee(&config(), &vault, Decimal::one()).unwrap();
         assert_eq!(fee.denom, vault.target_denom);
     }
 
@@ -246,7 +433,7 @@ mod tests {
     fn zero_fee_is_in_vault_receive_denom() {
         let vault = get_vault(TEN, TEN, TEN, TEN, TEN);
 
-        let fee = get_performance_fee(&vault, Decimal::one()).unwrap();
+        let fee = get_performance_fee(&config(), &vault, Decimal::one()).unwrap();
         assert_eq!(fee.denom, vault.target_denom);
     }
 
@@ -397,6 +584,84 @@ mod tests {
         );
     }
 
+    #[test]
+    fn lump_sum_fee_is_charged_on_value_above_lump_sum_purchase() {
+        let vault = Vault {
+            deposited_amount: Coin::new(2000, "swap_denom"),
+            swapped_amount: Coin::new(1000, "swap_denom"),
+            received_amount: Coin::new(1250, "receive_denom"),
+            escrowed_amount: Coin::new(1000, "receive_denom"),
+            escrow_level: Decimal::percent(5),
+            performance_assessment_strategy: Some(
+                PerformanceAssessmentStrategy::CompareToLumpSum {
+                    start_price: Some(Decimal::one()),
+                },
+            ),
+            ..Vault::default()
+        };
+
+        let fee = get_performance_fee(&config(), &vault, Decimal::percent(80)).unwrap();
+
+        assert_eq!(fee.amount, Uint128::new(100));
+    }
+
+    #[test]
+    fn lump_sum_fee_is_zero_before_start_price_is_recorded() {
+        let vault = Vault {
+            deposited_amount: Coin::new(2000, "swap_denom"),
+            swapped_amount: Coin::new(1000, "swap_denom"),
+            received_amount: Coin::new(1250, "receive_denom"),
+            escrowed_amount: Coin::new(1000, "receive_denom"),
+            escrow_level: Decimal::percent(5),
+            performance_assessment_strategy: Some(
+                PerformanceAssessmentStrategy::CompareToLumpSum { start_price: None },
+            ),
+            ..Vault::default()
+        };
+
+        let fee = get_performance_fee(&config(), &vault, Decimal::percent(80)).unwrap();
+
+        assert!(fee.amount.is_zero());
+    }
+
+    #[test]
+    fn performance_fee_rate_scales_with_escrow_level() {
+        let vault = Vault {
+            escrow_level: Decimal::percent(10),
+            performance_assessment_strategy: Some(PerformanceAssessmentStrategy::default()),
+            ..Vault::default()
+        };
+
+        let fee_rate = get_performance_fee_rate(
+            &config(),
+            &vault,
+            &vault.performance_assessment_strategy.clone().unwrap(),
+        );
+
+        assert_eq!(fee_rate, Decimal::percent(40));
+    }
+
+    #[test]
+    fn performance_fee_rate_ignores_later_config_escrow_level_changes() {
+        let vault = Vault {
+            escrow_level: Decimal::percent(10),
+            base_escrow_level: Decimal::percent(5),
+            performance_assessment_strategy: Some(PerformanceAssessmentStrategy::default()),
+            ..Vault::default()
+        };
+
+        let fee_rate = get_performance_fee_rate(
+            &Config {
+                risk_weighted_average_escrow_level: Decimal::percent(20),
+                ..config()
+            },
+            &vault,
+            &vault.performance_assessment_strategy.clone().unwrap(),
+        );
+
+        assert_eq!(fee_rate, Decimal::percent(40));
+    }
+
     #[test]
     fn swap_adjustment_specific_fee_level_is_used() {
         let mut deps = mock_dependencies();
@@ -414,11 +679,95 @@ mod tests {
             ..Default::default()
         };
 
-        let fee_rate =
-            get_swap_fee_rate(deps.as_ref().storage, &vault.swap_adjustment_strategy).unwrap();
+        let fee_rate = get_swap_fee_rate(deps.as_ref().storage, &vault).unwrap();
 
         let config = get_config(deps.as_ref().storage).unwrap();
 
         assert_eq!(config.weighted_scale_swap_fee_percent, fee_rate);
     }
+
+    #[test]
+    fn lowest_custom_denom_fee_is_used() {
+        let mut deps = mock_dependencies();
+
+        instantiate_contract(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]));
+
+        save_custom_swap_fee(
+            deps.as_mut().storage,
+            DENOM_UKUJI,
+            None,
+            Decimal::percent(2),
+        )
+        .unwrap();
+        save_custom_swap_fee(deps.as_mut().storage, DENOM_UUSK, None, Decimal::percent(1)).unwrap();
+
+        let fee_rate = get_swap_fee_rate(deps.as_ref().storage, &Vault::default()).unwrap();
+
+        assert_eq!(fee_rate, Decimal::percent(1));
+    }
+
+    #[test]
+    fn custom_pair_fee_takes_precedence_over_custom_denom_fees() {
+        let mut deps = mock_dependencies();
+
+        instantiate_contract(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]));
+
+        save_custom_swap_fee(
+            deps.as_mut().storage,
+            DENOM_UKUJI,
+            None,
+            Decimal::percent(1),
+        )
+        .unwrap();
+        save_custom_swap_fee(
+            deps.as_mut().storage,
+            DENOM_UUSK,
+            Some(DENOM_UKUJI),
+            Decimal::percent(3),
+        )
+        .unwrap();
+
+        let fee_rate = get_swap_fee_rate(deps.as_ref().storage, &Vault::default()).unwrap();
+
+        assert_eq!(fee_rate, Decimal::percent(3));
+    }
+
+    #[test]
+    fn custom_fees_do_not_apply_to_fee_free_strategies() {
+        let mut deps = mock_dependencies();
+
+        instantiate_contract(deps.as_mut(), mock_env(), mock_info(ADMIN, &[]));
+
+        save_custom_swap_fee(
+            deps.as_mut().storage,
+            DENOM_UKUJI,
+            None,
+            Decimal::percent(1),
+        )
+        .unwrap();
+
+        let vault = Vault {
+            swap_adjustment_strategy: Some(SwapAdjustmentStrategy::RiskWeightedAverage {
+                model_id: 30,
+                base_denom: BaseDenom::Bitcoin,
+                position_type: PositionType::Enter,
+            }),
+            ..Vault::default()
+        };
+
+        let fee_rate = get_swap_fee_rate(deps.as_ref().storage, &vault).unwrap();
+
+        assert_eq!(fee_rate, Decimal::zero());
+    }
+
+    #[test]
+    fn deduct_keeper_bounty_takes_cut_of_each_fee() {
+        let (fee_amounts, keeper_bounty) = deduct_keeper_bounty(
+            vec![Uint128::new(1000), Uint128::new(500)],
+            Decimal::percent(10),
+        );
+
+        assert_eq!(fee_amounts, vec![Uint128::new(900), Uint128::new(450)]);
+        assert_eq!(keeper_bounty, Uint128::new(150));
+    }
 }
@@ -0,0 +1,183 @@
+use cosmos_sdk_proto::{cosmos::base::v1beta1::Coin as ProtoCoin, ibc::core::client::v1::Height};
+use cosmwasm_std::{Addr, Binary, Coin, CosmosMsg, Timestamp};
+use prost::Message;
+
+pub const MSG_TRANSFER_TYPE_URL: &str = "/ibc.applications.transfer.v1.MsgTransfer";
+
+/// ICS-20 `MsgTransfer` with the `memo` field, which the bundled
+/// cosmos-sdk-proto definition predates.
+#[derive(Clone, PartialEq, Message)]
+pub struct MsgTransfer {
+    #[prost(string, tag = "1")]
+    pub source_port: String,
+    #[prost(string, tag = "2")]
+    pub source_channel: String,
+    #[prost(message, optional, tag = "3")]
+    pub token: Option<ProtoCoin>,
+    #[prost(string, tag = "4")]
+    pub sender: String,
+    #[prost(string, tag = "5")]
+    pub receiver: String,
+    #[prost(message, optional, tag = "6")]
+    pub timeout_height: Option<Height>,
+    #[prost(uint64, tag = "7")]
+    pub timeout_timestamp: u64,
+    #[prost(string, tag = "8")]
+    pub memo: String,
+}
+
+impl MsgTransfer {
+    pub fn funds(&self) -> Option<Coin> {
+        self.token.as_ref().and_then(|token| {
+            token
+                .amount
+                .parse::<u128>()
+                .ok()
+                .map(|amount| Coin::new(amount, token.denom.clone()))
+        })
+    }
+}
+
+pub fn get_ibc_callback_memo(contract_address: &Addr) -> String {
+    format!("{{\"ibc_callback\":\"{}\"}}", contract_address)
+}
+
+pub fn create_ibc_transfer_message(
+    contract_address: &Addr,
+    channel: String,
+    receiver: String,
+    amount: Coin,
+    timeout: Timestamp,
+) -> CosmosMsg {
+    into_stargate_msg(&MsgTransfer {
+        source_port: "transfer".to_string(),
+        source_channel: channel,
+        token: Some(ProtoCoin {
+            denom: amount.denom,
+            amount: amount.amount.to_string(),
+        }),
+        sender: contract_address.to_string(),
+        receiver,
+        timeout_height: None,
+        timeout_timestamp: timeout.nanos(),
+        memo: get_ibc_callback_memo(contract_address),
+    })
+}
+
+pub fn decode_ibc_transfer_message(msg: &CosmosMsg) -> Option<MsgTransfer> {
+    match msg {
+        CosmosMsg::Stargate { type_url, value } if type_url == MSG_TRANSFER_TYPE_URL => {
+            MsgTransfer::decode(value.as_slice()).ok()
+        }
+        _ => None,
+    }
+}
+
+pub fn refresh_ibc_transfer_timeout(
+    msg: CosmosMsg,
+    created_at: Timestamp,
+    now: Timestamp,
+) -> CosmosMsg {
+    match decode_ibc_transfer_message(&msg) {
+        Some(transfer) => into_stargate_msg(&MsgTransfer {
+            timeout_timestamp: now
+                .plus_nanos(
+                    transfer
+                        .timeout_timestamp
+                        .saturating_sub(created_at.nanos()),
+                )
+                .nanos(),
+            ..transfer
+        }),
+        None => msg,
+    }
+}
+
+fn into_stargate_msg(transfer: &MsgTransfer) -> CosmosMsg {
+    CosmosMsg::Stargate {
+        type_url: MSG_TRANSFER_TYPE_URL.to_string(),
+        value: Binary::from(transfer.encode_to_vec()),
+    }
+}
+
+#[cfg(test)]
+mod create_ibc_transfer_message_tests {
+    use super::{create_ibc_transfer_message, decode_ibc_transfer_message};
+    use cosmwasm_std::{testing::mock_env, Coin};
+
+    #[test]
+    fn sets_ibc_callback_memo_to_contract_address() {
+        let env = mock_env();
+
+        let msg = create_ibc_transfer_message(
+            &env.contract.address,
+            "channel-0".to_string(),
+            "osmo1receiver".to_string(),
+            Coin::new(100, "uosmo"),
+            env.block.time.plus_seconds(600),
+        );
+
+        let transfer = decode_ibc_transfer_message(&msg).unwrap();
+
+        assert_eq!(
+            transfer.memo,
+            format!("{{\"ibc_callback\":\"{}\"}}", env.contract.address)
+        );
+        assert_eq!(transfer.sender, env.contract.address.to_string());
+        assert_eq!(transfer.source_channel, "channel-0");
+        assert_eq!(transfer.funds(), Some(Coin::new(100, "uosmo")));
+        assert_eq!(
+            transfer.timeout_timestamp,
+            env.block.time.plus_seconds(600).nanos()
+        );
+    }
+}
+
+#[cfg(test)]
+mod refresh_ibc_transfer_timeout_tests {
+    use super::{
+        create_ibc_transfer_message, decode_ibc_transfer_message, refresh_ibc_transfer_timeout,
+    };
+    use cosmwasm_std::{testing::mock_env, BankMsg, Coin, CosmosMsg};
+
+    #[test]
+    fn keeps_original_timeout_duration_from_now() {
+        let env = mock_env();
+
+        let msg = create_ibc_transfer_message(
+            &env.contract.address,
+            "channel-0".to_string(),
+            "osmo1receiver".to_string(),
+            Coin::new(100, "uosmo"),
+            env.block.time.plus_seconds(600),
+        );
+
+        let now = env.block.time.plus_seconds(3600);
+
+        let transfer =
+            decode_ibc_transfer_message(&refresh_ibc_transfer_timeout(msg, env.block.time, now))
+                .unwrap();
+
+        assert_eq!(transfer.timeout_timestamp, now.plus_seconds(600).nanos());
+        assert_eq!(transfer.funds(), Some(Coin::new(100, "uosmo")));
+    }
+
+    #[test]
+    fn leaves_other_messages_unchanged() {
+        let env = mock_env();
+
+        let msg = CosmosMsg::Bank(BankMsg::Send {
+            to_address: "receiver".to_string(),
+            amount: vec![Coin::new(100, "uosmo")],
+        });
+
+        assert_eq!(
+            refresh_ibc_transfer_timeout(
+                msg.clone(),
+                env.block.time,
+                env.block.time.plus_seconds(3600)
+            ),
+            msg
+        );
+    }
+}
@@ -1,4 +1,6 @@
-use cosmwasm_std::{Event, StdError, StdResult};
+use cosmwasm_std::{Event, StdError, StdResult, Uint128};
+
+const VAULT_REPLY_ID_SHIFT: u32 = 8;
 
 pub fn get_attribute_in_event(
     events: &[Event],
@@ -20,3 +22,16 @@ pub fn get_attribute_in_event(
 
     Ok(attribute.value.clone())
 }
+
+pub fn get_vault_reply_id(reply_id: u64, vault_id: Uint128) -> u64 {
+    ((vault_id.u128() << VAULT_REPLY_ID_SHIFT) | reply_id as u128)
+        .try_into()
+        .expect("vault id should fit in a reply id")
+}
+
+pub fn split_vault_reply_id(id: u64) -> (u64, Uint128) {
+    (
+        id & ((1 << VAULT_REPLY_ID_SHIFT) - 1),
+        Uint128::from(id >> VAULT_REPLY_ID_SHIFT),
+    )
+}
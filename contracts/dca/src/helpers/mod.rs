@@ -1,7 +1,10 @@
+pub mod aggregation;
 pub mod authz;
 pub mod disbursement;
 pub mod fees;
+pub mod ibc;
 pub mod math;
+pub mod netting;
 pub mod message;
 pub mod price;
 pub mod state;
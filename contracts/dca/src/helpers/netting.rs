@@ -0,0 +1,358 @@
+use super::{price::get_twap_to_now, time::get_missed_interval_count};
+use crate::{
+    state::{config::get_config, vaults::get_vault},
+    types::{netted_execution::NettedExecution, trigger::TriggerConfiguration, vault::Vault},
+};
+use cosmwasm_std::{Coin, Decimal, Deps, Env, Fraction, StdResult, Timestamp, Uint128};
+use std::{cmp::min, collections::BTreeMap};
+
+pub fn get_nettable_swap_amount(vault: &Vault, current_time: Timestamp) -> Uint128 {
+    if vault.minimum_receive_amount.is_some() {
+        return Uint128::zero();
+    }
+
+    get_internal_swap_amount(vault, current_time)
+}
+
+pub fn get_internal_swap_amount(vault: &Vault, current_time: Timestamp) -> Uint128 {
+    let is_due_without_missed_executions = match vault.trigger {
+        Some(TriggerConfiguration::Time { target_time })
+        | Some(TriggerConfiguration::Retry { target_time, .. }) => {
+            target_time <= current_time
+                && get_missed_interval_count(current_time, target_time, &vault.time_interval) == 0
+        }
+        _ => false,
+    };
+
+    if !is_due_without_missed_executions
+        || !vault.is_active()
+        || vault.basket.is_some()
+        || vault.swap_adjustment_strategy.is_some()
+        || vault.performance_assessment_strategy.is_some()
+        || vault.catch_up_schedule.is_some()
+        || vault.recurring_deposits
+        || vault.get_reached_end_condition(current_time).is_some()
+    {
+        return Uint128::zero();
+    }
+
+    min(vault.swap_amount, vault.balance.amount)
+}
+
+pub fn get_netted_executions(
+    deps: Deps,
+    env: &Env,
+    trigger_ids: &[Uint128],
+) -> StdResult<Vec<Vec<NettedExecution>>> {
+    let mut vaults_by_pair = BTreeMap::<(String, String), Vec<Vault>>::new();
+
+    for trigger_id in trigger_ids {
+        if let Ok(vault) = get_vault(deps.storage, *trigger_id) {
+            if !get_nettable_swap_amount(&vault, env.block.time).is_zero() {
+                vaults_by_pair
+                    .entry((vault.get_swap_denom(), vault.target_denom.clone()))
+                    .or_default()
+                    .push(vault);
+            }
+        }
+    }
+
+    let config = get_config(deps.storage)?;
+
+    let mut netted_executions = Vec::<Vec<NettedExecution>>::new();
+
+    for ((swap_denom, target_denom), vaults) in vaults_by_pair.iter() {
+        if swap_denom > target_denom {
+            continue;
+        }
+
+        if let Some(opposing_vaults) =
+            vaults_by_pair.get(&(target_denom.clone(), swap_denom.clone()))
+        {
+            let Ok(price) = get_twap_to_now(
+                &deps.querier,
+                config.exchange_contract_address.clone(),
+                swap_denom.clone(),
+                target_denom.clone(),
+                config.twap_period,
+                vaults[0].route.clone(),
+            ) else {
+                continue;
+            };
+
+            let executions = cross_opposing_vaults(vaults, opposing_vaults, price, env.block.time);
+
+            if !executions.is_empty() {
+                netted_executions.push(executions);
+            }
+        }
+    }
+
+    Ok(netted_executions)
+}
+
+pub fn cross_opposing_vaults(
+    vaults: &[Vault],
+    opposing_vaults: &[Vault],
+    price: Decimal,
+    current_time: Timestamp,
+) -> Vec<NettedExecution> {
+    let swap_amounts = vaults
+        .iter()
+        .map(|vault| get_nettable_swap_amount(vault, current_time))
+        .collect::<Vec<Uint128>>();
+
+    let opposing_swap_amounts = opposing_vaults
+        .iter()
+        .map(|vault| get_nettable_swap_amount(vault, current_time))
+        .collect::<Vec<Uint128>>();
+
+    let total = swap_amounts.iter().sum::<Uint128>();
+    let opposing_total = opposing_swap_amounts.iter().sum::<Uint128>();
+
+    let inverse_price = match price.inv() {
+        Some(inverse_price) => inverse_price,
+        None => return vec![],
+    };
+
+    let matched = min(total, opposing_total * price);
+    let opposing_matched = min(opposing_total, matched * inverse_price);
+
+    let sent = distribute(&swap_amounts, matched, total);
+    let opposing_sent = distribute(&opposing_swap_amounts, opposing_matched, opposing_total);
+
+    let pool = sent.iter().sum::<Uint128>();
+    let opposing_pool = opposing_sent.iter().sum::<Uint128>();
+
+    if pool.is_zero() || opposing_pool.is_zero() {
+        return vec![];
+    }
+
+    let received = distribute(&sent, opposing_pool, pool);
+    let opposing_received = distribute(&opposing_sent, pool, opposing_pool);
+
+    get_executions(vaults, &swap_amounts, &sent, &received, price)
+        .into_iter()
+        .chain(get_executions(
+            opposing_vaults,
+            &opposing_swap_amounts,
+            &opposing_sent,
+            &opposing_received,
+            inverse_price,
+        ))
+        .collect()
+}
+
+fn distribute(weights: &[Uint128], amount: Uint128, total: Uint128) -> Vec<Uint128> {
+    weights
+        .iter()
+        .map(|weight| {
+            if total.is_zero() {
+                Uint128::zero()
+            } else {
+                amount.multiply_ratio(*weight, total)
+            }
+        })
+        .collect()
+}
+
+fn get_executions(
+    vaults: &[Vault],
+    swap_amounts: &[Uint128],
+    sent: &[Uint128],
+    received: &[Uint128],
+    asset_price: Decimal,
+) -> Vec<NettedExecution> {
+    vaults
+        .iter()
+        .zip(swap_amounts)
+        .zip(sent.iter().zip(received))
+        .filter(|(_, (sent, received))| !sent.is_zero() && !received.is_zero())
+        .map(|((vault, swap_amount), (sent, received))| NettedExecution {
+            vault_id: vault.id,
+            sent: Coin::new((*sent).into(), vault.get_swap_denom()),
+            received: Coin::new((*received).into(), vault.target_denom.clone()),
+            residual: Coin::new((swap_amount - sent).into(), vault.get_swap_denom()),
+            asset_price,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod cross_opposing_vaults_tests {
+    use super::*;
+    use crate::tests::mocks::{DENOM_UKUJI, DENOM_UUSK};
+    use cosmwasm_std::testing::mock_env;
+
+    fn vault(id: u128, swap_amount: u128, swap_denom: &str, target_denom: &str) -> Vault {
+        Vault {
+            id: Uint128::new(id),
+            balance: Coin::new(swap_amount * 10, swap_denom),
+            swap_amount: Uint128::new(swap_amount),
+            target_denom: target_denom.to_string(),
+            trigger: Some(TriggerConfiguration::Time {
+                target_time: mock_env().block.time,
+            }),
+            ..Vault::default()
+        }
+    }
+
+    #[test]
+    fn crosses_equal_opposing_vaults_completely() {
+        let env = mock_env();
+
+        let executions = cross_opposing_vaults(
+            &[vault(1, 1000, DENOM_UKUJI, DENOM_UUSK)],
+            &[vault(2, 500, DENOM_UUSK, DENOM_UKUJI)],
+            Decimal::percent(200),
+            env.block.time,
+        );
+
+        assert_eq!(
+            executions,
+            vec![
+                NettedExecution {
+                    vault_id: Uint128::new(1),
+                    sent: Coin::new(1000, DENOM_UKUJI),
+                    received: Coin::new(500, DENOM_UUSK),
+                    residual: Coin::new(0, DENOM_UKUJI),
+                    asset_price: Decimal::percent(200),
+                },
+                NettedExecution {
+                    vault_id: Uint128::new(2),
+                    sent: Coin::new(500, DENOM_UUSK),
+                    received: Coin::new(1000, DENOM_UKUJI),
+                    residual: Coin::new(0, DENOM_UUSK),
+                    asset_price: Decimal::percent(50),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn leaves_imbalance_as_residual_on_larger_side() {
+        let env = mock_env();
+
+        let executions = cross_opposing_vaults(
+            &[
+                vault(1, 1000, DENOM_UKUJI, DENOM_UUSK),
+                vault(2, 3000, DENOM_UKUJI, DENOM_UUSK),
+            ],
+            &[vault(3, 2000, DENOM_UUSK, DENOM_UKUJI)],
+            Decimal::one(),
+            env.block.time,
+        );
+
+        assert_eq!(
+            executions,
+            vec![
+                NettedExecution {
+                    vault_id: Uint128::new(1),
+                    sent: Coin::new(500, DENOM_UKUJI),
+                    received: Coin::new(500, DENOM_UUSK),
+                    residual: Coin::new(500, DENOM_UKUJI),
+                    asset_price: Decimal::one(),
+                },
+                NettedExecution {
+                    vault_id: Uint128::new(2),
+                    sent: Coin::new(1500, DENOM_UKUJI),
+                    received: Coin::new(1500, DENOM_UUSK),
+                    residual: Coin::new(1500, DENOM_UKUJI),
+                    asset_price: Decimal::one(),
+                },
+                NettedExecution {
+                    vault_id: Uint128::new(3),
+                    sent: Coin::new(2000, DENOM_UUSK),
+                    received: Coin::new(2000, DENOM_UKUJI),
+                    residual: Coin::new(0, DENOM_UUSK),
+                    asset_price: Decimal::one(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn never_distributes_more_than_the_opposing_side_sent() {
+        let env = mock_env();
+
+        let executions = cross_opposing_vaults(
+            &[
+                vault(1, 333, DENOM_UKUJI, DENOM_UUSK),
+                vault(2, 333, DENOM_UKUJI, DENOM_UUSK),
+                vault(3, 334, DENOM_UKUJI, DENOM_UUSK),
+            ],
+            &[vault(4, 700, DENOM_UUSK, DENOM_UKUJI)],
+            Decimal::from_ratio(3u128, 7u128),
+            env.block.time,
+        );
+
+        let sent_by_denom = |denom: &str| {
+            executions
+                .iter()
+                .filter(|execution| execution.sent.denom == denom)
+                .map(|execution| execution.sent.amount)
+                .sum::<Uint128>()
+        };
+
+        let received_by_denom = |denom: &str| {
+            executions
+                .iter()
+                .filter(|execution| execution.received.denom == denom)
+                .map(|execution| execution.received.amount)
+                .sum::<Uint128>()
+        };
+
+        assert!(received_by_denom(DENOM_UKUJI) <= sent_by_denom(DENOM_UKUJI));
+        assert!(received_by_denom(DENOM_UUSK) <= sent_by_denom(DENOM_UUSK));
+    }
+
+    #[test]
+    fn skips_vaults_that_are_not_due() {
+        let env = mock_env();
+
+        let executions = cross_opposing_vaults(
+            &[vault(1, 1000, DENOM_UKUJI, DENOM_UUSK)],
+            &[Vault {
+                trigger: Some(TriggerConfiguration::Time {
+                    target_time: env.block.time.plus_seconds(10),
+                }),
+                ..vault(2, 1000, DENOM_UUSK, DENOM_UKUJI)
+            }],
+            Decimal::one(),
+            env.block.time,
+        );
+
+        assert!(executions.is_empty());
+    }
+
+    #[test]
+    fn skips_vaults_with_missed_executions() {
+        let env = mock_env();
+
+        let executions = cross_opposing_vaults(
+            &[vault(1, 1000, DENOM_UKUJI, DENOM_UUSK)],
+            &[vault(2, 1000, DENOM_UUSK, DENOM_UKUJI)],
+            Decimal::one(),
+            env.block.time.plus_seconds(2 * 24 * 60 * 60),
+        );
+
+        assert!(executions.is_empty());
+    }
+
+    #[test]
+    fn skips_vaults_with_recurring_deposits() {
+        let env = mock_env();
+
+        let executions = cross_opposing_vaults(
+            &[vault(1, 1000, DENOM_UKUJI, DENOM_UUSK)],
+            &[Vault {
+                recurring_deposits: true,
+                ..vault(2, 1000, DENOM_UUSK, DENOM_UKUJI)
+            }],
+            Decimal::one(),
+            env.block.time,
+        );
+
+        assert!(executions.is_empty());
+    }
+}
@@ -1,7 +1,7 @@
 use cosmwasm_std::{Addr, Binary, Coin, Decimal, QuerierWrapper, StdResult, Storage, Uint128};
 use exchange::msg::QueryMsg;
 
-use crate::state::config::get_config;
+use crate::{constants::PARTIAL_SWAP_AMOUNT_SEARCH_STEPS, state::config::get_config};
 
 pub fn get_twap_to_now(
     querier: &QuerierWrapper,
@@ -73,6 +73,44 @@ pub fn get_slippage(
     Ok(expected_price.abs_diff(belief_price) / belief_price)
 }
 
+pub fn get_largest_swap_amount_within_slippage_tolerance(
+    querier: &QuerierWrapper,
+    exchange_contract_address: Addr,
+    swap_amount: Coin,
+    target_denom: String,
+    belief_price: Decimal,
+    route: Option<Binary>,
+    slippage_tolerance: Decimal,
+) -> StdResult<Coin> {
+    let mut lower_bound = Uint128::zero();
+    let mut upper_bound = swap_amount.amount;
+
+    for _ in 0..PARTIAL_SWAP_AMOUNT_SEARCH_STEPS {
+        let midpoint = lower_bound + (upper_bound - lower_bound) / Uint128::new(2);
+
+        if midpoint == lower_bound {
+            break;
+        }
+
+        let slippage = get_slippage(
+            querier,
+            exchange_contract_address.clone(),
+            Coin::new(midpoint.into(), swap_amount.denom.clone()),
+            target_denom.clone(),
+            belief_price,
+            route.clone(),
+        )?;
+
+        if slippage <= slippage_tolerance {
+            lower_bound = midpoint;
+        } else {
+            upper_bound = midpoint;
+        }
+    }
+
+    Ok(Coin::new(lower_bound.into(), swap_amount.denom))
+}
+
 pub fn get_price(
     querier: &QuerierWrapper,
     storage: &dyn Storage,
@@ -1,4 +1,4 @@
-use crate::types::time_interval::TimeInterval;
+use crate::types::time_interval::{TimeInterval, Weekday};
 use chrono::{DateTime, Datelike, Duration, TimeZone, Utc};
 use cosmwasm_std::Timestamp;
 use std::convert::TryInto;
@@ -20,6 +20,15 @@ pub fn get_next_target_time(
         .timestamp_opt(started_at.seconds().try_into().unwrap(), 0)
         .unwrap();
 
+    if interval.is_calendar_schedule() {
+        return Timestamp::from_seconds(
+            get_next_calendar_time(current_time, &interval)
+                .timestamp()
+                .try_into()
+                .unwrap(),
+        );
+    }
+
     let mut next_execution_time = get_next_time(started_at_time, &interval);
 
     match interval {
@@ -45,6 +54,41 @@ pub fn get_next_target_time(
     Timestamp::from_seconds(next_execution_time.timestamp().try_into().unwrap())
 }
 
+pub fn get_missed_interval_count(
+    current_timestamp: Timestamp,
+    target_timestamp: Timestamp,
+    interval: &TimeInterval,
+) -> u64 {
+    let current_time = Utc
+        .timestamp_opt(current_timestamp.seconds().try_into().unwrap(), 0)
+        .unwrap();
+
+    let target_time = Utc
+        .timestamp_opt(target_timestamp.seconds().try_into().unwrap(), 0)
+        .unwrap();
+
+    // every-block vaults have no wall-clock interval, so a late block is never a missed execution
+    if current_time <= target_time || interval == &TimeInterval::EveryBlock {
+        return 0;
+    }
+
+    if interval.is_calendar_schedule() || interval == &TimeInterval::Monthly {
+        let mut missed_intervals = 0;
+        let mut next_execution_time = get_next_time(target_time, interval);
+
+        while next_execution_time <= current_time {
+            missed_intervals += 1;
+            next_execution_time = get_next_time(next_execution_time, interval);
+        }
+
+        return missed_intervals;
+    }
+
+    ((current_time - target_time).num_seconds() / get_duration(target_time, interval).num_seconds())
+        .try_into()
+        .expect("missed interval count should be >= 0")
+}
+
 pub fn get_total_execution_duration(
     block_time: Timestamp,
     iterations: u128,
@@ -61,6 +105,30 @@ pub fn get_total_execution_duration(
     })
 }
 
+pub fn get_execution_count_before(
+    block_time: Timestamp,
+    end_time: Timestamp,
+    iterations: u128,
+    interval: &TimeInterval,
+) -> u128 {
+    let end_time = Utc
+        .timestamp_opt(end_time.seconds().try_into().unwrap(), 0)
+        .unwrap();
+
+    let mut execution_time = Utc
+        .timestamp_opt(block_time.seconds().try_into().unwrap(), 0)
+        .unwrap();
+
+    let mut executions = 0;
+
+    while executions < iterations && execution_time < end_time {
+        executions += 1;
+        execution_time += get_duration(execution_time, interval);
+    }
+
+    executions
+}
+
 fn get_duration(previous: DateTime<Utc>, interval: &TimeInterval) -> Duration {
     match interval {
         TimeInterval::EveryBlock => Duration::seconds(1),
@@ -73,6 +141,55 @@ fn get_duration(previous: DateTime<Utc>, interval: &TimeInterval) -> Duration {
         TimeInterval::Fortnightly => Duration::days(14),
         TimeInterval::Monthly => shift_months(previous, 1) - previous,
         TimeInterval::Custom { seconds } => Duration::seconds(*seconds as i64),
+        TimeInterval::DailyAt { .. }
+        | TimeInterval::WeekdaysAt { .. }
+        | TimeInterval::DaysOfMonthAt { .. } => {
+            get_next_calendar_time(previous, interval) - previous
+        }
+    }
+}
+
+fn get_next_calendar_time(previous: DateTime<Utc>, interval: &TimeInterval) -> DateTime<Utc> {
+    let (hour, minute) = match interval {
+        TimeInterval::DailyAt { hour, minute }
+        | TimeInterval::WeekdaysAt { hour, minute, .. }
+        | TimeInterval::DaysOfMonthAt { hour, minute, .. } => (*hour as u32, *minute as u32),
+        _ => panic!("{} is not a calendar schedule", interval),
+    };
+
+    // days of the month past the end of a month fall on its last day, so every schedule
+    // has a next time within the following month
+    (0..=62)
+        .map(|offset| previous.date_naive() + Duration::days(offset))
+        .filter(|date| match interval {
+            TimeInterval::WeekdaysAt { weekdays, .. } => weekdays
+                .iter()
+                .any(|weekday| to_chrono_weekday(weekday) == date.weekday()),
+            TimeInterval::DaysOfMonthAt { days, .. } => days
+                .iter()
+                .any(|day| normalise_day(date.year(), date.month(), *day as u32) == date.day()),
+            _ => true,
+        })
+        .map(|date| {
+            Utc.from_utc_datetime(
+                &date
+                    .and_hms_opt(hour, minute, 0)
+                    .expect("hour and minute should be valid"),
+            )
+        })
+        .find(|time| time > &previous)
+        .expect("calendar schedule should have a next time within 62 days")
+}
+
+fn to_chrono_weekday(weekday: &Weekday) -> chrono::Weekday {
+    match weekday {
+        Weekday::Monday => chrono::Weekday::Mon,
+        Weekday::Tuesday => chrono::Weekday::Tue,
+        Weekday::Wednesday => chrono::Weekday::Wed,
+        Weekday::Thursday => chrono::Weekday::Thu,
+        Weekday::Friday => chrono::Weekday::Fri,
+        Weekday::Saturday => chrono::Weekday::Sat,
+        Weekday::Sunday => chrono::Weekday::Sun,
     }
 }
 
@@ -519,11 +636,244 @@ mod tests {
         );
     }
 
+    #[test]
+    fn assert_daily_at_next_execution_times() {
+        let last_execution_time = Utc.with_ymd_and_hms(2022, 1, 1, 1, 0, 0).unwrap();
+        let scenarios = vec![
+            (
+                Utc.with_ymd_and_hms(2022, 1, 1, 10, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2022, 1, 1, 14, 0, 0).unwrap(),
+            ),
+            (
+                Utc.with_ymd_and_hms(2022, 1, 1, 14, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2022, 1, 2, 14, 0, 0).unwrap(),
+            ),
+            (
+                Utc.with_ymd_and_hms(2022, 1, 1, 14, 0, 1).unwrap(),
+                Utc.with_ymd_and_hms(2022, 1, 2, 14, 0, 0).unwrap(),
+            ),
+            (
+                Utc.with_ymd_and_hms(2022, 1, 31, 23, 59, 0).unwrap(),
+                Utc.with_ymd_and_hms(2022, 2, 1, 14, 0, 0).unwrap(),
+            ),
+        ];
+
+        assert_expected_next_execution_times(
+            &TimeInterval::DailyAt {
+                hour: 14,
+                minute: 0,
+            },
+            last_execution_time,
+            scenarios,
+        );
+    }
+
+    #[test]
+    fn assert_weekdays_at_next_execution_times() {
+        let last_execution_time = Utc.with_ymd_and_hms(2022, 1, 1, 1, 0, 0).unwrap();
+        let scenarios = vec![
+            (
+                Utc.with_ymd_and_hms(2022, 1, 1, 10, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2022, 1, 3, 9, 30, 0).unwrap(),
+            ),
+            (
+                Utc.with_ymd_and_hms(2022, 1, 3, 9, 29, 59).unwrap(),
+                Utc.with_ymd_and_hms(2022, 1, 3, 9, 30, 0).unwrap(),
+            ),
+            (
+                Utc.with_ymd_and_hms(2022, 1, 3, 9, 30, 0).unwrap(),
+                Utc.with_ymd_and_hms(2022, 1, 7, 9, 30, 0).unwrap(),
+            ),
+            (
+                Utc.with_ymd_and_hms(2022, 1, 7, 10, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2022, 1, 10, 9, 30, 0).unwrap(),
+            ),
+        ];
+
+        assert_expected_next_execution_times(
+            &TimeInterval::WeekdaysAt {
+                weekdays: vec![Weekday::Monday, Weekday::Friday],
+                hour: 9,
+                minute: 30,
+            },
+            last_execution_time,
+            scenarios,
+        );
+    }
+
+    #[test]
+    fn assert_days_of_month_at_next_execution_times() {
+        let last_execution_time = Utc.with_ymd_and_hms(2022, 1, 1, 1, 0, 0).unwrap();
+        let scenarios = vec![
+            (
+                Utc.with_ymd_and_hms(2022, 1, 1, 11, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2022, 1, 1, 12, 0, 0).unwrap(),
+            ),
+            (
+                Utc.with_ymd_and_hms(2022, 1, 1, 12, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2022, 1, 15, 12, 0, 0).unwrap(),
+            ),
+            (
+                Utc.with_ymd_and_hms(2022, 1, 20, 0, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2022, 2, 1, 12, 0, 0).unwrap(),
+            ),
+            (
+                Utc.with_ymd_and_hms(2022, 12, 15, 12, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2023, 1, 1, 12, 0, 0).unwrap(),
+            ),
+        ];
+
+        assert_expected_next_execution_times(
+            &TimeInterval::DaysOfMonthAt {
+                days: vec![1, 15],
+                hour: 12,
+                minute: 0,
+            },
+            last_execution_time,
+            scenarios,
+        );
+    }
+
+    #[test]
+    fn assert_days_of_month_at_end_of_month_next_execution_times() {
+        let last_execution_time = Utc.with_ymd_and_hms(2022, 1, 1, 1, 0, 0).unwrap();
+        let scenarios = vec![
+            (
+                Utc.with_ymd_and_hms(2022, 2, 1, 0, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2022, 2, 28, 12, 0, 0).unwrap(),
+            ),
+            (
+                Utc.with_ymd_and_hms(2022, 2, 28, 12, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2022, 3, 31, 12, 0, 0).unwrap(),
+            ),
+            (
+                Utc.with_ymd_and_hms(2022, 4, 1, 0, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2022, 4, 30, 12, 0, 0).unwrap(),
+            ),
+            (
+                Utc.with_ymd_and_hms(2024, 2, 1, 0, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2024, 2, 29, 12, 0, 0).unwrap(),
+            ),
+        ];
+
+        assert_expected_next_execution_times(
+            &TimeInterval::DaysOfMonthAt {
+                days: vec![31],
+                hour: 12,
+                minute: 0,
+            },
+            last_execution_time,
+            scenarios,
+        );
+    }
+
+    fn assert_missed_interval_count(
+        current_time: DateTime<Utc>,
+        target_time: DateTime<Utc>,
+        interval: TimeInterval,
+        expected_missed_interval_count: u64,
+    ) {
+        assert_eq!(
+            get_missed_interval_count(
+                Timestamp::from_seconds(current_time.timestamp().try_into().unwrap()),
+                Timestamp::from_seconds(target_time.timestamp().try_into().unwrap()),
+                &interval,
+            ),
+            expected_missed_interval_count
+        );
+    }
+
+    #[test]
+    fn get_missed_interval_count_before_target_time_is_zero() {
+        let target_time = Utc.with_ymd_and_hms(2022, 1, 1, 1, 0, 0).unwrap();
+
+        assert_missed_interval_count(
+            target_time - Duration::hours(1),
+            target_time,
+            TimeInterval::Daily,
+            0,
+        );
+        assert_missed_interval_count(target_time, target_time, TimeInterval::Daily, 0);
+    }
+
+    #[test]
+    fn get_missed_interval_count_within_first_interval_is_zero() {
+        let target_time = Utc.with_ymd_and_hms(2022, 1, 1, 1, 0, 0).unwrap();
+
+        assert_missed_interval_count(
+            target_time + Duration::hours(23),
+            target_time,
+            TimeInterval::Daily,
+            0,
+        );
+    }
+
+    #[test]
+    fn get_missed_interval_count_counts_elapsed_intervals() {
+        let target_time = Utc.with_ymd_and_hms(2022, 1, 1, 1, 0, 0).unwrap();
+
+        assert_missed_interval_count(
+            target_time + Duration::days(3) + Duration::hours(2),
+            target_time,
+            TimeInterval::Daily,
+            3,
+        );
+        assert_missed_interval_count(
+            target_time + Duration::minutes(150),
+            target_time,
+            TimeInterval::HalfHourly,
+            5,
+        );
+    }
+
+    #[test]
+    fn get_missed_interval_count_for_every_block_is_zero() {
+        let target_time = Utc.with_ymd_and_hms(2022, 1, 1, 1, 0, 0).unwrap();
+
+        assert_missed_interval_count(
+            target_time + Duration::seconds(6),
+            target_time,
+            TimeInterval::EveryBlock,
+            0,
+        );
+        assert_missed_interval_count(
+            target_time + Duration::days(1),
+            target_time,
+            TimeInterval::EveryBlock,
+            0,
+        );
+    }
+
+    #[test]
+    fn get_missed_interval_count_counts_elapsed_months() {
+        let target_time = Utc.with_ymd_and_hms(2022, 1, 15, 1, 0, 0).unwrap();
+
+        assert_missed_interval_count(
+            Utc.with_ymd_and_hms(2022, 4, 1, 0, 0, 0).unwrap(),
+            target_time,
+            TimeInterval::Monthly,
+            2,
+        );
+    }
+
+    #[test]
+    fn get_missed_interval_count_counts_elapsed_calendar_times() {
+        assert_missed_interval_count(
+            Utc.with_ymd_and_hms(2022, 1, 11, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2022, 1, 3, 9, 30, 0).unwrap(),
+            TimeInterval::WeekdaysAt {
+                weekdays: vec![Weekday::Monday, Weekday::Friday],
+                hour: 9,
+                minute: 30,
+            },
+            2,
+        );
+    }
+
     #[test]
     fn execution_interval_elapsed_with_time_in_past_should_return_true() {
-        let current_time = Timestamp::from_seconds(Uint64::new(17000000000).try_into().unwrap());
-        let time_in_the_past =
-            Timestamp::from_seconds(Uint64::new(16000000000).try_into().unwrap());
+        let current_time = Timestamp::from_seconds(Uint64::new(17000000000).into());
+        let time_in_the_past = Timestamp::from_seconds(Uint64::new(16000000000).into());
 
         let result = target_time_elapsed(current_time, time_in_the_past);
 
@@ -532,9 +882,8 @@ mod tests {
 
     #[test]
     fn execution_interval_elapsed_with_time_in_future_should_return_false() {
-        let current_time = Timestamp::from_seconds(Uint64::new(17000000000).try_into().unwrap());
-        let time_in_the_future =
-            Timestamp::from_seconds(Uint64::new(18000000000).try_into().unwrap());
+        let current_time = Timestamp::from_seconds(Uint64::new(17000000000).into());
+        let time_in_the_future = Timestamp::from_seconds(Uint64::new(18000000000).into());
 
         let result = target_time_elapsed(current_time, time_in_the_future);
 
@@ -543,9 +892,8 @@ mod tests {
 
     #[test]
     fn execution_interval_elapsed_with_current_time_should_return_true() {
-        let current_time = Timestamp::from_seconds(Uint64::new(17000000000).try_into().unwrap());
-        let time_in_the_future =
-            Timestamp::from_seconds(Uint64::new(17000000000).try_into().unwrap());
+        let current_time = Timestamp::from_seconds(Uint64::new(17000000000).into());
+        let time_in_the_future = Timestamp::from_seconds(Uint64::new(17000000000).into());
 
         let result = target_time_elapsed(current_time, time_in_the_future);
 
@@ -633,7 +981,7 @@ mod tests {
 #[cfg(test)]
 mod get_total_execution_duration_tests {
     use super::{get_total_execution_duration, shift_months};
-    use crate::types::time_interval::TimeInterval;
+    use crate::types::time_interval::{TimeInterval, Weekday};
     use chrono::{Duration, TimeZone, Utc};
     use cosmwasm_std::Timestamp;
 
@@ -713,5 +1061,77 @@ mod get_total_execution_duration_tests {
             TimeInterval::Monthly,
             shift_months(block_time_utc, 3) - block_time_utc,
         );
+
+        assert_total_execution_duration(
+            block_timestamp,
+            3,
+            TimeInterval::DailyAt {
+                hour: 10,
+                minute: 0,
+            },
+            Duration::days(3),
+        );
+        assert_total_execution_duration(
+            block_timestamp,
+            2,
+            TimeInterval::WeekdaysAt {
+                weekdays: vec![Weekday::Monday],
+                hour: 10,
+                minute: 0,
+            },
+            Duration::days(9),
+        );
+    }
+}
+
+#[cfg(test)]
+mod get_execution_count_before_tests {
+    use super::get_execution_count_before;
+    use crate::types::time_interval::TimeInterval;
+    use cosmwasm_std::testing::mock_env;
+
+    #[test]
+    fn counts_executions_before_end_time() {
+        let env = mock_env();
+
+        assert_eq!(
+            get_execution_count_before(
+                env.block.time,
+                env.block.time.plus_seconds(3 * 24 * 60 * 60 + 1),
+                10,
+                &TimeInterval::Daily,
+            ),
+            4
+        );
+    }
+
+    #[test]
+    fn excludes_execution_at_end_time() {
+        let env = mock_env();
+
+        assert_eq!(
+            get_execution_count_before(
+                env.block.time,
+                env.block.time.plus_seconds(3 * 24 * 60 * 60),
+                10,
+                &TimeInterval::Daily,
+            ),
+            3
+        );
+    }
+
+    #[test]
+    fn is_capped_at_iterations() {
+        let env = mock_env();
+
+        assert_eq!(
+            get_execution_count_before(
+                env.block.time,
+                env.block.time.plus_seconds(30 * 24 * 60 * 60),
+                10,
+                &TimeInterval::Daily,
+            ),
+            10
+        );
     }
 }
@@ -1,13 +1,26 @@
 use crate::error::ContractError;
 use crate::msg::ExecuteMsg;
 use crate::state::config::get_config;
-use crate::types::destination::Destination;
+use crate::state::disburse_escrow_tasks::get_disburse_escrow_task_due_date;
+use crate::state::triggers::get_trigger;
+use crate::state::vaults::get_vault;
+use crate::types::basket::BasketAssetParams;
+use crate::types::config::Config;
+use crate::types::deposit_conversion::DepositConversion;
+use crate::types::destination::{Destination, DestinationAction};
+use crate::types::end_conditions::EndConditionsParams;
+use crate::types::escrow_vesting::EscrowVestingSchedule;
 use crate::types::fee_collector::FeeCollector;
-use crate::types::performance_assessment_strategy::PerformanceAssessmentStrategyParams;
+use crate::types::missed_execution_policy::MissedExecutionPolicy;
+use crate::types::performance_assessment_strategy::{
+    PerformanceAssessmentStrategy, PerformanceAssessmentStrategyParams,
+};
+use crate::types::retry_policy::RetryPolicy;
 use crate::types::swap_adjustment_strategy::{
     SwapAdjustmentStrategy, SwapAdjustmentStrategyParams,
 };
 use crate::types::time_interval::TimeInterval;
+use crate::types::trigger::{Trigger, TriggerConfiguration};
 use crate::types::vault::{Vault, VaultStatus};
 use cosmwasm_std::{
     from_json, Addr, Binary, Coin, Decimal, Deps, Env, Storage, Timestamp, Uint128,
@@ -23,7 +36,7 @@ pub fn assert_exactly_one_asset(funds: Vec<Coin>) -> Result<(), ContractError> {
     Ok(())
 }
 
-pub fn assert_contract_is_not_paused(storage: &mut dyn Storage) -> Result<(), ContractError> {
+pub fn assert_contract_is_not_paused(storage: &dyn Storage) -> Result<(), ContractError> {
     let config = get_config(storage)?;
     if config.paused {
         return Err(ContractError::CustomError {
@@ -59,6 +72,26 @@ pub fn assert_sender_is_executor(
     Ok(())
 }
 
+pub fn assert_keeper_can_disburse_escrow(
+    storage: &mut dyn Storage,
+    env: &Env,
+    vault_id: Uint128,
+) -> Result<(), ContractError> {
+    if !get_config(storage)?.permissionless_execution {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match get_disburse_escrow_task_due_date(storage, vault_id)? {
+        Some(due_date) if due_date <= env.block.time => Ok(()),
+        _ => Err(ContractError::CustomError {
+            val: format!(
+                "no escrow disbursement is due for vault with id {}",
+                vault_id
+            ),
+        }),
+    }
+}
+
 pub fn asset_sender_is_vault_owner(vault_owner: Addr, sender: Addr) -> Result<(), ContractError> {
     if sender != vault_owner {
         return Err(ContractError::Unauthorized {});
@@ -99,6 +132,71 @@ pub fn assert_vault_is_not_cancelled(vault: &Vault) -> Result<(), ContractError>
     Ok(())
 }
 
+pub fn assert_vault_can_be_paused(vault: &Vault) -> Result<(), ContractError> {
+    if !vault.is_active() {
+        return Err(ContractError::CustomError {
+            val: format!(
+                "vault is {:?}, only active vaults can be paused",
+                vault.status
+            ),
+        });
+    }
+    Ok(())
+}
+
+pub fn assert_vault_can_be_resumed(vault: &Vault) -> Result<(), ContractError> {
+    if !vault.is_paused() {
+        return Err(ContractError::CustomError {
+            val: format!(
+                "vault is {:?}, only paused vaults can be resumed",
+                vault.status
+            ),
+        });
+    }
+    Ok(())
+}
+
+pub fn assert_withdrawal_amount_is_valid(
+    vault: &Vault,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    if amount.is_zero() {
+        return Err(ContractError::CustomError {
+            val: "withdrawal amount must be greater than 0".to_string(),
+        });
+    }
+    if amount > vault.balance.amount {
+        return Err(ContractError::CustomError {
+            val: format!(
+                "cannot withdraw {} from vault with balance {}",
+                amount, vault.balance
+            ),
+        });
+    }
+    if let Some(PerformanceAssessmentStrategy::CompareToStandardDca { swapped_amount, .. }) =
+        &vault.performance_assessment_strategy
+    {
+        if amount
+            > vault
+                .deposited_amount
+                .amount
+                .saturating_sub(swapped_amount.amount)
+        {
+            return Err(ContractError::CustomError {
+                val: format!(
+                    "cannot withdraw {} from vault with standard DCA balance {}",
+                    amount,
+                    vault
+                        .deposited_amount
+                        .amount
+                        .saturating_sub(swapped_amount.amount)
+                ),
+            });
+        }
+    }
+    Ok(())
+}
+
 pub fn assert_swap_amount_is_greater_than_50000(swap_amount: Uint128) -> Result<(), ContractError> {
     if swap_amount <= Uint128::from(50000u128) {
         return Err(ContractError::CustomError {
@@ -147,6 +245,28 @@ pub fn assert_target_time_is_in_past(
     Ok(())
 }
 
+pub fn assert_trigger_is_due(
+    storage: &dyn Storage,
+    current_time: Timestamp,
+    vault_id: Uint128,
+) -> Result<(), ContractError> {
+    match get_trigger(storage, vault_id)? {
+        Some(Trigger {
+            configuration:
+                TriggerConfiguration::Time { target_time }
+                | TriggerConfiguration::Retry { target_time, .. },
+            ..
+        }) => assert_target_time_is_in_past(current_time, target_time),
+        Some(_) => Ok(()),
+        None => Err(ContractError::CustomError {
+            val: format!(
+                "vault with id {} has no trigger attached, and is not available for execution",
+                vault_id
+            ),
+        }),
+    }
+}
+
 pub fn assert_fee_collector_addresses_are_valid(
     deps: Deps,
     fee_collectors: &[FeeCollector],
@@ -204,6 +324,73 @@ pub fn assert_slippage_tolerance_is_less_than_or_equal_to_one(
     Ok(())
 }
 
+pub fn assert_referral_fee_percent_is_no_greater_than_100_percent(
+    referral_fee_percent: Decimal,
+) -> Result<(), ContractError> {
+    if referral_fee_percent > Decimal::percent(100) {
+        return Err(ContractError::CustomError {
+            val: "referral fee percent must be less than or equal to 100%".to_string(),
+        });
+    }
+    Ok(())
+}
+
+pub fn assert_high_water_mark_performance_fee_percent_is_no_greater_than_100_percent(
+    high_water_mark_performance_fee_percent: Decimal,
+) -> Result<(), ContractError> {
+    if high_water_mark_performance_fee_percent > Decimal::percent(100) {
+        return Err(ContractError::CustomError {
+            val: "high water mark performance fee percent must be less than or equal to 100%"
+                .to_string(),
+        });
+    }
+    Ok(())
+}
+
+pub fn assert_escrow_level_bounds_are_valid(
+    minimum_escrow_level: Decimal,
+    maximum_escrow_level: Decimal,
+) -> Result<(), ContractError> {
+    if maximum_escrow_level > Decimal::percent(100) {
+        return Err(ContractError::CustomError {
+            val: "maximum escrow level must be less than or equal to 100%".to_string(),
+        });
+    }
+    if minimum_escrow_level > maximum_escrow_level {
+        return Err(ContractError::CustomError {
+            val: "minimum escrow level must be less than or equal to maximum escrow level"
+                .to_string(),
+        });
+    }
+    Ok(())
+}
+
+pub fn assert_escrow_level_is_within_bounds(
+    config: &Config,
+    escrow_level: Decimal,
+) -> Result<(), ContractError> {
+    if escrow_level < config.minimum_escrow_level || escrow_level > config.maximum_escrow_level {
+        return Err(ContractError::CustomError {
+            val: format!(
+                "escrow level must be between {} and {}",
+                config.minimum_escrow_level, config.maximum_escrow_level
+            ),
+        });
+    }
+    Ok(())
+}
+
+pub fn assert_keeper_bounty_percent_is_no_greater_than_100_percent(
+    keeper_bounty_percent: Decimal,
+) -> Result<(), ContractError> {
+    if keeper_bounty_percent > Decimal::percent(100) {
+        return Err(ContractError::CustomError {
+            val: "keeper bounty percent must be less than or equal to 100%".to_string(),
+        });
+    }
+    Ok(())
+}
+
 pub fn assert_no_more_than_10_fee_collectors(
     fee_collectors: &[FeeCollector],
 ) -> Result<(), ContractError> {
@@ -298,16 +485,88 @@ pub fn assert_contract_destination_callbacks_are_valid(
     destinations
         .iter()
         .filter(|d| d.address == *contract_address)
-        .try_for_each(|d| {
-            d.msg
-                .clone()
-                .map_or(Ok(()), |msg| match from_json(msg).unwrap() {
-                    ExecuteMsg::ZDelegate { .. } | ExecuteMsg::Deposit { .. } => Ok(()),
-                    _ => Err(ContractError::CustomError {
-                        val: "Cannot invoke provided destination callback against the DCA contract"
-                            .to_string(),
-                    }),
-                })
+        .try_for_each(|d| match &d.action {
+            DestinationAction::ContractCall { msg } => match from_json(msg) {
+                Ok(ExecuteMsg::ZDelegate { .. }) | Ok(ExecuteMsg::Deposit { .. }) => Ok(()),
+                _ => Err(ContractError::CustomError {
+                    val: "Cannot invoke provided destination callback against the DCA contract"
+                        .to_string(),
+                }),
+            },
+            _ => Ok(()),
+        })
+}
+
+pub fn assert_destination_actions_are_valid(
+    deps: Deps,
+    destinations: &[Destination],
+    disbursed_denoms: &[String],
+) -> Result<(), ContractError> {
+    destinations
+        .iter()
+        .try_for_each(|destination| match &destination.action {
+            DestinationAction::Send | DestinationAction::ContractCall { .. } => Ok(()),
+            DestinationAction::Delegate { validator } => {
+                assert_validator_is_valid(deps, validator.to_string())
+            }
+            DestinationAction::DepositToVault { vault_id } => {
+                let vault =
+                    get_vault(deps.storage, *vault_id).map_err(|_| ContractError::CustomError {
+                        val: format!("vault with id {} does not exist", vault_id),
+                    })?;
+
+                assert_vault_is_not_cancelled(&vault)?;
+
+                if vault.owner != destination.address {
+                    return Err(ContractError::CustomError {
+                        val: format!(
+                            "destination address must be the owner of vault with id {}",
+                            vault_id
+                        ),
+                    });
+                }
+
+                if let Some(denom) = disbursed_denoms
+                    .iter()
+                    .find(|denom| **denom != vault.balance.denom)
+                {
+                    return Err(ContractError::CustomError {
+                        val: format!(
+                            "vault with id {} cannot accept deposits of {}",
+                            vault_id, denom
+                        ),
+                    });
+                }
+
+                Ok(())
+            }
+            DestinationAction::IbcTransfer {
+                channel,
+                receiver,
+                timeout,
+            } => {
+                if !channel.starts_with("channel-") {
+                    return Err(ContractError::CustomError {
+                        val: format!("ibc channel {} is invalid", channel),
+                    });
+                }
+
+                if receiver.is_empty() {
+                    return Err(ContractError::CustomError {
+                        val: String::from("ibc transfer receiver must be provided"),
+                    });
+                }
+
+                if !(60..=604800).contains(timeout) {
+                    return Err(ContractError::CustomError {
+                        val: String::from(
+                            "ibc transfer timeout must be between 60 and 604800 seconds",
+                        ),
+                    });
+                }
+
+                Ok(())
+            }
         })
 }
 
@@ -354,6 +613,33 @@ pub fn assert_route_exists_for_denoms(
     Ok(())
 }
 
+pub fn assert_deposit_conversion_is_valid(
+    deps: Deps,
+    deposit: &Coin,
+    swap_denom: &str,
+    conversion: &DepositConversion,
+) -> Result<(), ContractError> {
+    if deposit.denom == swap_denom {
+        return Err(ContractError::CustomError {
+            val: format!("deposits of {} do not need to be converted", swap_denom),
+        });
+    }
+    if conversion.minimum_receive_amount.denom != swap_denom {
+        return Err(ContractError::CustomError {
+            val: format!(
+                "deposit conversion must receive {}, but received {}",
+                swap_denom, conversion.minimum_receive_amount.denom
+            ),
+        });
+    }
+    assert_route_exists_for_denoms(
+        deps,
+        deposit.denom.clone(),
+        swap_denom.to_string(),
+        conversion.route.clone(),
+    )
+}
+
 pub fn assert_swap_adjustment_and_performance_assessment_strategies_are_compatible(
     swap_adjustment_strategy_params: &Option<SwapAdjustmentStrategyParams>,
     performance_assessment_strategy_params: &Option<PerformanceAssessmentStrategyParams>,
@@ -361,7 +647,9 @@ pub fn assert_swap_adjustment_and_performance_assessment_strategies_are_compatib
     match swap_adjustment_strategy_params {
         Some(SwapAdjustmentStrategyParams::RiskWeightedAverage { .. }) => {
             match performance_assessment_strategy_params {
-                Some(PerformanceAssessmentStrategyParams::CompareToStandardDca) => Ok(()),
+                Some(PerformanceAssessmentStrategyParams::CompareToStandardDca)
+                | Some(PerformanceAssessmentStrategyParams::HighWaterMark)
+                | Some(PerformanceAssessmentStrategyParams::CompareToLumpSum) => Ok(()),
                 None => Err(ContractError::CustomError {
                     val: "incompatible swap adjustment and performance assessment strategies"
                         .to_string(),
@@ -376,7 +664,9 @@ pub fn assert_swap_adjustment_and_performance_assessment_strategies_are_compatib
                             .to_string(),
                     })
                 }
-                None => Ok(()),
+                Some(PerformanceAssessmentStrategyParams::HighWaterMark)
+                | Some(PerformanceAssessmentStrategyParams::CompareToLumpSum)
+                | None => Ok(()),
             }
         }
         None => match performance_assessment_strategy_params {
@@ -415,12 +705,50 @@ pub fn assert_target_start_time_is_not_in_the_past(
 }
 
 pub fn assert_time_interval_is_valid(interval: &TimeInterval) -> Result<(), ContractError> {
-    if let TimeInterval::Custom { seconds } = interval {
-        if *seconds < 60 {
+    match interval {
+        TimeInterval::Custom { seconds } if *seconds < 60 => {
             return Err(ContractError::CustomError {
                 val: String::from("custom time interval must be at least 60 seconds"),
             });
         }
+        TimeInterval::DailyAt { hour, minute } => {
+            assert_time_of_day_is_valid(*hour, *minute)?;
+        }
+        TimeInterval::WeekdaysAt {
+            weekdays,
+            hour,
+            minute,
+        } => {
+            if weekdays.is_empty() {
+                return Err(ContractError::CustomError {
+                    val: String::from("at least one weekday must be provided"),
+                });
+            }
+            assert_time_of_day_is_valid(*hour, *minute)?;
+        }
+        TimeInterval::DaysOfMonthAt { days, hour, minute } => {
+            if days.is_empty() {
+                return Err(ContractError::CustomError {
+                    val: String::from("at least one day of the month must be provided"),
+                });
+            }
+            if days.iter().any(|day| !(1..=31).contains(day)) {
+                return Err(ContractError::CustomError {
+                    val: String::from("days of the month must be between 1 and 31"),
+                });
+            }
+            assert_time_of_day_is_valid(*hour, *minute)?;
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn assert_time_of_day_is_valid(hour: u8, minute: u8) -> Result<(), ContractError> {
+    if hour > 23 || minute > 59 {
+        return Err(ContractError::CustomError {
+            val: format!("{:02}:{:02} is not a valid time of day", hour, minute),
+        });
     }
     Ok(())
 }
@@ -464,6 +792,253 @@ pub fn assert_destination_allocations_add_up_to_one(
     Ok(())
 }
 
+pub fn assert_basket_size_is_valid(basket: &[BasketAssetParams]) -> Result<(), ContractError> {
+    if basket.len() < 2 || basket.len() > 10 {
+        return Err(ContractError::CustomError {
+            val: String::from("a basket must contain between 2 and 10 assets"),
+        });
+    }
+    Ok(())
+}
+
+pub fn assert_no_basket_weights_are_zero(
+    basket: &[BasketAssetParams],
+) -> Result<(), ContractError> {
+    if basket.iter().any(|asset| asset.weight.is_zero()) {
+        return Err(ContractError::CustomError {
+            val: String::from("all basket asset weights must be greater than 0"),
+        });
+    }
+    Ok(())
+}
+
+pub fn assert_basket_weights_add_up_to_one(
+    basket: &[BasketAssetParams],
+) -> Result<(), ContractError> {
+    if basket.iter().fold(Decimal::zero(), |acc, asset| {
+        acc.checked_add(asset.weight).unwrap_or(Decimal::MAX)
+    }) != Decimal::percent(100)
+    {
+        return Err(ContractError::CustomError {
+            val: String::from("basket asset weights must add up to 1"),
+        });
+    }
+    Ok(())
+}
+
+pub fn assert_basket_denoms_are_unique(
+    swap_denom: &str,
+    basket: &[BasketAssetParams],
+) -> Result<(), ContractError> {
+    for (index, asset) in basket.iter().enumerate() {
+        if asset.target_denom == swap_denom {
+            return Err(ContractError::CustomError {
+                val: format!(
+                    "basket asset {} cannot be the same as the swap denom",
+                    asset.target_denom
+                ),
+            });
+        }
+
+        if basket[..index]
+            .iter()
+            .any(|other| other.target_denom == asset.target_denom)
+        {
+            return Err(ContractError::CustomError {
+                val: format!(
+                    "basket asset {} is provided more than once",
+                    asset.target_denom
+                ),
+            });
+        }
+    }
+    Ok(())
+}
+
+pub fn assert_basket_vault_options_are_supported(
+    target_denom: &str,
+    basket: &[BasketAssetParams],
+    minimum_receive_amount: Option<Uint128>,
+    target_receive_amount: Option<Uint128>,
+    performance_assessment_strategy_params: &Option<PerformanceAssessmentStrategyParams>,
+    swap_adjustment_strategy_params: &Option<SwapAdjustmentStrategyParams>,
+    end_conditions: &Option<EndConditionsParams>,
+) -> Result<(), ContractError> {
+    if basket
+        .first()
+        .is_none_or(|asset| asset.target_denom != target_denom)
+    {
+        return Err(ContractError::CustomError {
+            val: String::from("target denom must match the first basket asset"),
+        });
+    }
+
+    if minimum_receive_amount.is_some() || target_receive_amount.is_some() {
+        return Err(ContractError::CustomError {
+            val: String::from("basket vaults do not support price based options"),
+        });
+    }
+
+    if performance_assessment_strategy_params.is_some() || swap_adjustment_strategy_params.is_some()
+    {
+        return Err(ContractError::CustomError {
+            val: String::from("basket vaults do not support swap adjustment strategies"),
+        });
+    }
+
+    if end_conditions
+        .as_ref()
+        .is_some_and(|end_conditions| end_conditions.received_amount_goal.is_some())
+    {
+        return Err(ContractError::CustomError {
+            val: String::from("basket vaults do not support a received amount goal"),
+        });
+    }
+    Ok(())
+}
+
+pub fn assert_end_conditions_are_valid(
+    current_time: Timestamp,
+    target_start_time: Option<Timestamp>,
+    end_conditions: &EndConditionsParams,
+) -> Result<(), ContractError> {
+    if let Some(end_time_utc_seconds) = end_conditions.end_time_utc_seconds {
+        let end_time = Timestamp::from_seconds(end_time_utc_seconds.u64());
+
+        if end_time <= current_time {
+            return Err(ContractError::CustomError {
+                val: String::from("end_time_utc_seconds must be some time in the future"),
+            });
+        }
+
+        if target_start_time.is_some_and(|target_start_time| end_time <= target_start_time) {
+            return Err(ContractError::CustomError {
+                val: String::from(
+                    "end_time_utc_seconds must be after target_start_time_utc_seconds",
+                ),
+            });
+        }
+    }
+
+    if end_conditions
+        .max_executions
+        .is_some_and(|max_executions| max_executions.is_zero())
+    {
+        return Err(ContractError::CustomError {
+            val: String::from("max executions must be greater than 0"),
+        });
+    }
+
+    if end_conditions
+        .received_amount_goal
+        .is_some_and(|received_amount_goal| received_amount_goal.is_zero())
+    {
+        return Err(ContractError::CustomError {
+            val: String::from("received amount goal must be greater than 0"),
+        });
+    }
+    Ok(())
+}
+
+pub fn assert_missed_execution_policy_is_valid(
+    missed_execution_policy: &MissedExecutionPolicy,
+) -> Result<(), ContractError> {
+    if let MissedExecutionPolicy::Spread { executions } = missed_execution_policy {
+        if *executions == 0 {
+            return Err(ContractError::CustomError {
+                val: String::from("missed executions must be spread over at least 1 execution"),
+            });
+        }
+    }
+    Ok(())
+}
+
+pub fn assert_retry_policy_is_valid(retry_policy: &RetryPolicy) -> Result<(), ContractError> {
+    if retry_policy.retry_delay_minutes == 0 {
+        return Err(ContractError::CustomError {
+            val: String::from("retry delay must be at least 1 minute"),
+        });
+    }
+
+    if !(1..=10).contains(&retry_policy.max_attempts) {
+        return Err(ContractError::CustomError {
+            val: String::from("max retry attempts must be between 1 and 10"),
+        });
+    }
+    Ok(())
+}
+
+pub fn assert_escrow_vesting_schedule_is_valid(
+    schedule: &EscrowVestingSchedule,
+) -> Result<(), ContractError> {
+    if schedule.interval_seconds() == 0 {
+        return Err(ContractError::CustomError {
+            val: String::from("escrow vesting interval must be at least 1 second"),
+        });
+    }
+
+    if !(1..=10).contains(&schedule.tranche_count()) {
+        return Err(ContractError::CustomError {
+            val: String::from("escrow vesting must have between 1 and 10 tranches"),
+        });
+    }
+
+    let shares = schedule.tranche_shares();
+
+    if shares.iter().any(|share| share.is_zero()) {
+        return Err(ContractError::CustomError {
+            val: String::from("escrow vesting tranche shares must be greater than 0"),
+        });
+    }
+
+    if let EscrowVestingSchedule::Stepped { shares, .. } = schedule {
+        let total = shares
+            .iter()
+            .fold(Decimal::zero(), |total, share| total + share);
+
+        if total != Decimal::one() {
+            return Err(ContractError::CustomError {
+                val: format!(
+                    "escrow vesting tranche shares must add up to 1, got {}",
+                    total
+                ),
+            });
+        }
+    }
+    Ok(())
+}
+
+pub fn assert_trigger_batch_is_valid(
+    trigger_ids: &[Uint128],
+    routes: &Option<Vec<Option<Binary>>>,
+) -> Result<(), ContractError> {
+    if trigger_ids.is_empty() {
+        return Err(ContractError::CustomError {
+            val: String::from("at least one trigger id must be provided"),
+        });
+    }
+
+    if routes
+        .as_ref()
+        .is_some_and(|routes| routes.len() != trigger_ids.len())
+    {
+        return Err(ContractError::CustomError {
+            val: String::from("a route must be provided for every trigger id"),
+        });
+    }
+
+    let mut unique_trigger_ids = trigger_ids.to_vec();
+    unique_trigger_ids.sort();
+    unique_trigger_ids.dedup();
+
+    if unique_trigger_ids.len() != trigger_ids.len() {
+        return Err(ContractError::CustomError {
+            val: String::from("trigger ids must be unique"),
+        });
+    }
+    Ok(())
+}
+
 pub fn assert_swap_adjustment_value_is_valid(
     strategy: &SwapAdjustmentStrategy,
     value: Decimal,
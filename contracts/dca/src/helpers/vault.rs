@@ -4,23 +4,33 @@ use super::{
     time::get_total_execution_duration,
 };
 use crate::{
+    constants::FAIL_SILENTLY_REPLY_ID,
     state::{
-        config::get_config, events::create_event, swap_adjustments::get_swap_adjustment,
+        config::get_config,
+        disburse_escrow_tasks::save_disburse_escrow_task,
+        events::create_event,
+        swap_adjustments::get_swap_adjustment,
+        triggers::{delete_trigger, get_trigger, save_trigger},
+        vault_ownership_transfers::delete_vault_ownership_transfer,
         vaults::update_vault,
     },
     types::{
+        end_conditions::EndCondition,
         event::{EventBuilder, EventData, ExecutionSkippedReason},
+        missed_execution_policy::{CatchUpSchedule, MissedExecutionPolicy},
         performance_assessment_strategy::PerformanceAssessmentStrategy,
         swap_adjustment_strategy::SwapAdjustmentStrategy,
         time_interval::TimeInterval,
-        vault::Vault,
+        trigger::{Trigger, TriggerConfiguration},
+        vault::{Vault, VaultStatus},
     },
 };
 use cosmwasm_std::{
-    Coin, Decimal, Deps, Env, QuerierWrapper, Response, StdError, StdResult, Storage, Timestamp,
-    Uint128,
+    to_json_binary, BankMsg, Coin, Decimal, Deps, Env, QuerierWrapper, Response, StdError,
+    StdResult, Storage, SubMsg, Timestamp, Uint128, WasmMsg,
 };
-use shared::coin::add_to;
+use exchange::msg::ExecuteMsg as ExchangeExecuteMsg;
+use shared::coin::{add_to, empty_of};
 use std::cmp::min;
 
 pub fn get_swap_amount(deps: &Deps, env: &Env, vault: &Vault) -> StdResult<Coin> {
@@ -70,6 +80,94 @@ pub fn get_swap_amount(deps: &Deps, env: &Env, vault: &Vault) -> StdResult<Coin>
     ))
 }
 
+pub fn get_catch_up_schedule(
+    vault: &Vault,
+    swap_amount: &Coin,
+    missed_executions: u64,
+) -> Option<CatchUpSchedule> {
+    if missed_executions == 0 {
+        return vault.catch_up_schedule.clone();
+    }
+
+    let remaining_amount = min(
+        vault
+            .catch_up_schedule
+            .clone()
+            .map_or(Uint128::zero(), |schedule| schedule.remaining_amount)
+            .saturating_add(
+                swap_amount
+                    .amount
+                    .saturating_mul(Uint128::from(missed_executions)),
+            ),
+        vault.balance.amount.saturating_sub(swap_amount.amount),
+    );
+
+    if remaining_amount.is_zero() {
+        return None;
+    }
+
+    match vault.missed_execution_policy {
+        MissedExecutionPolicy::Skip => None,
+        MissedExecutionPolicy::CatchUp => Some(CatchUpSchedule {
+            remaining_amount,
+            executions_remaining: 1,
+        }),
+        MissedExecutionPolicy::Spread { executions } => Some(CatchUpSchedule {
+            remaining_amount,
+            executions_remaining: executions,
+        }),
+    }
+}
+
+pub fn schedule_execution_retry(
+    store: &mut dyn Storage,
+    env: &Env,
+    vault: &Vault,
+    attempt: u32,
+) -> StdResult<Option<Timestamp>> {
+    let next_target_time = match get_trigger(store, vault.id)? {
+        Some(Trigger {
+            configuration: TriggerConfiguration::Time { target_time },
+            ..
+        }) if target_time > env.block.time => target_time,
+        _ => return Ok(None),
+    };
+
+    let retry_policy = match vault.retry_policy.clone() {
+        Some(retry_policy) => retry_policy,
+        None => match get_config(store)?.default_retry_policy {
+            Some(retry_policy) => retry_policy,
+            None => return Ok(None),
+        },
+    };
+
+    if attempt >= retry_policy.max_attempts {
+        return Ok(None);
+    }
+
+    let retry_time = env
+        .block
+        .time
+        .plus_seconds(retry_policy.retry_delay_minutes * 60);
+
+    if retry_time >= next_target_time {
+        return Ok(None);
+    }
+
+    save_trigger(
+        store,
+        Trigger {
+            vault_id: vault.id,
+            configuration: TriggerConfiguration::Retry {
+                target_time: retry_time,
+                attempt: attempt + 1,
+            },
+        },
+    )?;
+
+    Ok(Some(retry_time))
+}
+
 pub fn get_risk_weighted_average_model_id(
     block_time: &Timestamp,
     balance: &Coin,
@@ -118,12 +216,38 @@ pub fn get_performance_factor(vault: &Vault, current_price: Decimal) -> StdResul
                 standard_dca_vault_total_value,
             ))
         }
+        Some(PerformanceAssessmentStrategy::HighWaterMark { mark }) => {
+            Ok(get_vault_value_ratio(vault, current_price)
+                .checked_div(*mark)
+                .unwrap_or(Decimal::one()))
+        }
+        Some(PerformanceAssessmentStrategy::CompareToLumpSum { start_price }) => start_price
+            .map_or(Ok(Decimal::one()), |start_price| {
+                let vault_total_value = vault.deposited_amount.amount - vault.swapped_amount.amount
+                    + vault.received_amount.amount * current_price;
+
+                let lump_sum_total_value =
+                    vault.deposited_amount.amount * (current_price / start_price);
+
+                Ok(
+                    Decimal::checked_from_ratio(vault_total_value, lump_sum_total_value)
+                        .unwrap_or(Decimal::one()),
+                )
+            }),
         None => Err(StdError::generic_err(
             "performance assessment strategy not set",
         )),
     }
 }
 
+pub fn get_vault_value_ratio(vault: &Vault, current_price: Decimal) -> Decimal {
+    let vault_total_value = vault.deposited_amount.amount - vault.swapped_amount.amount
+        + vault.received_amount.amount * current_price;
+
+    Decimal::checked_from_ratio(vault_total_value, vault.deposited_amount.amount)
+        .unwrap_or(Decimal::one())
+}
+
 pub fn simulate_standard_dca_execution(
     mut response: Response,
     querier: &QuerierWrapper,
@@ -133,7 +257,9 @@ pub fn simulate_standard_dca_execution(
     belief_price: Decimal,
 ) -> StdResult<(Vault, Response)> {
     match vault.performance_assessment_strategy.clone() {
-        None => Ok((vault, response)),
+        None
+        | Some(PerformanceAssessmentStrategy::HighWaterMark { .. })
+        | Some(PerformanceAssessmentStrategy::CompareToLumpSum { .. }) => Ok((vault, response)),
         Some(PerformanceAssessmentStrategy::CompareToStandardDca {
             swapped_amount,
             received_amount,
@@ -208,8 +334,8 @@ pub fn simulate_standard_dca_execution(
                 return Ok((vault, response));
             }
 
-            let fee_rate = get_swap_fee_rate(storage, &vault.swap_adjustment_strategy)?
-                + get_automation_fee_rate(storage, &vault)?;
+            let fee_rate =
+                get_swap_fee_rate(storage, &vault)? + get_automation_fee_rate(storage, &vault)?;
 
             let received_amount_before_fee = swap_amount * (Decimal::one() / actual_price);
             let fee_amount = received_amount_before_fee * fee_rate;
@@ -258,6 +384,92 @@ pub fn simulate_standard_dca_execution(
     }
 }
 
+pub fn get_retract_order_messages(store: &dyn Storage, vault: &Vault) -> StdResult<Vec<SubMsg>> {
+    let Some(Trigger {
+        configuration: TriggerConfiguration::Price { order_idx, .. },
+        ..
+    }) = get_trigger(store, vault.id)?
+    else {
+        return Ok(vec![]);
+    };
+
+    let config = get_config(store)?;
+
+    Ok(vec![
+        SubMsg::reply_on_error(
+            WasmMsg::Execute {
+                contract_addr: config.exchange_contract_address.to_string(),
+                msg: to_json_binary(&ExchangeExecuteMsg::RetractOrder {
+                    order_idx,
+                    denoms: vault.denoms(),
+                })?,
+                funds: vec![],
+            },
+            FAIL_SILENTLY_REPLY_ID,
+        ),
+        SubMsg::reply_on_error(
+            WasmMsg::Execute {
+                contract_addr: config.exchange_contract_address.to_string(),
+                msg: to_json_binary(&ExchangeExecuteMsg::WithdrawOrder {
+                    order_idx,
+                    denoms: vault.denoms(),
+                })?,
+                funds: vec![],
+            },
+            FAIL_SILENTLY_REPLY_ID,
+        ),
+    ])
+}
+
+pub fn end_vault(
+    store: &mut dyn Storage,
+    env: &Env,
+    vault: Vault,
+    condition: EndCondition,
+) -> StdResult<(Vault, Vec<SubMsg>)> {
+    create_event(
+        store,
+        EventBuilder::new(
+            vault.id,
+            env.block.clone(),
+            EventData::DcaVaultEndConditionReached { condition },
+        ),
+    )?;
+
+    if vault.escrowed_amount.amount > Uint128::zero() {
+        save_disburse_escrow_task(
+            store,
+            vault.id,
+            vault.get_expected_execution_completed_date(env.block.time),
+        )?;
+    }
+
+    let mut sub_msgs = Vec::<SubMsg>::new();
+
+    if vault.balance.amount > Uint128::zero() {
+        sub_msgs.push(SubMsg::new(BankMsg::Send {
+            to_address: vault.owner.to_string(),
+            amount: vec![vault.balance.clone()],
+        }));
+    }
+
+    sub_msgs.append(&mut get_retract_order_messages(store, &vault)?);
+
+    delete_trigger(store, vault.id)?;
+    delete_vault_ownership_transfer(store, vault.id);
+
+    let vault = update_vault(
+        store,
+        Vault {
+            status: VaultStatus::Inactive,
+            balance: empty_of(vault.balance.clone()),
+            ..vault
+        },
+    )?;
+
+    Ok((vault, sub_msgs))
+}
+
 #[cfg(test)]
 mod get_swap_amount_tests {
     use super::*;
@@ -798,6 +1010,44 @@ mod get_performance_factor_tests {
         );
     }
 
+    #[test]
+    fn compare_to_lump_sum_with_nothing_deposited_is_neutral() {
+        let vault = Vault {
+            deposited_amount: Coin::new(0, "swap_denom"),
+            swapped_amount: Coin::new(0, "swap_denom"),
+            received_amount: Coin::new(0, "receive_denom"),
+            performance_assessment_strategy: Some(
+                PerformanceAssessmentStrategy::CompareToLumpSum {
+                    start_price: Some(Decimal::one()),
+                },
+            ),
+            ..Vault::default()
+        };
+
+        assert_eq!(
+            get_performance_factor(&vault, Decimal::from_str("1.1").unwrap()).unwrap(),
+            Decimal::one()
+        );
+    }
+
+    #[test]
+    fn high_water_mark_of_zero_is_neutral() {
+        let vault = Vault {
+            deposited_amount: Coin::new(2000, "swap_denom"),
+            swapped_amount: Coin::new(1000, "swap_denom"),
+            received_amount: Coin::new(1000, "receive_denom"),
+            performance_assessment_strategy: Some(PerformanceAssessmentStrategy::HighWaterMark {
+                mark: Decimal::zero(),
+            }),
+            ..Vault::default()
+        };
+
+        assert_eq!(
+            get_performance_factor(&vault, Decimal::from_str("1.1").unwrap()).unwrap(),
+            Decimal::one()
+        );
+    }
+
     #[test]
     fn performance_is_worse_when_same_amount_swapped_and_less_received() {
         let deposit = Uint128::new(2000);
@@ -1040,11 +1290,7 @@ mod simulate_standard_dca_execution_tests {
                 .unwrap()
                 .events;
 
-        let fee_rate = get_swap_fee_rate(
-            storage_deps.as_ref().storage,
-            &vault.swap_adjustment_strategy,
-        )
-        .unwrap()
+        let fee_rate = get_swap_fee_rate(storage_deps.as_ref().storage, &vault).unwrap()
             + get_automation_fee_rate(storage_deps.as_ref().storage, &vault).unwrap();
 
         let received_amount = vault.swap_amount * Decimal::percent(95);
@@ -1098,11 +1344,7 @@ mod simulate_standard_dca_execution_tests {
         )
         .unwrap();
 
-        let fee_rate = get_swap_fee_rate(
-            storage_deps.as_ref().storage,
-            &vault.swap_adjustment_strategy,
-        )
-        .unwrap()
+        let fee_rate = get_swap_fee_rate(storage_deps.as_ref().storage, &vault).unwrap()
             + get_automation_fee_rate(storage_deps.as_ref().storage, &vault).unwrap();
 
         let received_amount_before_fee = vault.swap_amount * Decimal::percent(95);
@@ -1116,6 +1358,7 @@ mod simulate_standard_dca_execution_tests {
             match performance_assessment_strategy.clone() {
                 PerformanceAssessmentStrategy::CompareToStandardDca { swapped_amount, .. } =>
                     swapped_amount,
+                _ => panic!("expected standard DCA performance assessment"),
             },
             Coin::new(vault.swap_amount.into(), vault.get_swap_denom()),
         );
@@ -1124,8 +1367,145 @@ mod simulate_standard_dca_execution_tests {
                 PerformanceAssessmentStrategy::CompareToStandardDca {
                     received_amount, ..
                 } => received_amount,
+                _ => panic!("expected standard DCA performance assessment"),
             },
             Coin::new(received_amount_after_fee.into(), vault.target_denom)
         );
     }
 }
+
+#[cfg(test)]
+mod end_vault_tests {
+    use super::*;
+    use crate::state::vault_ownership_transfers::{
+        get_vault_ownership_transfer, save_vault_ownership_transfer, VaultOwnershipTransfer,
+    };
+    use crate::tests::{
+        helpers::{instantiate_contract, setup_vault},
+        mocks::{calc_mock_dependencies, ADMIN},
+    };
+    use cosmwasm_std::{
+        testing::{mock_env, mock_info},
+        Addr,
+    };
+
+    #[test]
+    fn with_price_trigger_retracts_and_withdraws_limit_order() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let order_idx = Uint128::new(123);
+
+        let vault = setup_vault(
+            deps.as_mut(),
+            env.clone(),
+            Vault {
+                trigger: Some(TriggerConfiguration::Price {
+                    target_price: Decimal::percent(200),
+                    order_idx,
+                }),
+                ..Vault::default()
+            },
+        );
+
+        let (_, sub_msgs) = end_vault(
+            deps.as_mut().storage,
+            &env,
+            vault.clone(),
+            EndCondition::EndTimeReached,
+        )
+        .unwrap();
+
+        let config = get_config(deps.as_ref().storage).unwrap();
+
+        assert!(sub_msgs.contains(&SubMsg::reply_on_error(
+            WasmMsg::Execute {
+                contract_addr: config.exchange_contract_address.to_string(),
+                msg: to_json_binary(&ExchangeExecuteMsg::RetractOrder {
+                    order_idx,
+                    denoms: vault.denoms(),
+                })
+                .unwrap(),
+                funds: vec![],
+            },
+            FAIL_SILENTLY_REPLY_ID,
+        )));
+        assert!(sub_msgs.contains(&SubMsg::reply_on_error(
+            WasmMsg::Execute {
+                contract_addr: config.exchange_contract_address.to_string(),
+                msg: to_json_binary(&ExchangeExecuteMsg::WithdrawOrder {
+                    order_idx,
+                    denoms: vault.denoms(),
+                })
+                .unwrap(),
+                funds: vec![],
+            },
+            FAIL_SILENTLY_REPLY_ID,
+        )));
+        assert!(get_trigger(deps.as_ref().storage, vault.id)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn with_time_trigger_does_not_retract_limit_order() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        let (_, sub_msgs) = end_vault(
+            deps.as_mut().storage,
+            &env,
+            vault.clone(),
+            EndCondition::EndTimeReached,
+        )
+        .unwrap();
+
+        assert_eq!(
+            sub_msgs,
+            vec![SubMsg::new(BankMsg::Send {
+                to_address: vault.owner.to_string(),
+                amount: vec![vault.balance],
+            })]
+        );
+    }
+
+    #[test]
+    fn clears_pending_ownership_transfer() {
+        let mut deps = calc_mock_dependencies();
+        let env = mock_env();
+
+        instantiate_contract(deps.as_mut(), env.clone(), mock_info(ADMIN, &[]));
+
+        let vault = setup_vault(deps.as_mut(), env.clone(), Vault::default());
+
+        save_vault_ownership_transfer(
+            deps.as_mut().storage,
+            vault.id,
+            &VaultOwnershipTransfer {
+                new_owner: Addr::unchecked("new-owner"),
+                update_destinations: false,
+            },
+        )
+        .unwrap();
+
+        end_vault(
+            deps.as_mut().storage,
+            &env,
+            vault.clone(),
+            EndCondition::EndTimeReached,
+        )
+        .unwrap();
+
+        assert!(
+            get_vault_ownership_transfer(deps.as_ref().storage, vault.id)
+                .unwrap()
+                .is_none()
+        );
+    }
+}
@@ -1,15 +1,23 @@
+use crate::state::post_execution_action_retries::PendingPostExecutionAction;
+use crate::types::basket::BasketAssetParams;
 use crate::types::config::Config;
+use crate::types::deposit_conversion::DepositConversion;
 use crate::types::destination::Destination;
+use crate::types::end_conditions::EndConditionsParams;
+use crate::types::escrow_vesting::EscrowVestingSchedule;
 use crate::types::event::Event;
 use crate::types::fee_collector::FeeCollector;
+use crate::types::missed_execution_policy::MissedExecutionPolicy;
+use crate::types::netted_execution::NettedExecution;
 use crate::types::performance_assessment_strategy::PerformanceAssessmentStrategyParams;
+use crate::types::retry_policy::RetryPolicy;
 use crate::types::swap_adjustment_strategy::{
     SwapAdjustmentStrategy, SwapAdjustmentStrategyParams,
 };
 use crate::types::time_interval::TimeInterval;
 use crate::types::vault::{Vault, VaultStatus};
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Binary, Coin, Decimal, Uint128, Uint64};
+use cosmwasm_std::{Addr, Binary, Coin, Decimal, Timestamp, Uint128, Uint64};
 use cw20::Cw20ReceiveMsg;
 use exchange::msg::Pair;
 
@@ -27,6 +35,15 @@ pub struct InstantiateMsg {
     pub twap_period: u64,
     pub default_slippage_tolerance: Decimal,
     pub exchange_contract_address: Addr,
+    pub default_retry_policy: Option<RetryPolicy>,
+    pub permissionless_execution: bool,
+    pub keeper_bounty_percent: Decimal,
+    pub referral_fee_percent: Decimal,
+    pub high_water_mark_performance_fee_percent: Decimal,
+    pub minimum_escrow_level: Decimal,
+    pub maximum_escrow_level: Decimal,
+    pub post_execution_action_max_retry_attempts: u32,
+    pub post_execution_action_max_retry_age_seconds: u64,
 }
 
 #[cw_serde]
@@ -43,9 +60,19 @@ pub struct MigrateMsg {
     pub twap_period: u64,
     pub default_slippage_tolerance: Decimal,
     pub exchange_contract_address: Addr,
+    pub default_retry_policy: Option<RetryPolicy>,
+    pub permissionless_execution: bool,
+    pub keeper_bounty_percent: Decimal,
+    pub referral_fee_percent: Decimal,
+    pub high_water_mark_performance_fee_percent: Decimal,
+    pub minimum_escrow_level: Decimal,
+    pub maximum_escrow_level: Decimal,
+    pub post_execution_action_max_retry_attempts: u32,
+    pub post_execution_action_max_retry_age_seconds: u64,
 }
 
 #[cw_serde]
+#[allow(clippy::large_enum_variant)]
 pub enum ExecuteMsg {
     CreateVault {
         owner: Option<Addr>,
@@ -61,10 +88,26 @@ pub enum ExecuteMsg {
         target_receive_amount: Option<Uint128>,
         performance_assessment_strategy: Option<PerformanceAssessmentStrategyParams>,
         swap_adjustment_strategy: Option<SwapAdjustmentStrategyParams>,
+        basket: Option<Vec<BasketAssetParams>>,
+        end_conditions: Option<EndConditionsParams>,
+        missed_execution_policy: Option<MissedExecutionPolicy>,
+        allow_partial_executions: Option<bool>,
+        retry_policy: Option<RetryPolicy>,
+        retry_failed_post_execution_actions: Option<bool>,
+        recurring_deposits: Option<bool>,
+        deposit_conversion: Option<DepositConversion>,
+        referrer: Option<Addr>,
+        escrow_vesting: Option<EscrowVestingSchedule>,
+        escrow_level: Option<Decimal>,
     },
     Deposit {
         address: Addr,
         vault_id: Uint128,
+        conversion: Option<DepositConversion>,
+    },
+    WithdrawFromVault {
+        vault_id: Uint128,
+        amount: Uint128,
     },
     UpdateVault {
         vault_id: Uint128,
@@ -75,14 +118,36 @@ pub enum ExecuteMsg {
         time_interval: Option<TimeInterval>,
         swap_adjustment_strategy: Option<SwapAdjustmentStrategyParams>,
         swap_amount: Option<Uint128>,
+        allow_partial_executions: Option<bool>,
+        retry_failed_post_execution_actions: Option<bool>,
+        recurring_deposits: Option<bool>,
+        escrow_level: Option<Decimal>,
     },
     CancelVault {
         vault_id: Uint128,
     },
+    PauseVault {
+        vault_id: Uint128,
+    },
+    ResumeVault {
+        vault_id: Uint128,
+    },
+    TransferVaultOwnership {
+        vault_id: Uint128,
+        new_owner: Addr,
+        update_destinations: Option<bool>,
+    },
+    AcceptVaultOwnership {
+        vault_id: Uint128,
+    },
     ExecuteTrigger {
         trigger_id: Uint128,
         route: Option<Binary>,
     },
+    ExecuteTriggers {
+        trigger_ids: Vec<Uint128>,
+        routes: Option<Vec<Option<Binary>>>,
+    },
     UpdateConfig {
         executors: Option<Vec<Addr>>,
         fee_collectors: Option<Vec<FeeCollector>>,
@@ -95,26 +160,95 @@ pub enum ExecuteMsg {
         twap_period: Option<u64>,
         default_slippage_tolerance: Option<Decimal>,
         exchange_contract_address: Option<Addr>,
+        default_retry_policy: Option<RetryPolicy>,
+        permissionless_execution: Option<bool>,
+        keeper_bounty_percent: Option<Decimal>,
+        referral_fee_percent: Option<Decimal>,
+        high_water_mark_performance_fee_percent: Option<Decimal>,
+        minimum_escrow_level: Option<Decimal>,
+        maximum_escrow_level: Option<Decimal>,
+        post_execution_action_max_retry_attempts: Option<u32>,
+        post_execution_action_max_retry_age_seconds: Option<u64>,
     },
     UpdateSwapAdjustment {
         strategy: SwapAdjustmentStrategy,
         value: Decimal,
     },
+    CreateCustomSwapFee {
+        denom: String,
+        paired_denom: Option<String>,
+        swap_fee_percent: Decimal,
+    },
+    RemoveCustomSwapFee {
+        denom: String,
+        paired_denom: Option<String>,
+    },
+    UpdateReferralFeeCaps {
+        referrer: Addr,
+        caps: Vec<Coin>,
+    },
     DisburseEscrow {
         vault_id: Uint128,
     },
+    ExecuteAggregatedSwap {
+        vault_ids: Vec<Uint128>,
+        swap_amounts: Vec<Uint128>,
+        minimum_receive_amount: Coin,
+        route: Option<Binary>,
+        asset_price: Decimal,
+        keeper: Option<Addr>,
+    },
+    SettleExecution {
+        vault_id: Uint128,
+        sent: Coin,
+        received: Coin,
+        asset_price: Decimal,
+        residual: Coin,
+        keeper: Option<Addr>,
+    },
+    SettleNettedExecutions {
+        executions: Vec<NettedExecution>,
+        keeper: Option<Addr>,
+    },
     ZDelegate {
         delegator_address: Addr,
         validator_address: Addr,
     },
+    RetryPostExecutionAction {
+        vault_id: Uint128,
+    },
     Receive(Cw20ReceiveMsg),
 }
 
+#[cw_serde]
+pub enum SudoMsg {
+    IbcLifecycleComplete(IbcLifecycleComplete),
+}
+
+#[cw_serde]
+pub enum IbcLifecycleComplete {
+    IbcAck {
+        channel: String,
+        sequence: u64,
+        ack: String,
+        success: bool,
+    },
+    IbcTimeout {
+        channel: String,
+        sequence: u64,
+    },
+}
+
 #[cw_serde]
 #[derive(QueryResponses)]
+#[allow(clippy::large_enum_variant)]
 pub enum QueryMsg {
     #[returns(ConfigResponse)]
     GetConfig {},
+    #[returns(CustomFeesResponse)]
+    GetCustomSwapFees {},
+    #[returns(ReferralFeesResponse)]
+    GetReferralFees { referrer: Addr },
     #[returns(PairsResponse)]
     GetPairs {
         start_after: Option<Pair>,
@@ -156,6 +290,36 @@ pub enum QueryMsg {
     GetVaultPerformance { vault_id: Uint128 },
     #[returns(DisburseEscrowTasksResponse)]
     GetDisburseEscrowTasks { limit: Option<u16> },
+    #[returns(PendingPostExecutionActionsResponse)]
+    GetPendingPostExecutionActions { vault_id: Uint128 },
+    #[returns(VaultSimulationResponse)]
+    SimulateVault {
+        deposit: Coin,
+        owner: Addr,
+        label: Option<String>,
+        destinations: Option<Vec<Destination>>,
+        target_denom: String,
+        route: Option<Binary>,
+        slippage_tolerance: Option<Decimal>,
+        minimum_receive_amount: Option<Uint128>,
+        swap_amount: Uint128,
+        time_interval: TimeInterval,
+        target_start_time_utc_seconds: Option<Uint64>,
+        target_receive_amount: Option<Uint128>,
+        performance_assessment_strategy: Option<PerformanceAssessmentStrategyParams>,
+        swap_adjustment_strategy: Option<SwapAdjustmentStrategyParams>,
+        basket: Option<Vec<BasketAssetParams>>,
+        end_conditions: Option<EndConditionsParams>,
+        missed_execution_policy: Option<MissedExecutionPolicy>,
+        allow_partial_executions: Option<bool>,
+        retry_policy: Option<RetryPolicy>,
+        retry_failed_post_execution_actions: Option<bool>,
+        recurring_deposits: Option<bool>,
+        deposit_conversion: Option<DepositConversion>,
+        referrer: Option<Addr>,
+        escrow_vesting: Option<EscrowVestingSchedule>,
+        escrow_level: Option<Decimal>,
+    },
 }
 
 #[cw_serde]
@@ -187,6 +351,7 @@ pub struct VaultResponse {
 pub struct VaultPerformanceResponse {
     pub fee: Coin,
     pub factor: Decimal,
+    pub high_water_mark: Option<Decimal>,
 }
 
 #[cw_serde]
@@ -204,7 +369,43 @@ pub struct CustomFeesResponse {
     pub custom_fees: Vec<(String, Decimal)>,
 }
 
+#[cw_serde]
+pub struct ReferralFeesResponse {
+    pub fees: Vec<Coin>,
+    pub caps: Vec<Coin>,
+}
+
+#[cw_serde]
+pub struct DisburseEscrowTask {
+    pub vault_id: Uint128,
+    pub tranche: u32,
+    pub due_date: Timestamp,
+}
+
 #[cw_serde]
 pub struct DisburseEscrowTasksResponse {
     pub vault_ids: Vec<Uint128>,
+    pub tasks: Vec<DisburseEscrowTask>,
+}
+
+#[cw_serde]
+pub struct PendingPostExecutionActionsResponse {
+    pub actions: Vec<PendingPostExecutionAction>,
+}
+
+#[cw_serde]
+pub struct VaultSimulation {
+    pub expected_executions: Uint128,
+    pub expected_completion_date: Option<Timestamp>,
+    pub risk_weighted_average_model_id: Option<u8>,
+    pub swap_fee_rate: Decimal,
+    pub automation_fee_rate: Decimal,
+    pub performance_fee_rate: Option<Decimal>,
+    pub escrow_level: Decimal,
+}
+
+#[cw_serde]
+pub struct VaultSimulationResponse {
+    pub simulation: Option<VaultSimulation>,
+    pub validation_error: Option<String>,
 }
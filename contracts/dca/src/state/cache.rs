@@ -1,23 +1,81 @@
+use super::post_execution_action_retries::PendingPostExecutionAction;
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Coin, SubMsg, Uint128};
+use cosmwasm_std::{Addr, Binary, Coin, Decimal, SubMsg, Uint128};
 use cw_storage_plus::{Item, Map};
 use std::collections::VecDeque;
 
-pub const VAULT_ID_CACHE: Item<Uint128> = Item::new("vault_id_cache_v1");
-
 #[cw_serde]
 pub struct SwapCache {
     pub swap_denom_balance: Coin,
     pub receive_denom_balance: Coin,
 }
 
-pub const SWAP_CACHE: Item<SwapCache> = Item::new("swap_cache_v1");
+pub const SWAP_CACHE: Map<u128, SwapCache> = Map::new("swap_cache_v2");
+
+pub const BASKET_SWAP_CACHE: Map<u128, VecDeque<String>> = Map::new("basket_swap_cache_v2");
+
+pub const PARTIAL_SWAP_CACHE: Map<u128, Coin> = Map::new("partial_swap_cache_v2");
+
+pub const RETRY_ATTEMPT_CACHE: Map<u128, u32> = Map::new("retry_attempt_cache_v1");
+
+pub const CATCH_UP_AMOUNT_CACHE: Map<u128, Uint128> = Map::new("catch_up_amount_cache_v1");
+
+pub const BATCH_KEEPER_CACHE: Item<Addr> = Item::new("batch_keeper_cache_v1");
+
+pub const KEEPER_CACHE: Map<u128, Addr> = Map::new("keeper_cache_v1");
+
+#[cw_serde]
+pub struct AggregatedSwapCache {
+    pub vault_ids: Vec<Uint128>,
+    pub swap_amounts: Vec<Uint128>,
+    pub swap_denom_balance: Coin,
+    pub receive_denom_balance: Coin,
+    pub asset_price: Decimal,
+    pub keeper: Option<Addr>,
+}
+
+pub const AGGREGATED_SWAP_CACHE: Item<AggregatedSwapCache> = Item::new("aggregated_swap_cache_v1");
+
+pub const BATCHED_AGGREGATED_SWAP_CACHE: Map<u128, Vec<Uint128>> =
+    Map::new("batched_aggregated_swap_cache_v1");
+
+pub const BATCHED_SETTLEMENT_CACHE: Map<u128, Vec<Uint128>> =
+    Map::new("batched_settlement_cache_v1");
+
+pub const BATCHED_TRIGGER_EXECUTION_CACHE: Item<VecDeque<Uint128>> =
+    Item::new("batched_trigger_execution_cache_v1");
 
 #[cw_serde]
 pub struct PostExecutionActionCacheEntry {
     pub msg: SubMsg,
     pub funds: Vec<Coin>,
+    pub fallback_address: Addr,
 }
 
 pub const POST_EXECUTION_ACTION_CACHE: Map<u128, VecDeque<PostExecutionActionCacheEntry>> =
     Map::new("post_execution_action_cache_v1");
+
+pub const POST_EXECUTION_ACTION_RETRY_CACHE: Item<(Uint128, PendingPostExecutionAction)> =
+    Item::new("post_execution_action_retry_cache_v1");
+
+#[cw_serde]
+pub struct RecurringDepositCache {
+    pub vault_id: Uint128,
+    pub amount: Coin,
+    pub route: Option<Binary>,
+}
+
+pub const RECURRING_DEPOSIT_CACHE: Item<RecurringDepositCache> =
+    Item::new("recurring_deposit_cache_v1");
+
+pub const RESIDUAL_SWAP_AMOUNT_CACHE: Map<u128, Coin> = Map::new("residual_swap_amount_cache_v1");
+
+#[cw_serde]
+pub struct DepositConversionCache {
+    pub vault_id: Uint128,
+    pub deposit: Coin,
+    pub swap_denom_balance: Coin,
+}
+
+pub const DEPOSIT_CONVERSION_CACHE: Item<DepositConversionCache> =
+    Item::new("deposit_conversion_cache_v1");
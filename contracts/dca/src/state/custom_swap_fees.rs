@@ -0,0 +1,46 @@
+use cosmwasm_std::{Decimal, Order, StdResult, Storage};
+use cw_storage_plus::Map;
+
+const CUSTOM_SWAP_FEES: Map<String, Decimal> = Map::new("custom_swap_fees_v1");
+
+pub fn get_custom_swap_fee_key(denom: &str, paired_denom: Option<&str>) -> String {
+    match paired_denom {
+        Some(paired_denom) => {
+            let mut denoms = [denom, paired_denom];
+            denoms.sort();
+            denoms.join(",")
+        }
+        None => denom.to_string(),
+    }
+}
+
+pub fn save_custom_swap_fee(
+    store: &mut dyn Storage,
+    denom: &str,
+    paired_denom: Option<&str>,
+    swap_fee_percent: Decimal,
+) -> StdResult<()> {
+    CUSTOM_SWAP_FEES.save(
+        store,
+        get_custom_swap_fee_key(denom, paired_denom),
+        &swap_fee_percent,
+    )
+}
+
+pub fn get_custom_swap_fee(
+    store: &dyn Storage,
+    denom: &str,
+    paired_denom: Option<&str>,
+) -> StdResult<Option<Decimal>> {
+    CUSTOM_SWAP_FEES.may_load(store, get_custom_swap_fee_key(denom, paired_denom))
+}
+
+pub fn get_custom_swap_fees(store: &dyn Storage) -> StdResult<Vec<(String, Decimal)>> {
+    CUSTOM_SWAP_FEES
+        .range(store, None, None, Order::Ascending)
+        .collect()
+}
+
+pub fn delete_custom_swap_fee(store: &mut dyn Storage, denom: &str, paired_denom: Option<&str>) {
+    CUSTOM_SWAP_FEES.remove(store, get_custom_swap_fee_key(denom, paired_denom))
+}
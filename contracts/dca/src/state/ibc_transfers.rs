@@ -0,0 +1,34 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Coin, StdResult, Storage, Uint128};
+use cw_storage_plus::Map;
+
+#[cw_serde]
+pub struct IbcTransfer {
+    pub vault_id: Uint128,
+    pub channel_id: String,
+    pub sequence: u64,
+    pub funds: Coin,
+    pub refund_address: Addr,
+}
+
+const IBC_TRANSFERS: Map<(&str, u64), IbcTransfer> = Map::new("ibc_transfers_v1");
+
+pub fn save_ibc_transfer(store: &mut dyn Storage, transfer: &IbcTransfer) -> StdResult<()> {
+    IBC_TRANSFERS.save(
+        store,
+        (transfer.channel_id.as_str(), transfer.sequence),
+        transfer,
+    )
+}
+
+pub fn get_ibc_transfer(
+    store: &dyn Storage,
+    channel_id: &str,
+    sequence: u64,
+) -> StdResult<Option<IbcTransfer>> {
+    IBC_TRANSFERS.may_load(store, (channel_id, sequence))
+}
+
+pub fn delete_ibc_transfer(store: &mut dyn Storage, channel_id: &str, sequence: u64) {
+    IBC_TRANSFERS.remove(store, (channel_id, sequence))
+}
@@ -1,8 +1,14 @@
 pub mod cache;
 pub mod config;
+pub mod custom_swap_fees;
 pub mod disburse_escrow_tasks;
 pub mod events;
+pub mod ibc_transfers;
+pub mod post_execution_action_retries;
+pub mod recurring_deposit_failures;
+pub mod referrals;
 pub mod state_helpers;
 pub mod swap_adjustments;
 pub mod triggers;
+pub mod vault_ownership_transfers;
 pub mod vaults;
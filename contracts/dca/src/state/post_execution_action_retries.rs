@@ -0,0 +1,51 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Coin, StdResult, Storage, SubMsg, Timestamp, Uint128};
+use cw_storage_plus::Map;
+use std::collections::VecDeque;
+
+#[cw_serde]
+pub struct PendingPostExecutionAction {
+    pub msg: SubMsg,
+    pub funds: Vec<Coin>,
+    pub fallback_address: Addr,
+    pub attempts: u32,
+    pub failed_at: Timestamp,
+}
+
+const POST_EXECUTION_ACTION_RETRY_QUEUE: Map<u128, VecDeque<PendingPostExecutionAction>> =
+    Map::new("post_execution_action_retry_queue_v1");
+
+pub fn push_pending_post_execution_action(
+    store: &mut dyn Storage,
+    vault_id: Uint128,
+    action: PendingPostExecutionAction,
+) -> StdResult<()> {
+    let mut queue = get_pending_post_execution_actions(store, vault_id)?;
+    queue.push_back(action);
+    POST_EXECUTION_ACTION_RETRY_QUEUE.save(store, vault_id.into(), &queue)
+}
+
+pub fn pop_pending_post_execution_action(
+    store: &mut dyn Storage,
+    vault_id: Uint128,
+) -> StdResult<Option<PendingPostExecutionAction>> {
+    let mut queue = get_pending_post_execution_actions(store, vault_id)?;
+    let action = queue.pop_front();
+
+    if queue.is_empty() {
+        POST_EXECUTION_ACTION_RETRY_QUEUE.remove(store, vault_id.into());
+    } else {
+        POST_EXECUTION_ACTION_RETRY_QUEUE.save(store, vault_id.into(), &queue)?;
+    }
+
+    Ok(action)
+}
+
+pub fn get_pending_post_execution_actions(
+    store: &dyn Storage,
+    vault_id: Uint128,
+) -> StdResult<VecDeque<PendingPostExecutionAction>> {
+    Ok(POST_EXECUTION_ACTION_RETRY_QUEUE
+        .may_load(store, vault_id.into())?
+        .unwrap_or_default())
+}
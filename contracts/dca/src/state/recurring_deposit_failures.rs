@@ -0,0 +1,26 @@
+use cosmwasm_std::{StdResult, Storage, Uint128};
+use cw_storage_plus::Map;
+
+const RECURRING_DEPOSIT_FAILED_ATTEMPTS: Map<u128, u32> =
+    Map::new("recurring_deposit_failure_cache_v1");
+
+pub fn save_recurring_deposit_failed_attempts(
+    store: &mut dyn Storage,
+    vault_id: Uint128,
+    failed_attempts: u32,
+) -> StdResult<()> {
+    RECURRING_DEPOSIT_FAILED_ATTEMPTS.save(store, vault_id.into(), &failed_attempts)
+}
+
+pub fn get_recurring_deposit_failed_attempts(
+    store: &dyn Storage,
+    vault_id: Uint128,
+) -> StdResult<u32> {
+    Ok(RECURRING_DEPOSIT_FAILED_ATTEMPTS
+        .may_load(store, vault_id.into())?
+        .unwrap_or_default())
+}
+
+pub fn delete_recurring_deposit_failed_attempts(store: &mut dyn Storage, vault_id: Uint128) {
+    RECURRING_DEPOSIT_FAILED_ATTEMPTS.remove(store, vault_id.into())
+}
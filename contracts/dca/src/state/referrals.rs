@@ -0,0 +1,64 @@
+use cosmwasm_std::{Addr, Coin, Order, StdResult, Storage, Uint128};
+use cw_storage_plus::Map;
+
+const REFERRAL_FEES: Map<(&Addr, &str), Uint128> = Map::new("referral_fees_v1");
+
+const REFERRAL_FEE_CAPS: Map<(&Addr, &str), Uint128> = Map::new("referral_fee_caps_v1");
+
+pub fn add_referral_fee(store: &mut dyn Storage, referrer: &Addr, fee: &Coin) -> StdResult<()> {
+    REFERRAL_FEES.update(
+        store,
+        (referrer, &fee.denom),
+        |accumulated| -> StdResult<_> { Ok(accumulated.unwrap_or_default() + fee.amount) },
+    )?;
+    Ok(())
+}
+
+pub fn get_referral_fees(store: &dyn Storage, referrer: &Addr) -> StdResult<Vec<Coin>> {
+    REFERRAL_FEES
+        .prefix(referrer)
+        .range(store, None, None, Order::Ascending)
+        .map(|result| result.map(|(denom, amount)| Coin { denom, amount }))
+        .collect()
+}
+
+pub fn save_referral_fee_caps(
+    store: &mut dyn Storage,
+    referrer: &Addr,
+    caps: &[Coin],
+) -> StdResult<()> {
+    for cap in get_referral_fee_caps(store, referrer)? {
+        REFERRAL_FEE_CAPS.remove(store, (referrer, &cap.denom));
+    }
+
+    for cap in caps {
+        REFERRAL_FEE_CAPS.save(store, (referrer, &cap.denom), &cap.amount)?;
+    }
+
+    Ok(())
+}
+
+pub fn get_referral_fee_caps(store: &dyn Storage, referrer: &Addr) -> StdResult<Vec<Coin>> {
+    REFERRAL_FEE_CAPS
+        .prefix(referrer)
+        .range(store, None, None, Order::Ascending)
+        .map(|result| result.map(|(denom, amount)| Coin { denom, amount }))
+        .collect()
+}
+
+pub fn get_remaining_referral_fee_cap(
+    store: &dyn Storage,
+    referrer: &Addr,
+    denom: &str,
+) -> StdResult<Uint128> {
+    let cap = match REFERRAL_FEE_CAPS.may_load(store, (referrer, denom))? {
+        Some(cap) => cap,
+        None => return Ok(Uint128::zero()),
+    };
+
+    let accumulated = REFERRAL_FEES
+        .may_load(store, (referrer, denom))?
+        .unwrap_or_default();
+
+    Ok(cap.saturating_sub(accumulated))
+}
@@ -19,7 +19,8 @@ pub(crate) fn trigger_store<'a>() -> IndexedMap<'a, u128, Trigger, TriggerIndexe
     let indexes = TriggerIndexes {
         due_date: MultiIndex::new(
             |_, trigger| match trigger.configuration {
-                TriggerConfiguration::Time { target_time } => target_time.seconds(),
+                TriggerConfiguration::Time { target_time }
+                | TriggerConfiguration::Retry { target_time, .. } => target_time.seconds(),
                 _ => u64::MAX,
             },
             "triggers_v8",
@@ -108,6 +109,27 @@ mod tests {
         assert_eq!(trigger_ids, vec![trigger.vault_id]);
     }
 
+    #[test]
+    fn fetches_trigger_ids_for_retry_triggers_that_are_due() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let trigger = Trigger {
+            vault_id: Uint128::from(1u128),
+            configuration: TriggerConfiguration::Retry {
+                target_time: env.block.time,
+                attempt: 1,
+            },
+        };
+
+        save_trigger(&mut deps.storage, trigger.clone()).unwrap();
+
+        let trigger_ids =
+            get_time_triggers(&deps.storage, env.block.time.plus_seconds(10), Some(100)).unwrap();
+
+        assert_eq!(trigger_ids, vec![trigger.vault_id]);
+    }
+
     #[test]
     fn does_not_fetch_trigger_ids_for_time_triggers_that_are_not_due() {
         let mut deps = mock_dependencies();
@@ -0,0 +1,31 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, StdResult, Storage, Uint128};
+use cw_storage_plus::Map;
+
+#[cw_serde]
+pub struct VaultOwnershipTransfer {
+    pub new_owner: Addr,
+    pub update_destinations: bool,
+}
+
+const VAULT_OWNERSHIP_TRANSFERS: Map<u128, VaultOwnershipTransfer> =
+    Map::new("vault_ownership_transfers_v1");
+
+pub fn save_vault_ownership_transfer(
+    store: &mut dyn Storage,
+    vault_id: Uint128,
+    transfer: &VaultOwnershipTransfer,
+) -> StdResult<()> {
+    VAULT_OWNERSHIP_TRANSFERS.save(store, vault_id.into(), transfer)
+}
+
+pub fn get_vault_ownership_transfer(
+    store: &dyn Storage,
+    vault_id: Uint128,
+) -> StdResult<Option<VaultOwnershipTransfer>> {
+    VAULT_OWNERSHIP_TRANSFERS.may_load(store, vault_id.into())
+}
+
+pub fn delete_vault_ownership_transfer(store: &mut dyn Storage, vault_id: Uint128) {
+    VAULT_OWNERSHIP_TRANSFERS.remove(store, vault_id.into())
+}
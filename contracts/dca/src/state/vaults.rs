@@ -2,8 +2,13 @@ use super::{config::get_config, triggers::get_trigger};
 use crate::{
     helpers::state::fetch_and_increment_counter,
     types::{
-        destination::Destination,
+        basket::BasketAsset,
+        destination::{Destination, DestinationAction},
+        end_conditions::EndConditions,
+        escrow_vesting::EscrowVesting,
+        missed_execution_policy::{CatchUpSchedule, MissedExecutionPolicy},
         performance_assessment_strategy::PerformanceAssessmentStrategy,
+        retry_policy::RetryPolicy,
         swap_adjustment_strategy::SwapAdjustmentStrategy,
         time_interval::TimeInterval,
         vault::{Vault, VaultBuilder, VaultStatus},
@@ -108,13 +113,46 @@ pub fn update_vault(store: &mut dyn Storage, vault: Vault) -> StdResult<Vault> {
     Ok(vault)
 }
 
+#[cw_serde]
+struct DestinationData {
+    allocation: Decimal,
+    address: Addr,
+    msg: Option<Binary>,
+    action: Option<DestinationAction>,
+}
+
+impl From<Destination> for DestinationData {
+    fn from(destination: Destination) -> Self {
+        Self {
+            allocation: destination.allocation,
+            address: destination.address,
+            msg: None,
+            action: Some(destination.action),
+        }
+    }
+}
+
+impl From<DestinationData> for Destination {
+    fn from(data: DestinationData) -> Self {
+        Self {
+            allocation: data.allocation,
+            address: data.address,
+            action: data.action.unwrap_or_else(|| {
+                data.msg.map_or(DestinationAction::Send, |msg| {
+                    DestinationAction::ContractCall { msg }
+                })
+            }),
+        }
+    }
+}
+
 #[cw_serde]
 struct VaultData {
     id: Uint128,
     created_at: Timestamp,
     owner: Addr,
     label: Option<String>,
-    destinations: Vec<Destination>,
+    destinations: Vec<DestinationData>,
     status: VaultStatus,
     balance: Coin,
     target_denom: String,
@@ -125,12 +163,23 @@ struct VaultData {
     time_interval: TimeInterval,
     started_at: Option<Timestamp>,
     escrow_level: Decimal,
+    base_escrow_level: Option<Decimal>,
     deposited_amount: Coin,
     swapped_amount: Coin,
     received_amount: Coin,
     escrowed_amount: Coin,
     performance_assessment_strategy: Option<PerformanceAssessmentStrategy>,
     swap_adjustment_strategy: Option<SwapAdjustmentStrategy>,
+    basket: Option<Vec<BasketAsset>>,
+    end_conditions: Option<EndConditions>,
+    missed_execution_policy: Option<MissedExecutionPolicy>,
+    catch_up_schedule: Option<CatchUpSchedule>,
+    allow_partial_executions: Option<bool>,
+    retry_policy: Option<RetryPolicy>,
+    retry_failed_post_execution_actions: Option<bool>,
+    recurring_deposits: Option<bool>,
+    referrer: Option<Addr>,
+    escrow_vesting: Option<EscrowVesting>,
 }
 
 impl From<Vault> for VaultData {
@@ -144,19 +193,34 @@ impl From<Vault> for VaultData {
             balance: vault.balance,
             target_denom: vault.target_denom,
             route: vault.route,
-            destinations: vault.destinations,
+            destinations: vault
+                .destinations
+                .into_iter()
+                .map(DestinationData::from)
+                .collect(),
             swap_amount: vault.swap_amount,
             slippage_tolerance: vault.slippage_tolerance,
             minimum_receive_amount: vault.minimum_receive_amount,
             time_interval: vault.time_interval,
             started_at: vault.started_at,
             escrow_level: vault.escrow_level,
+            base_escrow_level: Some(vault.base_escrow_level),
             deposited_amount: vault.deposited_amount,
             swapped_amount: vault.swapped_amount,
             received_amount: vault.received_amount,
             escrowed_amount: vault.escrowed_amount,
             performance_assessment_strategy: vault.performance_assessment_strategy,
             swap_adjustment_strategy: vault.swap_adjustment_strategy,
+            basket: vault.basket,
+            end_conditions: vault.end_conditions,
+            missed_execution_policy: Some(vault.missed_execution_policy),
+            catch_up_schedule: vault.catch_up_schedule,
+            allow_partial_executions: Some(vault.allow_partial_executions),
+            retry_policy: vault.retry_policy,
+            retry_failed_post_execution_actions: Some(vault.retry_failed_post_execution_actions),
+            recurring_deposits: Some(vault.recurring_deposits),
+            referrer: vault.referrer,
+            escrow_vesting: vault.escrow_vesting,
         }
     }
 }
@@ -174,18 +238,39 @@ fn vault_from(store: &dyn Storage, data: &VaultData) -> StdResult<Vault> {
         swap_amount: data.swap_amount,
         target_denom: data.target_denom.clone(),
         route: data.route.clone(),
-        destinations: data.destinations.clone(),
+        destinations: data
+            .destinations
+            .iter()
+            .cloned()
+            .map(Destination::from)
+            .collect(),
         slippage_tolerance: data.slippage_tolerance,
         minimum_receive_amount: data.minimum_receive_amount,
         time_interval: data.time_interval.clone(),
         started_at: data.started_at,
         escrow_level: data.escrow_level,
+        base_escrow_level: data.base_escrow_level.unwrap_or(data.escrow_level),
         deposited_amount: data.deposited_amount.clone(),
         swapped_amount: data.swapped_amount.clone(),
         received_amount: data.received_amount.clone(),
         escrowed_amount: data.escrowed_amount.clone(),
         performance_assessment_strategy: data.performance_assessment_strategy.clone(),
         swap_adjustment_strategy: data.swap_adjustment_strategy.clone(),
+        basket: data.basket.clone(),
+        end_conditions: data.end_conditions.clone(),
+        missed_execution_policy: data
+            .missed_execution_policy
+            .clone()
+            .unwrap_or(MissedExecutionPolicy::Skip),
+        catch_up_schedule: data.catch_up_schedule.clone(),
+        allow_partial_executions: data.allow_partial_executions.unwrap_or(false),
+        retry_policy: data.retry_policy.clone(),
+        retry_failed_post_execution_actions: data
+            .retry_failed_post_execution_actions
+            .unwrap_or(false),
+        recurring_deposits: data.recurring_deposits.unwrap_or(false),
+        referrer: data.referrer.clone(),
+        escrow_vesting: data.escrow_vesting.clone(),
         trigger,
     })
 }
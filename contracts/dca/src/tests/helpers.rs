@@ -4,15 +4,15 @@ use crate::{
     contract::instantiate,
     msg::{ExecuteMsg, InstantiateMsg},
     state::{
-        cache::VAULT_ID_CACHE,
         triggers::save_trigger,
         vaults::{get_vault, update_vault},
     },
     types::{
         config::Config,
-        destination::Destination,
+        destination::{Destination, DestinationAction},
         event::{EventBuilder, EventData},
         fee_collector::FeeCollector,
+        missed_execution_policy::MissedExecutionPolicy,
         performance_assessment_strategy::PerformanceAssessmentStrategy,
         position_type::PositionType,
         swap_adjustment_strategy::{
@@ -45,6 +45,15 @@ pub fn instantiate_contract(deps: DepsMut, env: Env, info: MessageInfo) {
         twap_period: 30,
         default_slippage_tolerance: Decimal::percent(2),
         exchange_contract_address: Addr::unchecked(EXCHANGE_CONTRACT_ADDRESS),
+        default_retry_policy: None,
+        permissionless_execution: false,
+        keeper_bounty_percent: Decimal::zero(),
+        referral_fee_percent: Decimal::zero(),
+        high_water_mark_performance_fee_percent: Decimal::percent(20),
+        minimum_escrow_level: Decimal::zero(),
+        maximum_escrow_level: Decimal::percent(100),
+        post_execution_action_max_retry_attempts: 5,
+        post_execution_action_max_retry_age_seconds: 7 * 24 * 60 * 60,
     };
 
     instantiate(deps, env, info, instantiate_message).unwrap();
@@ -69,6 +78,15 @@ pub fn instantiate_contract_with_multiple_fee_collectors(
         twap_period: 30,
         default_slippage_tolerance: Decimal::percent(2),
         exchange_contract_address: Addr::unchecked(EXCHANGE_CONTRACT_ADDRESS),
+        default_retry_policy: None,
+        permissionless_execution: false,
+        keeper_bounty_percent: Decimal::zero(),
+        referral_fee_percent: Decimal::zero(),
+        high_water_mark_performance_fee_percent: Decimal::percent(20),
+        minimum_escrow_level: Decimal::zero(),
+        maximum_escrow_level: Decimal::percent(100),
+        post_execution_action_max_retry_attempts: 5,
+        post_execution_action_max_retry_age_seconds: 7 * 24 * 60 * 60,
     };
 
     instantiate(deps, env, info, instantiate_message).unwrap();
@@ -92,6 +110,15 @@ impl Default for Config {
             twap_period: 30,
             default_slippage_tolerance: Decimal::percent(2),
             exchange_contract_address: Addr::unchecked(EXCHANGE_CONTRACT_ADDRESS),
+            default_retry_policy: None,
+            permissionless_execution: false,
+            keeper_bounty_percent: Decimal::zero(),
+            referral_fee_percent: Decimal::zero(),
+            high_water_mark_performance_fee_percent: Decimal::percent(20),
+            minimum_escrow_level: Decimal::zero(),
+            maximum_escrow_level: Decimal::percent(100),
+            post_execution_action_max_retry_attempts: 5,
+            post_execution_action_max_retry_age_seconds: 7 * 24 * 60 * 60,
         }
     }
 }
@@ -101,7 +128,7 @@ impl Default for Destination {
         Self {
             allocation: Decimal::percent(100),
             address: Addr::unchecked(USER),
-            msg: None,
+            action: DestinationAction::Send,
         }
     }
 }
@@ -116,13 +143,13 @@ impl Default for Vault {
             destinations: vec![Destination {
                 allocation: Decimal::percent(100),
                 address: Addr::unchecked("staking-router"),
-                msg: Some(
-                    to_json_binary(&ExecuteMsg::ZDelegate {
+                action: DestinationAction::ContractCall {
+                    msg: to_json_binary(&ExecuteMsg::ZDelegate {
                         delegator_address: Addr::unchecked(USER),
                         validator_address: Addr::unchecked(VALIDATOR),
                     })
                     .unwrap(),
-                ),
+                },
             }],
             status: VaultStatus::Active,
             balance: Coin::new(TEN.into(), DENOM_UKUJI),
@@ -134,6 +161,7 @@ impl Default for Vault {
             time_interval: TimeInterval::Daily,
             started_at: None,
             escrow_level: Decimal::percent(0),
+            base_escrow_level: Decimal::percent(5),
             deposited_amount: Coin::new(TEN.into(), DENOM_UKUJI),
             swapped_amount: Coin::new(0, DENOM_UKUJI),
             received_amount: Coin::new(0, DENOM_UUSK),
@@ -143,6 +171,16 @@ impl Default for Vault {
             }),
             swap_adjustment_strategy: None,
             performance_assessment_strategy: None,
+            basket: None,
+            end_conditions: None,
+            missed_execution_policy: MissedExecutionPolicy::Skip,
+            catch_up_schedule: None,
+            allow_partial_executions: false,
+            retry_policy: None,
+            retry_failed_post_execution_actions: false,
+            recurring_deposits: false,
+            referrer: None,
+            escrow_vesting: None,
         }
     }
 }
@@ -195,6 +233,7 @@ impl Default for EventData {
             base_denom: DENOM_UUSK.to_string(),
             quote_denom: DENOM_UKUJI.to_string(),
             asset_price: Decimal::new(Uint128::one()),
+            missed_executions: None,
         }
     }
 }
@@ -209,15 +248,22 @@ pub fn setup_vault(deps: DepsMut, env: Env, mut vault: Vault) -> Vault {
 
     update_vault(deps.storage, vault.clone()).unwrap();
 
-    if vault.trigger.is_some() {
+    if let Some(trigger) = vault.trigger {
         save_trigger(
             deps.storage,
             Trigger {
                 vault_id: vault.id,
-                configuration: match vault.trigger.unwrap() {
+                configuration: match trigger {
                     TriggerConfiguration::Time { target_time } => TriggerConfiguration::Time {
                         target_time: max(target_time, env.block.time),
                     },
+                    TriggerConfiguration::Retry {
+                        target_time,
+                        attempt,
+                    } => TriggerConfiguration::Retry {
+                        target_time: max(target_time, env.block.time),
+                        attempt,
+                    },
                     TriggerConfiguration::Price {
                         target_price,
                         order_idx,
@@ -231,7 +277,5 @@ pub fn setup_vault(deps: DepsMut, env: Env, mut vault: Vault) -> Vault {
         .unwrap();
     }
 
-    VAULT_ID_CACHE.save(deps.storage, &vault.id).unwrap();
-
     get_vault(deps.storage, vault.id).unwrap()
 }
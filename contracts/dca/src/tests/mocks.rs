@@ -100,16 +100,16 @@ impl<C: CustomQuery + DeserializeOwned> Querier for CalcMockQuerier<C> {
 }
 
 impl<C: CustomQuery + DeserializeOwned> CalcMockQuerier<C> {
-    pub fn update_stargate<WH: 'static>(&mut self, stargate_handler: WH)
+    pub fn update_stargate<WH>(&mut self, stargate_handler: WH)
     where
-        WH: Fn(&str, &Binary) -> StdResult<Binary>,
+        WH: 'static + Fn(&str, &Binary) -> StdResult<Binary>,
     {
         self.stargate_handler = Box::from(stargate_handler);
     }
 
-    pub fn update_wasm<WH: 'static>(&mut self, wasm_handler: WH)
+    pub fn update_wasm<WH>(&mut self, wasm_handler: WH)
     where
-        WH: Fn(&WasmQuery) -> QuerierResult,
+        WH: 'static + Fn(&WasmQuery) -> QuerierResult,
     {
         self.mock_querier.update_wasm(wasm_handler);
     }
@@ -0,0 +1,108 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Binary, Coin, Decimal, Uint128};
+
+#[cw_serde]
+pub struct BasketAsset {
+    pub target_denom: String,
+    pub weight: Decimal,
+    pub route: Option<Binary>,
+    pub received_amount: Coin,
+}
+
+#[cw_serde]
+pub struct BasketAssetParams {
+    pub target_denom: String,
+    pub weight: Decimal,
+    pub route: Option<Binary>,
+}
+
+impl From<BasketAssetParams> for BasketAsset {
+    fn from(params: BasketAssetParams) -> Self {
+        Self {
+            received_amount: Coin::new(0, params.target_denom.clone()),
+            target_denom: params.target_denom,
+            weight: params.weight,
+            route: params.route,
+        }
+    }
+}
+
+pub fn get_basket_swap_amounts(basket: &[BasketAsset], swap_amount: Uint128) -> Vec<Uint128> {
+    let mut remaining = swap_amount;
+
+    basket
+        .iter()
+        .enumerate()
+        .map(|(index, asset)| {
+            let amount = if index == basket.len() - 1 {
+                remaining
+            } else {
+                (swap_amount * asset.weight).min(remaining)
+            };
+            remaining -= amount;
+            amount
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod get_basket_swap_amounts_tests {
+    use super::*;
+    use crate::tests::mocks::{DENOM_UKUJI, DENOM_UUSK};
+
+    fn basket(weights: Vec<Decimal>) -> Vec<BasketAsset> {
+        weights
+            .into_iter()
+            .enumerate()
+            .map(|(index, weight)| {
+                BasketAsset::from(BasketAssetParams {
+                    target_denom: if index % 2 == 0 {
+                        DENOM_UUSK
+                    } else {
+                        DENOM_UKUJI
+                    }
+                    .to_string(),
+                    weight,
+                    route: None,
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn splits_swap_amount_by_weight() {
+        assert_eq!(
+            get_basket_swap_amounts(
+                &basket(vec![
+                    Decimal::percent(50),
+                    Decimal::percent(30),
+                    Decimal::percent(20)
+                ]),
+                Uint128::new(1000)
+            ),
+            vec![Uint128::new(500), Uint128::new(300), Uint128::new(200)]
+        );
+    }
+
+    #[test]
+    fn gives_rounding_remainder_to_last_asset() {
+        let amounts = get_basket_swap_amounts(
+            &basket(vec![
+                Decimal::percent(33),
+                Decimal::percent(33),
+                Decimal::percent(34),
+            ]),
+            Uint128::new(100001),
+        );
+
+        assert_eq!(
+            amounts,
+            vec![
+                Uint128::new(33000),
+                Uint128::new(33000),
+                Uint128::new(34001)
+            ]
+        );
+        assert_eq!(amounts.iter().sum::<Uint128>(), Uint128::new(100001));
+    }
+}
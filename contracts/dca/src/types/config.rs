@@ -1,4 +1,4 @@
-use super::fee_collector::FeeCollector;
+use super::{fee_collector::FeeCollector, retry_policy::RetryPolicy};
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, Decimal};
 
@@ -16,4 +16,13 @@ pub struct Config {
     pub twap_period: u64,
     pub default_slippage_tolerance: Decimal,
     pub exchange_contract_address: Addr,
+    pub default_retry_policy: Option<RetryPolicy>,
+    pub permissionless_execution: bool,
+    pub keeper_bounty_percent: Decimal,
+    pub referral_fee_percent: Decimal,
+    pub high_water_mark_performance_fee_percent: Decimal,
+    pub minimum_escrow_level: Decimal,
+    pub maximum_escrow_level: Decimal,
+    pub post_execution_action_max_retry_attempts: u32,
+    pub post_execution_action_max_retry_age_seconds: u64,
 }
@@ -0,0 +1,8 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Binary, Coin};
+
+#[cw_serde]
+pub struct DepositConversion {
+    pub minimum_receive_amount: Coin,
+    pub route: Option<Binary>,
+}
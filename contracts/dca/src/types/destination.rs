@@ -1,9 +1,45 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Binary, Decimal};
+use cosmwasm_std::{Addr, Binary, Decimal, Uint128};
 
 #[cw_serde]
 pub struct Destination {
     pub allocation: Decimal,
     pub address: Addr,
-    pub msg: Option<Binary>,
+    pub action: DestinationAction,
+}
+
+#[cw_serde]
+pub enum DestinationAction {
+    Send,
+    Delegate {
+        validator: Addr,
+    },
+    DepositToVault {
+        vault_id: Uint128,
+    },
+    IbcTransfer {
+        channel: String,
+        receiver: String,
+        timeout: u64,
+    },
+    ContractCall {
+        msg: Binary,
+    },
+}
+
+impl DestinationAction {
+    pub fn incurs_automation_fee(&self) -> bool {
+        !matches!(self, DestinationAction::Send)
+    }
+}
+
+impl Destination {
+    pub fn get_fallback_address(&self, vault_owner: &Addr) -> Addr {
+        match self.action {
+            DestinationAction::Delegate { .. } | DestinationAction::DepositToVault { .. } => {
+                self.address.clone()
+            }
+            _ => vault_owner.clone(),
+        }
+    }
 }
@@ -0,0 +1,37 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Timestamp, Uint128, Uint64};
+
+#[cw_serde]
+pub enum EndCondition {
+    EndTimeReached,
+    MaxExecutionsReached,
+    ReceivedAmountGoalReached,
+}
+
+#[cw_serde]
+pub struct EndConditions {
+    pub end_time: Option<Timestamp>,
+    pub max_executions: Option<u64>,
+    pub received_amount_goal: Option<Uint128>,
+    pub executions: u64,
+}
+
+#[cw_serde]
+pub struct EndConditionsParams {
+    pub end_time_utc_seconds: Option<Uint64>,
+    pub max_executions: Option<Uint64>,
+    pub received_amount_goal: Option<Uint128>,
+}
+
+impl From<EndConditionsParams> for EndConditions {
+    fn from(params: EndConditionsParams) -> Self {
+        Self {
+            end_time: params
+                .end_time_utc_seconds
+                .map(|seconds| Timestamp::from_seconds(seconds.u64())),
+            max_executions: params.max_executions.map(|max_executions| max_executions.u64()),
+            received_amount_goal: params.received_amount_goal,
+            executions: 0,
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Decimal;
+use std::cmp::min;
+
+#[cw_serde]
+pub enum EscrowVestingSchedule {
+    Linear {
+        tranches: u32,
+        interval_seconds: u64,
+    },
+    Stepped {
+        shares: Vec<Decimal>,
+        interval_seconds: u64,
+    },
+}
+
+impl EscrowVestingSchedule {
+    pub fn tranche_shares(&self) -> Vec<Decimal> {
+        match self {
+            EscrowVestingSchedule::Linear { tranches, .. } => {
+                vec![Decimal::from_ratio(1u128, *tranches); *tranches as usize]
+            }
+            EscrowVestingSchedule::Stepped { shares, .. } => shares.clone(),
+        }
+    }
+
+    pub fn tranche_count(&self) -> usize {
+        match self {
+            EscrowVestingSchedule::Linear { tranches, .. } => *tranches as usize,
+            EscrowVestingSchedule::Stepped { shares, .. } => shares.len(),
+        }
+    }
+
+    pub fn interval_seconds(&self) -> u64 {
+        match self {
+            EscrowVestingSchedule::Linear {
+                interval_seconds, ..
+            }
+            | EscrowVestingSchedule::Stepped {
+                interval_seconds, ..
+            } => *interval_seconds,
+        }
+    }
+}
+
+#[cw_serde]
+pub struct EscrowVesting {
+    pub schedule: EscrowVestingSchedule,
+    pub released_tranches: u32,
+}
+
+impl EscrowVesting {
+    pub fn is_last_tranche(&self) -> bool {
+        self.released_tranches + 1 >= self.schedule.tranche_count() as u32
+    }
+
+    pub fn next_tranche_share_of_total_escrow(&self) -> Decimal {
+        self.schedule
+            .tranche_shares()
+            .get(self.next_tranche_index())
+            .copied()
+            .unwrap_or(Decimal::one())
+    }
+
+    pub fn remaining_share_of_total_escrow(&self) -> Decimal {
+        let shares = self.schedule.tranche_shares();
+
+        shares
+            .get(self.next_tranche_index()..)
+            .unwrap_or_default()
+            .iter()
+            .fold(Decimal::zero(), |total, share| total + share)
+    }
+
+    pub fn next_tranche_share_of_remaining_escrow(&self) -> Decimal {
+        if self.is_last_tranche() {
+            return Decimal::one();
+        }
+
+        self.next_tranche_share_of_total_escrow() / self.remaining_share_of_total_escrow()
+    }
+
+    fn next_tranche_index(&self) -> usize {
+        min(
+            self.released_tranches as usize,
+            self.schedule.tranche_count().saturating_sub(1),
+        )
+    }
+}
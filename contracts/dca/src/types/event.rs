@@ -1,6 +1,7 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{BlockInfo, Coin, Decimal, SubMsg, Timestamp, Uint128};
+use cosmwasm_std::{Addr, BlockInfo, Coin, Decimal, SubMsg, Timestamp, Uint128};
 
+use super::end_conditions::EndCondition;
 use super::update::Update;
 
 #[cw_serde]
@@ -10,6 +11,7 @@ pub enum ExecutionSkippedReason {
     SwapAmountAdjustedToZero,
     SlippageQueryError,
     UnknownError { msg: String },
+    InsufficientWalletFunds,
 }
 
 #[cw_serde]
@@ -17,16 +19,26 @@ pub enum EventData {
     DcaVaultFundsDeposited {
         amount: Coin,
     },
+    DcaVaultFundsWithdrawn {
+        amount: Coin,
+    },
     DcaVaultExecutionTriggered {
         base_denom: String,
         quote_denom: String,
         asset_price: Decimal,
+        missed_executions: Option<u64>,
     },
     DcaVaultExecutionCompleted {
         sent: Coin,
         received: Coin,
         fee: Coin,
     },
+    DcaVaultExecutionPartiallyCompleted {
+        sent: Coin,
+        scheduled: Coin,
+        received: Coin,
+        fee: Coin,
+    },
     SimulatedDcaVaultExecutionCompleted {
         sent: Coin,
         received: Coin,
@@ -39,6 +51,15 @@ pub enum EventData {
         reason: ExecutionSkippedReason,
     },
     DcaVaultCancelled {},
+    DcaVaultEndConditionReached {
+        condition: EndCondition,
+    },
+    DcaVaultPaused {},
+    DcaVaultResumed {},
+    DcaVaultOwnershipTransferred {
+        previous_owner: Addr,
+        new_owner: Addr,
+    },
     DcaVaultEscrowDisbursed {
         amount_disbursed: Coin,
         performance_fee: Coin,
@@ -47,6 +68,12 @@ pub enum EventData {
         msg: SubMsg,
         funds: Vec<Coin>,
     },
+    DcaVaultIbcTransferFailed {
+        channel_id: String,
+        sequence: u64,
+        funds: Vec<Coin>,
+        reason: String,
+    },
     DcaVaultUpdated {
         updates: Vec<Update>,
     },
@@ -0,0 +1,88 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Uint128;
+
+#[cw_serde]
+pub enum MissedExecutionPolicy {
+    Skip,
+    CatchUp,
+    Spread { executions: u64 },
+}
+
+#[cw_serde]
+pub struct CatchUpSchedule {
+    pub remaining_amount: Uint128,
+    pub executions_remaining: u64,
+}
+
+impl CatchUpSchedule {
+    pub fn next_amount(&self) -> Uint128 {
+        self.remaining_amount
+            .checked_div(Uint128::from(self.executions_remaining))
+            .unwrap_or(self.remaining_amount)
+    }
+
+    pub fn after_execution(&self, caught_up_amount: Uint128) -> Option<CatchUpSchedule> {
+        let remaining_amount = self.remaining_amount.saturating_sub(caught_up_amount);
+        let executions_remaining = self.executions_remaining.saturating_sub(1);
+
+        if remaining_amount.is_zero() || executions_remaining == 0 {
+            return None;
+        }
+
+        Some(CatchUpSchedule {
+            remaining_amount,
+            executions_remaining,
+        })
+    }
+}
+
+#[cfg(test)]
+mod catch_up_schedule_tests {
+    use super::*;
+
+    #[test]
+    fn next_amount_spreads_remaining_amount_over_remaining_executions() {
+        let schedule = CatchUpSchedule {
+            remaining_amount: Uint128::new(1000),
+            executions_remaining: 3,
+        };
+
+        assert_eq!(schedule.next_amount(), Uint128::new(333));
+    }
+
+    #[test]
+    fn after_execution_reduces_remaining_amount_and_executions() {
+        let schedule = CatchUpSchedule {
+            remaining_amount: Uint128::new(1000),
+            executions_remaining: 3,
+        };
+
+        assert_eq!(
+            schedule.after_execution(Uint128::new(333)),
+            Some(CatchUpSchedule {
+                remaining_amount: Uint128::new(667),
+                executions_remaining: 2,
+            })
+        );
+    }
+
+    #[test]
+    fn after_last_execution_is_none() {
+        let schedule = CatchUpSchedule {
+            remaining_amount: Uint128::new(1000),
+            executions_remaining: 1,
+        };
+
+        assert_eq!(schedule.after_execution(Uint128::new(500)), None);
+    }
+
+    #[test]
+    fn after_catching_up_full_amount_is_none() {
+        let schedule = CatchUpSchedule {
+            remaining_amount: Uint128::new(1000),
+            executions_remaining: 3,
+        };
+
+        assert_eq!(schedule.after_execution(Uint128::new(1000)), None);
+    }
+}
@@ -1,10 +1,17 @@
+pub mod basket;
 pub mod config;
 pub mod dca_plus_config;
+pub mod deposit_conversion;
 pub mod destination;
+pub mod end_conditions;
+pub mod escrow_vesting;
 pub mod event;
 pub mod fee_collector;
+pub mod missed_execution_policy;
+pub mod netted_execution;
 pub mod performance_assessment_strategy;
 pub mod position_type;
+pub mod retry_policy;
 pub mod swap_adjustment_strategy;
 pub mod time_interval;
 pub mod trigger;
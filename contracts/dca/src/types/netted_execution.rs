@@ -0,0 +1,11 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Coin, Decimal, Uint128};
+
+#[cw_serde]
+pub struct NettedExecution {
+    pub vault_id: Uint128,
+    pub sent: Coin,
+    pub received: Coin,
+    pub residual: Coin,
+    pub asset_price: Decimal,
+}
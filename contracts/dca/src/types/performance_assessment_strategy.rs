@@ -1,7 +1,7 @@
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Coin, Decimal};
 
-use super::vault::Vault;
+use super::{config::Config, vault::Vault};
 
 #[cw_serde]
 pub enum PerformanceAssessmentStrategy {
@@ -9,11 +9,19 @@ pub enum PerformanceAssessmentStrategy {
         swapped_amount: Coin,
         received_amount: Coin,
     },
+    HighWaterMark {
+        mark: Decimal,
+    },
+    CompareToLumpSum {
+        start_price: Option<Decimal>,
+    },
 }
 
 #[cw_serde]
 pub enum PerformanceAssessmentStrategyParams {
     CompareToStandardDca,
+    HighWaterMark,
+    CompareToLumpSum,
 }
 
 impl PerformanceAssessmentStrategy {
@@ -22,12 +30,18 @@ impl PerformanceAssessmentStrategy {
             PerformanceAssessmentStrategy::CompareToStandardDca { swapped_amount, .. } => {
                 vault.deposited_amount.amount > swapped_amount.amount
             }
+            PerformanceAssessmentStrategy::HighWaterMark { .. }
+            | PerformanceAssessmentStrategy::CompareToLumpSum { .. } => false,
         }
     }
 
-    pub fn performance_fee_rate(&self) -> Decimal {
+    pub fn performance_fee_rate(&self, config: &Config) -> Decimal {
         match self {
-            PerformanceAssessmentStrategy::CompareToStandardDca { .. } => Decimal::percent(20),
+            PerformanceAssessmentStrategy::CompareToStandardDca { .. }
+            | PerformanceAssessmentStrategy::CompareToLumpSum { .. } => Decimal::percent(20),
+            PerformanceAssessmentStrategy::HighWaterMark { .. } => {
+                config.high_water_mark_performance_fee_percent
+            }
         }
     }
 }
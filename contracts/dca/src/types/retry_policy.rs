@@ -0,0 +1,7 @@
+use cosmwasm_schema::cw_serde;
+
+#[cw_serde]
+pub struct RetryPolicy {
+    pub retry_delay_minutes: u64,
+    pub max_attempts: u32,
+}
@@ -2,6 +2,17 @@ use std::fmt::Display;
 
 use cosmwasm_schema::cw_serde;
 
+#[cw_serde]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
 #[cw_serde]
 pub enum TimeInterval {
     EveryBlock,
@@ -13,23 +24,39 @@ pub enum TimeInterval {
     Weekly,
     Fortnightly,
     Monthly,
-    Custom { seconds: u64 },
+    Custom {
+        seconds: u64,
+    },
+    DailyAt {
+        hour: u8,
+        minute: u8,
+    },
+    WeekdaysAt {
+        weekdays: Vec<Weekday>,
+        hour: u8,
+        minute: u8,
+    },
+    DaysOfMonthAt {
+        days: Vec<u8>,
+        hour: u8,
+        minute: u8,
+    },
+}
+
+impl TimeInterval {
+    pub fn is_calendar_schedule(&self) -> bool {
+        matches!(
+            self,
+            TimeInterval::DailyAt { .. }
+                | TimeInterval::WeekdaysAt { .. }
+                | TimeInterval::DaysOfMonthAt { .. }
+        )
+    }
 }
 
 impl From<TimeInterval> for String {
     fn from(time_interval: TimeInterval) -> Self {
-        match time_interval {
-            TimeInterval::EveryBlock => "EveryBlock".to_string(),
-            TimeInterval::EveryMinute => "EveryMinute".to_string(),
-            TimeInterval::HalfHourly => "HalfHourly".to_string(),
-            TimeInterval::Hourly => "Hourly".to_string(),
-            TimeInterval::HalfDaily => "HalfDaily".to_string(),
-            TimeInterval::Daily => "Daily".to_string(),
-            TimeInterval::Weekly => "Weekly".to_string(),
-            TimeInterval::Fortnightly => "Fortnightly".to_string(),
-            TimeInterval::Monthly => "Monthly".to_string(),
-            TimeInterval::Custom { seconds } => format!("Custom:{}", seconds),
-        }
+        time_interval.to_string()
     }
 }
 
@@ -46,6 +73,34 @@ impl Display for TimeInterval {
             TimeInterval::Fortnightly => write!(f, "Fortnightly"),
             TimeInterval::Monthly => write!(f, "Monthly"),
             TimeInterval::Custom { seconds } => write!(f, "Custom:{}", seconds),
+            TimeInterval::DailyAt { hour, minute } => {
+                write!(f, "DailyAt:{:02}:{:02}", hour, minute)
+            }
+            TimeInterval::WeekdaysAt {
+                weekdays,
+                hour,
+                minute,
+            } => write!(
+                f,
+                "WeekdaysAt:{}:{:02}:{:02}",
+                weekdays
+                    .iter()
+                    .map(|weekday| format!("{:?}", weekday))
+                    .collect::<Vec<String>>()
+                    .join(","),
+                hour,
+                minute
+            ),
+            TimeInterval::DaysOfMonthAt { days, hour, minute } => write!(
+                f,
+                "DaysOfMonthAt:{}:{:02}:{:02}",
+                days.iter()
+                    .map(|day| day.to_string())
+                    .collect::<Vec<String>>()
+                    .join(","),
+                hour,
+                minute
+            ),
         }
     }
 }
@@ -10,6 +10,10 @@ pub enum TriggerConfiguration {
         target_price: Decimal,
         order_idx: Uint128,
     },
+    Retry {
+        target_time: Timestamp,
+        attempt: u32,
+    },
 }
 
 #[cw_serde]
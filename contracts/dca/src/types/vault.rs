@@ -1,14 +1,21 @@
 use super::{
-    destination::Destination, performance_assessment_strategy::PerformanceAssessmentStrategy,
-    swap_adjustment_strategy::SwapAdjustmentStrategy, time_interval::TimeInterval,
+    basket::BasketAsset,
+    destination::Destination,
+    end_conditions::{EndCondition, EndConditions},
+    escrow_vesting::EscrowVesting,
+    missed_execution_policy::{CatchUpSchedule, MissedExecutionPolicy},
+    performance_assessment_strategy::PerformanceAssessmentStrategy,
+    retry_policy::RetryPolicy,
+    swap_adjustment_strategy::SwapAdjustmentStrategy,
+    time_interval::TimeInterval,
     trigger::TriggerConfiguration,
 };
-use crate::helpers::time::get_total_execution_duration;
+use crate::helpers::time::{get_execution_count_before, get_total_execution_duration};
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{
     Addr, Binary, Coin, Decimal, Decimal256, StdResult, Timestamp, Uint128, Uint256,
 };
-use std::cmp::max;
+use std::cmp::{max, min};
 
 #[cw_serde]
 pub enum VaultStatus {
@@ -16,6 +23,7 @@ pub enum VaultStatus {
     Active,
     Inactive,
     Cancelled,
+    Paused,
 }
 
 #[cw_serde]
@@ -35,6 +43,7 @@ pub struct Vault {
     pub minimum_receive_amount: Option<Uint128>,
     pub time_interval: TimeInterval,
     pub escrow_level: Decimal,
+    pub base_escrow_level: Decimal,
     pub deposited_amount: Coin,
     pub swapped_amount: Coin,
     pub received_amount: Coin,
@@ -42,6 +51,16 @@ pub struct Vault {
     pub trigger: Option<TriggerConfiguration>,
     pub performance_assessment_strategy: Option<PerformanceAssessmentStrategy>,
     pub swap_adjustment_strategy: Option<SwapAdjustmentStrategy>,
+    pub basket: Option<Vec<BasketAsset>>,
+    pub end_conditions: Option<EndConditions>,
+    pub missed_execution_policy: MissedExecutionPolicy,
+    pub catch_up_schedule: Option<CatchUpSchedule>,
+    pub allow_partial_executions: bool,
+    pub retry_policy: Option<RetryPolicy>,
+    pub retry_failed_post_execution_actions: bool,
+    pub recurring_deposits: bool,
+    pub referrer: Option<Addr>,
+    pub escrow_vesting: Option<EscrowVesting>,
 }
 
 impl Vault {
@@ -54,6 +73,38 @@ impl Vault {
     }
 
     pub fn get_expected_execution_completed_date(&self, current_time: Timestamp) -> Timestamp {
+        self.get_execution_completed_date(
+            current_time,
+            self.get_expected_execution_count(current_time, self.swap_amount, None),
+        )
+    }
+
+    pub fn get_execution_completed_date(
+        &self,
+        current_time: Timestamp,
+        executions: Uint128,
+    ) -> Timestamp {
+        let execution_duration =
+            get_total_execution_duration(current_time, executions.into(), &self.time_interval);
+
+        current_time.plus_seconds(
+            execution_duration
+                .num_seconds()
+                .try_into()
+                .expect("executed duration should be >= 0 seconds"),
+        )
+    }
+
+    pub fn get_expected_execution_count(
+        &self,
+        current_time: Timestamp,
+        swap_amount: Uint128,
+        receive_price: Option<Decimal>,
+    ) -> Uint128 {
+        if swap_amount.is_zero() {
+            return Uint128::zero();
+        }
+
         let remaining_balance = match self.performance_assessment_strategy.clone() {
             Some(PerformanceAssessmentStrategy::CompareToStandardDca {
                 swapped_amount, ..
@@ -64,21 +115,40 @@ impl Vault {
             _ => self.balance.amount,
         };
 
-        let execution_duration = get_total_execution_duration(
-            current_time,
-            remaining_balance
-                .checked_div(self.swap_amount)
-                .unwrap()
-                .into(),
-            &self.time_interval,
-        );
+        let mut executions = div_ceil(remaining_balance, swap_amount);
+
+        if let Some(end_conditions) = &self.end_conditions {
+            if let Some(max_executions) = end_conditions.max_executions {
+                executions = min(
+                    executions,
+                    Uint128::from(max_executions.saturating_sub(end_conditions.executions)),
+                );
+            }
+
+            if let (Some(received_amount_goal), Some(receive_price)) =
+                (end_conditions.received_amount_goal, receive_price)
+            {
+                let remaining_goal =
+                    received_amount_goal.saturating_sub(self.received_amount.amount);
+
+                executions = min(
+                    executions,
+                    div_ceil(remaining_goal.mul_ceil(receive_price), swap_amount),
+                );
+            }
+
+            if let Some(end_time) = end_conditions.end_time {
+                executions = get_execution_count_before(
+                    current_time,
+                    end_time,
+                    executions.into(),
+                    &self.time_interval,
+                )
+                .into();
+            }
+        }
 
-        current_time.plus_seconds(
-            execution_duration
-                .num_seconds()
-                .try_into()
-                .expect("executed duration should be >= 0 seconds"),
-        )
+        executions
     }
 
     pub fn price_threshold_exceeded(&self, belief_price: Decimal) -> StdResult<bool> {
@@ -112,8 +182,7 @@ impl Vault {
 
     pub fn should_not_continue(&self) -> bool {
         self.is_inactive()
-            && self.performance_assessment_strategy.clone().map_or(
-                true,
+            && self.performance_assessment_strategy.clone().is_none_or(
                 |performance_assessment_strategy| {
                     !performance_assessment_strategy.should_continue(self)
                 },
@@ -123,6 +192,45 @@ impl Vault {
     pub fn is_cancelled(&self) -> bool {
         self.status == VaultStatus::Cancelled
     }
+
+    pub fn is_paused(&self) -> bool {
+        self.status == VaultStatus::Paused
+    }
+
+    pub fn get_reached_end_condition(&self, current_time: Timestamp) -> Option<EndCondition> {
+        let end_conditions = self.end_conditions.as_ref()?;
+
+        if end_conditions
+            .end_time
+            .is_some_and(|end_time| end_time <= current_time)
+        {
+            return Some(EndCondition::EndTimeReached);
+        }
+
+        if end_conditions
+            .max_executions
+            .is_some_and(|max_executions| end_conditions.executions >= max_executions)
+        {
+            return Some(EndCondition::MaxExecutionsReached);
+        }
+
+        if end_conditions
+            .received_amount_goal
+            .is_some_and(|received_amount_goal| self.received_amount.amount >= received_amount_goal)
+        {
+            return Some(EndCondition::ReceivedAmountGoalReached);
+        }
+
+        None
+    }
+}
+
+fn div_ceil(dividend: Uint128, divisor: Uint128) -> Uint128 {
+    if (dividend % divisor).is_zero() {
+        dividend / divisor
+    } else {
+        dividend / divisor + Uint128::one()
+    }
 }
 
 pub struct VaultBuilder {
@@ -140,15 +248,26 @@ pub struct VaultBuilder {
     pub time_interval: TimeInterval,
     pub started_at: Option<Timestamp>,
     pub escrow_level: Decimal,
+    pub base_escrow_level: Decimal,
     pub deposited_amount: Coin,
     pub swapped_amount: Coin,
     pub received_amount: Coin,
     pub escrowed_amount: Coin,
     pub performance_assessment_strategy: Option<PerformanceAssessmentStrategy>,
     pub swap_adjustment_strategy: Option<SwapAdjustmentStrategy>,
+    pub basket: Option<Vec<BasketAsset>>,
+    pub end_conditions: Option<EndConditions>,
+    pub missed_execution_policy: MissedExecutionPolicy,
+    pub allow_partial_executions: bool,
+    pub retry_policy: Option<RetryPolicy>,
+    pub retry_failed_post_execution_actions: bool,
+    pub recurring_deposits: bool,
+    pub referrer: Option<Addr>,
+    pub escrow_vesting: Option<EscrowVesting>,
 }
 
 impl VaultBuilder {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         created_at: Timestamp,
         owner: Addr,
@@ -164,12 +283,22 @@ impl VaultBuilder {
         time_interval: TimeInterval,
         started_at: Option<Timestamp>,
         escrow_level: Decimal,
+        base_escrow_level: Decimal,
         deposited_amount: Coin,
         swapped_amount: Coin,
         received_amount: Coin,
         escrowed_amount: Coin,
         performance_assessment_strategy: Option<PerformanceAssessmentStrategy>,
         swap_adjustment_strategy: Option<SwapAdjustmentStrategy>,
+        basket: Option<Vec<BasketAsset>>,
+        end_conditions: Option<EndConditions>,
+        missed_execution_policy: MissedExecutionPolicy,
+        allow_partial_executions: bool,
+        retry_policy: Option<RetryPolicy>,
+        retry_failed_post_execution_actions: bool,
+        recurring_deposits: bool,
+        referrer: Option<Addr>,
+        escrow_vesting: Option<EscrowVesting>,
     ) -> VaultBuilder {
         VaultBuilder {
             created_at,
@@ -186,12 +315,22 @@ impl VaultBuilder {
             time_interval,
             started_at,
             escrow_level,
+            base_escrow_level,
             deposited_amount,
             swapped_amount,
             received_amount,
             escrowed_amount,
             performance_assessment_strategy,
             swap_adjustment_strategy,
+            basket,
+            end_conditions,
+            missed_execution_policy,
+            allow_partial_executions,
+            retry_policy,
+            retry_failed_post_execution_actions,
+            recurring_deposits,
+            referrer,
+            escrow_vesting,
         }
     }
 
@@ -212,12 +351,23 @@ impl VaultBuilder {
             minimum_receive_amount: self.minimum_receive_amount,
             time_interval: self.time_interval,
             escrow_level: self.escrow_level,
+            base_escrow_level: self.base_escrow_level,
             deposited_amount: self.deposited_amount,
             swapped_amount: self.swapped_amount,
             received_amount: self.received_amount,
             escrowed_amount: self.escrowed_amount,
             performance_assessment_strategy: self.performance_assessment_strategy,
             swap_adjustment_strategy: self.swap_adjustment_strategy,
+            basket: self.basket,
+            end_conditions: self.end_conditions,
+            missed_execution_policy: self.missed_execution_policy,
+            catch_up_schedule: None,
+            allow_partial_executions: self.allow_partial_executions,
+            retry_policy: self.retry_policy,
+            retry_failed_post_execution_actions: self.retry_failed_post_execution_actions,
+            recurring_deposits: self.recurring_deposits,
+            referrer: self.referrer,
+            escrow_vesting: self.escrow_vesting,
             trigger: None,
         }
     }
@@ -425,3 +575,95 @@ mod price_threshold_exceeded_tests {
         );
     }
 }
+
+#[cfg(test)]
+mod get_reached_end_condition_tests {
+    use super::*;
+    use crate::constants::ONE;
+    use cosmwasm_std::testing::mock_env;
+
+    fn end_conditions() -> EndConditions {
+        EndConditions {
+            end_time: None,
+            max_executions: None,
+            received_amount_goal: None,
+            executions: 0,
+        }
+    }
+
+    #[test]
+    fn without_end_conditions_is_none() {
+        let vault = Vault::default();
+
+        assert_eq!(vault.get_reached_end_condition(mock_env().block.time), None);
+    }
+
+    #[test]
+    fn with_unmet_end_conditions_is_none() {
+        let env = mock_env();
+
+        let vault = Vault {
+            end_conditions: Some(EndConditions {
+                end_time: Some(env.block.time.plus_seconds(10)),
+                max_executions: Some(2),
+                received_amount_goal: Some(ONE),
+                executions: 1,
+            }),
+            ..Vault::default()
+        };
+
+        assert_eq!(vault.get_reached_end_condition(env.block.time), None);
+    }
+
+    #[test]
+    fn with_end_time_in_past_is_end_time_reached() {
+        let env = mock_env();
+
+        let vault = Vault {
+            end_conditions: Some(EndConditions {
+                end_time: Some(env.block.time),
+                ..end_conditions()
+            }),
+            ..Vault::default()
+        };
+
+        assert_eq!(
+            vault.get_reached_end_condition(env.block.time),
+            Some(EndCondition::EndTimeReached)
+        );
+    }
+
+    #[test]
+    fn with_max_executions_completed_is_max_executions_reached() {
+        let vault = Vault {
+            end_conditions: Some(EndConditions {
+                max_executions: Some(3),
+                executions: 3,
+                ..end_conditions()
+            }),
+            ..Vault::default()
+        };
+
+        assert_eq!(
+            vault.get_reached_end_condition(mock_env().block.time),
+            Some(EndCondition::MaxExecutionsReached)
+        );
+    }
+
+    #[test]
+    fn with_received_amount_goal_met_is_received_amount_goal_reached() {
+        let vault = Vault {
+            received_amount: Coin::new(ONE.into(), "uusk"),
+            end_conditions: Some(EndConditions {
+                received_amount_goal: Some(ONE),
+                ..end_conditions()
+            }),
+            ..Vault::default()
+        };
+
+        assert_eq!(
+            vault.get_reached_end_condition(mock_env().block.time),
+            Some(EndCondition::ReceivedAmountGoalReached)
+        );
+    }
+}